@@ -0,0 +1,126 @@
+use bencher_endpoint::{CorsResponse, Endpoint, Post, ResponseCreated};
+use bencher_json::{JsonToken, system::auth::JsonCiAuth};
+use bencher_oidc::{Issuer, unverified_issuer};
+use bencher_rbac::project::Permission;
+use bencher_schema::{
+    conn_lock,
+    context::ApiContext,
+    error::{forbidden_error, resource_conflict_err, resource_not_found_err, unauthorized_error},
+    model::{
+        project::{QueryProject, trust_policy::QueryTrustPolicy},
+        user::{
+            QueryUser,
+            auth::AuthUser,
+            token::{InsertToken, QueryToken},
+        },
+    },
+    schema,
+};
+use diesel::{ExpressionMethods as _, QueryDsl as _, RunQueryDsl as _};
+use dropshot::{HttpError, RequestContext, TypedBody, endpoint};
+use slog::Logger;
+
+use crate::CI_TOKEN_TTL;
+
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/auth/ci",
+    tags = ["auth"]
+}]
+pub async fn auth_ci_options(
+    _rqctx: RequestContext<ApiContext>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Exchange a CI ID token for a CI token
+///
+/// Exchange an OpenID Connect ID token issued to a CI job for a short-lived CI token.
+/// The ID token must match one of the trust policies for the project.
+/// The CI token is issued for the user that created the matching trust policy,
+/// and it can only be used to view the project and submit runs.
+#[endpoint {
+    method = POST,
+    path = "/v0/auth/ci",
+    tags = ["auth"]
+}]
+pub async fn auth_ci_post(
+    rqctx: RequestContext<ApiContext>,
+    body: TypedBody<JsonCiAuth>,
+) -> Result<ResponseCreated<JsonToken>, HttpError> {
+    let json = post_inner(&rqctx.log, rqctx.context(), body.into_inner()).await?;
+    Ok(Post::pub_response_created(json))
+}
+
+async fn post_inner(
+    log: &Logger,
+    context: &ApiContext,
+    json_ci_auth: JsonCiAuth,
+) -> Result<JsonToken, HttpError> {
+    let id_token = json_ci_auth.id_token.as_ref();
+    let query_project = QueryProject::from_resource_id(conn_lock!(context), &json_ci_auth.project)?;
+
+    // Only issuers that are trusted by the project are ever contacted
+    let issuer = unverified_issuer(id_token).map_err(unauthorized_error)?;
+    let trust_policies =
+        QueryTrustPolicy::all_for_issuer(conn_lock!(context), &query_project, &issuer)?;
+    if trust_policies.is_empty() {
+        return Err(unauthorized_error(format!(
+            "No trust policy for project ({project}) with issuer ({issuer})",
+            project = json_ci_auth.project
+        )));
+    }
+
+    let issuer_url = issuer
+        .parse::<url::Url>()
+        .map_err(|e| unauthorized_error(format!("Invalid ID token issuer ({issuer}): {e}")))?;
+    // The audience for the ID token is the Bencher Console URL
+    let audience = context.console_url.as_str().trim_end_matches('/');
+    let allow_private = context.allow_private_webhooks;
+    let claims = Issuer::new(issuer_url)
+        .verify_ci_id_token(audience, id_token, |url| {
+            QueryTrustPolicy::is_allowed_url(url, allow_private)
+        })
+        .await
+        .map_err(unauthorized_error)?;
+    slog::info!(log, "Verified CI ID token"; "issuer" => &issuer, "sub" => &claims.sub);
+
+    let Some(query_trust_policy) = trust_policies
+        .into_iter()
+        .find(|policy| policy.is_trusted(&claims))
+    else {
+        return Err(forbidden_error(format!(
+            "No trust policy for project ({project}) matches the ID token ({sub})",
+            project = json_ci_auth.project,
+            sub = claims.sub
+        )));
+    };
+
+    // The user that created the trust policy must still be allowed to create runs
+    let query_user = QueryUser::get(conn_lock!(context), query_trust_policy.user_id)?;
+    let auth_user = AuthUser::from_user(conn_lock!(context), query_user)?;
+    query_project.try_allowed(&context.rbac, &auth_user, Permission::Create)?;
+
+    let pruned = QueryToken::prune_expired_ci(conn_lock!(context), &query_project)?;
+    if pruned > 0 {
+        slog::info!(log, "Pruned expired CI tokens"; "project" => %query_project.uuid, "count" => pruned);
+    }
+
+    let insert_token = InsertToken::from_trust_policy(
+        conn_lock!(context),
+        &context.token_key,
+        &query_project,
+        &query_trust_policy,
+        CI_TOKEN_TTL,
+    )?;
+    diesel::insert_into(schema::token::table)
+        .values(&insert_token)
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(Token, insert_token))?;
+
+    conn_lock!(context, |conn| schema::token::table
+        .filter(schema::token::uuid.eq(&insert_token.uuid))
+        .first::<QueryToken>(conn)
+        .map_err(resource_not_found_err!(Token, insert_token))?
+        .into_json(conn))
+}
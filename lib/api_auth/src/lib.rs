@@ -1,7 +1,10 @@
 mod accept;
+mod ci;
 mod confirm;
 mod github;
 mod login;
+mod oauth;
+mod oidc;
 mod signup;
 
 // TODO Custom max TTL
@@ -10,6 +13,8 @@ const AUTH_TOKEN_TTL: u32 = 30 * 60;
 // TODO Custom max TTL
 // 30 days * 24 hours / day * 60 minutes / hour * 60 seconds / minute
 const CLIENT_TOKEN_TTL: u32 = 30 * 24 * 60 * 60;
+// 1 hour * 60 minutes / hour * 60 seconds / minute
+const CI_TOKEN_TTL: u32 = 60 * 60;
 
 #[cfg(feature = "plus")]
 const PLAN_ARG: &str = "plan";
@@ -35,6 +40,12 @@ impl bencher_endpoint::Registrar for Api {
         api_description.register(confirm::auth_confirm_post)?;
         api_description.register(accept::auth_accept_post)?;
 
+        // CI OpenID Connect
+        if http_options {
+            api_description.register(ci::auth_ci_options)?;
+        }
+        api_description.register(ci::auth_ci_post)?;
+
         #[cfg(feature = "plus")]
         {
             // GitHub OAuth
@@ -42,6 +53,13 @@ impl bencher_endpoint::Registrar for Api {
                 api_description.register(github::auth_github_options)?;
             }
             api_description.register(github::auth_github_post)?;
+
+            // OpenID Connect
+            if http_options {
+                api_description.register(oidc::auth_oidc_options)?;
+            }
+            api_description.register(oidc::auth_oidc_get)?;
+            api_description.register(oidc::auth_oidc_post)?;
         }
 
         Ok(())
@@ -0,0 +1,113 @@
+#![cfg(feature = "plus")]
+
+use bencher_json::{Email, JsonAuthUser, JsonSignup, PlanLevel, UserName, system::auth::JsonOAuth};
+use bencher_schema::{
+    conn_lock,
+    context::ApiContext,
+    error::{issue_error, payment_required_error},
+    model::{
+        organization::{QueryOrganization, plan::LicenseUsage},
+        user::{InsertUser, QueryUser},
+    },
+};
+use dropshot::HttpError;
+use slog::Logger;
+
+use super::CLIENT_TOKEN_TTL;
+
+/// If not on Bencher Cloud, then at least one organization must have a valid Bencher Plus license
+pub async fn check_license(context: &ApiContext, provider: &str) -> Result<(), HttpError> {
+    if !context.is_bencher_cloud
+        && LicenseUsage::get_for_server(
+            &context.database.connection,
+            &context.licensor,
+            Some(PlanLevel::Enterprise),
+        )
+        .await?
+        .is_empty()
+    {
+        return Err(payment_required_error(format!(
+            "You must have a valid Bencher Plus Enterprise license for at least one organization on the server to use {provider}"
+        )));
+    }
+    Ok(())
+}
+
+/// Log in or sign up the user authenticated by an OAuth provider
+pub async fn oauth_user(
+    log: &Logger,
+    context: &ApiContext,
+    json_oauth: JsonOAuth,
+    name: UserName,
+    email: Email,
+    provider: &str,
+) -> Result<JsonAuthUser, HttpError> {
+    // If the user already exists, then we just need to check if they are locked and possible accept an invite
+    // Otherwise, we need to create a new user and notify the admins
+    let query_user = QueryUser::get_with_email(conn_lock!(context), &email);
+    let user = if let Ok(query_user) = query_user {
+        query_user.check_is_locked()?;
+        if let Some(invite) = &json_oauth.invite {
+            query_user.accept_invite(conn_lock!(context), &context.token_key, invite)?;
+        } else if let Some(organization_uuid) = json_oauth.claim {
+            let query_organization =
+                QueryOrganization::from_uuid(conn_lock!(context), organization_uuid)?;
+            query_organization.claim(context, &query_user).await?;
+        }
+        query_user
+    } else {
+        let json_signup = JsonSignup {
+            name,
+            slug: None,
+            email: email.clone(),
+            plan: json_oauth.plan,
+            invite: json_oauth.invite.clone(),
+            claim: json_oauth.claim,
+            i_agree: true,
+        };
+
+        let invited = json_signup.invite.is_some();
+        let insert_user =
+            InsertUser::from_json(conn_lock!(context), &context.token_key, &json_signup)?;
+
+        insert_user.notify(
+            log,
+            conn_lock!(context),
+            &context.messenger,
+            &context.console_url,
+            invited,
+            provider,
+        )?;
+
+        QueryUser::get_with_email(conn_lock!(context), &email)?
+    }
+    .into_json();
+
+    let token = context
+        .token_key
+        .new_client(email.clone(), CLIENT_TOKEN_TTL)
+        .map_err(|e| {
+            issue_error(
+                &format!("Failed to create client JWT for {provider}"),
+                &format!(
+                    "Failed to create client JWT for {provider} ({email} | {CLIENT_TOKEN_TTL})"
+                ),
+                e,
+            )
+        })?;
+
+    let claims = context.token_key.validate_client(&token).map_err(|e| {
+        issue_error(
+            &format!("Failed to validate new client JWT for {provider}"),
+            &format!("Failed to validate new client JWT for {provider}: {token}"),
+            e,
+        )
+    })?;
+
+    Ok(JsonAuthUser {
+        user,
+        token,
+        creation: claims.issued_at(),
+        expiration: claims.expiration(),
+    })
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "plus")]
+
+use bencher_endpoint::{CorsResponse, Endpoint, Get, Post, ResponseAccepted, ResponseOk};
+use bencher_json::{
+    JsonAuthUser, NonEmpty,
+    system::auth::{JsonOAuth, JsonOAuthUrl},
+};
+use bencher_oidc::Oidc;
+use bencher_schema::{
+    context::ApiContext,
+    error::{issue_error, payment_required_error, unauthorized_error},
+};
+use dropshot::{HttpError, Query, RequestContext, TypedBody, endpoint};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use slog::Logger;
+use url::Url;
+
+use crate::oauth::{check_license, oauth_user};
+
+pub const OIDC: &str = "OpenID Connect";
+const OIDC_REDIRECT_PATH: &str = "/auth/oidc";
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AuthOidcQuery {
+    /// The state to pass through the OpenID Connect provider.
+    /// This is returned to the Console along with the authorization code.
+    pub state: Option<NonEmpty>,
+}
+
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/auth/oidc",
+    tags = ["auth"]
+}]
+pub async fn auth_oidc_options(
+    _rqctx: RequestContext<ApiContext>,
+    _query_params: Query<AuthOidcQuery>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Post.into()]))
+}
+
+/// Get the OpenID Connect authorization URL
+///
+/// Get the URL to redirect the user to in order to log in with the configured OpenID Connect provider.
+/// After authenticating, the provider redirects the user to the Console with an authorization code.
+#[endpoint {
+    method = GET,
+    path =  "/v0/auth/oidc",
+    tags = ["auth"]
+}]
+pub async fn auth_oidc_get(
+    rqctx: RequestContext<ApiContext>,
+    query_params: Query<AuthOidcQuery>,
+) -> Result<ResponseOk<JsonOAuthUrl>, HttpError> {
+    let json = get_inner(&rqctx.log, rqctx.context(), query_params.into_inner()).await?;
+    Ok(Get::pub_response_ok(json))
+}
+
+async fn get_inner(
+    log: &Logger,
+    context: &ApiContext,
+    query_params: AuthOidcQuery,
+) -> Result<JsonOAuthUrl, HttpError> {
+    let oidc = oidc(log, context)?;
+    check_license(context, OIDC).await?;
+
+    let url = oidc
+        .authorization_url(redirect_url(context)?, query_params.state.map(Into::into))
+        .await
+        .map_err(|e| {
+            issue_error(
+                "Failed to create OpenID Connect authorization URL",
+                &format!(
+                    "Failed to create OpenID Connect authorization URL for {issuer}",
+                    issuer = oidc.issuer().as_str()
+                ),
+                e,
+            )
+        })?;
+
+    Ok(JsonOAuthUrl { url: url.into() })
+}
+
+/// Log in with OpenID Connect
+///
+/// Exchange an authorization code from the configured OpenID Connect provider for a client token.
+/// If a user with the verified email does not exist, then a new user is created.
+#[endpoint {
+    method = POST,
+    path = "/v0/auth/oidc",
+    tags = ["auth"]
+}]
+pub async fn auth_oidc_post(
+    rqctx: RequestContext<ApiContext>,
+    body: TypedBody<JsonOAuth>,
+) -> Result<ResponseAccepted<JsonAuthUser>, HttpError> {
+    let json = post_inner(&rqctx.log, rqctx.context(), body.into_inner()).await?;
+    Ok(Post::pub_response_accepted(json))
+}
+
+async fn post_inner(
+    log: &Logger,
+    context: &ApiContext,
+    json_oauth: JsonOAuth,
+) -> Result<JsonAuthUser, HttpError> {
+    let oidc = oidc(log, context)?;
+    check_license(context, OIDC).await?;
+
+    let (name, email) = oidc
+        .oauth_user(redirect_url(context)?, json_oauth.code.clone())
+        .await
+        .map_err(unauthorized_error)?;
+
+    oauth_user(log, context, json_oauth, name, email, OIDC).await
+}
+
+fn oidc<'a>(log: &Logger, context: &'a ApiContext) -> Result<&'a Oidc, HttpError> {
+    context.oidc.as_ref().ok_or_else(|| {
+        let err = "OpenID Connect is not configured";
+        slog::warn!(log, "{err}");
+        payment_required_error(err)
+    })
+}
+
+fn redirect_url(context: &ApiContext) -> Result<Url, HttpError> {
+    context.console_url.join(OIDC_REDIRECT_PATH).map_err(|e| {
+        issue_error(
+            "Failed to create OpenID Connect redirect URL",
+            &format!(
+                "Failed to create OpenID Connect redirect URL for {console_url}",
+                console_url = context.console_url
+            ),
+            e,
+        )
+    })
+}
@@ -1,7 +1,10 @@
 use bencher_endpoint::{CorsResponse, Endpoint, Get, Patch, ResponseOk, TotalCount};
 use bencher_json::{
     AlertUuid, JsonAlert, JsonAlerts, JsonDirection, JsonPagination, ResourceId,
-    project::alert::{AlertStatus, JsonUpdateAlert},
+    project::{
+        alert::{AlertStatus, JsonUpdateAlert},
+        webhook::JsonWebhookEventData,
+    },
 };
 use bencher_rbac::project::Permission;
 use bencher_schema::{
@@ -12,6 +15,7 @@ use bencher_schema::{
         project::{
             QueryProject,
             threshold::alert::{QueryAlert, UpdateAlert},
+            webhook::QueryWebhook,
         },
         user::auth::{AuthUser, BearerToken, PubBearerToken},
     },
@@ -24,6 +28,7 @@ use diesel::{
 use dropshot::{HttpError, Path, Query, RequestContext, TypedBody, endpoint};
 use schemars::JsonSchema;
 use serde::Deserialize;
+use slog::Logger;
 
 #[derive(Deserialize, JsonSchema)]
 pub struct ProjAlertsParams {
@@ -360,6 +365,7 @@ pub async fn proj_alert_patch(
 ) -> Result<ResponseOk<JsonAlert>, HttpError> {
     let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
     let json = patch_inner(
+        &rqctx.log,
         rqctx.context(),
         path_params.into_inner(),
         body.into_inner(),
@@ -370,6 +376,7 @@ pub async fn proj_alert_patch(
 }
 
 async fn patch_inner(
+    log: &Logger,
     context: &ApiContext,
     path_params: ProjAlertParams,
     json_alert: JsonUpdateAlert,
@@ -395,5 +402,22 @@ async fn patch_inner(
     let alert = QueryAlert::get(conn_lock!(context), query_alert.id)?;
 
     // Separate out this query to prevent a deadlock when getting the conn_lock
-    alert.into_json(context).await
+    let json_alert = alert.into_json(context).await?;
+
+    // Only notify the project webhooks if the alert was just dismissed
+    if matches!(json_alert.status, AlertStatus::Dismissed)
+        && !matches!(query_alert.status, AlertStatus::Dismissed)
+    {
+        QueryWebhook::send_events(
+            log,
+            context,
+            &query_project,
+            vec![JsonWebhookEventData::AlertDismissed(Box::new(
+                json_alert.clone(),
+            ))],
+        )
+        .await;
+    }
+
+    Ok(json_alert)
 }
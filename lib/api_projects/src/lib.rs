@@ -10,12 +10,15 @@ mod projects;
 mod reports;
 mod testbeds;
 mod thresholds;
+mod trust_policies;
+mod webhooks;
 
 mod macros;
 
 pub struct Api;
 
 impl bencher_endpoint::Registrar for Api {
+    #[expect(clippy::too_many_lines)]
     fn register(
         api_description: &mut dropshot::ApiDescription<bencher_schema::ApiContext>,
         http_options: bool,
@@ -141,6 +144,28 @@ impl bencher_endpoint::Registrar for Api {
         api_description.register(alerts::proj_alert_get)?;
         api_description.register(alerts::proj_alert_patch)?;
 
+        // Trust Policies
+        if http_options {
+            api_description.register(trust_policies::proj_trust_policies_options)?;
+            api_description.register(trust_policies::proj_trust_policy_options)?;
+        }
+        api_description.register(trust_policies::proj_trust_policies_get)?;
+        api_description.register(trust_policies::proj_trust_policy_post)?;
+        api_description.register(trust_policies::proj_trust_policy_get)?;
+        api_description.register(trust_policies::proj_trust_policy_delete)?;
+
+        // Webhooks
+        if http_options {
+            api_description.register(webhooks::proj_webhooks_options)?;
+            api_description.register(webhooks::proj_webhook_options)?;
+            api_description.register(webhooks::proj_webhook_deliveries_options)?;
+        }
+        api_description.register(webhooks::proj_webhooks_get)?;
+        api_description.register(webhooks::proj_webhook_post)?;
+        api_description.register(webhooks::proj_webhook_get)?;
+        api_description.register(webhooks::proj_webhook_delete)?;
+        api_description.register(webhooks::proj_webhook_deliveries_get)?;
+
         Ok(())
     }
 }
@@ -149,6 +149,10 @@ async fn get_one_inner(
                     schema::model::window,
                     schema::model::lower_boundary,
                     schema::model::upper_boundary,
+                    schema::model::smoothing,
+                    schema::model::trend,
+                    schema::model::season,
+                    schema::model::environment_change,
                     schema::model::created,
                     schema::model::replaced,
                 ),
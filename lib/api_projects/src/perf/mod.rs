@@ -330,6 +330,10 @@ async fn perf_query(
                     schema::model::window,
                     schema::model::lower_boundary,
                     schema::model::upper_boundary,
+                    schema::model::smoothing,
+                    schema::model::trend,
+                    schema::model::season,
+                    schema::model::environment_change,
                     schema::model::created,
                     schema::model::replaced,
                 ),
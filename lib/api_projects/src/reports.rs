@@ -321,6 +321,7 @@ pub async fn proj_report_options(
 /// View a report for a project.
 /// If the project is public, then the user does not need to be authenticated.
 /// If the project is private, then the user must be authenticated and have `view` permissions for the project.
+/// A CI token can only view the reports that it created.
 #[endpoint {
     method = GET,
     path =  "/v0/projects/{project}/reports/{report}",
@@ -331,7 +332,7 @@ pub async fn proj_report_get(
     bearer_token: PubBearerToken,
     path_params: Path<ProjReportParams>,
 ) -> Result<ResponseOk<JsonReport>, HttpError> {
-    let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
+    let auth_user = AuthUser::from_pub_ci_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(
         &rqctx.log,
         rqctx.context(),
@@ -355,13 +356,20 @@ async fn get_one_inner(
         auth_user,
     )?;
 
-    let report = QueryReport::belonging_to(&query_project)
+    let mut query = QueryReport::belonging_to(&query_project)
         .filter(schema::report::uuid.eq(path_params.report.to_string()))
-        .first::<QueryReport>(conn_lock!(context))
-        .map_err(resource_not_found_err!(
-            Report,
-            (&query_project, path_params.report)
-        ))?;
+        .into_boxed();
+    // A CI token can only view the reports that it created
+    if let Some(ci_token) = auth_user.and_then(AuthUser::ci_token) {
+        query = query.filter(schema::report::token_id.eq(ci_token.id));
+    }
+    let report =
+        query
+            .first::<QueryReport>(conn_lock!(context))
+            .map_err(resource_not_found_err!(
+                Report,
+                (&query_project, path_params.report)
+            ))?;
 
     // Separate out this query to prevent a deadlock when getting the conn_lock
     report.into_json(log, context).await
@@ -8,6 +8,7 @@ use bencher_json::{
         JsonNewThreshold, JsonRemoveModel, JsonThreshold, JsonThresholdQuery,
         JsonThresholdQueryParams, JsonUpdateModel, JsonUpdateThreshold,
     },
+    project::webhook::JsonWebhookEventData,
 };
 use bencher_rbac::project::Permission;
 use bencher_schema::{
@@ -24,6 +25,7 @@ use bencher_schema::{
             measure::QueryMeasure,
             testbed::QueryTestbed,
             threshold::{InsertThreshold, QueryThreshold, model::QueryModel},
+            webhook::QueryWebhook,
         },
         user::auth::{AuthUser, BearerToken, PubBearerToken},
     },
@@ -36,6 +38,7 @@ use diesel::{
 use dropshot::{HttpError, Path, Query, RequestContext, TypedBody, endpoint};
 use schemars::JsonSchema;
 use serde::Deserialize;
+use slog::Logger;
 
 use crate::macros::{filter_branch_name_id, filter_measure_name_id, filter_testbed_name_id};
 
@@ -415,6 +418,7 @@ pub async fn proj_threshold_put(
 ) -> Result<ResponseOk<JsonThreshold>, HttpError> {
     let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
     let json = put_inner(
+        &rqctx.log,
         rqctx.context(),
         path_params.into_inner(),
         body.into_inner(),
@@ -425,6 +429,7 @@ pub async fn proj_threshold_put(
 }
 
 async fn put_inner(
+    log: &Logger,
     context: &ApiContext,
     path_params: ProjThresholdParams,
     json_threshold: JsonUpdateThreshold,
@@ -458,10 +463,25 @@ async fn put_inner(
         .await?;
 
     // Get the updated threshold with the new model
-    let query_threshold = QueryThreshold::get(conn_lock!(context), query_threshold.id)?;
+    let updated_threshold = QueryThreshold::get(conn_lock!(context), query_threshold.id)?;
+    let model_changed = updated_threshold.model_id != query_threshold.model_id;
+    let json_threshold = updated_threshold.into_json(context).await?;
+
+    // Only notify the project webhooks if the threshold model actually changed
+    if model_changed {
+        QueryWebhook::send_events(
+            log,
+            context,
+            &query_project,
+            vec![JsonWebhookEventData::ThresholdUpdated(Box::new(
+                json_threshold.clone(),
+            ))],
+        )
+        .await;
+    }
 
     // Return the updated threshold with the new model
-    query_threshold.into_json(context).await
+    Ok(json_threshold)
 }
 
 /// Delete a threshold
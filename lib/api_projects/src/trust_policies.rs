@@ -0,0 +1,321 @@
+use bencher_endpoint::{
+    CorsResponse, Delete, Endpoint, Get, Post, ResponseCreated, ResponseDeleted, ResponseOk,
+    TotalCount,
+};
+use bencher_json::{
+    JsonDirection, JsonNewTrustPolicy, JsonPagination, JsonTrustPolicies, JsonTrustPolicy,
+    ResourceId, TrustPolicyUuid,
+};
+use bencher_rbac::project::Permission;
+use bencher_schema::{
+    conn_lock,
+    context::ApiContext,
+    error::{resource_conflict_err, resource_not_found_err},
+    model::{
+        project::{
+            QueryProject,
+            trust_policy::{InsertTrustPolicy, QueryTrustPolicy},
+        },
+        user::auth::{AuthUser, BearerToken},
+    },
+    schema,
+};
+use diesel::{BelongingToDsl as _, ExpressionMethods as _, QueryDsl as _, RunQueryDsl as _};
+use dropshot::{HttpError, Path, Query, RequestContext, TypedBody, endpoint};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjTrustPoliciesParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+}
+
+pub type ProjTrustPoliciesPagination = JsonPagination<ProjTrustPoliciesSort>;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjTrustPoliciesSort {
+    /// Sort by trust policy creation date time.
+    #[default]
+    Created,
+}
+
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/trust",
+    tags = ["projects", "trust"]
+}]
+pub async fn proj_trust_policies_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjTrustPoliciesParams>,
+    _pagination_params: Query<ProjTrustPoliciesPagination>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Post.into()]))
+}
+
+/// List trust policies for a project
+///
+/// List all trust policies for a project.
+/// The user must have `view` permissions for the project.
+/// By default, the trust policies are sorted by creation date time in chronological order.
+/// The HTTP response header `X-Total-Count` contains the total number of trust policies.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/trust",
+    tags = ["projects", "trust"]
+}]
+pub async fn proj_trust_policies_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjTrustPoliciesParams>,
+    pagination_params: Query<ProjTrustPoliciesPagination>,
+) -> Result<ResponseOk<JsonTrustPolicies>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let (json, total_count) = get_ls_inner(
+        rqctx.context(),
+        &auth_user,
+        path_params.into_inner(),
+        pagination_params.into_inner(),
+    )
+    .await?;
+    Ok(Get::auth_response_ok_with_total_count(json, total_count))
+}
+
+async fn get_ls_inner(
+    context: &ApiContext,
+    auth_user: &AuthUser,
+    path_params: ProjTrustPoliciesParams,
+    pagination_params: ProjTrustPoliciesPagination,
+) -> Result<(JsonTrustPolicies, TotalCount), HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::View,
+    )?;
+
+    let trust_policies = get_ls_query(&query_project, &pagination_params)
+        .offset(pagination_params.offset())
+        .limit(pagination_params.limit())
+        .load::<QueryTrustPolicy>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            TrustPolicy,
+            (&query_project, &pagination_params)
+        ))?;
+
+    // Separate out these queries to prevent a deadlock when getting the conn_lock
+    let mut json_trust_policies = Vec::with_capacity(trust_policies.len());
+    for trust_policy in trust_policies {
+        match trust_policy.into_json_for_project(conn_lock!(context), &query_project) {
+            Ok(trust_policy) => json_trust_policies.push(trust_policy),
+            Err(err) => {
+                debug_assert!(false, "{err}");
+                #[cfg(feature = "sentry")]
+                sentry::capture_error(&err);
+            },
+        }
+    }
+
+    let total_count = get_ls_query(&query_project, &pagination_params)
+        .count()
+        .get_result::<i64>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            TrustPolicy,
+            (&query_project, &pagination_params)
+        ))?
+        .try_into()?;
+
+    Ok((json_trust_policies.into(), total_count))
+}
+
+fn get_ls_query<'q>(
+    query_project: &'q QueryProject,
+    pagination_params: &ProjTrustPoliciesPagination,
+) -> schema::trust_policy::BoxedQuery<'q, diesel::sqlite::Sqlite> {
+    let query = QueryTrustPolicy::belonging_to(&query_project).into_boxed();
+
+    match pagination_params.order() {
+        ProjTrustPoliciesSort::Created => match pagination_params.direction {
+            Some(JsonDirection::Asc) | None => query.order(schema::trust_policy::created.asc()),
+            Some(JsonDirection::Desc) => query.order(schema::trust_policy::created.desc()),
+        },
+    }
+}
+
+/// Create a trust policy
+///
+/// Create a trust policy for a project.
+/// A trust policy allows CI jobs with a matching OpenID Connect ID token
+/// to exchange it for a short-lived CI token for the project, without storing an API token as a secret.
+/// The CI tokens are issued for the user that created the trust policy.
+/// The user must have `manage` permissions for the project.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/trust",
+    tags = ["projects", "trust"]
+}]
+pub async fn proj_trust_policy_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjTrustPoliciesParams>,
+    body: TypedBody<JsonNewTrustPolicy>,
+) -> Result<ResponseCreated<JsonTrustPolicy>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = post_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_created(json))
+}
+
+async fn post_inner(
+    context: &ApiContext,
+    path_params: ProjTrustPoliciesParams,
+    json_trust_policy: JsonNewTrustPolicy,
+    auth_user: &AuthUser,
+) -> Result<JsonTrustPolicy, HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Manage,
+    )?;
+
+    let insert_trust_policy = InsertTrustPolicy::from_json(
+        &query_project,
+        auth_user,
+        json_trust_policy,
+        context.allow_private_webhooks,
+    )?;
+    diesel::insert_into(schema::trust_policy::table)
+        .values(&insert_trust_policy)
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(TrustPolicy, insert_trust_policy))?;
+
+    conn_lock!(context, |conn| QueryTrustPolicy::get_with_uuid(
+        conn,
+        &query_project,
+        insert_trust_policy.uuid
+    )
+    .and_then(
+        |trust_policy| trust_policy.into_json_for_project(conn, &query_project)
+    ))
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjTrustPolicyParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+    /// The UUID for a trust policy.
+    pub trust_policy: TrustPolicyUuid,
+}
+
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/trust/{trust_policy}",
+    tags = ["projects", "trust"]
+}]
+pub async fn proj_trust_policy_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjTrustPolicyParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Delete.into()]))
+}
+
+/// View a trust policy
+///
+/// View a trust policy for a project.
+/// The user must have `view` permissions for the project.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/trust/{trust_policy}",
+    tags = ["projects", "trust"]
+}]
+pub async fn proj_trust_policy_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjTrustPolicyParams>,
+) -> Result<ResponseOk<JsonTrustPolicy>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = get_one_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Get::auth_response_ok(json))
+}
+
+async fn get_one_inner(
+    context: &ApiContext,
+    path_params: ProjTrustPolicyParams,
+    auth_user: &AuthUser,
+) -> Result<JsonTrustPolicy, HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::View,
+    )?;
+
+    conn_lock!(context, |conn| QueryTrustPolicy::get_with_uuid(
+        conn,
+        &query_project,
+        path_params.trust_policy
+    )
+    .and_then(
+        |trust_policy| trust_policy.into_json_for_project(conn, &query_project)
+    ))
+}
+
+/// Delete a trust policy
+///
+/// Delete a trust policy for a project.
+/// CI tokens that were already issued for the trust policy are not revoked.
+/// The user must have `manage` permissions for the project.
+#[endpoint {
+    method = DELETE,
+    path =  "/v0/projects/{project}/trust/{trust_policy}",
+    tags = ["projects", "trust"]
+}]
+pub async fn proj_trust_policy_delete(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjTrustPolicyParams>,
+) -> Result<ResponseDeleted, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    delete_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Delete::auth_response_deleted())
+}
+
+async fn delete_inner(
+    context: &ApiContext,
+    path_params: ProjTrustPolicyParams,
+    auth_user: &AuthUser,
+) -> Result<(), HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Manage,
+    )?;
+
+    let query_trust_policy = QueryTrustPolicy::get_with_uuid(
+        conn_lock!(context),
+        &query_project,
+        path_params.trust_policy,
+    )?;
+
+    diesel::delete(
+        schema::trust_policy::table.filter(schema::trust_policy::id.eq(query_trust_policy.id)),
+    )
+    .execute(conn_lock!(context))
+    .map_err(resource_conflict_err!(TrustPolicy, query_trust_policy))?;
+
+    Ok(())
+}
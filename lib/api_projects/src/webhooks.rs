@@ -0,0 +1,402 @@
+use bencher_endpoint::{
+    CorsResponse, Delete, Endpoint, Get, Post, ResponseCreated, ResponseDeleted, ResponseOk,
+    TotalCount,
+};
+use bencher_json::{
+    JsonDirection, JsonNewWebhook, JsonPagination, JsonWebhook, JsonWebhookDeliveries,
+    JsonWebhooks, ResourceId, WebhookUuid,
+};
+use bencher_rbac::project::Permission;
+use bencher_schema::{
+    conn_lock,
+    context::ApiContext,
+    error::{resource_conflict_err, resource_not_found_err},
+    model::{
+        project::{
+            QueryProject,
+            webhook::{InsertWebhook, QueryWebhook, delivery::QueryWebhookDelivery},
+        },
+        user::auth::{AuthUser, BearerToken},
+    },
+    schema,
+};
+use diesel::{BelongingToDsl as _, ExpressionMethods as _, QueryDsl as _, RunQueryDsl as _};
+use dropshot::{HttpError, Path, Query, RequestContext, TypedBody, endpoint};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjWebhooksParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+}
+
+pub type ProjWebhooksPagination = JsonPagination<ProjWebhooksSort>;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjWebhooksSort {
+    /// Sort by webhook creation date time.
+    #[default]
+    Created,
+}
+
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/webhooks",
+    tags = ["projects", "webhooks"]
+}]
+pub async fn proj_webhooks_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjWebhooksParams>,
+    _pagination_params: Query<ProjWebhooksPagination>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Post.into()]))
+}
+
+/// List webhooks for a project
+///
+/// List all webhooks for a project.
+/// The user must have `manage` permissions for the project.
+/// By default, the webhooks are sorted by creation date time in chronological order.
+/// The HTTP response header `X-Total-Count` contains the total number of webhooks.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/webhooks",
+    tags = ["projects", "webhooks"]
+}]
+pub async fn proj_webhooks_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjWebhooksParams>,
+    pagination_params: Query<ProjWebhooksPagination>,
+) -> Result<ResponseOk<JsonWebhooks>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let (json, total_count) = get_ls_inner(
+        rqctx.context(),
+        &auth_user,
+        path_params.into_inner(),
+        pagination_params.into_inner(),
+    )
+    .await?;
+    Ok(Get::auth_response_ok_with_total_count(json, total_count))
+}
+
+async fn get_ls_inner(
+    context: &ApiContext,
+    auth_user: &AuthUser,
+    path_params: ProjWebhooksParams,
+    pagination_params: ProjWebhooksPagination,
+) -> Result<(JsonWebhooks, TotalCount), HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Manage,
+    )?;
+
+    let webhooks = get_ls_query(&query_project, &pagination_params)
+        .offset(pagination_params.offset())
+        .limit(pagination_params.limit())
+        .load::<QueryWebhook>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            Webhook,
+            (&query_project, &pagination_params)
+        ))?;
+
+    let json_webhooks = webhooks
+        .into_iter()
+        .map(|webhook| webhook.into_json_for_project(&query_project))
+        .collect();
+
+    let total_count = get_ls_query(&query_project, &pagination_params)
+        .count()
+        .get_result::<i64>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            Webhook,
+            (&query_project, &pagination_params)
+        ))?
+        .try_into()?;
+
+    Ok((json_webhooks, total_count))
+}
+
+fn get_ls_query<'q>(
+    query_project: &'q QueryProject,
+    pagination_params: &ProjWebhooksPagination,
+) -> schema::webhook::BoxedQuery<'q, diesel::sqlite::Sqlite> {
+    let query = QueryWebhook::belonging_to(&query_project).into_boxed();
+
+    match pagination_params.order() {
+        ProjWebhooksSort::Created => match pagination_params.direction {
+            Some(JsonDirection::Asc) | None => query.order(schema::webhook::created.asc()),
+            Some(JsonDirection::Desc) => query.order(schema::webhook::created.desc()),
+        },
+    }
+}
+
+/// Create a webhook
+///
+/// Create a webhook for a project.
+/// A webhook is sent a signed JSON payload whenever one of the events that it is subscribed to happens in the project.
+/// Alternatively, a webhook can be sent a chat message formatted for Slack, Microsoft Teams, or Matrix.
+/// Failed deliveries are retried with exponential backoff.
+/// The user must have `manage` permissions for the project.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/webhooks",
+    tags = ["projects", "webhooks"]
+}]
+pub async fn proj_webhook_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjWebhooksParams>,
+    body: TypedBody<JsonNewWebhook>,
+) -> Result<ResponseCreated<JsonWebhook>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = post_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_created(json))
+}
+
+async fn post_inner(
+    context: &ApiContext,
+    path_params: ProjWebhooksParams,
+    json_webhook: JsonNewWebhook,
+    auth_user: &AuthUser,
+) -> Result<JsonWebhook, HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Manage,
+    )?;
+
+    let insert_webhook =
+        InsertWebhook::from_json(&query_project, json_webhook, context.allow_private_webhooks)?;
+    diesel::insert_into(schema::webhook::table)
+        .values(&insert_webhook)
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(Webhook, insert_webhook))?;
+
+    QueryWebhook::get_with_uuid(conn_lock!(context), &query_project, insert_webhook.uuid)
+        .map(|webhook| webhook.into_json_for_project(&query_project))
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjWebhookParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+    /// The UUID for a webhook.
+    pub webhook: WebhookUuid,
+}
+
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/webhooks/{webhook}",
+    tags = ["projects", "webhooks"]
+}]
+pub async fn proj_webhook_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjWebhookParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Delete.into()]))
+}
+
+/// View a webhook
+///
+/// View a webhook for a project.
+/// The user must have `manage` permissions for the project.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/webhooks/{webhook}",
+    tags = ["projects", "webhooks"]
+}]
+pub async fn proj_webhook_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjWebhookParams>,
+) -> Result<ResponseOk<JsonWebhook>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = get_one_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Get::auth_response_ok(json))
+}
+
+async fn get_one_inner(
+    context: &ApiContext,
+    path_params: ProjWebhookParams,
+    auth_user: &AuthUser,
+) -> Result<JsonWebhook, HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Manage,
+    )?;
+
+    QueryWebhook::get_with_uuid(conn_lock!(context), &query_project, path_params.webhook)
+        .map(|webhook| webhook.into_json_for_project(&query_project))
+}
+
+/// Delete a webhook
+///
+/// Delete a webhook for a project.
+/// The delivery log for the webhook is also deleted.
+/// The user must have `manage` permissions for the project.
+#[endpoint {
+    method = DELETE,
+    path =  "/v0/projects/{project}/webhooks/{webhook}",
+    tags = ["projects", "webhooks"]
+}]
+pub async fn proj_webhook_delete(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjWebhookParams>,
+) -> Result<ResponseDeleted, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    delete_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Delete::auth_response_deleted())
+}
+
+async fn delete_inner(
+    context: &ApiContext,
+    path_params: ProjWebhookParams,
+    auth_user: &AuthUser,
+) -> Result<(), HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Manage,
+    )?;
+
+    let query_webhook =
+        QueryWebhook::get_with_uuid(conn_lock!(context), &query_project, path_params.webhook)?;
+
+    diesel::delete(schema::webhook::table.filter(schema::webhook::id.eq(query_webhook.id)))
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(Webhook, query_webhook))?;
+
+    Ok(())
+}
+
+pub type ProjWebhookDeliveriesPagination = JsonPagination<ProjWebhookDeliveriesSort>;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjWebhookDeliveriesSort {
+    /// Sort by webhook delivery creation date time.
+    #[default]
+    Created,
+}
+
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/webhooks/{webhook}/deliveries",
+    tags = ["projects", "webhooks"]
+}]
+pub async fn proj_webhook_deliveries_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjWebhookParams>,
+    _pagination_params: Query<ProjWebhookDeliveriesPagination>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into()]))
+}
+
+/// List deliveries for a webhook
+///
+/// List the delivery log for a webhook.
+/// Each delivery shows its event, status, number of attempts, and the result of the last attempt.
+/// The user must have `manage` permissions for the project.
+/// By default, the deliveries are sorted by creation date time in reverse chronological order.
+/// The HTTP response header `X-Total-Count` contains the total number of deliveries.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/webhooks/{webhook}/deliveries",
+    tags = ["projects", "webhooks"]
+}]
+pub async fn proj_webhook_deliveries_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjWebhookParams>,
+    pagination_params: Query<ProjWebhookDeliveriesPagination>,
+) -> Result<ResponseOk<JsonWebhookDeliveries>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let (json, total_count) = get_deliveries_inner(
+        rqctx.context(),
+        &auth_user,
+        path_params.into_inner(),
+        pagination_params.into_inner(),
+    )
+    .await?;
+    Ok(Get::auth_response_ok_with_total_count(json, total_count))
+}
+
+async fn get_deliveries_inner(
+    context: &ApiContext,
+    auth_user: &AuthUser,
+    path_params: ProjWebhookParams,
+    pagination_params: ProjWebhookDeliveriesPagination,
+) -> Result<(JsonWebhookDeliveries, TotalCount), HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Manage,
+    )?;
+    let query_webhook =
+        QueryWebhook::get_with_uuid(conn_lock!(context), &query_project, path_params.webhook)?;
+
+    let deliveries = get_deliveries_query(&query_webhook, &pagination_params)
+        .offset(pagination_params.offset())
+        .limit(pagination_params.limit())
+        .load::<QueryWebhookDelivery>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            WebhookDelivery,
+            (&query_webhook, &pagination_params)
+        ))?;
+
+    let json_deliveries = deliveries
+        .into_iter()
+        .map(|delivery| delivery.into_json_for_webhook(&query_webhook))
+        .collect();
+
+    let total_count = get_deliveries_query(&query_webhook, &pagination_params)
+        .count()
+        .get_result::<i64>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            WebhookDelivery,
+            (&query_webhook, &pagination_params)
+        ))?
+        .try_into()?;
+
+    Ok((json_deliveries, total_count))
+}
+
+fn get_deliveries_query<'q>(
+    query_webhook: &'q QueryWebhook,
+    pagination_params: &ProjWebhookDeliveriesPagination,
+) -> schema::webhook_delivery::BoxedQuery<'q, diesel::sqlite::Sqlite> {
+    let query = QueryWebhookDelivery::belonging_to(query_webhook).into_boxed();
+
+    match pagination_params.order() {
+        ProjWebhookDeliveriesSort::Created => match pagination_params.direction {
+            Some(JsonDirection::Asc) => query.order(schema::webhook_delivery::created.asc()),
+            Some(JsonDirection::Desc) | None => {
+                query.order(schema::webhook_delivery::created.desc())
+            },
+        },
+    }
+}
@@ -1,10 +1,10 @@
 use bencher_endpoint::{CorsResponse, Endpoint, Post, ResponseCreated};
-use bencher_json::{JsonNewRun, JsonReport, ResourceName, RunContext, Slug};
+use bencher_json::{JsonNewReport, JsonNewRun, JsonReport, ResourceName, RunContext, Slug};
 use bencher_rbac::project::Permission;
 use bencher_schema::{
     conn_lock,
     context::ApiContext,
-    error::{bad_request_error, unauthorized_error},
+    error::{bad_request_error, forbidden_error, unauthorized_error},
     model::{
         project::{QueryProject, report::QueryReport},
         user::auth::{AuthUser, PubBearerToken},
@@ -27,6 +27,7 @@ pub async fn run_options(_rqctx: RequestContext<ApiContext>) -> Result<CorsRespo
 /// Create a run.
 /// The user does not need have an account yet or be authenticated.
 /// The project may or may not exist yet.
+/// A CI token may be used, but only for the project and branches that it is limited to.
 #[endpoint {
     method = POST,
     path =  "/v0/run",
@@ -37,7 +38,7 @@ pub async fn run_post(
     bearer_token: PubBearerToken,
     body: TypedBody<JsonNewRun>,
 ) -> Result<ResponseCreated<JsonReport>, HttpError> {
-    let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
+    let auth_user = AuthUser::from_pub_ci_token(rqctx.context(), bearer_token).await?;
     let json = post_inner(&rqctx.log, rqctx.context(), auth_user, body.into_inner()).await?;
     Ok(Post::auth_response_created(json))
 }
@@ -64,6 +65,20 @@ async fn post_inner(
         .await?
     };
 
+    let json_report: JsonNewReport = json_run.into();
+    // If the user is authenticated with a CI token,
+    // then check that the token is for this project and branch
+    if let Some(ci_token) = auth_user.as_ref().and_then(AuthUser::ci_token) {
+        if ci_token.project_id != Some(query_project.id) {
+            return Err(forbidden_error(format!(
+                "CI token ({uuid}) can not be used to create a run for project ({project})",
+                uuid = ci_token.uuid,
+                project = query_project.slug
+            )));
+        }
+        ci_token.check_branch(conn_lock!(context), query_project.id, &json_report.branch)?;
+    }
+
     let query_organization = query_project.organization(conn_lock!(context))?;
     let is_claimed = query_organization.is_claimed(conn_lock!(context))?;
     // If the organization is claimed, check permissions
@@ -87,7 +102,7 @@ async fn post_inner(
         log,
         context,
         &query_project,
-        json_run.into(),
+        json_report,
         auth_user.as_ref(),
     )
     .await
@@ -1,6 +1,7 @@
 mod backup;
 mod config;
 mod restart;
+mod restore;
 mod root;
 mod spec;
 mod stats;
@@ -30,6 +31,8 @@ impl bencher_endpoint::Registrar for Api {
             api_description.register(config::server_config_options)?;
             api_description.register(config::server_config_console_options)?;
             api_description.register(backup::server_backup_options)?;
+            api_description.register(restore::server_restore_options)?;
+            api_description.register(restore::server_restore_upload_options)?;
         }
         api_description.register(version::server_version_get)?;
         api_description.register(spec::server_spec_get)?;
@@ -38,6 +41,8 @@ impl bencher_endpoint::Registrar for Api {
         api_description.register(config::server_config_put)?;
         api_description.register(config::server_config_console_get)?;
         api_description.register(backup::server_backup_post)?;
+        api_description.register(restore::server_restore_post)?;
+        api_description.register(restore::server_restore_upload_post)?;
 
         #[cfg(feature = "plus")]
         {
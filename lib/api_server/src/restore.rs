@@ -0,0 +1,182 @@
+use std::path::Path;
+
+use bencher_endpoint::{CorsResponse, Endpoint, Post, ResponseAccepted};
+use bencher_json::{JsonRestore, JsonRestored};
+use bencher_schema::{
+    context::{ApiContext, RestoreSource},
+    error::{bad_request_error, payload_too_large_error},
+    model::user::{admin::AdminUser, auth::BearerToken},
+};
+use dropshot::{HttpError, Query, RequestContext, StreamingBody, TypedBody, endpoint};
+use futures_util::StreamExt as _;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use slog::Logger;
+use tokio::{fs::File, io::AsyncWriteExt as _};
+
+use crate::restart::countdown;
+
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/server/restore",
+    tags = ["server"]
+}]
+pub async fn server_restore_options(
+    _rqctx: RequestContext<ApiContext>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Restore server from data store
+///
+/// Restore the API server database from a backup in the data store and then restart the server.
+/// The backup is verified against the checksum in the data store manifest, if listed.
+/// It must be a Bencher database at a migration version that is compatible with the server.
+/// The current database is kept aside in the same directory.
+/// The user must be an admin on the server to use this route.
+#[endpoint {
+    method = POST,
+    path =  "/v0/server/restore",
+    tags = ["server"]
+}]
+pub async fn server_restore_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    body: TypedBody<JsonRestore>,
+) -> Result<ResponseAccepted<JsonRestored>, HttpError> {
+    let admin_user = AdminUser::from_token(rqctx.context(), bearer_token).await?;
+    let JsonRestore { backup, delay } = body.into_inner();
+    let json = restore_inner(
+        &rqctx.log,
+        rqctx.context(),
+        RestoreSource::DataStore(backup),
+        delay,
+        &admin_user,
+    )
+    .await?;
+    Ok(Post::auth_response_accepted(json))
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ServerRestoreUploadQuery {
+    /// The delay in seconds before the server restarts.
+    /// Defaults to 3 seconds, if not specified.
+    pub delay: Option<u64>,
+}
+
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/server/restore/upload",
+    tags = ["server"]
+}]
+pub async fn server_restore_upload_options(
+    _rqctx: RequestContext<ApiContext>,
+    _query_params: Query<ServerRestoreUploadQuery>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Restore server from upload
+///
+/// Restore the API server database from an uploaded backup and then restart the server.
+/// The backup may be compressed with gzip.
+/// It must be a Bencher database at a migration version that is compatible with the server.
+/// The current database is kept aside in the same directory.
+/// The user must be an admin on the server to use this route.
+#[endpoint {
+    method = POST,
+    path =  "/v0/server/restore/upload",
+    tags = ["server"],
+    // Database backups can be much larger than the default request body limit,
+    // so the configured maximum upload size is checked as the upload is streamed to disk.
+    request_body_max_bytes = usize::MAX,
+}]
+pub async fn server_restore_upload_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    query_params: Query<ServerRestoreUploadQuery>,
+    body: StreamingBody,
+) -> Result<ResponseAccepted<JsonRestored>, HttpError> {
+    let admin_user = AdminUser::from_token(rqctx.context(), bearer_token).await?;
+    let database = &rqctx.context().database;
+    let upload_path = database.upload_path();
+    save_upload(body, &upload_path, database.max_upload_size()).await?;
+    let json = restore_inner(
+        &rqctx.log,
+        rqctx.context(),
+        RestoreSource::File(upload_path),
+        query_params.into_inner().delay,
+        &admin_user,
+    )
+    .await?;
+    Ok(Post::auth_response_accepted(json))
+}
+
+async fn save_upload(
+    body: StreamingBody,
+    upload_path: &Path,
+    max_upload_size: u64,
+) -> Result<(), HttpError> {
+    // Never write over an existing file, such as another upload
+    let upload_file = File::create_new(upload_path)
+        .await
+        .map_err(|e| bad_request_error(format!("Failed to create upload file: {e}")))?;
+    if let Err(e) = write_upload(body, upload_file, max_upload_size).await {
+        let _rm = tokio::fs::remove_file(upload_path).await;
+        return Err(e);
+    }
+    Ok(())
+}
+
+async fn write_upload(
+    body: StreamingBody,
+    mut upload_file: File,
+    max_upload_size: u64,
+) -> Result<(), HttpError> {
+    let stream = body.into_stream();
+    tokio::pin!(stream);
+    let mut upload_size: u64 = 0;
+    while let Some(data) = stream.next().await {
+        let mut data = data?;
+        upload_size = u64::try_from(data.len())
+            .ok()
+            .and_then(|len| upload_size.checked_add(len))
+            .filter(|size| *size <= max_upload_size)
+            .ok_or_else(|| {
+                payload_too_large_error(format!(
+                    "Upload is larger than the maximum upload size ({max_upload_size} bytes)"
+                ))
+            })?;
+        upload_file
+            .write_all_buf(&mut data)
+            .await
+            .map_err(|e| bad_request_error(format!("Failed to write upload file: {e}")))?;
+    }
+    upload_file
+        .flush()
+        .await
+        .map_err(|e| bad_request_error(format!("Failed to write upload file: {e}")))
+}
+
+async fn restore_inner(
+    log: &Logger,
+    context: &ApiContext,
+    source: RestoreSource,
+    delay: Option<u64>,
+    admin_user: &AdminUser,
+) -> Result<JsonRestored, HttpError> {
+    let json = context
+        .database
+        .restore(source)
+        .await
+        .map_err(bad_request_error)?;
+    slog::warn!(
+        log,
+        "Restored database backup. Kept aside the previous database as {}",
+        json.previous
+    );
+
+    countdown(log, context.restart_tx.clone(), delay, admin_user.user().id);
+
+    Ok(json)
+}
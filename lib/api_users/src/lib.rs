@@ -1,3 +1,4 @@
+mod notifications;
 mod tokens;
 mod users;
 
@@ -27,6 +28,14 @@ impl bencher_endpoint::Registrar for Api {
         api_description.register(tokens::user_token_post)?;
         api_description.register(tokens::user_token_get)?;
         api_description.register(tokens::user_token_patch)?;
+        api_description.register(tokens::user_token_delete)?;
+
+        // Notifications
+        if http_options {
+            api_description.register(notifications::user_notification_options)?;
+        }
+        api_description.register(notifications::user_notification_get)?;
+        api_description.register(notifications::user_notification_patch)?;
 
         Ok(())
     }
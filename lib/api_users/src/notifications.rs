@@ -0,0 +1,135 @@
+use bencher_endpoint::{CorsResponse, Endpoint, Get, Patch, ResponseOk};
+use bencher_json::{JsonNotification, JsonUpdateNotification, ResourceId};
+use bencher_schema::{
+    conn_lock,
+    context::{ApiContext, DbConnection},
+    error::{BencherResource, resource_not_found_error},
+    model::{
+        project::QueryProject,
+        user::{
+            QueryUser,
+            auth::{AuthUser, BearerToken},
+            notification::QueryNotification,
+            same_user,
+        },
+    },
+};
+use dropshot::{HttpError, Path, RequestContext, TypedBody, endpoint};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct UserNotificationParams {
+    /// The slug or UUID for a user.
+    pub user: ResourceId,
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+}
+
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/users/{user}/notifications/{project}",
+    tags = ["users", "notifications"]
+}]
+pub async fn user_notification_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<UserNotificationParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Patch.into()]))
+}
+
+/// View notifications for a project
+///
+/// View the email notification settings for a user on a project.
+/// The user must be a member of the project.
+/// Only the authenticated user themselves and server admins have access to this endpoint.
+#[endpoint {
+    method = GET,
+    path =  "/v0/users/{user}/notifications/{project}",
+    tags = ["users", "notifications"]
+}]
+pub async fn user_notification_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<UserNotificationParams>,
+) -> Result<ResponseOk<JsonNotification>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = get_one_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Get::auth_response_ok(json))
+}
+
+async fn get_one_inner(
+    context: &ApiContext,
+    path_params: UserNotificationParams,
+    auth_user: &AuthUser,
+) -> Result<JsonNotification, HttpError> {
+    let query_user = QueryUser::from_resource_id(conn_lock!(context), &path_params.user)?;
+    same_user!(auth_user, context.rbac, query_user.uuid);
+
+    conn_lock!(context, |conn| {
+        let query_project = member_project(conn, &query_user, &path_params.project)?;
+        QueryNotification::get_json(conn, &query_user, &query_project)
+    })
+}
+
+/// Update notifications for a project
+///
+/// Update the email notification settings for a user on a project.
+/// Alert emails can be sent immediately, as a daily digest, or not at all.
+/// By default, alert emails are off.
+/// The user must be a member of the project.
+/// Only the authenticated user themselves and server admins have access to this endpoint.
+#[endpoint {
+    method = PATCH,
+    path =  "/v0/users/{user}/notifications/{project}",
+    tags = ["users", "notifications"]
+}]
+pub async fn user_notification_patch(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<UserNotificationParams>,
+    body: TypedBody<JsonUpdateNotification>,
+) -> Result<ResponseOk<JsonNotification>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = patch_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Patch::auth_response_ok(json))
+}
+
+async fn patch_inner(
+    context: &ApiContext,
+    path_params: UserNotificationParams,
+    json_notification: JsonUpdateNotification,
+    auth_user: &AuthUser,
+) -> Result<JsonNotification, HttpError> {
+    let query_user = QueryUser::from_resource_id(conn_lock!(context), &path_params.user)?;
+    same_user!(auth_user, context.rbac, query_user.uuid);
+
+    conn_lock!(context, |conn| {
+        let query_project = member_project(conn, &query_user, &path_params.project)?;
+        QueryNotification::update(conn, &query_user, &query_project, &json_notification)
+    })
+}
+
+// Do not leak information about projects that the user is not a member of.
+fn member_project(
+    conn: &mut DbConnection,
+    query_user: &QueryUser,
+    project: &ResourceId,
+) -> Result<QueryProject, HttpError> {
+    let query_project = QueryProject::from_resource_id(conn, project)?;
+    if QueryNotification::is_member(conn, query_user.id, &query_project)? {
+        Ok(query_project)
+    } else {
+        Err(resource_not_found_error(
+            BencherResource::Project,
+            project,
+            format!("User ({}) is not a member of the project", query_user.uuid),
+        ))
+    }
+}
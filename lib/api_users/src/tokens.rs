@@ -1,5 +1,6 @@
 use bencher_endpoint::{
-    CorsResponse, Endpoint, Get, Patch, Post, ResponseCreated, ResponseOk, TotalCount,
+    CorsResponse, Delete, Endpoint, Get, Patch, Post, ResponseCreated, ResponseDeleted, ResponseOk,
+    TotalCount,
 };
 use bencher_json::{
     JsonDirection, JsonNewToken, JsonPagination, JsonToken, JsonTokens, ResourceId, ResourceName,
@@ -112,11 +113,10 @@ async fn get_ls_inner(
             (&pagination_params, &query_params, auth_user)
         ))?;
 
-    // Drop connection lock before iterating
-    let json_tokens = tokens
+    let json_tokens = conn_lock!(context, |conn| tokens
         .into_iter()
-        .map(|query_token| query_token.into_json_for_user(auth_user))
-        .collect();
+        .map(|query_token| query_token.into_json_for_user(conn, &query_user))
+        .collect::<Result<_, _>>())?;
 
     let total_count = get_ls_query(&pagination_params, &query_params, query_user.id)
         .count()
@@ -235,7 +235,7 @@ pub async fn user_token_options(
     _rqctx: RequestContext<ApiContext>,
     _path_params: Path<UserTokenParams>,
 ) -> Result<CorsResponse, HttpError> {
-    Ok(Endpoint::cors(&[Get.into(), Patch.into()]))
+    Ok(Endpoint::cors(&[Get.into(), Patch.into(), Delete.into()]))
 }
 
 /// View a token
@@ -327,3 +327,50 @@ async fn patch_inner(
     conn_lock!(context, |conn| QueryToken::get(conn, query_token.id)?
         .into_json(conn))
 }
+
+/// Revoke a token
+///
+/// Revoke an API token for a user.
+/// A revoked token is kept on a deny-list and can no longer be used to authenticate.
+/// Only the authenticated user themselves and server admins have access to this endpoint.
+#[endpoint {
+    method = DELETE,
+    path =  "/v0/users/{user}/tokens/{token}",
+    tags = ["users", "tokens"]
+}]
+pub async fn user_token_delete(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<UserTokenParams>,
+) -> Result<ResponseDeleted, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    delete_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Delete::auth_response_deleted())
+}
+
+async fn delete_inner(
+    context: &ApiContext,
+    path_params: UserTokenParams,
+    auth_user: &AuthUser,
+) -> Result<(), HttpError> {
+    let query_user = QueryUser::from_resource_id(conn_lock!(context), &path_params.user)?;
+    same_user!(auth_user, context.rbac, query_user.uuid);
+
+    let query_token = QueryToken::get_user_token(
+        conn_lock!(context),
+        query_user.id,
+        &path_params.token.to_string(),
+    )?;
+    // Keep the original revocation time
+    if query_token.revoked.is_some() {
+        return Ok(());
+    }
+
+    let update_token = UpdateToken::revoke();
+    diesel::update(schema::token::table.filter(schema::token::id.eq(query_token.id)))
+        .set(&update_token)
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(Token, (&query_user, &query_token)))?;
+
+    Ok(())
+}
@@ -16,12 +16,19 @@ use crate::{
     results::adapter_results::AdapterResults,
 };
 
+use super::catch2_xml::AdapterCppCatch2Xml;
+
 const CATCH2_METRICS_LINE_COUNT: usize = 5;
 
 pub struct AdapterCppCatch2;
 
 impl Adaptable for AdapterCppCatch2 {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
+        // The XML reporter (ie `-r xml`)
+        if let Some(results) = AdapterCppCatch2Xml::parse(input, settings) {
+            return Some(results);
+        }
+
         match settings.average {
             Some(JsonAverage::Mean) | None => {},
             Some(JsonAverage::Median) => return None,
@@ -194,7 +201,10 @@ pub(crate) mod test_cpp_catch2 {
 
     use crate::{
         AdapterResults, Settings,
-        adapters::test_util::{convert_file_path, opt_convert_file_path, validate_latency},
+        adapters::{
+            cpp::catch2_xml::test_cpp_catch2_xml,
+            test_util::{convert_file_path, opt_convert_file_path, validate_latency},
+        },
     };
 
     use super::{AdapterCppCatch2, parse_catch2_prelude_line};
@@ -280,6 +290,12 @@ pub(crate) mod test_cpp_catch2 {
         validate_latency(metrics, 3789.0, Some(3427.0), Some(4151.0));
     }
 
+    #[test]
+    fn test_adapter_cpp_catch2_xml() {
+        let results = convert_file_path::<AdapterCppCatch2>("./tool_output/cpp/catch2/xml.xml");
+        test_cpp_catch2_xml::validate_adapter_cpp_catch2_xml(&results);
+    }
+
     #[test]
     fn test_adapter_cpp_catch2_two() {
         let results = convert_cpp_catch2("two");
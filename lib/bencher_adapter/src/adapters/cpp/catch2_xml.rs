@@ -0,0 +1,173 @@
+use bencher_json::{BenchmarkName, JsonNewMetric, project::report::JsonAverage};
+use ordered_float::OrderedFloat;
+use quick_xml::{
+    Reader,
+    events::{BytesStart, Event},
+};
+
+use crate::{Adaptable, Settings, results::adapter_results::AdapterResults};
+
+const TEST_CASE_TAG: &[u8] = b"TestCase";
+const BENCHMARK_RESULTS_TAG: &[u8] = b"BenchmarkResults";
+const MEAN_TAG: &[u8] = b"mean";
+const STANDARD_DEVIATION_TAG: &[u8] = b"standardDeviation";
+const NAME_ATTRIBUTE: &[u8] = b"name";
+const VALUE_ATTRIBUTE: &[u8] = b"value";
+
+/// Catch2 XML reporter output (ie `-r xml`)
+pub struct AdapterCppCatch2Xml;
+
+impl Adaptable for AdapterCppCatch2Xml {
+    fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
+        match settings.average {
+            Some(JsonAverage::Mean) | None => {},
+            Some(JsonAverage::Median) => return None,
+        }
+
+        let mut benchmark_metrics = Vec::new();
+        let mut test_case = None;
+        let mut benchmark = None;
+        let mut reader = Reader::from_str(input);
+        loop {
+            match reader.read_event().ok()? {
+                Event::Start(element) | Event::Empty(element) => match element.name().as_ref() {
+                    TEST_CASE_TAG => test_case = attribute(&element, NAME_ATTRIBUTE),
+                    BENCHMARK_RESULTS_TAG => {
+                        benchmark = attribute(&element, NAME_ATTRIBUTE).map(Catch2Benchmark::new);
+                    },
+                    // All values are in nanoseconds
+                    MEAN_TAG => {
+                        if let Some(benchmark) = benchmark.as_mut() {
+                            benchmark.mean = value_attribute(&element);
+                        }
+                    },
+                    STANDARD_DEVIATION_TAG => {
+                        if let Some(benchmark) = benchmark.as_mut() {
+                            benchmark.std_dev = value_attribute(&element);
+                        }
+                    },
+                    _ => {},
+                },
+                Event::End(element) if element.name().as_ref() == BENCHMARK_RESULTS_TAG => {
+                    if let Some(benchmark_metric) = benchmark
+                        .take()
+                        .and_then(|benchmark| benchmark.into_metric(test_case.as_deref()))
+                    {
+                        benchmark_metrics.push(benchmark_metric);
+                    }
+                },
+                Event::Eof => break,
+                Event::End(_)
+                | Event::Text(_)
+                | Event::CData(_)
+                | Event::Comment(_)
+                | Event::Decl(_)
+                | Event::PI(_)
+                | Event::DocType(_) => {},
+            }
+        }
+
+        AdapterResults::new_latency(benchmark_metrics)
+    }
+}
+
+struct Catch2Benchmark {
+    name: String,
+    mean: Option<OrderedFloat<f64>>,
+    std_dev: Option<OrderedFloat<f64>>,
+}
+
+impl Catch2Benchmark {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            mean: None,
+            std_dev: None,
+        }
+    }
+
+    // The benchmark name matches the console reporter (ie `Test Case: Benchmark`)
+    fn into_metric(self, test_case: Option<&str>) -> Option<(BenchmarkName, JsonNewMetric)> {
+        let Self {
+            name,
+            mean,
+            std_dev,
+        } = self;
+        let name = if let Some(test_case) = test_case {
+            format!("{test_case}: {name}")
+        } else {
+            name
+        };
+        let benchmark_name = name.parse().ok()?;
+        let mean = mean?;
+        let json_metric = JsonNewMetric {
+            value: mean,
+            lower_value: std_dev.map(|std_dev| mean - std_dev),
+            upper_value: std_dev.map(|std_dev| mean + std_dev),
+        };
+        Some((benchmark_name, json_metric))
+    }
+}
+
+fn attribute(element: &BytesStart, name: &[u8]) -> Option<String> {
+    element
+        .try_get_attribute(name)
+        .ok()??
+        .unescape_value()
+        .ok()
+        .map(Into::into)
+}
+
+fn value_attribute(element: &BytesStart) -> Option<OrderedFloat<f64>> {
+    attribute(element, VALUE_ATTRIBUTE)?
+        .parse::<f64>()
+        .ok()
+        .map(Into::into)
+}
+
+#[cfg(test)]
+pub(crate) mod test_cpp_catch2_xml {
+    use bencher_json::project::report::JsonAverage;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        AdapterResults, Settings,
+        adapters::test_util::{convert_file_path, opt_convert_file_path, validate_latency},
+    };
+
+    use super::AdapterCppCatch2Xml;
+
+    const FILE_PATH: &str = "./tool_output/cpp/catch2/xml.xml";
+
+    #[test]
+    fn test_adapter_cpp_catch2_xml_average() {
+        assert_eq!(
+            None,
+            opt_convert_file_path::<AdapterCppCatch2Xml>(
+                FILE_PATH,
+                Settings {
+                    average: Some(JsonAverage::Median)
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_adapter_cpp_catch2_xml() {
+        let results = convert_file_path::<AdapterCppCatch2Xml>(FILE_PATH);
+        validate_adapter_cpp_catch2_xml(&results);
+    }
+
+    pub fn validate_adapter_cpp_catch2_xml(results: &AdapterResults) {
+        assert_eq!(results.inner.len(), 3);
+
+        let metrics = results.get("Fibonacci: Fibonacci 10").unwrap();
+        validate_latency(metrics, 344.5, Some(325.25), Some(363.75));
+
+        let metrics = results.get("Fibonacci: Fibonacci 20").unwrap();
+        validate_latency(metrics, 41_731.0, Some(38_475.0), Some(44_987.0));
+
+        let metrics = results.get("More Fibonacci: Fibonacci~ 5!").unwrap();
+        validate_latency(metrics, 36.0, Some(32.0), Some(40.0));
+    }
+}
@@ -1,26 +1,47 @@
-use bencher_json::{BenchmarkName, JsonAny, JsonNewMetric, project::report::JsonAverage};
-use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+use bencher_json::{
+    BenchmarkName, JsonAny, JsonNewMetric, MeasureNameId,
+    project::{
+        measure::built_in::{self, BuiltInMeasure as _},
+        report::JsonAverage,
+    },
+};
+use ordered_float::OrderedFloat;
 use serde::Deserialize;
 
 use crate::{
-    Adaptable, AdapterError, Settings,
+    Adaptable, Settings,
     adapters::util::{Units, latency_as_nanos},
-    results::adapter_results::AdapterResults,
+    results::{
+        adapter_metrics::{AdapterMetrics, MetricsMap},
+        adapter_results::{AdapterResults, ResultsMap},
+    },
 };
 
+const MEAN_AGGREGATE: &str = "mean";
+const MEDIAN_AGGREGATE: &str = "median";
+const STD_DEV_AGGREGATE: &str = "stddev";
+const MIN_AGGREGATE: &str = "min";
+const MAX_AGGREGATE: &str = "max";
+
+// Numeric benchmark fields that are not user counters
+const NON_COUNTERS: [&str; 6] = [
+    "family_index",
+    "per_family_instance_index",
+    "repetitions",
+    "repetition_index",
+    "threads",
+    "iterations",
+];
+
 pub struct AdapterCppGoogle;
 
 impl Adaptable for AdapterCppGoogle {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
-        match settings.average {
-            Some(JsonAverage::Mean) | None => {},
-            Some(JsonAverage::Median) => return None,
-        }
-
         serde_json::from_str::<Google>(input)
             .ok()?
-            .try_into()
-            .ok()?
+            .into_results(settings.average)
     }
 }
 
@@ -38,44 +59,155 @@ pub struct Context {
 #[derive(Debug, Clone, Deserialize)]
 pub struct Benchmark {
     pub name: BenchmarkName,
-    #[serde(with = "rust_decimal::serde::float")]
-    pub real_time: Decimal,
+    pub run_name: Option<BenchmarkName>,
+    pub run_type: Option<RunType>,
+    pub aggregate_name: Option<String>,
+    #[serde(default)]
+    pub error_occurred: bool,
+    pub real_time: f64,
+    pub cpu_time: Option<f64>,
     pub time_unit: Units,
+    #[serde(flatten)]
+    pub counters: HashMap<String, serde_json::Value>,
 }
 
-impl TryFrom<Google> for Option<AdapterResults> {
-    type Error = AdapterError;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunType {
+    Iteration,
+    Aggregate,
+}
 
-    fn try_from(google: Google) -> Result<Self, Self::Error> {
-        let mut benchmark_metrics = Vec::with_capacity(google.benchmarks.len());
-        for benchmark in google.benchmarks {
+type Values = HashMap<MeasureNameId, OrderedFloat<f64>>;
+
+#[derive(Default)]
+struct Run {
+    iteration: Option<Values>,
+    aggregates: HashMap<String, Values>,
+}
+
+impl Google {
+    fn into_results(self, average: Option<JsonAverage>) -> Option<AdapterResults> {
+        let mut runs = HashMap::<BenchmarkName, Run>::new();
+        for benchmark in self.benchmarks {
+            if benchmark.error_occurred {
+                continue;
+            }
+            let values = benchmark.values();
             let Benchmark {
                 name,
-                real_time,
-                time_unit,
+                run_name,
+                run_type,
+                aggregate_name,
+                ..
             } = benchmark;
-            let value = latency_as_nanos(real_time, time_unit);
-            let json_metric = JsonNewMetric {
-                value,
-                lower_value: None,
-                upper_value: None,
-            };
+            let run = runs.entry(run_name.unwrap_or(name)).or_default();
+            match (run_type, aggregate_name) {
+                (Some(RunType::Aggregate), Some(aggregate_name)) => {
+                    run.aggregates.insert(aggregate_name, values);
+                },
+                (Some(RunType::Aggregate), None) => {},
+                // With repetitions, the aggregates are used instead of the last iteration
+                (Some(RunType::Iteration) | None, _) => run.iteration = Some(values),
+            }
+        }
 
-            benchmark_metrics.push((name, json_metric));
+        let mut results_map = ResultsMap::new();
+        for (benchmark_name, run) in runs {
+            if let Some(metrics_map) = run.into_metrics(average) {
+                results_map.insert(benchmark_name, AdapterMetrics::from(metrics_map));
+            }
         }
+        (!results_map.is_empty()).then(|| results_map.into())
+    }
+}
+
+impl Benchmark {
+    fn values(&self) -> Values {
+        let mut values = Values::new();
+        values.insert(
+            built_in::default::Latency::name_id(),
+            latency_as_nanos(self.real_time, self.time_unit),
+        );
+        if let Some(cpu_time) = self.cpu_time {
+            values.insert(
+                built_in::google::CpuTime::name_id(),
+                latency_as_nanos(cpu_time, self.time_unit),
+            );
+        }
+        // User counters (ie `state.counters["Foo"]`), including `bytes_per_second` and `items_per_second`
+        for (counter, value) in &self.counters {
+            if NON_COUNTERS.contains(&counter.as_str()) {
+                continue;
+            }
+            if let (Ok(measure), Some(value)) = (counter.parse(), value.as_f64()) {
+                values.insert(measure, value.into());
+            }
+        }
+        values
+    }
+}
 
-        Ok(AdapterResults::new_latency(benchmark_metrics))
+impl Run {
+    fn into_metrics(mut self, average: Option<JsonAverage>) -> Option<MetricsMap> {
+        match average {
+            // The mean is bounded by one standard deviation below and above
+            None | Some(JsonAverage::Mean) => {
+                if let Some(mean) = self.aggregates.remove(MEAN_AGGREGATE) {
+                    let std_dev = self.aggregates.remove(STD_DEV_AGGREGATE);
+                    Some(into_metrics(mean, |measure, value| {
+                        let std_dev = std_dev.as_ref()?.get(measure)?;
+                        Some((value - std_dev, value + std_dev))
+                    }))
+                } else {
+                    self.iteration
+                        .map(|iteration| into_metrics(iteration, |_, _| None))
+                }
+            },
+            // The median is bounded by the min and max, if they are available
+            Some(JsonAverage::Median) => {
+                let median = self.aggregates.remove(MEDIAN_AGGREGATE)?;
+                let min = self.aggregates.remove(MIN_AGGREGATE);
+                let max = self.aggregates.remove(MAX_AGGREGATE);
+                Some(into_metrics(median, |measure, _| {
+                    Some((*min.as_ref()?.get(measure)?, *max.as_ref()?.get(measure)?))
+                }))
+            },
+        }
     }
 }
 
+fn into_metrics<F>(values: Values, bounds: F) -> MetricsMap
+where
+    F: Fn(&MeasureNameId, OrderedFloat<f64>) -> Option<(OrderedFloat<f64>, OrderedFloat<f64>)>,
+{
+    values
+        .into_iter()
+        .map(|(measure, value)| {
+            let (lower_value, upper_value) = bounds(&measure, value).unzip();
+            let json_metric = JsonNewMetric {
+                value,
+                lower_value,
+                upper_value,
+            };
+            (measure, json_metric)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 pub(crate) mod test_cpp_google {
-    use bencher_json::project::report::JsonAverage;
+    use bencher_json::project::{
+        measure::built_in::{BuiltInMeasure as _, default::Latency, google::CpuTime},
+        report::JsonAverage,
+    };
+    use ordered_float::OrderedFloat;
     use pretty_assertions::assert_eq;
 
     use crate::{
         AdapterResults, Settings,
-        adapters::test_util::{convert_file_path, opt_convert_file_path, validate_latency},
+        adapters::test_util::{convert_file_path, opt_convert_file_path},
+        results::adapter_metrics::AdapterMetrics,
     };
 
     use super::AdapterCppGoogle;
@@ -85,6 +217,27 @@ pub(crate) mod test_cpp_google {
         convert_file_path::<AdapterCppGoogle>(&file_path)
     }
 
+    fn validate_metric(
+        metrics: &AdapterMetrics,
+        key: &str,
+        value: f64,
+        bounds: Option<(f64, f64)>,
+    ) {
+        let metric = metrics.get(key).unwrap();
+        assert_eq!(metric.value, OrderedFloat::from(value), "{key}");
+        let (lower_value, upper_value) = bounds.unzip();
+        assert_eq!(
+            metric.lower_value,
+            lower_value.map(OrderedFloat::from),
+            "{key}"
+        );
+        assert_eq!(
+            metric.upper_value,
+            upper_value.map(OrderedFloat::from),
+            "{key}"
+        );
+    }
+
     #[test]
     fn test_adapter_cpp_google_average() {
         let file_path = "./tool_output/cpp/google/two.txt";
@@ -118,9 +271,55 @@ pub(crate) mod test_cpp_google {
         assert_eq!(results.inner.len(), 2);
 
         let metrics = results.get("fib_10").unwrap();
-        validate_latency(metrics, 214.989_801_145_479_53, None, None);
+        assert_eq!(metrics.inner.len(), 2);
+        validate_metric(metrics, Latency::SLUG_STR, 214.989_801_145_479_53, None);
+        validate_metric(metrics, CpuTime::SLUG_STR, 213.655_072_061_632_95, None);
 
         let metrics = results.get("fib_20").unwrap();
-        validate_latency(metrics, 27_455.600_415_007_055, None, None);
+        assert_eq!(metrics.inner.len(), 2);
+        validate_metric(metrics, Latency::SLUG_STR, 27_455.600_415_007_055, None);
+        validate_metric(metrics, CpuTime::SLUG_STR, 27_364.903_204_272_36, None);
+    }
+
+    #[test]
+    fn test_adapter_cpp_google_aggregates() {
+        let results = convert_cpp_google("aggregates");
+        // Benchmarks with errors are skipped
+        assert_eq!(results.inner.len(), 2);
+
+        let metrics = results.get("fib_10").unwrap();
+        assert_eq!(metrics.inner.len(), 4);
+        validate_metric(metrics, Latency::SLUG_STR, 220.0, Some((210.0, 230.0)));
+        validate_metric(metrics, CpuTime::SLUG_STR, 219.0, Some((209.0, 229.0)));
+        validate_metric(
+            metrics,
+            "items_per_second",
+            4_545_454.5,
+            Some((4_338_843.0, 4_752_066.0)),
+        );
+        validate_metric(metrics, "Foo", 4.0, Some((4.0, 4.0)));
+
+        let metrics = results.get("fib_20").unwrap();
+        assert_eq!(metrics.inner.len(), 2);
+        validate_metric(metrics, Latency::SLUG_STR, 27_500.0, None);
+        validate_metric(metrics, CpuTime::SLUG_STR, 27_250.0, None);
+    }
+
+    #[test]
+    fn test_adapter_cpp_google_aggregates_median() {
+        let results = opt_convert_file_path::<AdapterCppGoogle>(
+            "./tool_output/cpp/google/aggregates.txt",
+            Settings {
+                average: Some(JsonAverage::Median),
+            },
+        )
+        .unwrap();
+        // Only benchmarks with repetitions have a median
+        assert_eq!(results.inner.len(), 1);
+
+        let metrics = results.get("fib_10").unwrap();
+        assert_eq!(metrics.inner.len(), 4);
+        validate_metric(metrics, Latency::SLUG_STR, 220.0, None);
+        validate_metric(metrics, CpuTime::SLUG_STR, 219.0, None);
     }
 }
@@ -1,4 +1,5 @@
 pub mod catch2;
+pub mod catch2_xml;
 pub mod google;
 
 use crate::{Adaptable, AdapterResults, Settings};
@@ -18,7 +19,7 @@ impl Adaptable for AdapterCpp {
 mod test_cpp {
     use super::AdapterCpp;
     use crate::adapters::{
-        cpp::{catch2::test_cpp_catch2, google::test_cpp_google},
+        cpp::{catch2::test_cpp_catch2, catch2_xml::test_cpp_catch2_xml, google::test_cpp_google},
         test_util::convert_file_path,
     };
 
@@ -28,6 +29,12 @@ mod test_cpp {
         test_cpp_catch2::validate_adapter_cpp_catch2(&results);
     }
 
+    #[test]
+    fn test_adapter_cpp_catch2_xml() {
+        let results = convert_file_path::<AdapterCpp>("./tool_output/cpp/catch2/xml.xml");
+        test_cpp_catch2_xml::validate_adapter_cpp_catch2_xml(&results);
+    }
+
     #[test]
     fn test_adapter_cpp_google() {
         let results = convert_file_path::<AdapterCpp>("./tool_output/cpp/google/two.txt");
@@ -1,22 +1,27 @@
+use std::borrow::Cow;
+
 use bencher_json::{BenchmarkName, JsonNewMetric, project::report::JsonAverage};
 use nom::{
     IResult,
-    branch::alt,
     bytes::complete::{tag, take_till1},
-    character::complete::{anychar, space1},
-    combinator::{eof, map, map_res},
-    multi::many_till,
-    sequence::tuple,
+    character::complete::{space0, space1},
+    combinator::map_res,
+    multi::many0,
+    sequence::{preceded, tuple},
 };
+use ordered_float::OrderedFloat;
 
 use crate::{
     Adaptable, Settings,
     adapters::util::{
-        NomError, latency_as_nanos, parse_benchmark_name, parse_f64, parse_u64, parse_units,
+        NomError, Units, latency_as_nanos, nom_error, parse_benchmark_name, parse_f64, parse_u64,
+        parse_units,
     },
-    results::adapter_results::AdapterResults,
+    results::adapter_results::{AdapterResults, GoMeasure},
 };
 
+use super::{benchstat::parse_benchstat, test2json::parse_test2json};
+
 pub struct AdapterGoBench;
 
 impl Adaptable for AdapterGoBench {
@@ -26,9 +31,13 @@ impl Adaptable for AdapterGoBench {
             Some(JsonAverage::Median) => return None,
         }
 
+        // `go test -json` wraps the benchmark output in test2json events
+        let input = parse_test2json(input).map_or(Cow::Borrowed(input), Cow::Owned);
+
         let mut benchmark_metrics = Vec::new();
 
         for line in input.lines() {
+            // Any content after the value and unit pairs means that this is not a benchmark line.
             if let Ok((remainder, benchmark_metric)) = parse_go(line) {
                 if remainder.is_empty() {
                     benchmark_metrics.push(benchmark_metric);
@@ -36,11 +45,11 @@ impl Adaptable for AdapterGoBench {
             }
         }
 
-        AdapterResults::new_latency(benchmark_metrics)
+        AdapterResults::new_go(benchmark_metrics).or_else(|| parse_benchstat(&input))
     }
 }
 
-fn parse_go(input: &str) -> IResult<&str, (BenchmarkName, JsonNewMetric)> {
+fn parse_go(input: &str) -> IResult<&str, (BenchmarkName, Vec<GoMeasure>)> {
     map_res(
         tuple((
             take_till1(|c| c == ' ' || c == '\t'),
@@ -48,43 +57,87 @@ fn parse_go(input: &str) -> IResult<&str, (BenchmarkName, JsonNewMetric)> {
             parse_u64,
             space1,
             parse_go_bench,
-            alt((
-                map(eof, |_| ()),
-                map(
-                    tuple((space1, parse_f64, space1, many_till(anychar, eof))),
-                    |_| (),
-                ),
-            )),
+            many0(preceded(space1, parse_go_measure)),
+            space0,
         )),
-        |(name, _, _iter, _, json_metric, ())| -> Result<(BenchmarkName, JsonNewMetric), NomError> {
+        |(name, _, _iter, _, latency, mut measures, _)| -> Result<(BenchmarkName, Vec<GoMeasure>), NomError> {
             let benchmark_name = parse_benchmark_name(name)?;
-            Ok((benchmark_name, json_metric))
+            measures.insert(0, latency);
+            Ok((benchmark_name, measures))
         },
     )(input)
 }
 
-fn parse_go_bench(input: &str) -> IResult<&str, JsonNewMetric> {
+// The latency (ie `ns/op`) is always reported first.
+fn parse_go_bench(input: &str) -> IResult<&str, GoMeasure> {
     map_res(
         tuple((parse_f64, space1, parse_units, tag("/op"))),
-        |(duration, _, units, _)| -> Result<JsonNewMetric, NomError> {
-            let value = latency_as_nanos(duration, units);
-            Ok(JsonNewMetric {
-                value,
-                lower_value: None,
-                upper_value: None,
-            })
+        |(duration, _, units, _)| -> Result<GoMeasure, NomError> {
+            Ok(go_latency(duration, units, None))
         },
     )(input)
 }
 
+// Every other value and unit pair on a benchmark line is a separate measure:
+// `B/op` and `allocs/op` with `-benchmem`, `MB/s` with `b.SetBytes`,
+// and any custom units from `b.ReportMetric`.
+fn parse_go_measure(input: &str) -> IResult<&str, GoMeasure> {
+    map_res(
+        tuple((parse_f64, space1, take_till1(char::is_whitespace))),
+        |(value, _, unit)| go_measure(value, unit, None).ok_or_else(|| nom_error(unit)),
+    )(input)
+}
+
+/// Create a Go latency measure from a duration, its units, and an optional variance percentage.
+pub(super) fn go_latency(duration: f64, units: Units, variance: Option<f64>) -> GoMeasure {
+    GoMeasure::Latency(go_metric(latency_as_nanos(duration, units), variance))
+}
+
+/// Create a Go measure from a value, its unit, and an optional variance percentage.
+/// Only the first value on a benchmark line is the latency,
+/// so any other unit (including a custom `*/op` unit) is its own measure.
+pub(super) fn go_measure(value: f64, unit: &str, variance: Option<f64>) -> Option<GoMeasure> {
+    let json_metric = go_metric(value.into(), variance);
+    Some(match unit {
+        "B/op" => GoMeasure::BytesPerOp(json_metric),
+        "allocs/op" => GoMeasure::AllocsPerOp(json_metric),
+        "MB/s" => GoMeasure::MegabytesPerSecond(json_metric),
+        _ => GoMeasure::Custom(unit.parse().ok()?, json_metric),
+    })
+}
+
+fn go_metric(value: OrderedFloat<f64>, variance: Option<f64>) -> JsonNewMetric {
+    let (lower_value, upper_value) = variance.map_or((None, None), |variance| {
+        let spread = value * (variance / 100.0);
+        (Some(value - spread), Some(value + spread))
+    });
+    JsonNewMetric {
+        value,
+        lower_value,
+        upper_value,
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod test_go_bench {
-    use bencher_json::{JsonNewMetric, project::report::JsonAverage};
+    use bencher_json::{
+        JsonNewMetric,
+        project::{
+            measure::built_in::{
+                BuiltInMeasure as _,
+                default::Latency,
+                go::{AllocsPerOp, BytesPerOp, MegabytesPerSecond},
+            },
+            report::JsonAverage,
+        },
+    };
+    use ordered_float::OrderedFloat;
     use pretty_assertions::assert_eq;
 
     use crate::{
         AdapterResults, Settings,
         adapters::test_util::{convert_file_path, opt_convert_file_path, validate_latency},
+        results::{adapter_metrics::AdapterMetrics, adapter_results::GoMeasure},
     };
 
     use super::{AdapterGoBench, parse_go};
@@ -94,7 +147,16 @@ pub(crate) mod test_go_bench {
         convert_file_path::<AdapterGoBench>(&file_path)
     }
 
+    fn json_metric(value: f64) -> JsonNewMetric {
+        JsonNewMetric {
+            value: value.into(),
+            lower_value: None,
+            upper_value: None,
+        }
+    }
+
     #[test]
+    #[expect(clippy::too_many_lines)]
     fn test_parse_go() {
         for (index, (expected, input)) in [
             (
@@ -102,11 +164,7 @@ pub(crate) mod test_go_bench {
                     "",
                     (
                         "BenchmarkFib10-8".parse().unwrap(),
-                        JsonNewMetric {
-                            value: 325.0.into(),
-                            lower_value: None,
-                            upper_value: None,
-                        },
+                        vec![GoMeasure::Latency(json_metric(325.0))],
                     ),
                 )),
                 "BenchmarkFib10-8   		 					5000000		325 ns/op",
@@ -116,11 +174,7 @@ pub(crate) mod test_go_bench {
                     "",
                     (
                         "BenchmarkFib20".parse().unwrap(),
-                        JsonNewMetric {
-                            value: 40_537.123.into(),
-                            lower_value: None,
-                            upper_value: None,
-                        },
+                        vec![GoMeasure::Latency(json_metric(40_537.123))],
                     ),
                 )),
                 "BenchmarkFib20  	 	   					30000		40537.123 ns/op",
@@ -130,11 +184,7 @@ pub(crate) mod test_go_bench {
                     "",
                     (
                         "BenchmarkFib/my_tabled_benchmark_-_10-8".parse().unwrap(),
-                        JsonNewMetric {
-                            value: 325.0.into(),
-                            lower_value: None,
-                            upper_value: None,
-                        },
+                        vec![GoMeasure::Latency(json_metric(325.0))],
                     ),
                 )),
                 "BenchmarkFib/my_tabled_benchmark_-_10-8    	5000000		325 ns/op",
@@ -144,11 +194,7 @@ pub(crate) mod test_go_bench {
                     "",
                     (
                         "BenchmarkFib/my_tabled_benchmark_-_20".parse().unwrap(),
-                        JsonNewMetric {
-                            value: 40_537.123.into(),
-                            lower_value: None,
-                            upper_value: None,
-                        },
+                        vec![GoMeasure::Latency(json_metric(40_537.123))],
                     ),
                 )),
                 "BenchmarkFib/my_tabled_benchmark_-_20		30000		40537.123 ns/op",
@@ -158,11 +204,7 @@ pub(crate) mod test_go_bench {
                     "",
                     (
                         "BenchmarkFib/my/tabled/benchmark_-_20".parse().unwrap(),
-                        JsonNewMetric {
-                            value: 40_537.456.into(),
-                            lower_value: None,
-                            upper_value: None,
-                        },
+                        vec![GoMeasure::Latency(json_metric(40_537.456))],
                     ),
                 )),
                 "BenchmarkFib/my/tabled/benchmark_-_20		30001		40537.456 ns/op",
@@ -172,15 +214,72 @@ pub(crate) mod test_go_bench {
                     "",
                     (
                         "BenchmarkFib20WithAuxMetric-8".parse().unwrap(),
-                        JsonNewMetric {
-                            value: 25_829.0.into(),
-                            lower_value: None,
-                            upper_value: None,
-                        },
+                        vec![
+                            GoMeasure::Latency(json_metric(25_829.0)),
+                            GoMeasure::Custom(
+                                "auxMetricUnits".parse().unwrap(),
+                                json_metric(4.0),
+                            ),
+                        ],
                     ),
                 )),
                 "BenchmarkFib20WithAuxMetric-8              46714             25829 ns/op                 4.000 auxMetricUnits",
             ),
+            (
+                Ok((
+                    "",
+                    (
+                        "BenchmarkCopy-16".parse().unwrap(),
+                        vec![
+                            GoMeasure::Latency(json_metric(10_492.0)),
+                            GoMeasure::MegabytesPerSecond(json_metric(99.94)),
+                            GoMeasure::BytesPerOp(json_metric(1024.0)),
+                            GoMeasure::AllocsPerOp(json_metric(2.0)),
+                        ],
+                    ),
+                )),
+                "BenchmarkCopy-16         	  100000	     10492 ns/op	  99.94 MB/s	    1024 B/op	       2 allocs/op",
+            ),
+            (
+                Ok((
+                    "",
+                    (
+                        "BenchmarkDecode-8".parse().unwrap(),
+                        vec![
+                            GoMeasure::Latency(json_metric(1_500.0)),
+                            GoMeasure::Custom("us/op".parse().unwrap(), json_metric(3.0)),
+                            GoMeasure::Custom("items/op".parse().unwrap(), json_metric(12.0)),
+                        ],
+                    ),
+                )),
+                "BenchmarkDecode-8   1000   1500 ns/op   3.000 us/op   12.00 items/op",
+            ),
+            (
+                Ok((
+                    "",
+                    (
+                        "BenchmarkFib10-8".parse().unwrap(),
+                        vec![GoMeasure::Latency(json_metric(325.0))],
+                    ),
+                )),
+                "BenchmarkFib10-8   5000000   325 ns/op   ",
+            ),
+            (
+                Ok((
+                    "--- trailing",
+                    (
+                        "BenchmarkFib20WithAuxMetric-8".parse().unwrap(),
+                        vec![
+                            GoMeasure::Latency(json_metric(25_829.0)),
+                            GoMeasure::Custom(
+                                "auxMetricUnits".parse().unwrap(),
+                                json_metric(4.0),
+                            ),
+                        ],
+                    ),
+                )),
+                "BenchmarkFib20WithAuxMetric-8   46714   25829 ns/op   4.000 auxMetricUnits   --- trailing",
+            ),
         ]
         .into_iter()
         .enumerate()
@@ -255,6 +354,142 @@ pub(crate) mod test_go_bench {
         validate_latency(metrics, 26264.0, None, None);
 
         let metrics = results.get("BenchmarkFib20WithAuxMetric-8").unwrap();
-        validate_latency(metrics, 25829.0, None, None);
+        assert_eq!(metrics.inner.len(), 2);
+        validate_measure(metrics, Latency::SLUG_STR, 25829.0, None);
+        validate_measure(metrics, "auxMetricUnits", 4.0, None);
+    }
+
+    fn validate_measure(metrics: &AdapterMetrics, key: &str, value: f64, variance: Option<f64>) {
+        let metric = metrics.get(key).unwrap();
+        assert_eq!(metric.value, OrderedFloat::from(value), "{key}");
+        let spread = variance.map(|variance| value * (variance / 100.0));
+        assert_eq!(
+            metric.lower_value,
+            spread.map(|spread| OrderedFloat::from(value - spread)),
+            "{key}"
+        );
+        assert_eq!(
+            metric.upper_value,
+            spread.map(|spread| OrderedFloat::from(value + spread)),
+            "{key}"
+        );
+    }
+
+    #[test]
+    fn test_adapter_go_bench_benchmem() {
+        let results = convert_go_bench("benchmem");
+        validate_adapter_go_bench_benchmem(&results);
+    }
+
+    pub fn validate_adapter_go_bench_benchmem(results: &AdapterResults) {
+        assert_eq!(results.inner.len(), 4);
+
+        let metrics = results.get("BenchmarkFib10-16").unwrap();
+        assert_eq!(metrics.inner.len(), 3);
+        validate_measure(metrics, Latency::SLUG_STR, 210.2, None);
+        validate_measure(metrics, BytesPerOp::SLUG_STR, 0.0, None);
+        validate_measure(metrics, AllocsPerOp::SLUG_STR, 0.0, None);
+
+        let metrics = results.get("BenchmarkFib20-16").unwrap();
+        assert_eq!(metrics.inner.len(), 3);
+        validate_measure(metrics, Latency::SLUG_STR, 26264.0, None);
+        validate_measure(metrics, BytesPerOp::SLUG_STR, 16.0, None);
+        validate_measure(metrics, AllocsPerOp::SLUG_STR, 1.0, None);
+
+        let metrics = results.get("BenchmarkCopy-16").unwrap();
+        assert_eq!(metrics.inner.len(), 4);
+        validate_measure(metrics, Latency::SLUG_STR, 10492.0, None);
+        validate_measure(metrics, MegabytesPerSecond::SLUG_STR, 99.94, None);
+        validate_measure(metrics, BytesPerOp::SLUG_STR, 1024.0, None);
+        validate_measure(metrics, AllocsPerOp::SLUG_STR, 2.0, None);
+
+        let metrics = results.get("BenchmarkFib20WithAuxMetric-16").unwrap();
+        assert_eq!(metrics.inner.len(), 4);
+        validate_measure(metrics, Latency::SLUG_STR, 25829.0, None);
+        validate_measure(metrics, "auxMetricUnits", 4.0, None);
+        validate_measure(metrics, BytesPerOp::SLUG_STR, 0.0, None);
+        validate_measure(metrics, AllocsPerOp::SLUG_STR, 0.0, None);
+    }
+
+    #[test]
+    fn test_adapter_go_bench_test2json() {
+        let results = convert_go_bench("test2json");
+        assert_eq!(results.inner.len(), 2);
+
+        let metrics = results.get("BenchmarkFib10-16").unwrap();
+        assert_eq!(metrics.inner.len(), 3);
+        validate_measure(metrics, Latency::SLUG_STR, 210.2, None);
+        validate_measure(metrics, BytesPerOp::SLUG_STR, 0.0, None);
+        validate_measure(metrics, AllocsPerOp::SLUG_STR, 0.0, None);
+
+        let metrics = results.get("BenchmarkFib20-16").unwrap();
+        assert_eq!(metrics.inner.len(), 3);
+        validate_measure(metrics, Latency::SLUG_STR, 26264.0, None);
+        validate_measure(metrics, BytesPerOp::SLUG_STR, 16.0, None);
+        validate_measure(metrics, AllocsPerOp::SLUG_STR, 1.0, None);
+    }
+
+    #[test]
+    fn test_adapter_go_bench_benchstat() {
+        let results = convert_go_bench("benchstat");
+        assert_eq!(results.inner.len(), 3);
+
+        let metrics = results.get("Fib10-16").unwrap();
+        assert_eq!(metrics.inner.len(), 3);
+        validate_measure(
+            metrics,
+            Latency::SLUG_STR,
+            210.2 * 1e-9 * 1_000_000_000.0,
+            Some(2.0),
+        );
+        validate_measure(metrics, BytesPerOp::SLUG_STR, 0.0, Some(0.0));
+        validate_measure(metrics, AllocsPerOp::SLUG_STR, 0.0, Some(0.0));
+
+        let metrics = results.get("Fib20-16").unwrap();
+        assert_eq!(metrics.inner.len(), 3);
+        validate_measure(
+            metrics,
+            Latency::SLUG_STR,
+            26.26 * 1e-6 * 1_000_000_000.0,
+            Some(1.0),
+        );
+        validate_measure(metrics, BytesPerOp::SLUG_STR, 16.0, Some(0.0));
+        validate_measure(metrics, AllocsPerOp::SLUG_STR, 1.0, Some(0.0));
+
+        let metrics = results.get("Copy-16").unwrap();
+        assert_eq!(metrics.inner.len(), 3);
+        validate_measure(
+            metrics,
+            Latency::SLUG_STR,
+            10.49 * 1e-6 * 1_000_000_000.0,
+            None,
+        );
+        validate_measure(metrics, BytesPerOp::SLUG_STR, 1024.0, None);
+        validate_measure(metrics, AllocsPerOp::SLUG_STR, 2.0, None);
+    }
+
+    #[test]
+    fn test_adapter_go_bench_benchstat_compare() {
+        // Only the last column is used when comparing multiple input files.
+        let results = convert_go_bench("benchstat_compare");
+        assert_eq!(results.inner.len(), 2);
+
+        let metrics = results.get("Fib10-16").unwrap();
+        assert_eq!(metrics.inner.len(), 1);
+        validate_measure(
+            metrics,
+            Latency::SLUG_STR,
+            205.1 * 1e-9 * 1_000_000_000.0,
+            Some(1.0),
+        );
+
+        let metrics = results.get("Fib20-16").unwrap();
+        assert_eq!(metrics.inner.len(), 1);
+        validate_measure(
+            metrics,
+            Latency::SLUG_STR,
+            26.10 * 1e-6 * 1_000_000_000.0,
+            Some(1.0),
+        );
     }
 }
@@ -0,0 +1,126 @@
+use bencher_json::BenchmarkName;
+
+use crate::{
+    adapters::util::Units,
+    results::adapter_results::{AdapterResults, GoMeasure},
+};
+
+use super::bench::{go_latency, go_measure};
+
+const TABLE_SEPARATOR: char = '│';
+const GEOMEAN: &str = "geomean";
+const VARIANCE: &str = "±";
+
+// https://pkg.go.dev/golang.org/x/perf/cmd/benchstat
+//                  │  fib.txt   │
+//                  │   sec/op   │
+// Fib10-16           210.2n ± 2%
+// Fib20-16           26.26µ ± 1%
+// geomean            2.350µ
+pub(super) fn parse_benchstat(input: &str) -> Option<AdapterResults> {
+    let mut benchmark_metrics = Vec::new();
+    let mut header = false;
+    let mut column = None;
+    for line in input.lines() {
+        if line.contains(TABLE_SEPARATOR) {
+            // Each table starts with a row of input files followed by a row of units.
+            if header {
+                column = parse_benchstat_column(line);
+                header = false;
+            } else {
+                header = true;
+                column = None;
+            }
+        } else if line.trim().is_empty() {
+            header = false;
+            column = None;
+        } else if let Some((offset, unit)) = column {
+            if let Some(benchmark_metric) = parse_benchstat_row(line, offset, unit) {
+                benchmark_metrics.push(benchmark_metric);
+            }
+        }
+    }
+
+    AdapterResults::new_go(benchmark_metrics)
+}
+
+// When comparing multiple input files, each one is a column and the last column is the most recent.
+// The rows are aligned with the table separators of the header,
+// so the last column starts at the character offset of its separator.
+//                  │   old.txt   │               new.txt               │
+//                  │   sec/op    │   sec/op     vs base                │
+fn parse_benchstat_column(line: &str) -> Option<(usize, &str)> {
+    let separators = line
+        .chars()
+        .enumerate()
+        .filter_map(|(offset, c)| (c == TABLE_SEPARATOR).then_some(offset))
+        .collect::<Vec<_>>();
+    let last = if line.trim_end().ends_with(TABLE_SEPARATOR) {
+        separators.len().checked_sub(2)?
+    } else {
+        separators.len().checked_sub(1)?
+    };
+    let unit = line
+        .split(TABLE_SEPARATOR)
+        .nth(last + 1)?
+        .split_whitespace()
+        .next()?;
+    Some((*separators.get(last)?, unit))
+}
+
+// Fib10-16           210.2n ± 2%
+// Copy-16            10.49µ ± ∞ ¹
+// Fib10-16             210.2n ± 2%   205.1n ± 1%  -2.43% (p=0.002 n=10)
+fn parse_benchstat_row(
+    line: &str,
+    offset: usize,
+    unit: &str,
+) -> Option<(BenchmarkName, Vec<GoMeasure>)> {
+    let name = line.split_whitespace().next()?;
+    if name == GEOMEAN {
+        return None;
+    }
+    let last_column = line.chars().skip(offset).collect::<String>();
+    let mut cells = last_column.split_whitespace();
+    let value = parse_benchstat_value(cells.next()?)?;
+    let variance = (cells.next() == Some(VARIANCE))
+        .then(|| cells.next())
+        .flatten()
+        .and_then(|variance| variance.strip_suffix('%'))
+        .and_then(|variance| variance.parse::<f64>().ok());
+    // benchstat normalizes the units reported by `go test -bench`
+    let measure = match unit {
+        "sec/op" => Some(go_latency(value, Units::Sec, variance)),
+        "B/s" => go_measure(value / 1_000_000.0, "MB/s", variance),
+        _ => go_measure(value, unit, variance),
+    }?;
+    Some((name.parse().ok()?, vec![measure]))
+}
+
+// benchstat scales values with SI and binary prefixes
+fn parse_benchstat_value(value: &str) -> Option<f64> {
+    const PREFIXES: [(&str, f64); 13] = [
+        ("Ki", 1024.0),
+        ("Mi", 1024.0 * 1024.0),
+        ("Gi", 1024.0 * 1024.0 * 1024.0),
+        ("Ti", 1024.0 * 1024.0 * 1024.0 * 1024.0),
+        ("n", 1e-9),
+        ("µ", 1e-6),
+        ("μ", 1e-6),
+        ("u", 1e-6),
+        ("m", 1e-3),
+        ("k", 1e3),
+        ("M", 1e6),
+        ("G", 1e9),
+        ("T", 1e12),
+    ];
+    PREFIXES
+        .iter()
+        .find_map(|(prefix, scale)| {
+            value
+                .strip_suffix(prefix)
+                .and_then(|value| value.parse::<f64>().ok())
+                .map(|value| value * scale)
+        })
+        .or_else(|| value.parse().ok())
+}
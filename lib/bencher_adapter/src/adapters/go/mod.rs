@@ -1,4 +1,6 @@
 pub mod bench;
+mod benchstat;
+mod test2json;
 
 use crate::{Adaptable, AdapterResults, Settings};
 use bench::AdapterGoBench;
@@ -21,4 +23,10 @@ mod test_go {
         let results = convert_file_path::<AdapterGo>("./tool_output/go/bench/five.txt");
         test_go_bench::validate_adapter_go_bench(&results);
     }
+
+    #[test]
+    fn test_adapter_go_bench_benchmem() {
+        let results = convert_file_path::<AdapterGo>("./tool_output/go/bench/benchmem.txt");
+        test_go_bench::validate_adapter_go_bench_benchmem(&results);
+    }
 }
@@ -0,0 +1,26 @@
+use serde::Deserialize;
+
+const OUTPUT_ACTION: &str = "output";
+
+// https://pkg.go.dev/cmd/test2json
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct TestEvent {
+    action: String,
+    output: Option<String>,
+}
+
+// Concatenate the output of all of the events,
+// as the output for a single benchmark may be split across multiple events.
+pub(super) fn parse_test2json(input: &str) -> Option<String> {
+    let mut output = String::new();
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        let event = serde_json::from_str::<TestEvent>(line).ok()?;
+        if event.action == OUTPUT_ACTION {
+            if let Some(event_output) = event.output {
+                output.push_str(&event_output);
+            }
+        }
+    }
+    (!output.is_empty()).then_some(output)
+}
@@ -26,7 +26,7 @@ mod test_magic {
     use super::AdapterMagic;
     use crate::adapters::{
         c_sharp::{AdapterCSharp, dot_net::test_c_sharp_dot_net},
-        cpp::{catch2::test_cpp_catch2, google::test_cpp_google},
+        cpp::{catch2::test_cpp_catch2, catch2_xml::test_cpp_catch2_xml, google::test_cpp_google},
         go::bench::test_go_bench,
         java::jmh::test_java_jmh,
         js::{benchmark::test_js_benchmark, time::test_js_time},
@@ -71,6 +71,12 @@ mod test_magic {
         test_cpp_catch2::validate_adapter_cpp_catch2(&results);
     }
 
+    #[test]
+    fn test_adapter_magic_cpp_catch2_xml() {
+        let results = convert_file_path::<AdapterMagic>("./tool_output/cpp/catch2/xml.xml");
+        test_cpp_catch2_xml::validate_adapter_cpp_catch2_xml(&results);
+    }
+
     #[test]
     fn test_adapter_magic_go_bench() {
         let results = convert_file_path::<AdapterMagic>("./tool_output/go/bench/five.txt");
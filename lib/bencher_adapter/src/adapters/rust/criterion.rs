@@ -17,10 +17,17 @@ use crate::{
     results::adapter_results::AdapterResults,
 };
 
+use super::criterion_estimates::AdapterRustCriterionEstimates;
+
 pub struct AdapterRustCriterion;
 
 impl Adaptable for AdapterRustCriterion {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
+        // A Criterion output directory (ie `bencher run --file target/criterion`)
+        if let Some(results) = AdapterRustCriterionEstimates::parse(input, settings) {
+            return Some(results);
+        }
+
         match settings.average {
             Some(JsonAverage::Mean) | None => {},
             Some(JsonAverage::Median) => return None,
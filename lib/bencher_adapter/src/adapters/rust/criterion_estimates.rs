@@ -0,0 +1,242 @@
+use bencher_json::{BenchmarkName, JsonNewMetric, project::report::JsonAverage};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Adaptable, Settings,
+    results::adapter_results::{AdapterResults, RustCriterionMeasure},
+};
+
+const NANOS_PER_SEC: f64 = 1_000_000_000.0;
+
+/// Criterion benchmarks gathered from a Criterion output directory (ie `target/criterion`).
+/// Each benchmark is the `benchmark.json` and `estimates.json` from its `new` directory.
+pub struct AdapterRustCriterionEstimates;
+
+impl Adaptable for AdapterRustCriterionEstimates {
+    fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
+        let criterion_benchmarks = serde_json::from_str::<Vec<CriterionBenchmark>>(input).ok()?;
+
+        let mut benchmark_metrics = Vec::with_capacity(criterion_benchmarks.len());
+        for CriterionBenchmark {
+            benchmark,
+            estimates,
+        } in criterion_benchmarks
+        {
+            let estimate = match settings.average {
+                // Criterion reports the slope when it is available, otherwise the mean.
+                None => estimates.slope.unwrap_or(estimates.mean),
+                Some(JsonAverage::Mean) => estimates.mean,
+                Some(JsonAverage::Median) => estimates.median,
+            };
+            let benchmark_name = benchmark.benchmark_name()?;
+            let measures = estimate.measures(benchmark.throughput);
+            benchmark_metrics.push((benchmark_name, measures));
+        }
+
+        AdapterResults::new_rust_criterion(benchmark_metrics)
+    }
+}
+
+// The CLI collects a Criterion output directory into a JSON array of these
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CriterionBenchmark {
+    pub benchmark: Benchmark,
+    pub estimates: Estimates,
+}
+
+// https://github.com/bheisler/criterion.rs/blob/master/src/report.rs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Benchmark {
+    pub group_id: String,
+    pub function_id: Option<String>,
+    pub value_str: Option<String>,
+    pub throughput: Option<Throughput>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Throughput {
+    Bytes(u64),
+    BytesDecimal(u64),
+    Elements(u64),
+    ElementsAndBytes { elements: u64, bytes: u64 },
+}
+
+// https://github.com/bheisler/criterion.rs/blob/master/src/estimate.rs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Estimates {
+    pub mean: Estimate,
+    pub median: Estimate,
+    pub slope: Option<Estimate>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Estimate {
+    pub confidence_interval: ConfidenceInterval,
+    pub point_estimate: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ConfidenceInterval {
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+}
+
+impl Benchmark {
+    // The group, function, and parameter make up the benchmark name hierarchy (ie `group/function/parameter`).
+    fn benchmark_name(&self) -> Option<BenchmarkName> {
+        let Self {
+            group_id,
+            function_id,
+            value_str,
+            ..
+        } = self;
+        std::iter::once(group_id)
+            .chain(function_id)
+            .chain(value_str)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("/")
+            .parse()
+            .ok()
+    }
+}
+
+impl Estimate {
+    fn measures(self, throughput: Option<Throughput>) -> Vec<RustCriterionMeasure> {
+        let Self {
+            confidence_interval:
+                ConfidenceInterval {
+                    lower_bound,
+                    upper_bound,
+                },
+            point_estimate,
+        } = self;
+        let latency = JsonNewMetric {
+            value: point_estimate.into(),
+            lower_value: Some(lower_bound.into()),
+            upper_value: Some(upper_bound.into()),
+        };
+        // The throughput is inversely proportional to the latency,
+        // so the upper bound of the latency is the lower bound of the throughput.
+        let per_second = |count: u64| {
+            #[expect(clippy::cast_precision_loss)]
+            let count = count as f64 * NANOS_PER_SEC;
+            JsonNewMetric {
+                value: (count / point_estimate).into(),
+                lower_value: Some((count / upper_bound).into()),
+                upper_value: Some((count / lower_bound).into()),
+            }
+        };
+
+        let mut measures = vec![RustCriterionMeasure::Latency(latency)];
+        match throughput {
+            Some(Throughput::Bytes(bytes) | Throughput::BytesDecimal(bytes)) => {
+                measures.push(RustCriterionMeasure::ByteThroughput(per_second(bytes)));
+            },
+            Some(Throughput::Elements(elements)) => {
+                measures.push(RustCriterionMeasure::Throughput(per_second(elements)));
+            },
+            Some(Throughput::ElementsAndBytes { elements, bytes }) => {
+                measures.push(RustCriterionMeasure::Throughput(per_second(elements)));
+                measures.push(RustCriterionMeasure::ByteThroughput(per_second(bytes)));
+            },
+            None => {},
+        }
+        measures
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_rust_criterion_estimates {
+    use bencher_json::project::{
+        measure::built_in::{
+            BuiltInMeasure as _,
+            criterion::ByteThroughput,
+            default::{Latency, Throughput},
+        },
+        report::JsonAverage,
+    };
+    use ordered_float::OrderedFloat;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        AdapterResults, Settings,
+        adapters::test_util::{convert_file_path, opt_convert_file_path},
+        results::adapter_metrics::AdapterMetrics,
+    };
+
+    use super::AdapterRustCriterionEstimates;
+
+    const FILE_PATH: &str = "./tool_output/rust/criterion/estimates.json";
+
+    fn validate_metric(metrics: &AdapterMetrics, key: &str, value: f64, lower: f64, upper: f64) {
+        let metric = metrics.get(key).unwrap();
+        assert_eq!(metric.value, OrderedFloat::from(value), "{key}");
+        assert_eq!(metric.lower_value, Some(OrderedFloat::from(lower)), "{key}");
+        assert_eq!(metric.upper_value, Some(OrderedFloat::from(upper)), "{key}");
+    }
+
+    #[test]
+    fn test_adapter_rust_criterion_estimates() {
+        let results = convert_file_path::<AdapterRustCriterionEstimates>(FILE_PATH);
+        validate_adapter_rust_criterion_estimates(&results);
+    }
+
+    pub fn validate_adapter_rust_criterion_estimates(results: &AdapterResults) {
+        assert_eq!(results.inner.len(), 3);
+
+        // The slope is used when it is available
+        let metrics = results.get("criterion_benchmark").unwrap();
+        assert_eq!(metrics.inner.len(), 1);
+        validate_metric(metrics, Latency::SLUG_STR, 284.5, 282.25, 286.75);
+
+        let metrics = results.get("fib/recursive/20").unwrap();
+        assert_eq!(metrics.inner.len(), 2);
+        validate_metric(metrics, Latency::SLUG_STR, 32_000.0, 25_000.0, 40_000.0);
+        validate_metric(
+            metrics,
+            Throughput::SLUG_STR,
+            1e9 / 32_000.0,
+            1e9 / 40_000.0,
+            1e9 / 25_000.0,
+        );
+
+        let metrics = results.get("copy/1024").unwrap();
+        assert_eq!(metrics.inner.len(), 2);
+        validate_metric(metrics, Latency::SLUG_STR, 640.0, 500.0, 800.0);
+        validate_metric(
+            metrics,
+            ByteThroughput::SLUG_STR,
+            1024.0 * 1e9 / 640.0,
+            1024.0 * 1e9 / 800.0,
+            1024.0 * 1e9 / 500.0,
+        );
+    }
+
+    #[test]
+    fn test_adapter_rust_criterion_estimates_average() {
+        let results = opt_convert_file_path::<AdapterRustCriterionEstimates>(
+            FILE_PATH,
+            Settings {
+                average: Some(JsonAverage::Mean),
+            },
+        )
+        .unwrap();
+        let metrics = results.get("criterion_benchmark").unwrap();
+        validate_metric(metrics, Latency::SLUG_STR, 285.5, 280.5, 290.5);
+        let metrics = results.get("copy/1024").unwrap();
+        validate_metric(metrics, Latency::SLUG_STR, 512.0, 400.0, 640.0);
+
+        let results = opt_convert_file_path::<AdapterRustCriterionEstimates>(
+            FILE_PATH,
+            Settings {
+                average: Some(JsonAverage::Median),
+            },
+        )
+        .unwrap();
+        let metrics = results.get("criterion_benchmark").unwrap();
+        validate_metric(metrics, Latency::SLUG_STR, 281.0, 279.0, 283.0);
+        let metrics = results.get("fib/recursive/20").unwrap();
+        validate_metric(metrics, Latency::SLUG_STR, 25_000.0, 24_000.0, 26_000.0);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod bench;
 pub mod criterion;
+pub mod criterion_estimates;
 pub mod iai;
 pub mod iai_callgrind;
 
@@ -1,24 +1,36 @@
 pub mod hyperfine;
+pub mod perf_stat;
 
 use crate::{Adaptable, AdapterResults, Settings};
 use hyperfine::AdapterShellHyperfine;
+use perf_stat::AdapterShellPerfStat;
 
 pub struct AdapterShell;
 
 impl Adaptable for AdapterShell {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
         AdapterShellHyperfine::parse(input, settings)
+            .or_else(|| AdapterShellPerfStat::parse(input, settings))
     }
 }
 
 #[cfg(test)]
 mod test_shell {
     use super::AdapterShell;
-    use crate::adapters::{shell::hyperfine::test_shell_hyperfine, test_util::convert_file_path};
+    use crate::adapters::{
+        shell::{hyperfine::test_shell_hyperfine, perf_stat::test_shell_perf_stat},
+        test_util::convert_file_path,
+    };
 
     #[test]
     fn test_adapter_shell_hyperfine() {
         let results = convert_file_path::<AdapterShell>("./tool_output/shell/hyperfine/two.json");
         test_shell_hyperfine::validate_adapter_shell_hyperfine(&results);
     }
+
+    #[test]
+    fn test_adapter_shell_perf_stat() {
+        let results = convert_file_path::<AdapterShell>("./tool_output/shell/perf_stat/text.txt");
+        test_shell_perf_stat::validate_adapter_shell_perf_stat(&results, "./target/release/fib 30");
+    }
 }
@@ -0,0 +1,347 @@
+use bencher_json::{BenchmarkName, JsonNewMetric, project::report::JsonAverage};
+use ordered_float::OrderedFloat;
+
+use crate::{
+    Adaptable, Settings,
+    adapters::util::{Units, latency_as_nanos},
+    results::adapter_results::{AdapterResults, PerfStatMeasure},
+};
+
+pub struct AdapterShellPerfStat;
+
+// The CSV output (`-x,`) does not include the command that was measured.
+// If there is output for more than one command, then the benchmark names are numbered (ie `perf stat 2`).
+const DEFAULT_BENCHMARK_NAME: &str = "perf stat";
+const HEADER_PREFIX: &str = "Performance counter stats for '";
+const CSV_SEPARATOR: char = ',';
+
+impl Adaptable for AdapterShellPerfStat {
+    fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
+        // `perf stat -r N` reports the mean of the runs
+        match settings.average {
+            None | Some(JsonAverage::Mean) => {},
+            Some(JsonAverage::Median) => return None,
+        }
+
+        let mut benchmark_metrics = Vec::new();
+        let mut benchmark_name = None;
+        let mut metrics: Vec<PerfStatMeasure> = Vec::new();
+        let mut events: Vec<&str> = Vec::new();
+        for line in input.lines() {
+            if let Some(name) = parse_header(line) {
+                if !metrics.is_empty() {
+                    benchmark_metrics.push((benchmark_name.take(), std::mem::take(&mut metrics)));
+                }
+                events.clear();
+                benchmark_name = Some(name);
+            } else if let Some((event, metric)) = parse_csv_line(line) {
+                // The CSV output does not include the measured command,
+                // so a repeated event is the start of the output for the next command.
+                // The raw event is used, as a hybrid CPU reports the same measure for each of its PMUs
+                // (ie `cpu_core/cycles/` and `cpu_atom/cycles/`).
+                if benchmark_name.is_none() && events.contains(&event) {
+                    benchmark_metrics.push((None, std::mem::take(&mut metrics)));
+                    events.clear();
+                }
+                events.push(event);
+                metrics.push(metric);
+            } else if benchmark_name.is_some() {
+                // Only parse the human readable output after a header,
+                // as it is much less structured than the CSV output.
+                if let Some(metric) = parse_text_line(line) {
+                    metrics.push(metric);
+                }
+            }
+        }
+        if !metrics.is_empty() {
+            benchmark_metrics.push((benchmark_name, metrics));
+        }
+
+        // Number the unnamed benchmarks if there are more than one of them, so they do not collide.
+        let unnamed = benchmark_metrics
+            .iter()
+            .filter(|(name, _)| name.is_none())
+            .count();
+        let mut index = 0;
+        let benchmark_metrics = benchmark_metrics
+            .into_iter()
+            .map(|(name, metrics)| {
+                let name = if let Some(name) = name {
+                    name
+                } else if unnamed == 1 {
+                    DEFAULT_BENCHMARK_NAME.parse().ok()?
+                } else {
+                    index += 1;
+                    format!("{DEFAULT_BENCHMARK_NAME} {index}").parse().ok()?
+                };
+                Some((name, metrics))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        AdapterResults::new_perf_stat(benchmark_metrics)
+    }
+}
+
+// Performance counter stats for './target/release/fib 30' (5 runs):
+fn parse_header(line: &str) -> Option<BenchmarkName> {
+    let command = line.trim().strip_prefix(HEADER_PREFIX)?;
+    let (command, _) = command.rsplit_once('\'')?;
+    command.parse().ok()
+}
+
+// <value>,<unit>,<event>,[<variance>%,]<run time>,<percentage>,<metric value>,<metric unit>
+fn parse_csv_line(line: &str) -> Option<(&str, PerfStatMeasure)> {
+    let mut fields = line.split(CSV_SEPARATOR);
+    let value = fields.next()?.parse::<f64>().ok()?;
+    let unit = fields.next()?;
+    let event = fields.next()?;
+    let variance = fields
+        .next()
+        .and_then(|field| field.strip_suffix('%'))
+        .and_then(|variance| variance.parse::<f64>().ok());
+    PerfStatMeasure::new(event, value, unit, variance).map(|measure| (event, measure))
+}
+
+//      1,962,305,712      cycles:u        #    3.839 GHz        ( +-  0.12% )  (83.26%)
+//             511.19 msec task-clock:u    #    0.998 CPUs utilized    ( +-  0.35% )
+fn parse_text_line(line: &str) -> Option<PerfStatMeasure> {
+    let (counter, comment) = line.split_once('#').unwrap_or((line, ""));
+    let mut tokens = counter.split_whitespace();
+    let value = tokens.next()?.replace(',', "").parse::<f64>().ok()?;
+    let token = tokens.next()?;
+    let (unit, event) = if parse_time_units(token).is_some() {
+        (token, tokens.next()?)
+    } else {
+        ("", token)
+    };
+    let variance = comment
+        .split_once("( +-")
+        .and_then(|(_, variance)| variance.split_once('%'))
+        .and_then(|(variance, _)| variance.trim().parse::<f64>().ok());
+    PerfStatMeasure::new(event, value, unit, variance)
+}
+
+impl PerfStatMeasure {
+    fn new(event: &str, value: f64, unit: &str, variance: Option<f64>) -> Option<Self> {
+        // Remove any PMU (ie `cpu_core/cycles/`) and modifiers (ie `cycles:u`)
+        let event = event
+            .strip_suffix('/')
+            .and_then(|event| event.split_once('/'))
+            .map_or(event, |(_pmu, event)| event);
+        let event = event.split(':').next()?;
+        let (into_variant, value): (fn(JsonNewMetric) -> Self, OrderedFloat<f64>) = match event {
+            "cycles" | "cpu-cycles" => (Self::Cycles, value.into()),
+            "instructions" => (Self::Instructions, value.into()),
+            "branch-misses" => (Self::BranchMisses, value.into()),
+            "cache-misses" => (Self::CacheMisses, value.into()),
+            "task-clock" => (
+                Self::TaskClock,
+                latency_as_nanos(value, parse_time_units(unit)?),
+            ),
+            _ => return None,
+        };
+        // The variance is the relative standard deviation of the mean across runs, as a percentage.
+        let (lower_value, upper_value) = variance.map_or((None, None), |variance| {
+            let spread = value * (variance / 100.0);
+            (Some(value - spread), Some(value + spread))
+        });
+        Some(into_variant(JsonNewMetric {
+            value,
+            lower_value,
+            upper_value,
+        }))
+    }
+}
+
+fn parse_time_units(unit: &str) -> Option<Units> {
+    Some(match unit {
+        "nsec" | "ns" => Units::Nano,
+        "usec" | "us" => Units::Micro,
+        "msec" | "ms" | "" => Units::Milli,
+        "sec" | "s" => Units::Sec,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod test_shell_perf_stat {
+    use bencher_json::project::{
+        measure::built_in::{
+            BuiltInMeasure as _,
+            perf::{BranchMisses, CacheMisses, Cycles, Instructions, TaskClock},
+        },
+        report::JsonAverage,
+    };
+    use ordered_float::OrderedFloat;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        Settings,
+        adapters::test_util::{convert_file_path, opt_convert_file_path},
+        results::{adapter_metrics::AdapterMetrics, adapter_results::AdapterResults},
+    };
+
+    use super::AdapterShellPerfStat;
+
+    fn convert_shell_perf_stat(suffix: &str) -> AdapterResults {
+        let file_path = file_path(suffix);
+        convert_file_path::<AdapterShellPerfStat>(&file_path)
+    }
+
+    fn file_path(suffix: &str) -> String {
+        format!("./tool_output/shell/perf_stat/{suffix}.txt")
+    }
+
+    fn validate_metric(
+        metrics: &AdapterMetrics,
+        key: &str,
+        value: f64,
+        variance: Option<(f64, f64)>,
+    ) {
+        let metric = metrics.get(key).unwrap();
+        assert_eq!(metric.value, OrderedFloat::from(value), "{key}");
+        let (lower_value, upper_value) = variance.unzip();
+        assert_eq!(
+            metric.lower_value,
+            lower_value.map(OrderedFloat::from),
+            "{key}"
+        );
+        assert_eq!(
+            metric.upper_value,
+            upper_value.map(OrderedFloat::from),
+            "{key}"
+        );
+    }
+
+    #[test]
+    fn test_adapter_shell_perf_stat_median() {
+        let results = opt_convert_file_path::<AdapterShellPerfStat>(
+            &file_path("text"),
+            Settings {
+                average: Some(JsonAverage::Median),
+            },
+        );
+        assert_eq!(results, None);
+    }
+
+    #[test]
+    fn test_adapter_shell_perf_stat_text() {
+        let results = convert_shell_perf_stat("text");
+        validate_adapter_shell_perf_stat(&results, "./target/release/fib 30");
+    }
+
+    #[test]
+    fn test_adapter_shell_perf_stat_csv() {
+        let results = convert_shell_perf_stat("csv");
+        validate_adapter_shell_perf_stat(&results, "perf stat");
+    }
+
+    pub fn validate_adapter_shell_perf_stat(results: &AdapterResults, benchmark_name: &str) {
+        assert_eq!(results.inner.len(), 1);
+
+        let metrics = results.get(benchmark_name).unwrap();
+        assert_eq!(metrics.inner.len(), 5);
+        validate_metric(metrics, TaskClock::SLUG_STR, 512.34 * 1_000_000.0, None);
+        validate_metric(metrics, Cycles::SLUG_STR, 1_962_305_712.0, None);
+        validate_metric(metrics, Instructions::SLUG_STR, 4_506_987_093.0, None);
+        validate_metric(metrics, BranchMisses::SLUG_STR, 12_345_678.0, None);
+        validate_metric(metrics, CacheMisses::SLUG_STR, 456_789.0, None);
+    }
+
+    #[test]
+    fn test_adapter_shell_perf_stat_repeat() {
+        let results = convert_shell_perf_stat("repeat");
+        validate_adapter_shell_perf_stat_repeat(&results, "./target/release/fib 30");
+    }
+
+    #[test]
+    fn test_adapter_shell_perf_stat_csv_repeat() {
+        let results = convert_shell_perf_stat("csv_repeat");
+        validate_adapter_shell_perf_stat_repeat(&results, "perf stat");
+    }
+
+    fn validate_adapter_shell_perf_stat_repeat(results: &AdapterResults, benchmark_name: &str) {
+        assert_eq!(results.inner.len(), 1);
+
+        let metrics = results.get(benchmark_name).unwrap();
+        // Cache misses were not counted
+        assert_eq!(metrics.inner.len(), 4);
+        let variance = |value: f64, percent: f64| {
+            let spread = value * (percent / 100.0);
+            Some((value - spread, value + spread))
+        };
+        validate_metric(
+            metrics,
+            TaskClock::SLUG_STR,
+            511.19 * 1_000_000.0,
+            variance(511.19 * 1_000_000.0, 0.35),
+        );
+        validate_metric(
+            metrics,
+            Cycles::SLUG_STR,
+            1_962_305_712.0,
+            variance(1_962_305_712.0, 0.12),
+        );
+        validate_metric(
+            metrics,
+            Instructions::SLUG_STR,
+            4_506_987_093.0,
+            variance(4_506_987_093.0, 0.01),
+        );
+        validate_metric(
+            metrics,
+            BranchMisses::SLUG_STR,
+            12_345_678.0,
+            variance(12_345_678.0, 0.5),
+        );
+    }
+
+    #[test]
+    fn test_adapter_shell_perf_stat_two() {
+        let results = convert_shell_perf_stat("two");
+        assert_eq!(results.inner.len(), 2);
+
+        let metrics = results.get("./target/release/fib 20").unwrap();
+        assert_eq!(metrics.inner.len(), 3);
+        validate_metric(metrics, TaskClock::SLUG_STR, 4.12 * 1_000_000.0, None);
+        validate_metric(metrics, Cycles::SLUG_STR, 15_803_452.0, None);
+        validate_metric(metrics, Instructions::SLUG_STR, 36_211_970.0, None);
+
+        let metrics = results.get("./target/release/fib 30").unwrap();
+        assert_eq!(metrics.inner.len(), 3);
+        validate_metric(metrics, TaskClock::SLUG_STR, 512.34 * 1_000_000.0, None);
+        validate_metric(metrics, Cycles::SLUG_STR, 1_962_305_712.0, None);
+        validate_metric(metrics, Instructions::SLUG_STR, 4_506_987_093.0, None);
+    }
+
+    #[test]
+    fn test_adapter_shell_perf_stat_csv_two() {
+        let results = convert_shell_perf_stat("csv_two");
+        assert_eq!(results.inner.len(), 2);
+
+        let metrics = results.get("perf stat 1").unwrap();
+        assert_eq!(metrics.inner.len(), 3);
+        validate_metric(metrics, TaskClock::SLUG_STR, 4.12 * 1_000_000.0, None);
+        validate_metric(metrics, Cycles::SLUG_STR, 15_803_452.0, None);
+        validate_metric(metrics, Instructions::SLUG_STR, 36_211_970.0, None);
+
+        let metrics = results.get("perf stat 2").unwrap();
+        assert_eq!(metrics.inner.len(), 3);
+        validate_metric(metrics, TaskClock::SLUG_STR, 512.34 * 1_000_000.0, None);
+        validate_metric(metrics, Cycles::SLUG_STR, 1_962_305_712.0, None);
+        validate_metric(metrics, Instructions::SLUG_STR, 4_506_987_093.0, None);
+    }
+
+    #[test]
+    fn test_adapter_shell_perf_stat_csv_hybrid() {
+        // The same measure from each PMU of a hybrid CPU does not start a new benchmark.
+        let results = convert_shell_perf_stat("csv_hybrid");
+        assert_eq!(results.inner.len(), 1);
+
+        let metrics = results.get("perf stat").unwrap();
+        assert_eq!(metrics.inner.len(), 3);
+        validate_metric(metrics, TaskClock::SLUG_STR, 512.34 * 1_000_000.0, None);
+        assert!(metrics.get(Cycles::SLUG_STR).is_some());
+        assert!(metrics.get(Instructions::SLUG_STR).is_some());
+    }
+}
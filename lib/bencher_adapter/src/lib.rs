@@ -19,7 +19,7 @@ use adapters::{
         AdapterRust, bench::AdapterRustBench, criterion::AdapterRustCriterion, iai::AdapterRustIai,
         iai_callgrind::AdapterRustIaiCallgrind,
     },
-    shell::{AdapterShell, hyperfine::AdapterShellHyperfine},
+    shell::{AdapterShell, hyperfine::AdapterShellHyperfine, perf_stat::AdapterShellPerfStat},
 };
 use bencher_json::project::report::{Adapter, JsonAverage};
 pub use bencher_json::{BenchmarkName, JsonNewMetric};
@@ -63,6 +63,7 @@ impl Adaptable for Adapter {
             Adapter::RustIaiCallgrind => AdapterRustIaiCallgrind::parse(input, settings),
             Adapter::Shell => AdapterShell::parse(input, settings),
             Adapter::ShellHyperfine => AdapterShellHyperfine::parse(input, settings),
+            Adapter::ShellPerfStat => AdapterShellPerfStat::parse(input, settings),
         }
     }
 
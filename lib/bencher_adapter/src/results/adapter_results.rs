@@ -1,7 +1,7 @@
 use std::{collections::HashMap, str::FromStr as _};
 
 use bencher_json::{
-    BenchmarkName, JsonNewMetric,
+    BenchmarkName, JsonNewMetric, MeasureNameId,
     project::{
         measure::built_in::{self, BuiltInMeasure as _},
         metric::Mean,
@@ -41,6 +41,31 @@ pub enum IaiMeasure {
     EstimatedCycles(JsonNewMetric),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RustCriterionMeasure {
+    Latency(JsonNewMetric),
+    Throughput(JsonNewMetric),
+    ByteThroughput(JsonNewMetric),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GoMeasure {
+    Latency(JsonNewMetric),
+    BytesPerOp(JsonNewMetric),
+    AllocsPerOp(JsonNewMetric),
+    MegabytesPerSecond(JsonNewMetric),
+    Custom(MeasureNameId, JsonNewMetric),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PerfStatMeasure {
+    Cycles(JsonNewMetric),
+    Instructions(JsonNewMetric),
+    BranchMisses(JsonNewMetric),
+    CacheMisses(JsonNewMetric),
+    TaskClock(JsonNewMetric),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum IaiCallgrindMeasure {
     /*
@@ -212,6 +237,107 @@ impl AdapterResults {
         Some(results_map.into())
     }
 
+    pub fn new_rust_criterion(
+        benchmark_metrics: Vec<(BenchmarkName, Vec<RustCriterionMeasure>)>,
+    ) -> Option<Self> {
+        if benchmark_metrics.is_empty() {
+            return None;
+        }
+
+        let mut results_map = HashMap::new();
+        for (benchmark_name, metrics) in benchmark_metrics {
+            let metrics_value = results_map
+                .entry(benchmark_name)
+                .or_insert_with(AdapterMetrics::default);
+            for metric in metrics {
+                let (resource_id, metric) = match metric {
+                    RustCriterionMeasure::Latency(json_metric) => {
+                        (built_in::default::Latency::name_id(), json_metric)
+                    },
+                    RustCriterionMeasure::Throughput(json_metric) => {
+                        (built_in::default::Throughput::name_id(), json_metric)
+                    },
+                    RustCriterionMeasure::ByteThroughput(json_metric) => {
+                        (built_in::criterion::ByteThroughput::name_id(), json_metric)
+                    },
+                };
+                metrics_value.inner.insert(resource_id, metric);
+            }
+        }
+
+        Some(results_map.into())
+    }
+
+    pub fn new_go(benchmark_metrics: Vec<(BenchmarkName, Vec<GoMeasure>)>) -> Option<Self> {
+        if benchmark_metrics.is_empty() {
+            return None;
+        }
+
+        let mut results_map = HashMap::new();
+        for (benchmark_name, metrics) in benchmark_metrics {
+            let metrics_value = results_map
+                .entry(benchmark_name)
+                .or_insert_with(AdapterMetrics::default);
+            for metric in metrics {
+                let (resource_id, metric) = match metric {
+                    GoMeasure::Latency(json_metric) => {
+                        (built_in::default::Latency::name_id(), json_metric)
+                    },
+                    GoMeasure::BytesPerOp(json_metric) => {
+                        (built_in::go::BytesPerOp::name_id(), json_metric)
+                    },
+                    GoMeasure::AllocsPerOp(json_metric) => {
+                        (built_in::go::AllocsPerOp::name_id(), json_metric)
+                    },
+                    GoMeasure::MegabytesPerSecond(json_metric) => {
+                        (built_in::go::MegabytesPerSecond::name_id(), json_metric)
+                    },
+                    GoMeasure::Custom(measure, json_metric) => (measure, json_metric),
+                };
+                metrics_value.inner.insert(resource_id, metric);
+            }
+        }
+
+        Some(results_map.into())
+    }
+
+    pub fn new_perf_stat(
+        benchmark_metrics: Vec<(BenchmarkName, Vec<PerfStatMeasure>)>,
+    ) -> Option<Self> {
+        if benchmark_metrics.is_empty() {
+            return None;
+        }
+
+        let mut results_map = HashMap::new();
+        for (benchmark_name, metrics) in benchmark_metrics {
+            let metrics_value = results_map
+                .entry(benchmark_name)
+                .or_insert_with(AdapterMetrics::default);
+            for metric in metrics {
+                let (resource_id, metric) = match metric {
+                    PerfStatMeasure::Cycles(json_metric) => {
+                        (built_in::perf::Cycles::name_id(), json_metric)
+                    },
+                    PerfStatMeasure::Instructions(json_metric) => {
+                        (built_in::perf::Instructions::name_id(), json_metric)
+                    },
+                    PerfStatMeasure::BranchMisses(json_metric) => {
+                        (built_in::perf::BranchMisses::name_id(), json_metric)
+                    },
+                    PerfStatMeasure::CacheMisses(json_metric) => {
+                        (built_in::perf::CacheMisses::name_id(), json_metric)
+                    },
+                    PerfStatMeasure::TaskClock(json_metric) => {
+                        (built_in::perf::TaskClock::name_id(), json_metric)
+                    },
+                };
+                metrics_value.inner.insert(resource_id, metric);
+            }
+        }
+
+        Some(results_map.into())
+    }
+
     #[expect(clippy::too_many_lines)]
     pub fn new_iai_callgrind(
         benchmark_metrics: Vec<(BenchmarkName, Vec<IaiCallgrindMeasure>)>,
@@ -1,9 +1,15 @@
-use bencher_json::{Boundary, ModelTest, SampleSize, project::boundary::BoundaryLimit};
-use slog::Logger;
+use bencher_json::{
+    Boundary, CdfBoundary, Model, ModelTest, Smoothing, project::boundary::BoundaryLimit,
+};
+use slog::{Logger, debug};
 
+use crate::change_point::ChangePoint;
+use crate::forecast::Forecast;
 use crate::limits::{MetricsLimits, NormalTestKind};
 use crate::ln::Ln;
+use crate::mann_whitney::MannWhitneyU;
 use crate::mean::Mean;
+use crate::p_value::PValue;
 use crate::quartiles::Quartiles;
 use crate::{BoundaryError, MetricsData};
 
@@ -11,39 +17,38 @@ use crate::{BoundaryError, MetricsData};
 pub struct MetricsBoundary {
     pub limits: MetricsLimits,
     pub outlier: Option<BoundaryLimit>,
+    /// The index of the first sample after a detected change point,
+    /// into the iterations of the sample (most recent first) followed by the historical metrics data.
+    pub change_point: Option<usize>,
+    /// The p-values of the sample, for statistical threshold model tests.
+    pub p_value: Option<PValue>,
 }
 
 impl MetricsBoundary {
+    /// The sample is all of the iterations for a benchmark and measure from a single report.
+    /// The historical metrics data must not include the sample.
     pub fn new(
         log: &Logger,
-        datum: f64,
-        metrics_data: &MetricsData,
-        model_test: ModelTest,
-        min_sample_size: Option<SampleSize>,
-        lower_boundary: Option<Boundary>,
-        upper_boundary: Option<Boundary>,
+        sample: &[f64],
+        history: &MetricsData,
+        model: Model,
     ) -> Result<Self, BoundaryError> {
-        Self::new_inner(
-            log,
-            datum,
-            metrics_data,
-            model_test,
-            min_sample_size,
-            lower_boundary,
-            upper_boundary,
-        )
-        .map(Option::unwrap_or_default)
+        Self::new_inner(log, sample, history, model).map(Option::unwrap_or_default)
     }
 
     fn new_inner(
         log: &Logger,
-        datum: f64,
-        metrics_data: &MetricsData,
-        model_test: ModelTest,
-        min_sample_size: Option<SampleSize>,
-        lower_boundary: Option<Boundary>,
-        upper_boundary: Option<Boundary>,
+        sample: &[f64],
+        history: &MetricsData,
+        model: Model,
     ) -> Result<Option<Self>, BoundaryError> {
+        let Model {
+            test: model_test,
+            min_sample_size,
+            lower_boundary,
+            upper_boundary,
+            ..
+        } = model;
         // If there is no boundary, then simply return.
         if lower_boundary.is_none() && upper_boundary.is_none() {
             slog::debug!(
@@ -52,7 +57,15 @@ impl MetricsBoundary {
             );
             return Ok(None);
         }
-        let data = &metrics_data.data;
+        // Most models compare a single datum against the historical data.
+        // For a sample with multiple iterations, the mean of the sample is used as that datum.
+        // The datum is included in the data as its most recent sample.
+        let Some(Mean { mean: datum }) = Mean::new(sample) else {
+            slog::debug!(log, "No sample for threshold model test {model_test:?}");
+            return Ok(None);
+        };
+        let data = [&[datum], history.data.as_slice()].concat();
+        let data = data.as_slice();
         let data_len = data.len();
         // If there is a min sample size, then check to see if it is met.
         // Otherwise, simply return.
@@ -106,6 +119,25 @@ impl MetricsBoundary {
             ModelTest::DeltaIqr => {
                 Self::new_iqr(log, datum, data, true, lower_boundary, upper_boundary)
             },
+            ModelTest::MannWhitneyU => {
+                Self::new_mann_whitney(log, sample, &history.data, lower_boundary, upper_boundary)
+            },
+            ModelTest::ChangePoint => {
+                Ok(Self::new_change_point(
+                    log,
+                    datum,
+                    &history.data,
+                    lower_boundary,
+                    upper_boundary,
+                )?
+                .map(|mut boundary| {
+                    // Map the change point onto the historical data that is preceded by every iteration of the sample.
+                    boundary.change_point =
+                        boundary.change_point.map(|index| index + sample.len() - 1);
+                    boundary
+                }))
+            },
+            ModelTest::Forecast => Self::new_forecast(log, datum, &history.data, model),
         }
     }
 
@@ -117,7 +149,12 @@ impl MetricsBoundary {
         let limits = MetricsLimits::new_static(lower_boundary, upper_boundary);
         let outlier = limits.outlier(datum);
 
-        Self { limits, outlier }
+        Self {
+            limits,
+            outlier,
+            change_point: None,
+            p_value: None,
+        }
     }
 
     fn new_percentage(
@@ -144,7 +181,12 @@ impl MetricsBoundary {
         let limits = MetricsLimits::new_percentage(log, mean, lower_boundary, upper_boundary);
         let outlier = limits.outlier(datum);
 
-        Ok(Some(Self { limits, outlier }))
+        Ok(Some(Self {
+            limits,
+            outlier,
+            change_point: None,
+            p_value: None,
+        }))
     }
 
     fn new_normal(
@@ -182,8 +224,14 @@ impl MetricsBoundary {
             upper_boundary,
         )?;
         let outlier = limits.outlier(datum);
+        let p_value = PValue::new_normal(mean, std_dev, test_kind, datum);
 
-        Ok(Some(Self { limits, outlier }))
+        Ok(Some(Self {
+            limits,
+            outlier,
+            change_point: None,
+            p_value,
+        }))
     }
 
     fn new_log_normal(
@@ -208,8 +256,14 @@ impl MetricsBoundary {
 
         let limits = MetricsLimits::new_log_normal(log, ln, lower_boundary, upper_boundary)?;
         let outlier = limits.outlier(datum);
+        let p_value = PValue::new_log_normal(ln, datum);
 
-        Ok(Some(Self { limits, outlier }))
+        Ok(Some(Self {
+            limits,
+            outlier,
+            change_point: None,
+            p_value,
+        }))
     }
 
     fn new_iqr(
@@ -251,6 +305,311 @@ impl MetricsBoundary {
         );
         let outlier = limits.outlier(datum);
 
-        Ok(Some(Self { limits, outlier }))
+        Ok(Some(Self {
+            limits,
+            outlier,
+            change_point: None,
+            p_value: None,
+        }))
+    }
+
+    fn new_mann_whitney(
+        log: &Logger,
+        sample: &[f64],
+        data: &[f64],
+        lower_boundary: Option<Boundary>,
+        upper_boundary: Option<Boundary>,
+    ) -> Result<Option<Self>, BoundaryError> {
+        let lower_boundary: Option<CdfBoundary> = lower_boundary
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(BoundaryError::Valid)?;
+        let upper_boundary: Option<CdfBoundary> = upper_boundary
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(BoundaryError::Valid)?;
+
+        let Some(limits) = MetricsLimits::new_mann_whitney(
+            log,
+            data,
+            sample.len(),
+            lower_boundary,
+            upper_boundary,
+        ) else {
+            return Ok(None);
+        };
+        let Some(mann_whitney) = MannWhitneyU::new(data, sample) else {
+            return Ok(None);
+        };
+        debug!(log, "Mann-Whitney U: {mann_whitney:?}");
+
+        // The sample is an outlier if it is stochastically less than or greater than the historical data.
+        let lower_outlier = lower_boundary
+            .is_some_and(|limit| mann_whitney.lower_p <= 1.0 - f64::from(limit))
+            .then_some((BoundaryLimit::Lower, mann_whitney.lower_p));
+        let upper_outlier = upper_boundary
+            .is_some_and(|limit| mann_whitney.upper_p <= 1.0 - f64::from(limit))
+            .then_some((BoundaryLimit::Upper, mann_whitney.upper_p));
+        let outlier = match (lower_outlier, upper_outlier) {
+            (Some(lower), Some(upper)) => Some(if lower.1 <= upper.1 { lower } else { upper }),
+            (Some(outlier), None) | (None, Some(outlier)) => Some(outlier),
+            (None, None) => None,
+        }
+        .map(|(boundary_limit, _)| boundary_limit);
+
+        Ok(Some(Self {
+            limits,
+            outlier,
+            change_point: None,
+            p_value: Some(PValue {
+                lower: mann_whitney.lower_p,
+                upper: mann_whitney.upper_p,
+            }),
+        }))
+    }
+
+    fn new_change_point(
+        log: &Logger,
+        datum: f64,
+        data: &[f64],
+        lower_boundary: Option<Boundary>,
+        upper_boundary: Option<Boundary>,
+    ) -> Result<Option<Self>, BoundaryError> {
+        let lower_boundary: Option<CdfBoundary> = lower_boundary
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(BoundaryError::Valid)?;
+        let upper_boundary: Option<CdfBoundary> = upper_boundary
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(BoundaryError::Valid)?;
+        let significance = |boundary: Option<CdfBoundary>| boundary.map(|b| 1.0 - f64::from(b));
+        let lower_significance = significance(lower_boundary);
+        let upper_significance = significance(upper_boundary);
+
+        // The historical data is ordered from most recent to least recent.
+        // Change point detection requires the data to be in chronological order.
+        let history = data.iter().rev().copied().collect::<Vec<_>>();
+        // Only consider the current regime, after any previous change points.
+        // Otherwise, a past change point that has already been alerted on would continue to alert.
+        let regime_significance = lower_significance
+            .into_iter()
+            .chain(upper_significance)
+            .fold(1.0, f64::min);
+        let regime_start = ChangePoint::regime_start(&history, regime_significance);
+        let Some(regime) = history.get(regime_start..) else {
+            return Ok(None);
+        };
+
+        let mut series = regime.to_vec();
+        series.push(datum);
+        let change_point = ChangePoint::new(&series);
+        let change_point_p_value = change_point.map(|cp| PValue {
+            lower: cp.lower_p,
+            upper: cp.upper_p,
+        });
+        debug!(
+            log,
+            "Change point: regime_start={regime_start}, change_point={change_point:?}"
+        );
+        let lower_outlier = change_point.and_then(|cp| {
+            lower_significance
+                .is_some_and(|significance| cp.lower_p <= significance)
+                .then_some((cp, BoundaryLimit::Lower, cp.lower_p))
+        });
+        let upper_outlier = change_point.and_then(|cp| {
+            upper_significance
+                .is_some_and(|significance| cp.upper_p <= significance)
+                .then_some((cp, BoundaryLimit::Upper, cp.upper_p))
+        });
+        let detected = match (lower_outlier, upper_outlier) {
+            (Some(lower), Some(upper)) => Some(if lower.2 <= upper.2 { lower } else { upper }),
+            (Some(outlier), None) | (None, Some(outlier)) => Some(outlier),
+            (None, None) => None,
+        };
+
+        // The limits are calculated from the samples before the change point, if one was detected.
+        // Otherwise, the limits are calculated from the entire current regime.
+        let baseline_data = detected
+            .and_then(|(cp, _, _)| series.get(..cp.index))
+            .unwrap_or(regime);
+        let Some(mean) = Mean::new(baseline_data) else {
+            return Ok(None);
+        };
+        let Some(std_dev) = mean.std_deviation(baseline_data) else {
+            return Ok(None);
+        };
+        let Mean { mean } = mean;
+        let limits = MetricsLimits::new_normal(
+            log,
+            mean,
+            std_dev,
+            #[expect(clippy::cast_precision_loss)]
+            NormalTestKind::T {
+                freedom: (baseline_data.len() - 1) as f64,
+            },
+            lower_boundary,
+            upper_boundary,
+        )?;
+
+        // Convert the chronological index of the change point back into an index into the historical data,
+        // where the current metric is the most recent sample.
+        let change_point =
+            detected.and_then(|(cp, _, _)| data.len().checked_sub(regime_start + cp.index));
+        Ok(Some(Self {
+            limits,
+            outlier: detected.map(|(_, boundary_limit, _)| boundary_limit),
+            change_point,
+            p_value: change_point_p_value,
+        }))
+    }
+
+    fn new_forecast(
+        log: &Logger,
+        datum: f64,
+        data: &[f64],
+        model: Model,
+    ) -> Result<Option<Self>, BoundaryError> {
+        let Model {
+            lower_boundary,
+            upper_boundary,
+            smoothing,
+            trend,
+            season,
+            ..
+        } = model;
+        let lower_boundary = lower_boundary
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(BoundaryError::Valid)?;
+        let upper_boundary = upper_boundary
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(BoundaryError::Valid)?;
+
+        // The historical data is ordered from most recent to least recent.
+        // Forecasting requires the data to be in chronological order.
+        let history = data.iter().rev().copied().collect::<Vec<_>>();
+        let Some(forecast) = Forecast::new(
+            &history,
+            smoothing.unwrap_or(Smoothing::THIRTY).into(),
+            trend.map(Into::into),
+            season.map(Into::into),
+        ) else {
+            return Ok(None);
+        };
+        debug!(log, "Forecast: {forecast:?}");
+
+        // The limits are the prediction interval around the forecast,
+        // using the one-step-ahead forecast errors as the standard deviation.
+        #[expect(clippy::cast_precision_loss)]
+        let test_kind = NormalTestKind::T {
+            freedom: (forecast.errors - 1) as f64,
+        };
+        let limits = MetricsLimits::new_normal(
+            log,
+            forecast.value,
+            forecast.std_dev,
+            test_kind,
+            lower_boundary,
+            upper_boundary,
+        )?;
+        let outlier = limits.outlier(datum);
+        let p_value = PValue::new_normal(forecast.value, forecast.std_dev, test_kind, datum);
+
+        Ok(Some(Self {
+            limits,
+            outlier,
+            change_point: None,
+            p_value,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{Model, ModelTest, project::boundary::BoundaryLimit};
+    use pretty_assertions::assert_eq;
+    use slog::{Logger, o};
+
+    use super::MetricsBoundary;
+    use crate::MetricsData;
+
+    fn log() -> Logger {
+        Logger::root(slog::Discard, o!())
+    }
+
+    fn model(test: ModelTest, upper_boundary: f64) -> Model {
+        Model {
+            test,
+            max_sample_size: None,
+            upper_boundary: Some(upper_boundary.try_into().unwrap()),
+            ..Model::upper_boundary()
+        }
+    }
+
+    #[test]
+    fn test_boundary_sample_mean() {
+        // The mean of the sample is the datum, and it is added to the history as a single sample.
+        let history = MetricsData {
+            data: vec![10.0, 10.0],
+        };
+        let model = model(ModelTest::Percentage, 0.1);
+        let boundary = MetricsBoundary::new(&log(), &[11.0, 11.0, 11.0], &history, model).unwrap();
+        let baseline = boundary.limits.baseline.unwrap();
+        assert!((baseline - 31.0 / 3.0).abs() < 1e-9, "{baseline}");
+        assert_eq!(boundary.outlier, None);
+
+        let boundary = MetricsBoundary::new(&log(), &[13.0, 14.0, 15.0], &history, model).unwrap();
+        assert_eq!(boundary.outlier, Some(BoundaryLimit::Upper));
+    }
+
+    #[test]
+    fn test_boundary_sample_mann_whitney() {
+        // The sample is compared against the history, which does not include the sample.
+        let history = MetricsData {
+            data: (1..=10).map(f64::from).collect(),
+        };
+        let model = model(ModelTest::MannWhitneyU, 0.95);
+        let boundary = MetricsBoundary::new(&log(), &[20.0, 21.0, 22.0], &history, model).unwrap();
+        assert_eq!(boundary.outlier, Some(BoundaryLimit::Upper));
+
+        let boundary = MetricsBoundary::new(&log(), &[4.0, 5.0, 6.0], &history, model).unwrap();
+        assert_eq!(boundary.outlier, None);
+    }
+
+    #[test]
+    fn test_boundary_single_mann_whitney() {
+        // A single iteration can only reach significance at the default boundary
+        // with at least 99 historical metrics.
+        let history = MetricsData {
+            data: (1..=99).map(f64::from).collect(),
+        };
+        let model = Model {
+            test: ModelTest::MannWhitneyU,
+            max_sample_size: None,
+            ..Model::upper_boundary()
+        };
+        let boundary = MetricsBoundary::new(&log(), &[100.0], &history, model).unwrap();
+        assert_eq!(boundary.outlier, Some(BoundaryLimit::Upper));
+        // The upper limit agrees with the alert.
+        let upper = boundary.limits.upper.as_ref().unwrap().value;
+        assert!(100.0 > upper, "{upper}");
+        assert_eq!(boundary.limits.outlier(100.0), boundary.outlier);
+
+        let boundary = MetricsBoundary::new(&log(), &[99.0], &history, model).unwrap();
+        assert_eq!(boundary.outlier, None);
+        assert_eq!(boundary.limits.outlier(99.0), boundary.outlier);
+    }
+
+    #[test]
+    fn test_boundary_empty_sample() {
+        let history = MetricsData {
+            data: vec![10.0, 10.0],
+        };
+        let model = model(ModelTest::Percentage, 0.1);
+        let boundary = MetricsBoundary::new(&log(), &[], &history, model).unwrap();
+        assert_eq!(boundary.outlier, None);
+        assert_eq!(boundary.limits.baseline, None);
     }
 }
@@ -0,0 +1,193 @@
+use statrs::distribution::{ContinuousCDF as _, StudentsT};
+
+use crate::mean::mean;
+
+// The minimum number of samples required on each side of a change point.
+// This prevents a single outlier from being detected as a change point,
+// as the shift must be sustained for at least this many samples.
+const MIN_SEGMENT_LEN: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChangePoint {
+    /// The index of the first sample after the change point.
+    pub index: usize,
+    /// The mean of the samples before the change point.
+    pub before: f64,
+    /// The mean of the samples after the change point.
+    pub after: f64,
+    /// The adjusted one-sided p-value that the series shifted down.
+    pub lower_p: f64,
+    /// The adjusted one-sided p-value that the series shifted up.
+    pub upper_p: f64,
+}
+
+impl ChangePoint {
+    // Find the most likely single change point in a chronologically ordered series.
+    // Every possible split is tested with Welch's t-test,
+    // and the p-values are Bonferroni adjusted for the number of splits tested.
+    pub fn new(series: &[f64]) -> Option<Self> {
+        let len = series.len();
+        if len < MIN_SEGMENT_LEN * 2 {
+            return None;
+        }
+        let candidates = len - MIN_SEGMENT_LEN * 2 + 1;
+
+        let mut most_likely: Option<Self> = None;
+        for index in MIN_SEGMENT_LEN..=(len - MIN_SEGMENT_LEN) {
+            let (before, after) = series.split_at(index);
+            let Some(change_point) = Self::welch(index, before, after, candidates) else {
+                continue;
+            };
+            if most_likely.is_none_or(|ml| change_point.p() < ml.p()) {
+                most_likely = Some(change_point);
+            }
+        }
+        most_likely
+    }
+
+    // Find the start of the current regime in a chronologically ordered series.
+    // Binary segmentation is used to repeatedly split off the most recent segment,
+    // as long as the change point between the segments is significant.
+    pub fn regime_start(series: &[f64], significance: f64) -> usize {
+        let mut start = 0;
+        while let Some(change_point) = series.get(start..).and_then(Self::new) {
+            // Use a two-sided test, as the direction of a past shift does not matter.
+            if change_point.p() * 2.0 > significance {
+                break;
+            }
+            start += change_point.index;
+        }
+        start
+    }
+
+    fn welch(index: usize, before: &[f64], after: &[f64], candidates: usize) -> Option<Self> {
+        let (before_mean, before_var) = sample_mean_variance(before)?;
+        let (after_mean, after_var) = sample_mean_variance(after)?;
+
+        #[expect(clippy::cast_precision_loss)]
+        let (before_len, after_len) = (before.len() as f64, after.len() as f64);
+        let before_se = before_var / before_len;
+        let after_se = after_var / after_len;
+        let std_err = (before_se + after_se).sqrt();
+        let delta = after_mean - before_mean;
+
+        let (lower_p, upper_p) = if std_err == 0.0 {
+            // Both segments are constant, so any difference is certain.
+            if delta < 0.0 {
+                (0.0, 1.0)
+            } else if delta > 0.0 {
+                (1.0, 0.0)
+            } else {
+                (1.0, 1.0)
+            }
+        } else {
+            // https://en.wikipedia.org/wiki/Welch%27s_t-test
+            let t = delta / std_err;
+            let freedom = (before_se + after_se).powi(2)
+                / (before_se.powi(2) / (before_len - 1.0) + after_se.powi(2) / (after_len - 1.0));
+            let students_t = StudentsT::new(0.0, 1.0, freedom).ok()?;
+            let lower_p = students_t.cdf(t);
+            (lower_p, 1.0 - lower_p)
+        };
+
+        #[expect(clippy::cast_precision_loss)]
+        let candidates = candidates as f64;
+        Some(Self {
+            index,
+            before: before_mean,
+            after: after_mean,
+            lower_p: (lower_p * candidates).min(1.0),
+            upper_p: (upper_p * candidates).min(1.0),
+        })
+        .filter(|change_point| change_point.lower_p.is_finite() && change_point.upper_p.is_finite())
+    }
+
+    fn p(self) -> f64 {
+        self.lower_p.min(self.upper_p)
+    }
+}
+
+fn sample_mean_variance(data: &[f64]) -> Option<(f64, f64)> {
+    if data.len() < 2 {
+        return None;
+    }
+    let location = mean(data)?;
+    #[expect(clippy::cast_precision_loss)]
+    let variance = data
+        .iter()
+        .map(|&value| (value - location).powi(2))
+        .sum::<f64>()
+        / (data.len() - 1) as f64;
+    variance.is_finite().then_some((location, variance))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::ChangePoint;
+
+    const SIGNIFICANCE: f64 = 0.01;
+
+    const DATA_TOO_SHORT: &[f64] = &[1.0, 2.0, 3.0];
+    const DATA_FLAT: &[f64] = &[
+        10.0, 10.2, 9.8, 10.1, 9.9, 10.0, 10.2, 9.8, 10.1, 9.9, 10.0, 10.2,
+    ];
+    const DATA_SHIFT_UP: &[f64] = &[
+        10.0, 10.2, 9.8, 10.1, 9.9, 10.0, 10.2, 9.8, 15.1, 14.9, 15.0, 15.2,
+    ];
+    const DATA_SHIFT_DOWN: &[f64] = &[
+        10.0, 10.2, 9.8, 10.1, 9.9, 10.0, 10.2, 9.8, 5.1, 4.9, 5.0, 5.2,
+    ];
+    const DATA_SINGLE_OUTLIER: &[f64] = &[
+        10.0, 10.2, 9.8, 10.1, 9.9, 10.0, 10.2, 9.8, 10.1, 9.9, 10.0, 50.0,
+    ];
+    const DATA_TWO_SHIFTS: &[f64] = &[
+        10.0, 10.2, 9.8, 10.1, 9.9, 20.0, 20.2, 19.8, 20.1, 19.9, 30.0, 30.2, 29.8, 30.1,
+    ];
+
+    #[test]
+    fn test_change_point_too_short() {
+        assert_eq!(ChangePoint::new(DATA_TOO_SHORT), None);
+        assert_eq!(ChangePoint::regime_start(DATA_TOO_SHORT, SIGNIFICANCE), 0);
+    }
+
+    #[test]
+    fn test_change_point_flat() {
+        let change_point = ChangePoint::new(DATA_FLAT).unwrap();
+        assert!(change_point.lower_p > SIGNIFICANCE, "{change_point:?}");
+        assert!(change_point.upper_p > SIGNIFICANCE, "{change_point:?}");
+        assert_eq!(ChangePoint::regime_start(DATA_FLAT, SIGNIFICANCE), 0);
+    }
+
+    #[test]
+    fn test_change_point_shift_up() {
+        let change_point = ChangePoint::new(DATA_SHIFT_UP).unwrap();
+        assert_eq!(change_point.index, 8);
+        assert!(change_point.upper_p < SIGNIFICANCE, "{change_point:?}");
+        assert!(change_point.lower_p > SIGNIFICANCE, "{change_point:?}");
+        assert!(change_point.after > change_point.before, "{change_point:?}");
+        assert_eq!(ChangePoint::regime_start(DATA_SHIFT_UP, SIGNIFICANCE), 8);
+    }
+
+    #[test]
+    fn test_change_point_shift_down() {
+        let change_point = ChangePoint::new(DATA_SHIFT_DOWN).unwrap();
+        assert_eq!(change_point.index, 8);
+        assert!(change_point.lower_p < SIGNIFICANCE, "{change_point:?}");
+        assert!(change_point.upper_p > SIGNIFICANCE, "{change_point:?}");
+        assert!(change_point.after < change_point.before, "{change_point:?}");
+    }
+
+    #[test]
+    fn test_change_point_single_outlier() {
+        let change_point = ChangePoint::new(DATA_SINGLE_OUTLIER).unwrap();
+        assert!(change_point.upper_p > SIGNIFICANCE, "{change_point:?}");
+        assert!(change_point.lower_p > SIGNIFICANCE, "{change_point:?}");
+    }
+
+    #[test]
+    fn test_change_point_two_shifts() {
+        assert_eq!(ChangePoint::regime_start(DATA_TWO_SHIFTS, SIGNIFICANCE), 10);
+    }
+}
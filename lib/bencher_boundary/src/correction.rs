@@ -0,0 +1,143 @@
+use bencher_json::{Boundary, CdfBoundary, Model, project::report::JsonCorrection};
+
+use crate::{BoundaryError, p_value::significance};
+
+// The largest valid CDF boundary, which is the largest `f64` less than `1.0`.
+// A corrected boundary can otherwise round to exactly `1.0` for a large number of tests.
+const MAX_CDF_BOUNDARY: f64 = 1.0 - f64::EPSILON / 2.0;
+
+/// A correction for multiple comparisons across all of the statistical boundary tests in a report.
+/// Each boundary is tested at its significance level scaled by the correction factor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Correction {
+    pub factor: f64,
+}
+
+impl Correction {
+    /// The normalized p-values are the p-value of each boundary test divided by its significance level.
+    #[expect(clippy::cast_precision_loss)]
+    pub fn new(correction: JsonCorrection, normalized: &[f64]) -> Option<Self> {
+        if normalized.is_empty() {
+            return None;
+        }
+        let tests = normalized.len() as f64;
+        let factor = match correction {
+            // https://en.wikipedia.org/wiki/Bonferroni_correction
+            JsonCorrection::Bonferroni => 1.0 / tests,
+            // Find the largest rank where the sorted normalized p-value is within the rank proportion of tests.
+            // All of the tests up to and including that rank are discoveries.
+            // https://en.wikipedia.org/wiki/False_discovery_rate#Benjamini%E2%80%93Hochberg_procedure
+            JsonCorrection::BenjaminiHochberg => {
+                let mut sorted = normalized.to_vec();
+                sorted.sort_unstable_by(f64::total_cmp);
+                let rank = sorted
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .find_map(|(index, &p_value)| {
+                        let rank = index + 1;
+                        (p_value <= rank as f64 / tests).then_some(rank)
+                    })
+                    // If there are no discoveries, then use the most conservative rank.
+                    .unwrap_or(1);
+                rank as f64 / tests
+            },
+        };
+        Some(Self { factor })
+    }
+
+    /// Adjust the model boundaries to their corrected significance levels.
+    pub fn model(self, model: Model) -> Result<Model, BoundaryError> {
+        Ok(Model {
+            lower_boundary: self.boundary(model.lower_boundary)?,
+            upper_boundary: self.boundary(model.upper_boundary)?,
+            ..model
+        })
+    }
+
+    fn boundary(self, boundary: Option<Boundary>) -> Result<Option<Boundary>, BoundaryError> {
+        significance(boundary)
+            .map(|significance| {
+                CdfBoundary::try_from((1.0 - significance * self.factor).min(MAX_CDF_BOUNDARY))
+                    .map(Into::into)
+                    .map_err(BoundaryError::Valid)
+            })
+            .transpose()
+    }
+
+    /// The corrected significance level of the model, across both of its boundaries.
+    pub fn significance(self, model: &Model) -> Option<f64> {
+        let lower = significance(model.lower_boundary);
+        let upper = significance(model.upper_boundary);
+        lower
+            .into_iter()
+            .chain(upper)
+            .reduce(|lower, upper| lower + upper)
+            .map(|significance| significance * self.factor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::project::report::JsonCorrection;
+    use pretty_assertions::assert_eq;
+
+    use super::{Correction, MAX_CDF_BOUNDARY};
+
+    const NORMALIZED: &[f64] = &[0.9, 0.01, 0.3, 0.2, 5.0];
+
+    #[test]
+    fn test_correction_empty() {
+        assert_eq!(Correction::new(JsonCorrection::Bonferroni, &[]), None);
+        assert_eq!(
+            Correction::new(JsonCorrection::BenjaminiHochberg, &[]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_correction_bonferroni() {
+        let correction = Correction::new(JsonCorrection::Bonferroni, NORMALIZED).unwrap();
+        assert_eq!(correction, Correction { factor: 0.2 });
+    }
+
+    #[test]
+    fn test_correction_benjamini_hochberg() {
+        // Sorted: 0.01 <= 0.2, 0.2 <= 0.4, 0.3 <= 0.6, 0.9 > 0.8, 5.0 > 1.0
+        let correction = Correction::new(JsonCorrection::BenjaminiHochberg, NORMALIZED).unwrap();
+        assert_eq!(correction, Correction { factor: 0.6 });
+
+        // No discoveries falls back to the most conservative rank.
+        let correction = Correction::new(JsonCorrection::BenjaminiHochberg, &[2.0, 3.0]).unwrap();
+        assert_eq!(correction, Correction { factor: 0.5 });
+    }
+
+    #[test]
+    fn test_correction_model() {
+        let correction = Correction { factor: 0.5 };
+        let model = bencher_json::Model {
+            lower_boundary: Some(0.9.try_into().unwrap()),
+            ..bencher_json::Model::upper_boundary()
+        };
+        let significance = correction.significance(&model).unwrap();
+        assert!((significance - 0.055).abs() < 1e-9, "{significance}");
+
+        let corrected = correction.model(model).unwrap();
+        let lower = f64::from(corrected.lower_boundary.unwrap());
+        let upper = f64::from(corrected.upper_boundary.unwrap());
+        assert!((lower - 0.95).abs() < 1e-9, "{lower}");
+        assert!((upper - 0.995).abs() < 1e-9, "{upper}");
+    }
+
+    #[test]
+    fn test_correction_model_max() {
+        // A tiny correction factor would round the boundary to exactly `1.0`.
+        let correction = Correction { factor: 1e-20 };
+        let corrected = correction
+            .model(bencher_json::Model::upper_boundary())
+            .unwrap();
+        let upper = f64::from(corrected.upper_boundary.unwrap());
+        assert!(upper < 1.0, "{upper}");
+        assert!((upper - MAX_CDF_BOUNDARY).abs() < f64::EPSILON, "{upper}");
+    }
+}
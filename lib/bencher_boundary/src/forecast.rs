@@ -0,0 +1,172 @@
+use crate::mean::mean;
+
+// The minimum number of one-step-ahead forecast errors required to estimate the prediction interval.
+const MIN_ERRORS: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Forecast {
+    /// The forecast for the next sample in the series.
+    pub value: f64,
+    /// The root mean square of the one-step-ahead forecast errors.
+    pub std_dev: f64,
+    /// The number of one-step-ahead forecast errors.
+    pub errors: usize,
+}
+
+impl Forecast {
+    // Fit additive Holt-Winters exponential smoothing to a chronologically ordered series,
+    // and forecast the next sample in the series.
+    // The seasonal component uses the same smoothing factor as the level.
+    // Without a trend or season, this is an exponentially weighted moving average (EWMA).
+    // The season is a number of samples, not a period of time,
+    // so it only follows a cycle in time if the samples are evenly spaced.
+    // https://otexts.com/fpp3/holt-winters.html
+    pub fn new(
+        series: &[f64],
+        smoothing: f64,
+        trend: Option<f64>,
+        season: Option<usize>,
+    ) -> Option<Self> {
+        let period = season.unwrap_or(1).max(1);
+        let (start, mut level, mut slope, mut seasonal) = if period > 1 {
+            // Initialize the level and seasonal components from the first season.
+            let first = series.get(..period)?;
+            let level = mean(first)?;
+            // With a trend, initialize it from the change between the first two seasons.
+            let slope = if trend.is_some() {
+                let second = series.get(period..period * 2)?;
+                #[expect(clippy::cast_precision_loss)]
+                let slope = (mean(second)? - level) / period as f64;
+                slope
+            } else {
+                0.0
+            };
+            let seasonal = first.iter().map(|value| value - level).collect::<Vec<_>>();
+            (period, level, slope, seasonal)
+        } else if trend.is_some() {
+            // Initialize the level and trend from the first two samples.
+            let (first, second) = (*series.first()?, *series.get(1)?);
+            (2, second, second - first, vec![0.0])
+        } else {
+            (1, *series.first()?, 0.0, vec![0.0])
+        };
+
+        let mut sum_squared_errors = 0.0;
+        let mut errors = 0;
+        for (index, &value) in series.iter().enumerate().skip(start) {
+            let season_index = index % period;
+            let last_seasonal = seasonal.get(season_index).copied()?;
+            let error = value - (level + slope + last_seasonal);
+            sum_squared_errors += error.powi(2);
+            errors += 1;
+
+            let next_level =
+                smoothing * (value - last_seasonal) + (1.0 - smoothing) * (level + slope);
+            if let Some(trend) = trend {
+                slope = trend * (next_level - level) + (1.0 - trend) * slope;
+            }
+            if period > 1 {
+                if let Some(s) = seasonal.get_mut(season_index) {
+                    *s = smoothing * (value - next_level) + (1.0 - smoothing) * last_seasonal;
+                }
+            }
+            level = next_level;
+        }
+        if errors < MIN_ERRORS {
+            return None;
+        }
+
+        let value = level + slope + seasonal.get(series.len() % period).copied()?;
+        #[expect(clippy::cast_precision_loss)]
+        let std_dev = (sum_squared_errors / errors as f64).sqrt();
+        // If the standard deviation is zero then the prediction interval is not going to work with `statrs`
+        (value.is_finite() && std_dev.is_finite() && std_dev > 0.0).then_some(Self {
+            value,
+            std_dev,
+            errors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::Forecast;
+
+    const SMOOTHING: f64 = 0.5;
+    const TREND: f64 = 0.3;
+
+    const DATA_TOO_SHORT: &[f64] = &[1.0, 2.0];
+    const DATA_CONST: &[f64] = &[1.0, 1.0, 1.0, 1.0, 1.0];
+    const DATA_FLAT: &[f64] = &[
+        10.0, 10.2, 9.8, 10.1, 9.9, 10.0, 10.2, 9.8, 10.1, 9.9, 10.0, 10.2,
+    ];
+    const DATA_TREND: &[f64] = &[
+        10.0, 11.1, 11.9, 13.0, 14.1, 14.9, 16.0, 17.1, 17.9, 19.0, 20.1, 20.9,
+    ];
+    const DATA_SEASON: &[f64] = &[
+        10.0, 20.1, 30.0, 10.1, 19.9, 30.1, 9.9, 20.0, 29.9, 10.0, 20.1, 30.0,
+    ];
+
+    const DATA_ONE_SEASON: &[f64] = &[10.0, 20.1, 30.0, 10.1, 19.9];
+
+    #[test]
+    fn test_forecast_too_short() {
+        assert_eq!(Forecast::new(DATA_TOO_SHORT, SMOOTHING, None, None), None);
+        assert_eq!(
+            Forecast::new(DATA_TOO_SHORT, SMOOTHING, Some(TREND), None),
+            None
+        );
+        assert_eq!(Forecast::new(DATA_FLAT, SMOOTHING, None, Some(11)), None);
+        assert_eq!(
+            Forecast::new(DATA_FLAT, SMOOTHING, Some(TREND), Some(7)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_forecast_const() {
+        assert_eq!(Forecast::new(DATA_CONST, SMOOTHING, None, None), None);
+    }
+
+    #[test]
+    fn test_forecast_flat() {
+        let forecast = Forecast::new(DATA_FLAT, SMOOTHING, None, None).unwrap();
+        assert!((forecast.value - 10.0).abs() < 0.2, "{forecast:?}");
+        assert!(forecast.std_dev < 0.5, "{forecast:?}");
+        assert_eq!(forecast.errors, DATA_FLAT.len() - 1);
+    }
+
+    #[test]
+    fn test_forecast_trend() {
+        // Without a trend, the forecast lags behind the series.
+        let level = Forecast::new(DATA_TREND, SMOOTHING, None, None).unwrap();
+        assert!(level.value < 21.0, "{level:?}");
+
+        let trend = Forecast::new(DATA_TREND, SMOOTHING, Some(TREND), None).unwrap();
+        assert!((trend.value - 22.0).abs() < 0.5, "{trend:?}");
+        assert!(trend.std_dev < level.std_dev, "{trend:?} {level:?}");
+        assert_eq!(trend.errors, DATA_TREND.len() - 2);
+    }
+
+    #[test]
+    fn test_forecast_season() {
+        // Without a season, the forecast is far from the next value.
+        let level = Forecast::new(DATA_SEASON, SMOOTHING, None, None).unwrap();
+        assert!((level.value - 10.0).abs() > 5.0, "{level:?}");
+
+        let season = Forecast::new(DATA_SEASON, SMOOTHING, None, Some(3)).unwrap();
+        assert!((season.value - 10.0).abs() < 0.5, "{season:?}");
+        assert!(season.std_dev < 0.5, "{season:?}");
+        assert_eq!(season.errors, DATA_SEASON.len() - 3);
+
+        // Without a trend, a single season is enough to initialize the forecast.
+        let one_season = Forecast::new(DATA_ONE_SEASON, SMOOTHING, None, Some(3)).unwrap();
+        assert!((one_season.value - 30.0).abs() < 0.5, "{one_season:?}");
+        assert_eq!(one_season.errors, 2);
+
+        let season_trend = Forecast::new(DATA_SEASON, SMOOTHING, Some(TREND), Some(3)).unwrap();
+        assert!((season_trend.value - 10.0).abs() < 0.5, "{season_trend:?}");
+    }
+}
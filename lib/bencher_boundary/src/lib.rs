@@ -1,12 +1,19 @@
 pub mod boundary;
+mod change_point;
+mod correction;
 mod error;
+mod forecast;
 pub mod limits;
 mod ln;
+mod mann_whitney;
 mod mean;
+mod p_value;
 mod quartiles;
 
 pub use boundary::MetricsBoundary;
+pub use correction::Correction;
 pub use error::BoundaryError;
+pub use p_value::PValue;
 
 #[derive(Debug, Clone)]
 pub struct MetricsData {
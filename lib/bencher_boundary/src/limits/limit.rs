@@ -35,6 +35,12 @@ impl MetricsLimit {
         Self { value: abs_limit }
     }
 
+    pub fn critical(critical_value: f64) -> Self {
+        Self {
+            value: critical_value,
+        }
+    }
+
     pub fn iqr_lower(quartiles: Quartiles, boundary: IqrBoundary) -> Self {
         Self {
             value: quartiles.q2 - quartiles.iqr(boundary),
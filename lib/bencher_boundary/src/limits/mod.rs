@@ -4,7 +4,7 @@ use bencher_json::{
 use slog::{Logger, debug};
 use statrs::distribution::{ContinuousCDF as _, LogNormal, Normal, StudentsT};
 
-use crate::{BoundaryError, ln::Ln, quartiles::Quartiles};
+use crate::{BoundaryError, ln::Ln, mann_whitney::MannWhitneyU, quartiles::Quartiles};
 
 mod limit;
 
@@ -197,6 +197,48 @@ impl MetricsLimits {
         }
     }
 
+    pub fn new_mann_whitney(
+        log: &Logger,
+        data: &[f64],
+        sample_size: usize,
+        lower_boundary: Option<CdfBoundary>,
+        upper_boundary: Option<CdfBoundary>,
+    ) -> Option<Self> {
+        if lower_boundary.is_none() && upper_boundary.is_none() {
+            return Some(Self::default());
+        }
+
+        // The limits are the critical values of the historical data for the sample size,
+        // so that a sample beyond a limit is also significant for the Mann-Whitney U test.
+        let median = Quartiles::percentile(data, 0.5)?;
+        let significance = |boundary: Option<CdfBoundary>| boundary.map(|b| 1.0 - f64::from(b));
+        let (lower, upper) = MannWhitneyU::critical_limits(
+            data,
+            sample_size,
+            significance(lower_boundary),
+            significance(upper_boundary),
+        );
+        debug!(
+            log,
+            "Mann-Whitney U: median={median}, sample_size={sample_size}, lower={lower:?}, upper={upper:?}"
+        );
+        if (lower_boundary.is_some() && lower.is_none())
+            || (upper_boundary.is_some() && upper.is_none())
+        {
+            debug!(
+                log,
+                "Not enough historical data ({len}) for a sample size of {sample_size} to reach significance",
+                len = data.len()
+            );
+        }
+
+        Some(Self {
+            baseline: Some(median),
+            lower: lower.map(MetricsLimit::critical),
+            upper: upper.map(MetricsLimit::critical),
+        })
+    }
+
     // An outlier occurs when the  datum exceeds a boundary limit.
     pub fn outlier(&self, datum: f64) -> Option<BoundaryLimit> {
         match (self.lower.as_ref(), self.upper.as_ref()) {
@@ -1354,4 +1396,63 @@ mod test {
         let side = limits.outlier(150.0);
         assert_eq!(side, Some(BoundaryLimit::Upper));
     }
+
+    #[test]
+    fn test_limits_mann_whitney_none() {
+        let log = bootstrap_logger();
+        let limits = MetricsLimits::new_mann_whitney(&log, LOG_DATA, 1, None, None).unwrap();
+        assert_eq!(limits.baseline, None);
+        assert_eq!(limits.lower, None);
+        assert_eq!(limits.upper, None);
+    }
+
+    #[test]
+    fn test_limits_mann_whitney_both() {
+        let log = bootstrap_logger();
+        let boundary = CdfBoundary::try_from(0.9).unwrap();
+        let limits =
+            MetricsLimits::new_mann_whitney(&log, LOG_DATA, 1, Some(boundary), Some(boundary))
+                .unwrap();
+        assert_eq!(
+            OrderedFloat::from(limits.baseline.unwrap()),
+            OrderedFloat::from(3.0)
+        );
+        assert_eq!(limits.lower, Some(MetricsLimit { value: 1.0 }));
+        assert_eq!(limits.upper, Some(MetricsLimit { value: 5.0 }));
+
+        let side = limits.outlier(0.0);
+        assert_eq!(side, Some(BoundaryLimit::Lower));
+
+        let side = limits.outlier(3.0);
+        assert_eq!(side, None);
+
+        let side = limits.outlier(6.0);
+        assert_eq!(side, Some(BoundaryLimit::Upper));
+    }
+
+    #[test]
+    fn test_limits_mann_whitney_sample_size() {
+        let log = bootstrap_logger();
+        // There is not enough historical data for a single iteration to reach significance.
+        let boundary = CdfBoundary::try_from(0.99).unwrap();
+        let limits =
+            MetricsLimits::new_mann_whitney(&log, LOG_DATA, 1, Some(boundary), Some(boundary))
+                .unwrap();
+        assert_eq!(limits.lower, None);
+        assert_eq!(limits.upper, None);
+
+        // With more iterations in the sample, the same historical data can reach significance.
+        let limits =
+            MetricsLimits::new_mann_whitney(&log, LOG_DATA, 5, Some(boundary), Some(boundary))
+                .unwrap();
+        assert_eq!(limits.lower, Some(MetricsLimit { value: 1.0 }));
+        assert_eq!(limits.upper, Some(MetricsLimit { value: 5.0 }));
+    }
+
+    #[test]
+    fn test_limits_mann_whitney_empty() {
+        let log = bootstrap_logger();
+        let limits = MetricsLimits::new_mann_whitney(&log, &[], 1, Some(*PERCENTILE), None);
+        assert!(limits.is_none(), "Expected no limits for empty data");
+    }
 }
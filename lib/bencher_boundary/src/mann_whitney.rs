@@ -0,0 +1,211 @@
+use std::cmp;
+
+use statrs::distribution::{ContinuousCDF as _, Normal};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MannWhitneyU {
+    /// The U statistic for the sample, the number of times a sample value exceeds a historical value.
+    /// Ties count as one half.
+    pub u: f64,
+    /// The one-sided p-value that the sample is stochastically less than the historical data.
+    pub lower_p: f64,
+    /// The one-sided p-value that the sample is stochastically greater than the historical data.
+    pub upper_p: f64,
+}
+
+impl MannWhitneyU {
+    // https://en.wikipedia.org/wiki/Mann%E2%80%93Whitney_U_test
+    pub fn new(data: &[f64], sample: &[f64]) -> Option<Self> {
+        if data.is_empty() || sample.is_empty() {
+            return None;
+        }
+
+        let u = sample
+            .iter()
+            .flat_map(|s| data.iter().map(move |d| (s, d)))
+            .map(|(s, d)| match s.partial_cmp(d) {
+                Some(cmp::Ordering::Greater) => 1.0,
+                Some(cmp::Ordering::Equal) => 0.5,
+                Some(cmp::Ordering::Less) | None => 0.0,
+            })
+            .sum::<f64>();
+
+        let mut combined = data.iter().chain(sample).copied().collect::<Vec<_>>();
+        combined.sort_unstable_by(|x, y| x.partial_cmp(y).unwrap_or(cmp::Ordering::Equal));
+        let ties = combined
+            .chunk_by(|x, y| x.partial_cmp(y) == Some(cmp::Ordering::Equal))
+            .map(|tied| Self::tie_correction(tied.len()))
+            .sum::<f64>();
+        let (lower_p, upper_p) = Self::p_values(u, data.len(), sample.len(), ties)?;
+
+        Some(Self {
+            u,
+            lower_p,
+            upper_p,
+        })
+    }
+
+    /// The critical limits of the historical data for a sample with `sample_size` iterations.
+    /// A sample with all of its iterations below the lower limit or above the upper limit
+    /// is significant at the given significance level.
+    /// A limit is `None` if no such sample could ever reach significance,
+    /// because there is not enough historical data.
+    pub fn critical_limits(
+        data: &[f64],
+        sample_size: usize,
+        lower_significance: Option<f64>,
+        upper_significance: Option<f64>,
+    ) -> (Option<f64>, Option<f64>) {
+        let mut sorted = data.to_vec();
+        sorted.sort_unstable_by(|x, y| x.partial_cmp(y).unwrap_or(cmp::Ordering::Equal));
+        // The iterations of the sample are all tied with one another.
+        let ties = sorted
+            .chunk_by(|x, y| x.partial_cmp(y) == Some(cmp::Ordering::Equal))
+            .map(|tied| Self::tie_correction(tied.len()))
+            .sum::<f64>()
+            + Self::tie_correction(sample_size);
+        #[expect(clippy::cast_precision_loss)]
+        let m = sample_size as f64;
+        let p_values = |count: usize| {
+            #[expect(clippy::cast_precision_loss)]
+            let u = m * count as f64;
+            Self::p_values(u, sorted.len(), sample_size, ties)
+        };
+
+        // The lower limit is the greatest historical value where a sample just below it is significant.
+        let lower = lower_significance.and_then(|significance| {
+            let mut below = 0;
+            let mut limit = None;
+            for tied in sorted.chunk_by(|x, y| x.partial_cmp(y) == Some(cmp::Ordering::Equal)) {
+                match (tied.first(), p_values(below)) {
+                    (Some(&value), Some((lower_p, _))) if lower_p <= significance => {
+                        limit = Some(value);
+                    },
+                    _ => break,
+                }
+                below += tied.len();
+            }
+            limit
+        });
+        // The upper limit is the least historical value where a sample just above it is significant.
+        let upper = upper_significance.and_then(|significance| {
+            let mut at_or_below = sorted.len();
+            let mut limit = None;
+            for tied in sorted
+                .chunk_by(|x, y| x.partial_cmp(y) == Some(cmp::Ordering::Equal))
+                .rev()
+            {
+                match (tied.first(), p_values(at_or_below)) {
+                    (Some(&value), Some((_, upper_p))) if upper_p <= significance => {
+                        limit = Some(value);
+                    },
+                    _ => break,
+                }
+                at_or_below -= tied.len();
+            }
+            limit
+        });
+
+        (lower, upper)
+    }
+
+    fn p_values(u: f64, data_len: usize, sample_len: usize, ties: f64) -> Option<(f64, f64)> {
+        #[expect(clippy::cast_precision_loss)]
+        let (n, m) = (data_len as f64, sample_len as f64);
+        let (lower_p, upper_p) = if sample_len == 1 {
+            // With a single sample value, the exact distribution of U is discrete uniform over `0..=n`.
+            // https://en.wikipedia.org/wiki/Mann%E2%80%93Whitney_U_test#Calculations
+            ((u + 1.0) / (n + 1.0), (n - u + 1.0) / (n + 1.0))
+        } else {
+            // Otherwise, use the normal approximation with a tie correction.
+            let mean = n * m / 2.0;
+            let total = n + m;
+            let variance = n * m / 12.0 * ((total + 1.0) - ties / (total * (total - 1.0)));
+            if !(variance > 0.0 && variance.is_finite()) {
+                return None;
+            }
+            let normal = Normal::new(mean, variance.sqrt()).ok()?;
+            // Apply a continuity correction of one half to each side.
+            (normal.cdf(u + 0.5), 1.0 - normal.cdf(u - 0.5))
+        };
+        Some((lower_p.clamp(0.0, 1.0), upper_p.clamp(0.0, 1.0)))
+    }
+
+    fn tie_correction(tied: usize) -> f64 {
+        #[expect(clippy::cast_precision_loss)]
+        let t = tied as f64;
+        t.powi(3) - t
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::float_cmp)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::MannWhitneyU;
+
+    const SIGNIFICANCE: f64 = 0.05;
+
+    const DATA: &[f64] = &[
+        10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0, 17.0, 18.0, 19.0, 20.0, 21.0, 22.0, 23.0, 24.0,
+        25.0, 26.0, 27.0, 28.0, 29.0,
+    ];
+    const SAMPLE_SAME: &[f64] = &[12.5, 17.5, 22.5, 27.5];
+    const SAMPLE_HIGH: &[f64] = &[30.0, 31.0, 32.0, 33.0];
+    const SAMPLE_LOW: &[f64] = &[5.0, 6.0, 7.0, 8.0];
+    const DATA_CONST: &[f64] = &[1.0, 1.0, 1.0, 1.0];
+
+    #[test]
+    fn test_mann_whitney_empty() {
+        assert_eq!(MannWhitneyU::new(&[], SAMPLE_SAME), None);
+        assert_eq!(MannWhitneyU::new(DATA, &[]), None);
+    }
+
+    #[test]
+    fn test_mann_whitney_single() {
+        let mwu = MannWhitneyU::new(DATA, &[30.0]).unwrap();
+        assert_eq!(mwu.u, 20.0);
+        assert_eq!(mwu.lower_p, 1.0);
+        assert_eq!(mwu.upper_p, 1.0 / 21.0);
+
+        let mwu = MannWhitneyU::new(DATA, &[5.0]).unwrap();
+        assert_eq!(mwu.u, 0.0);
+        assert_eq!(mwu.lower_p, 1.0 / 21.0);
+        assert_eq!(mwu.upper_p, 1.0);
+
+        let mwu = MannWhitneyU::new(DATA, &[20.0]).unwrap();
+        assert_eq!(mwu.u, 10.5);
+        assert!(mwu.lower_p > SIGNIFICANCE, "{mwu:?}");
+        assert!(mwu.upper_p > SIGNIFICANCE, "{mwu:?}");
+    }
+
+    #[test]
+    fn test_mann_whitney_same() {
+        let mwu = MannWhitneyU::new(DATA, SAMPLE_SAME).unwrap();
+        assert_eq!(mwu.u, 42.0);
+        assert!(mwu.lower_p > SIGNIFICANCE, "{mwu:?}");
+        assert!(mwu.upper_p > SIGNIFICANCE, "{mwu:?}");
+    }
+
+    #[test]
+    fn test_mann_whitney_high() {
+        let mwu = MannWhitneyU::new(DATA, SAMPLE_HIGH).unwrap();
+        assert_eq!(mwu.u, 80.0);
+        assert!(mwu.lower_p > SIGNIFICANCE, "{mwu:?}");
+        assert!(mwu.upper_p < SIGNIFICANCE, "{mwu:?}");
+    }
+
+    #[test]
+    fn test_mann_whitney_low() {
+        let mwu = MannWhitneyU::new(DATA, SAMPLE_LOW).unwrap();
+        assert_eq!(mwu.u, 0.0);
+        assert!(mwu.lower_p < SIGNIFICANCE, "{mwu:?}");
+        assert!(mwu.upper_p > SIGNIFICANCE, "{mwu:?}");
+    }
+
+    #[test]
+    fn test_mann_whitney_const() {
+        assert_eq!(MannWhitneyU::new(DATA_CONST, DATA_CONST), None);
+    }
+}
@@ -0,0 +1,113 @@
+use bencher_json::{Boundary, CdfBoundary};
+use statrs::distribution::{ContinuousCDF, LogNormal, Normal, StudentsT};
+
+use crate::{limits::NormalTestKind, ln::Ln};
+
+/// The one-sided p-values of a datum for a statistical threshold model test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PValue {
+    /// The probability of a datum at least this far below the baseline.
+    pub lower: f64,
+    /// The probability of a datum at least this far above the baseline.
+    pub upper: f64,
+}
+
+impl PValue {
+    pub fn new_normal(
+        mean: f64,
+        std_dev: f64,
+        test_kind: NormalTestKind,
+        datum: f64,
+    ) -> Option<Self> {
+        match test_kind {
+            NormalTestKind::Z => Normal::new(mean, std_dev)
+                .ok()
+                .map(|normal| Self::new_cdf(&normal, mean, datum)),
+            NormalTestKind::T { freedom } => StudentsT::new(mean, std_dev, freedom)
+                .ok()
+                .map(|students_t| Self::new_cdf(&students_t, mean, datum)),
+        }
+    }
+
+    pub fn new_log_normal(ln: Ln, datum: f64) -> Option<Self> {
+        let Ln { location, scale } = ln;
+        LogNormal::new(location, scale)
+            .ok()
+            .map(|log_normal| Self::new_cdf(&log_normal, location.exp(), datum))
+    }
+
+    // The lower p-value flips the datum to the other side of the baseline,
+    // in the same way that the lower boundary limit is flipped.
+    // This way a datum is a lower outlier if and only if its lower p-value is within the lower boundary significance level.
+    fn new_cdf<D>(distribution: &D, baseline: f64, datum: f64) -> Self
+    where
+        D: ContinuousCDF<f64, f64>,
+    {
+        Self {
+            lower: distribution.sf(baseline * 2.0 - datum),
+            upper: distribution.sf(datum),
+        }
+    }
+
+    // Normalize the p-values by the significance level of their boundary, and return the smallest.
+    // A normalized p-value less than or equal to one is an outlier.
+    pub fn normalized(
+        self,
+        lower_boundary: Option<Boundary>,
+        upper_boundary: Option<Boundary>,
+    ) -> Option<f64> {
+        let normalize = |p_value: f64, boundary: Option<Boundary>| {
+            significance(boundary).map(|significance| p_value / significance)
+        };
+        normalize(self.lower, lower_boundary)
+            .into_iter()
+            .chain(normalize(self.upper, upper_boundary))
+            .filter(|normalized| !normalized.is_nan())
+            .reduce(f64::min)
+    }
+}
+
+// The significance level of a boundary is the probability outside of its percentile.
+pub fn significance(boundary: Option<Boundary>) -> Option<f64> {
+    boundary
+        .and_then(|boundary| CdfBoundary::try_from(boundary).ok())
+        .map(|boundary| 1.0 - f64::from(boundary))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::PValue;
+    use crate::limits::NormalTestKind;
+
+    #[test]
+    fn test_p_value_normal() {
+        let p_value = PValue::new_normal(0.0, 1.0, NormalTestKind::Z, 0.0).unwrap();
+        assert!((p_value.lower - 0.5).abs() < f64::EPSILON, "{p_value:?}");
+        assert!((p_value.upper - 0.5).abs() < f64::EPSILON, "{p_value:?}");
+
+        let p_value = PValue::new_normal(0.0, 1.0, NormalTestKind::Z, 3.0).unwrap();
+        assert!(p_value.lower > 0.99, "{p_value:?}");
+        assert!(p_value.upper < 0.01, "{p_value:?}");
+
+        let p_value =
+            PValue::new_normal(0.0, 1.0, NormalTestKind::T { freedom: 4.0 }, -3.0).unwrap();
+        assert!(p_value.lower < 0.05, "{p_value:?}");
+        assert!(p_value.upper > 0.95, "{p_value:?}");
+    }
+
+    #[test]
+    fn test_p_value_normalized() {
+        let p_value = PValue {
+            lower: 0.5,
+            upper: 0.005,
+        };
+        let boundary = Some(0.99.try_into().unwrap());
+        let normalized = p_value.normalized(boundary, boundary).unwrap();
+        assert!((normalized - 0.5).abs() < 1e-9, "{normalized}");
+        let normalized = p_value.normalized(boundary, None).unwrap();
+        assert!((normalized - 50.0).abs() < 1e-9, "{normalized}");
+        assert_eq!(p_value.normalized(None, None), None);
+    }
+}
@@ -40,6 +40,12 @@ impl Quartiles {
         })
     }
 
+    pub fn percentile(data: &[f64], percentile: f64) -> Option<f64> {
+        let mut data = data.to_vec();
+        data.sort_unstable_by(|x, y| x.partial_cmp(y).unwrap_or(cmp::Ordering::Equal));
+        Self::percentile_of_sorted(&data, percentile).and_then(|p| p.is_finite().then_some(p))
+    }
+
     pub fn iqr(self, boundary: IqrBoundary) -> f64 {
         (self.q3 - self.q1) * f64::from(boundary)
     }
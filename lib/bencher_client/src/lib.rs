@@ -45,6 +45,8 @@ from_client!(
     ResourceId,
     ResourceName,
     SampleSize,
+    Secret,
+    Smoothing,
     Slug,
     Url,
     UserName,
@@ -66,7 +68,9 @@ from_client!(
     ModelUuid,
     AlertUuid,
     UserUuid,
-    TokenUuid
+    TokenUuid,
+    TrustPolicyUuid,
+    WebhookUuid
 );
 
 #[cfg(feature = "plus")]
@@ -136,11 +140,17 @@ try_from_client!(
     JsonModel,
     JsonAlerts,
     JsonAlert,
+    JsonTrustPolicies,
+    JsonTrustPolicy,
+    JsonWebhooks,
+    JsonWebhook,
+    JsonWebhookDeliveries,
     JsonUsers,
     JsonUser,
     JsonPubUser,
     JsonTokens,
     JsonToken,
+    JsonNotification,
     JsonSignup,
     JsonLogin,
     JsonConfirm,
@@ -148,6 +158,7 @@ try_from_client!(
     JsonAuthAck,
     JsonAuthUser,
     JsonBackupCreated,
+    JsonRestored,
     JsonConfig,
     JsonConsole,
     JsonApiVersion,
@@ -0,0 +1,624 @@
+use bencher_json::{
+    JsonAlert, Units,
+    project::{alert::AlertStatus, boundary::BoundaryLimit},
+};
+use serde_json::{Value, json};
+use url::Url;
+
+use crate::{
+    ReportComment, Resource, alert_status, alert_units, delta_percent, format_value,
+    lower_limit_percent, upper_limit_percent,
+};
+
+// Chat tools limit the size of a message, so only the first alerts are included
+const MAX_CHAT_ALERTS: usize = 10;
+// Slack header blocks are limited to 150 characters
+const SLACK_HEADER_MAX_LENGTH: usize = 150;
+const TEAMS_ALERT_COLOR: &str = "D93F0B";
+const TEAMS_REPORT_COLOR: &str = "3AA655";
+
+/// A report or alert summary for a chat tool.
+/// It can be rendered as a Slack Block Kit message,
+/// a Microsoft Teams `MessageCard`, or a Matrix `m.room.message`.
+pub struct ChatMessage {
+    title: String,
+    lines: Vec<String>,
+    url: Url,
+    link_text: &'static str,
+    active: bool,
+    alerts: Vec<ChatAlert>,
+    hidden_alerts: usize,
+}
+
+struct ChatAlert {
+    title: String,
+    facts: Vec<(&'static str, String)>,
+    links: Vec<(&'static str, Url)>,
+}
+
+impl ReportComment {
+    /// A chat message that summarizes the report and its alerts
+    pub fn chat(&self) -> ChatMessage {
+        let project = &self.json_report.project.name;
+        let alerts_len = self.json_report.alerts.len();
+        let title = if alerts_len == 0 {
+            format!("🐰 Bencher Report for {project}")
+        } else {
+            format!(
+                "🚨 {alerts_len} {alert} for {project}",
+                alert = if alerts_len == 1 { "Alert" } else { "Alerts" },
+            )
+        };
+
+        let mut lines = vec![format!(
+            "Branch: {branch} | Testbed: {testbed} | Benchmarks: {benchmark_count}",
+            branch = self.json_report.branch.name,
+            testbed = self.json_report.testbed.name,
+            benchmark_count = self.benchmark_count,
+        )];
+        if self.benchmark_count == 0 {
+            lines.push("⚠️ WARNING: No benchmarks found!".to_owned());
+        }
+        if let Some(note) = self.hardware_change_note() {
+            lines.push(format!("⚠️ WARNING: Testbed hardware changed! {note}"));
+        }
+
+        ChatMessage {
+            title,
+            lines,
+            url: self.resource_url(Resource::Report(self.json_report.uuid)),
+            link_text: "View report",
+            active: self.has_alert(),
+            alerts: self
+                .json_report
+                .alerts
+                .iter()
+                .take(MAX_CHAT_ALERTS)
+                .map(|alert| self.chat_alert_section(alert))
+                .collect(),
+            hidden_alerts: alerts_len.saturating_sub(MAX_CHAT_ALERTS),
+        }
+    }
+
+    /// A chat message for a single alert from the report
+    ///
+    /// The alert is passed in separately, as its status may have changed since the report was created.
+    pub fn chat_alert(&self, alert: &JsonAlert) -> ChatMessage {
+        let title = format!(
+            "{status} for {benchmark} ({measure})",
+            status = match alert.status {
+                AlertStatus::Active => "🚨 Alert",
+                AlertStatus::Dismissed => "🔕 Alert dismissed",
+                AlertStatus::Silenced => "🔕 Alert silenced",
+            },
+            benchmark = alert.benchmark.name,
+            measure = alert.threshold.measure.name,
+        );
+        let lines = vec![format!(
+            "Project: {project} | Branch: {branch} | Testbed: {testbed}",
+            project = self.json_report.project.name,
+            branch = self.json_report.branch.name,
+            testbed = self.json_report.testbed.name,
+        )];
+
+        ChatMessage {
+            title,
+            lines,
+            url: self.resource_url(Resource::Alert(alert.uuid)),
+            link_text: "View alert",
+            active: matches!(alert.status, AlertStatus::Active),
+            alerts: vec![self.chat_alert_section(alert)],
+            hidden_alerts: 0,
+        }
+    }
+
+    fn chat_alert_section(&self, alert: &JsonAlert) -> ChatAlert {
+        let units = alert_units(alert);
+        let factor = units.scale_factor();
+        let units_symbol = units.scale_units_symbol();
+        let value = alert.metric.value;
+
+        let mut facts = Vec::new();
+        if self.multiple_iterations {
+            facts.push(("Iteration", alert.iteration.to_string()));
+        }
+        let mut result = format_value(value, factor, &units_symbol);
+        if let Some(baseline) = alert.boundary.baseline {
+            let percent = delta_percent(value, baseline);
+            let plus = if percent > 0.0.into() { "+" } else { "" };
+            result.push_str(&format!(
+                " ({plus}{percent}%)",
+                percent = Units::format_float(percent.into())
+            ));
+            facts.push(("Benchmark Result", result));
+            facts.push(("Baseline", format_value(baseline, factor, &units_symbol)));
+        } else {
+            facts.push(("Benchmark Result", result));
+        }
+        let (boundary, limit, percent) = match alert.limit {
+            BoundaryLimit::Lower => (
+                "Lower Boundary",
+                alert.boundary.lower_limit,
+                alert
+                    .boundary
+                    .lower_limit
+                    .map(|limit| lower_limit_percent(value, limit)),
+            ),
+            BoundaryLimit::Upper => (
+                "Upper Boundary",
+                alert.boundary.upper_limit,
+                alert
+                    .boundary
+                    .upper_limit
+                    .map(|limit| upper_limit_percent(value, limit)),
+            ),
+        };
+        if let (Some(limit), Some(percent)) = (limit, percent) {
+            facts.push((
+                boundary,
+                format!(
+                    "{limit} ({percent}%)",
+                    limit = format_value(limit, factor, &units_symbol),
+                    percent = Units::format_float(percent.into()),
+                ),
+            ));
+        }
+        if alert.environment_change {
+            facts.push(("Environment", "🖥️ Testbed hardware changed".to_owned()));
+        }
+
+        let mut links = vec![
+            ("📈 Plot", self.alert_perf_url(alert)),
+            (
+                "🚷 Threshold",
+                self.resource_url(Resource::Threshold(alert.threshold.uuid)),
+            ),
+            ("🚨 Alert", self.resource_url(Resource::Alert(alert.uuid))),
+        ];
+        if let Some(change_point) = alert.boundary.change_point {
+            links.push((
+                "🔀 Change Point",
+                self.resource_url(Resource::Metric(change_point)),
+            ));
+        }
+
+        ChatAlert {
+            title: format!(
+                "{status} {benchmark} ({measure})",
+                status = alert_status(alert),
+                benchmark = alert.benchmark.name,
+                measure = alert.threshold.measure.name,
+            ),
+            facts,
+            links,
+        }
+    }
+}
+
+impl ChatMessage {
+    /// Render the message as a Slack Block Kit payload for an incoming webhook
+    pub fn slack(&self) -> Value {
+        let mut blocks = vec![
+            json!({
+                "type": "header",
+                "text": {
+                    "type": "plain_text",
+                    "text": self.title.chars().take(SLACK_HEADER_MAX_LENGTH).collect::<String>(),
+                    "emoji": true,
+                },
+            }),
+            json!({
+                "type": "section",
+                "text": {
+                    "type": "mrkdwn",
+                    "text": slack_escape(&self.lines.join("\n")),
+                },
+            }),
+        ];
+        for alert in &self.alerts {
+            let links = alert
+                .links
+                .iter()
+                .map(|(name, url)| format!("<{url}|{name}>"))
+                .collect::<Vec<_>>()
+                .join(" | ");
+            let fields = alert
+                .facts
+                .iter()
+                .map(|(name, value)| {
+                    json!({
+                        "type": "mrkdwn",
+                        "text": format!("*{name}*\n{value}", value = slack_escape(value)),
+                    })
+                })
+                .collect::<Vec<_>>();
+            blocks.push(json!({ "type": "divider" }));
+            blocks.push(json!({
+                "type": "section",
+                "text": {
+                    "type": "mrkdwn",
+                    "text": format!("*{title}*\n{links}", title = slack_escape(&alert.title)),
+                },
+                "fields": fields,
+            }));
+        }
+        if let Some(hidden) = self.hidden_alerts_text() {
+            blocks.push(json!({
+                "type": "context",
+                "elements": [{ "type": "mrkdwn", "text": hidden }],
+            }));
+        }
+        blocks.push(json!({
+            "type": "actions",
+            "elements": [{
+                "type": "button",
+                "text": { "type": "plain_text", "text": self.link_text },
+                "url": self.url.as_str(),
+            }],
+        }));
+
+        json!({
+            "text": format!("{title}: {url}", title = self.title, url = self.url),
+            "blocks": blocks,
+        })
+    }
+
+    /// Render the message as a Microsoft Teams `MessageCard` for an incoming webhook
+    pub fn teams(&self) -> Value {
+        let mut sections = self
+            .alerts
+            .iter()
+            .map(|alert| {
+                let links = alert
+                    .links
+                    .iter()
+                    .map(|(name, url)| format!("[{name}]({url})"))
+                    .collect::<Vec<_>>()
+                    .join(" | ");
+                let facts = alert
+                    .facts
+                    .iter()
+                    .map(|(name, value)| json!({ "name": name, "value": value }))
+                    .collect::<Vec<_>>();
+                json!({
+                    "activityTitle": alert.title,
+                    "facts": facts,
+                    "text": links,
+                })
+            })
+            .collect::<Vec<_>>();
+        if let Some(hidden) = self.hidden_alerts_text() {
+            sections.push(json!({ "text": hidden }));
+        }
+
+        json!({
+            "@type": "MessageCard",
+            "@context": "https://schema.org/extensions",
+            "summary": self.title,
+            "themeColor": if self.active { TEAMS_ALERT_COLOR } else { TEAMS_REPORT_COLOR },
+            "title": self.title,
+            // Teams markdown needs a blank line for a line break
+            "text": self.lines.join("\n\n"),
+            "sections": sections,
+            "potentialAction": [{
+                "@type": "OpenUri",
+                "name": self.link_text,
+                "targets": [{ "os": "default", "uri": self.url.as_str() }],
+            }],
+        })
+    }
+
+    /// Render the message as the content of a Matrix `m.room.message` event
+    pub fn matrix(&self) -> Value {
+        let mut body = self.title.clone();
+        let mut html = format!("<h4>{title}</h4>", title = html_escape(&self.title));
+
+        for line in &self.lines {
+            body.push('\n');
+            body.push_str(line);
+        }
+        html.push_str(&format!(
+            "<p>{lines}</p>",
+            lines = self
+                .lines
+                .iter()
+                .map(|line| html_escape(line))
+                .collect::<Vec<_>>()
+                .join("<br />")
+        ));
+
+        if !self.alerts.is_empty() {
+            html.push_str("<ul>");
+            for alert in &self.alerts {
+                body.push_str(&format!("\n\n{title}", title = alert.title));
+                html.push_str(&format!(
+                    "<li><b>{title}</b>",
+                    title = html_escape(&alert.title)
+                ));
+                for (name, value) in &alert.facts {
+                    body.push_str(&format!("\n{name}: {value}"));
+                    html.push_str(&format!(
+                        "<br /><b>{name}:</b> {value}",
+                        value = html_escape(value)
+                    ));
+                }
+                if let Some((plot, url)) = alert.links.first() {
+                    body.push_str(&format!("\n{plot}: {url}"));
+                }
+                html.push_str(&format!(
+                    "<br />{links}",
+                    links = alert
+                        .links
+                        .iter()
+                        .map(|(name, url)| format!(
+                            "<a href=\"{url}\">{name}</a>",
+                            url = html_escape(url.as_str())
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(" | ")
+                ));
+                html.push_str("</li>");
+            }
+            html.push_str("</ul>");
+        }
+        if let Some(hidden) = self.hidden_alerts_text() {
+            body.push_str(&format!("\n\n{hidden}"));
+            html.push_str(&format!("<p><i>{hidden}</i></p>"));
+        }
+
+        body.push_str(&format!(
+            "\n\n{link_text}: {url}",
+            link_text = self.link_text,
+            url = self.url
+        ));
+        html.push_str(&format!(
+            "<p><a href=\"{url}\">{link_text}</a></p>",
+            url = html_escape(self.url.as_str()),
+            link_text = self.link_text,
+        ));
+
+        json!({
+            "msgtype": "m.notice",
+            "body": body,
+            "format": "org.matrix.custom.html",
+            "formatted_body": html,
+        })
+    }
+
+    fn hidden_alerts_text(&self) -> Option<String> {
+        (self.hidden_alerts > 0).then(|| {
+            format!(
+                "…and {hidden} more {alert}",
+                hidden = self.hidden_alerts,
+                alert = if self.hidden_alerts == 1 {
+                    "alert"
+                } else {
+                    "alerts"
+                },
+            )
+        })
+    }
+}
+
+// Slack mrkdwn only requires these three characters to be escaped
+fn slack_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::project::report::JsonHardwareChange;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::{MAX_CHAT_ALERTS, SLACK_HEADER_MAX_LENGTH};
+    use crate::test::{json_report, report_comment};
+
+    const BENCHMARK: &str = "Vec<u8> & co";
+    const REPORT_URL: &str =
+        "https://console.example.com/perf/project/reports/00000001-0000-4000-8000-000000000000";
+    const PLOT_URL: &str = "https://console.example.com/perf/project?branches=00000003-0000-4000-8000-000000000000&heads=00000004-0000-4000-8000-000000000000&testbeds=00000005-0000-4000-8000-000000000000&benchmarks=00000008-0000-4000-8000-000000000000&measures=00000006-0000-4000-8000-000000000000&start_time=1701475200000&end_time=1704067260000&report=00000001-0000-4000-8000-000000000000&upper_boundary=true";
+    const THRESHOLD_URL: &str =
+        "https://console.example.com/perf/project/thresholds/0000000a-0000-4000-8000-000000000000";
+    const ALERT_URL: &str =
+        "https://console.example.com/perf/project/alerts/0000000b-0000-4000-8000-000000000000";
+
+    fn benchmarks(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("bench_{i}")).collect()
+    }
+
+    #[test]
+    fn test_chat_slack() {
+        let chat = report_comment(json_report(&[BENCHMARK], 1)).chat();
+        assert_eq!(
+            chat.slack(),
+            json!({
+                "text": format!("🚨 1 Alert for Project: {REPORT_URL}"),
+                "blocks": [
+                    {
+                        "type": "header",
+                        "text": { "type": "plain_text", "text": "🚨 1 Alert for Project", "emoji": true },
+                    },
+                    {
+                        "type": "section",
+                        "text": { "type": "mrkdwn", "text": "Branch: main | Testbed: localhost | Benchmarks: 1" },
+                    },
+                    { "type": "divider" },
+                    {
+                        "type": "section",
+                        "text": {
+                            "type": "mrkdwn",
+                            "text": format!("*🔔 Vec&lt;u8&gt; &amp; co (Latency)*\n<{PLOT_URL}|📈 Plot> | <{THRESHOLD_URL}|🚷 Threshold> | <{ALERT_URL}|🚨 Alert>"),
+                        },
+                        "fields": [
+                            { "type": "mrkdwn", "text": "*Benchmark Result*\n150.00 ns (+50.00%)" },
+                            { "type": "mrkdwn", "text": "*Baseline*\n100.00 ns" },
+                            { "type": "mrkdwn", "text": "*Upper Boundary*\n120.00 ns (125.00%)" },
+                        ],
+                    },
+                    {
+                        "type": "actions",
+                        "elements": [{
+                            "type": "button",
+                            "text": { "type": "plain_text", "text": "View report" },
+                            "url": REPORT_URL,
+                        }],
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_chat_teams() {
+        let chat = report_comment(json_report(&[BENCHMARK], 1)).chat();
+        assert_eq!(
+            chat.teams(),
+            json!({
+                "@type": "MessageCard",
+                "@context": "https://schema.org/extensions",
+                "summary": "🚨 1 Alert for Project",
+                "themeColor": "D93F0B",
+                "title": "🚨 1 Alert for Project",
+                "text": "Branch: main | Testbed: localhost | Benchmarks: 1",
+                "sections": [{
+                    "activityTitle": "🔔 Vec<u8> & co (Latency)",
+                    "facts": [
+                        { "name": "Benchmark Result", "value": "150.00 ns (+50.00%)" },
+                        { "name": "Baseline", "value": "100.00 ns" },
+                        { "name": "Upper Boundary", "value": "120.00 ns (125.00%)" },
+                    ],
+                    "text": format!("[📈 Plot]({PLOT_URL}) | [🚷 Threshold]({THRESHOLD_URL}) | [🚨 Alert]({ALERT_URL})"),
+                }],
+                "potentialAction": [{
+                    "@type": "OpenUri",
+                    "name": "View report",
+                    "targets": [{ "os": "default", "uri": REPORT_URL }],
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_chat_matrix() {
+        let chat = report_comment(json_report(&[BENCHMARK], 1)).chat();
+        let html_plot_url = PLOT_URL.replace('&', "&amp;");
+        assert_eq!(
+            chat.matrix(),
+            json!({
+                "msgtype": "m.notice",
+                "body": format!(
+                    "🚨 1 Alert for Project\nBranch: main | Testbed: localhost | Benchmarks: 1\n\n🔔 Vec<u8> & co (Latency)\nBenchmark Result: 150.00 ns (+50.00%)\nBaseline: 100.00 ns\nUpper Boundary: 120.00 ns (125.00%)\n📈 Plot: {PLOT_URL}\n\nView report: {REPORT_URL}"
+                ),
+                "format": "org.matrix.custom.html",
+                "formatted_body": format!(
+                    "<h4>🚨 1 Alert for Project</h4><p>Branch: main | Testbed: localhost | Benchmarks: 1</p><ul><li><b>🔔 Vec&lt;u8&gt; &amp; co (Latency)</b><br /><b>Benchmark Result:</b> 150.00 ns (+50.00%)<br /><b>Baseline:</b> 100.00 ns<br /><b>Upper Boundary:</b> 120.00 ns (125.00%)<br /><a href=\"{html_plot_url}\">📈 Plot</a> | <a href=\"{THRESHOLD_URL}\">🚷 Threshold</a> | <a href=\"{ALERT_URL}\">🚨 Alert</a></li></ul><p><a href=\"{REPORT_URL}\">View report</a></p>"
+                ),
+            })
+        );
+    }
+
+    #[test]
+    fn test_chat_max_alerts() {
+        let benchmarks = benchmarks(MAX_CHAT_ALERTS + 2);
+        let benchmarks = benchmarks.iter().map(String::as_str).collect::<Vec<_>>();
+        let chat = report_comment(json_report(&benchmarks, benchmarks.len())).chat();
+        let hidden = "…and 2 more alerts";
+
+        let slack = chat.slack();
+        assert_eq!(
+            slack.get("text"),
+            Some(&json!(format!("🚨 12 Alerts for Project: {REPORT_URL}")))
+        );
+        let blocks = slack
+            .get("blocks")
+            .and_then(|blocks| blocks.as_array())
+            .unwrap();
+        // The header, the summary, a divider and section for each included alert, the hidden alerts, and the button
+        assert_eq!(blocks.len(), 2 + MAX_CHAT_ALERTS * 2 + 2);
+        assert_eq!(
+            blocks.get(blocks.len() - 2),
+            Some(&json!({
+                "type": "context",
+                "elements": [{ "type": "mrkdwn", "text": hidden }],
+            }))
+        );
+        let last_alert = blocks
+            .get(blocks.len() - 3)
+            .and_then(|block| block.pointer("/text/text"))
+            .and_then(|text| text.as_str())
+            .unwrap();
+        assert!(
+            last_alert.starts_with("*🔔 bench_9 (Latency)*"),
+            "{last_alert}"
+        );
+
+        let teams = chat.teams();
+        let sections = teams
+            .get("sections")
+            .and_then(|sections| sections.as_array())
+            .unwrap();
+        assert_eq!(sections.len(), MAX_CHAT_ALERTS + 1);
+        assert_eq!(sections.last(), Some(&json!({ "text": hidden })));
+
+        let matrix = chat.matrix();
+        let body = matrix.get("body").and_then(|body| body.as_str()).unwrap();
+        assert!(
+            body.ends_with(&format!("\n\n{hidden}\n\nView report: {REPORT_URL}")),
+            "{body}"
+        );
+        assert!(!body.contains("bench_10"), "{body}");
+        let html = matrix
+            .get("formatted_body")
+            .and_then(|html| html.as_str())
+            .unwrap();
+        assert_eq!(html.matches("<li>").count(), MAX_CHAT_ALERTS);
+        assert!(html.contains(&format!("<p><i>{hidden}</i></p>")), "{html}");
+    }
+
+    #[test]
+    fn test_chat_slack_header_max_length() {
+        let benchmark = "a".repeat(SLACK_HEADER_MAX_LENGTH * 2);
+        let json_report = json_report(&[&benchmark], 1);
+        let comment = report_comment(json_report);
+        let alert = comment.json_report.alerts.first().unwrap();
+        let slack = comment.chat_alert(alert).slack();
+        let header = slack
+            .pointer("/blocks/0/text/text")
+            .and_then(|header| header.as_str())
+            .unwrap();
+        assert_eq!(header.chars().count(), SLACK_HEADER_MAX_LENGTH);
+        assert!(header.starts_with("🚨 Alert for aaa"), "{header}");
+    }
+
+    #[test]
+    fn test_chat_hardware_change() {
+        let mut json_report = json_report(&[BENCHMARK], 0);
+        json_report.environment_change = true;
+        let chat = report_comment(json_report.clone()).chat();
+        assert_eq!(
+            chat.lines.last().map(String::as_str),
+            Some(
+                "⚠️ WARNING: Testbed hardware changed! Any Alerts for this report may be due to the change in environment rather than a change in performance."
+            )
+        );
+
+        json_report.hardware_change = Some(JsonHardwareChange::Refuse);
+        let chat = report_comment(json_report).chat();
+        assert_eq!(
+            chat.lines.last().map(String::as_str),
+            Some(
+                "⚠️ WARNING: Testbed hardware changed! This report was not compared to the historical metrics, so it does not have any Alerts."
+            )
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use bencher_json::{
+    Units,
+    project::{
+        alert::AlertStatus,
+        boundary::BoundaryLimit,
+        report::{JsonReportMeasure, JsonReportResult},
+    },
+};
+
+use crate::{ReportComment, Resource, alert_message, format_value};
+
+impl ReportComment {
+    /// Render the report as `JUnit` XML.
+    /// Each iteration is a test suite, and each benchmark and measure is a test case.
+    /// A test case fails if it has an active alert.
+    /// Only the whole report is timed, so the time is only set on `<testsuites>`.
+    pub fn junit(&self) -> String {
+        let (tests, failures) = self.test_case_counts(None);
+        let time = (self.json_report.end_time.into_inner()
+            - self.json_report.start_time.into_inner())
+        .to_std()
+        .map(|duration| duration.as_secs_f64())
+        .unwrap_or_default();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"Bencher Report\" tests=\"{tests}\" failures=\"{failures}\" time=\"{time:.3}\">\n"
+        ));
+        for (i, iteration) in self.json_report.results.iter().enumerate() {
+            let (tests, failures) = self.test_case_counts(Some(i));
+            let mut name = format!(
+                "{project}: {branch} on {testbed}",
+                project = self.json_report.project.name,
+                branch = self.json_report.branch.name,
+                testbed = self.json_report.testbed.name,
+            );
+            if self.multiple_iterations {
+                name.push_str(&format!(" (Iteration {i})"));
+            }
+            xml.push_str(&format!(
+                "  <testsuite name=\"{name}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"0\" skipped=\"0\" timestamp=\"{timestamp}\">\n",
+                name = xml_escape(&name),
+                timestamp = self.json_report.start_time.into_inner().format("%Y-%m-%dT%H:%M:%S"),
+            ));
+            xml.push_str("    <properties>\n");
+            for (property, value) in [
+                (
+                    "report",
+                    self.resource_url(Resource::Report(self.json_report.uuid))
+                        .to_string(),
+                ),
+                ("branch", self.json_report.branch.slug.to_string()),
+                ("testbed", self.json_report.testbed.slug.to_string()),
+                ("adapter", self.json_report.adapter.to_string()),
+            ] {
+                xml.push_str(&format!(
+                    "      <property name=\"{property}\" value=\"{value}\" />\n",
+                    value = xml_escape(&value),
+                ));
+            }
+            xml.push_str("    </properties>\n");
+            for result in iteration {
+                for report_measure in &result.measures {
+                    self.junit_test_case(&mut xml, result, report_measure);
+                }
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+
+    fn junit_test_case(
+        &self,
+        xml: &mut String,
+        result: &JsonReportResult,
+        report_measure: &JsonReportMeasure,
+    ) {
+        xml.push_str(&format!(
+            "    <testcase name=\"{measure}\" classname=\"{benchmark}\">\n",
+            measure = xml_escape(report_measure.measure.name.as_ref()),
+            benchmark = xml_escape(result.benchmark.name.as_ref()),
+        ));
+
+        let alert = self.find_iteration_alert(result, report_measure);
+        if let Some(alert) = alert.filter(|alert| matches!(alert.status, AlertStatus::Active)) {
+            xml.push_str(&format!(
+                "      <failure message=\"{message}\" type=\"{limit}_boundary\">{message}\nView alert: {alert_url}\nView plot: {plot_url}</failure>\n",
+                message = xml_escape(&alert_message(alert)),
+                limit = match alert.limit {
+                    BoundaryLimit::Lower => "lower",
+                    BoundaryLimit::Upper => "upper",
+                },
+                alert_url = xml_escape(self.resource_url(Resource::Alert(alert.uuid)).as_str()),
+                plot_url = xml_escape(self.alert_perf_url(alert).as_str()),
+            ));
+        }
+
+        let units = Units::new(
+            report_measure.metric.value.into(),
+            report_measure.measure.units.clone(),
+        );
+        let mut system_out = format!(
+            "Benchmark Result: {value}",
+            value = format_value(
+                report_measure.metric.value,
+                units.scale_factor(),
+                &units.scale_units_symbol()
+            ),
+        );
+        if report_measure.threshold.is_none() {
+            system_out.push_str("\nNo Threshold");
+        }
+        if let Some(alert) = alert.filter(|alert| !matches!(alert.status, AlertStatus::Active)) {
+            system_out.push_str(&format!(
+                "\nAlert ({status}): {message}",
+                status = alert.status,
+                message = alert_message(alert)
+            ));
+        }
+        system_out.push_str(&format!(
+            "\nView plot: {url}",
+            url = self.perf_url(&result.benchmark, &report_measure.measure, None),
+        ));
+        xml.push_str(&format!(
+            "      <system-out>{system_out}</system-out>\n",
+            system_out = xml_escape(&system_out),
+        ));
+        xml.push_str("    </testcase>\n");
+    }
+
+    // Count the test cases and failures, either for the whole report or for a single iteration
+    fn test_case_counts(&self, iteration: Option<usize>) -> (usize, usize) {
+        let mut tests = 0;
+        let mut failures = 0;
+        for (i, results) in self.json_report.results.iter().enumerate() {
+            if iteration.is_some_and(|iteration| iteration != i) {
+                continue;
+            }
+            for result in results {
+                for report_measure in &result.measures {
+                    tests += 1;
+                    if self
+                        .find_iteration_alert(result, report_measure)
+                        .is_some_and(|alert| matches!(alert.status, AlertStatus::Active))
+                    {
+                        failures += 1;
+                    }
+                }
+            }
+        }
+        (tests, failures)
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use bencher_json::project::report::Iteration;
+    use pretty_assertions::assert_eq;
+    use quick_xml::{Reader, events::Event};
+
+    use crate::test::{json_report, report_comment};
+
+    #[derive(Debug, Default)]
+    struct JUnit {
+        testsuites: HashMap<String, String>,
+        testsuites_count: usize,
+        testsuite: Vec<HashMap<String, String>>,
+        testcases: Vec<(String, String)>,
+        failures: Vec<String>,
+    }
+
+    // Parse the JUnit XML, which also checks that every element is closed
+    fn parse(xml: &str) -> JUnit {
+        let mut junit = JUnit::default();
+        let mut reader = Reader::from_str(xml);
+        loop {
+            match reader.read_event().unwrap() {
+                Event::Start(element) | Event::Empty(element) => {
+                    let attributes = element
+                        .attributes()
+                        .map(|attribute| {
+                            let attribute = attribute.unwrap();
+                            (
+                                String::from_utf8(attribute.key.as_ref().to_vec()).unwrap(),
+                                attribute.unescape_value().unwrap().into_owned(),
+                            )
+                        })
+                        .collect::<HashMap<_, _>>();
+                    match element.name().as_ref() {
+                        b"testsuites" => {
+                            junit.testsuites = attributes;
+                            junit.testsuites_count += 1;
+                        },
+                        b"testsuite" => junit.testsuite.push(attributes),
+                        b"testcase" => junit.testcases.push((
+                            attributes.get("classname").cloned().unwrap(),
+                            attributes.get("name").cloned().unwrap(),
+                        )),
+                        b"failure" => junit
+                            .failures
+                            .push(attributes.get("type").cloned().unwrap()),
+                        _ => {},
+                    }
+                },
+                Event::Eof => break,
+                Event::End(_)
+                | Event::Text(_)
+                | Event::CData(_)
+                | Event::Comment(_)
+                | Event::Decl(_)
+                | Event::PI(_)
+                | Event::DocType(_) => {},
+            }
+        }
+        junit
+    }
+
+    fn attribute<'a>(attributes: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+        attributes.get(name).map(String::as_str)
+    }
+
+    #[test]
+    fn test_junit() {
+        let benchmarks = ["bench_1", "bench <&> \"2\"", "bench_3"];
+        let xml = report_comment(json_report(&benchmarks, 2)).junit();
+        let junit = parse(&xml);
+
+        assert_eq!(junit.testsuites_count, 1);
+        assert_eq!(attribute(&junit.testsuites, "tests"), Some("3"));
+        assert_eq!(attribute(&junit.testsuites, "failures"), Some("2"));
+        assert_eq!(attribute(&junit.testsuites, "time"), Some("60.000"));
+
+        assert_eq!(junit.testsuite.len(), 1, "{xml}");
+        let testsuite = junit.testsuite.first().unwrap();
+        assert_eq!(
+            attribute(testsuite, "name"),
+            Some("Project: main on localhost")
+        );
+        assert_eq!(attribute(testsuite, "tests"), Some("3"));
+        assert_eq!(attribute(testsuite, "failures"), Some("2"));
+        // The report is only timed as a whole
+        assert_eq!(attribute(testsuite, "time"), None);
+
+        assert_eq!(
+            junit.testcases,
+            benchmarks
+                .iter()
+                .map(|benchmark| ((*benchmark).to_owned(), "Latency".to_owned()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(junit.failures, ["upper_boundary", "upper_boundary"]);
+    }
+
+    #[test]
+    fn test_junit_iterations() {
+        let mut json_report = json_report(&["bench_1", "bench_2"], 1);
+        let mut iteration = json_report.results.first().cloned().unwrap();
+        for result in &mut iteration {
+            result.iteration = Iteration(1);
+        }
+        json_report.results.push(iteration);
+        let xml = report_comment(json_report).junit();
+        let junit = parse(&xml);
+
+        assert_eq!(attribute(&junit.testsuites, "tests"), Some("4"));
+        assert_eq!(attribute(&junit.testsuites, "failures"), Some("1"));
+        let counts = junit
+            .testsuite
+            .iter()
+            .map(|testsuite| {
+                (
+                    attribute(testsuite, "name").unwrap(),
+                    attribute(testsuite, "tests").unwrap(),
+                    attribute(testsuite, "failures").unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        // The alert is only for the first iteration
+        assert_eq!(
+            counts,
+            [
+                ("Project: main on localhost (Iteration 0)", "2", "1"),
+                ("Project: main on localhost (Iteration 1)", "2", "0"),
+            ]
+        );
+        assert_eq!(junit.testcases.len(), 4);
+        assert_eq!(junit.failures.len(), 1);
+    }
+}
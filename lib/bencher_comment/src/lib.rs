@@ -1,4 +1,8 @@
 #![expect(clippy::format_push_string, reason = "todo")]
+#![expect(
+    clippy::multiple_inherent_impl,
+    reason = "renderers split across modules"
+)]
 
 use std::{
     collections::{BTreeMap, HashSet, btree_map::Entry},
@@ -8,17 +12,24 @@ use std::{
 
 use bencher_json::{
     AlertUuid, JsonAlert, JsonBenchmark, JsonBoundary, JsonMeasure, JsonPerfQuery, JsonReport,
-    ReportUuid, ResourceName, Slug, ThresholdUuid, Units,
+    MetricUuid, ReportUuid, ResourceName, Slug, ThresholdUuid, Units,
     project::{
         alert::AlertStatus,
         boundary::BoundaryLimit,
         plot::{LOWER_BOUNDARY, UPPER_BOUNDARY},
-        report::{JsonReportIteration, JsonReportMeasure, JsonReportResult},
+        report::{JsonHardwareChange, JsonReportIteration, JsonReportMeasure, JsonReportResult},
     },
 };
 use ordered_float::OrderedFloat;
 use url::Url;
 
+mod chat;
+mod junit;
+mod markdown;
+mod sarif;
+
+pub use chat::ChatMessage;
+
 // 30 days
 const DEFAULT_REPORT_HISTORY: Duration = Duration::from_secs(30 * 24 * 60 * 60);
 
@@ -39,6 +50,7 @@ pub struct ReportComment {
 pub struct SubAdapter {
     pub build_time: bool,
     pub file_size: bool,
+    pub rusage: bool,
 }
 
 impl ReportComment {
@@ -64,6 +76,7 @@ impl ReportComment {
     pub fn human(&self) -> String {
         let mut text = String::new();
         self.human_results_list(&mut text);
+        self.human_hardware_change(&mut text);
         self.human_alerts_list(&mut text);
         self.human_unclaimed(&mut text);
         text
@@ -96,6 +109,12 @@ impl ReportComment {
         }
     }
 
+    fn human_hardware_change(&self, text: &mut String) {
+        if let Some(note) = self.hardware_change_note() {
+            text.push_str(&format!("\n\nWARNING: Testbed hardware changed! {note}"));
+        }
+    }
+
     fn human_alerts_list(&self, text: &mut String) {
         if self.json_report.alerts.is_empty() {
             return;
@@ -104,7 +123,7 @@ impl ReportComment {
         text.push_str("\n\nView alerts:");
         for alert in &self.json_report.alerts {
             text.push_str(&format!(
-                "\n- {benchmark_name} ({measure_name}){iter}: {console_url}",
+                "\n- {benchmark_name} ({measure_name}){iter}{environment_change}: {console_url}",
                 benchmark_name = alert.benchmark.name,
                 measure_name = alert.threshold.measure.name,
                 iter = if self.multiple_iterations {
@@ -112,6 +131,11 @@ impl ReportComment {
                 } else {
                     String::new()
                 },
+                environment_change = if alert.environment_change {
+                    " (environment change)"
+                } else {
+                    ""
+                },
                 console_url = self.alert_perf_url(alert)
             ));
         }
@@ -202,6 +226,7 @@ impl ReportComment {
     fn html_benchmarks(&self, html: &mut String, require_threshold: bool) {
         self.html_no_benchmarks(html);
         self.html_no_threshold(html, require_threshold);
+        self.html_environment_change(html);
         self.html_alerts(html);
         self.html_benchmark_details(html, require_threshold);
     }
@@ -234,6 +259,22 @@ impl ReportComment {
         html.push_str("</blockquote>");
     }
 
+    fn html_environment_change(&self, html: &mut String) {
+        let Some(note) = self.hardware_change_note() else {
+            return;
+        };
+
+        html.push_str("<blockquote>");
+        html.push_str("<h3>⚠️ WARNING: Testbed hardware changed!</h3>");
+        html.push_str(&format!(
+            "<p>The hardware for the <a href=\"{url}\">{testbed}</a> Testbed has changed since its previous report.<br />",
+            url = self.resource_url(Resource::Testbed(self.json_report.testbed.slug.clone())),
+            testbed = self.json_report.testbed.name,
+        ));
+        html.push_str(&format!("{note}</p>"));
+        html.push_str("</blockquote>");
+    }
+
     fn html_alerts(&self, html: &mut String) {
         if self.json_report.alerts.is_empty() {
             return;
@@ -278,14 +319,7 @@ impl ReportComment {
 
         for alert in &self.json_report.alerts {
             let (factor, units, units_symbol) = {
-                let mut min = alert.metric.value;
-                if let Some(lower_limit) = alert.boundary.lower_limit {
-                    min = min.min(lower_limit);
-                }
-                if let Some(upper_limit) = alert.boundary.upper_limit {
-                    min = min.min(upper_limit);
-                }
-                let units = Units::new(min.into(), alert.threshold.measure.units.clone());
+                let units = alert_units(alert);
                 (
                     units.scale_factor(),
                     units.scale_units(),
@@ -358,6 +392,17 @@ impl ReportComment {
             url = self.resource_url(Resource::Alert(alert.uuid)),
             status = alert_status(alert),
         ));
+        if alert.environment_change {
+            html.push_str("<br />");
+            html.push_str("🖥️ environment change");
+        }
+        if let Some(change_point) = alert.boundary.change_point {
+            html.push_str("<br />");
+            html.push_str(&format!(
+                "🔀 <a href=\"{url}\">change point</a>",
+                url = self.resource_url(Resource::Metric(change_point)),
+            ));
+        }
         html.push_str("</td>");
     }
 
@@ -583,7 +628,7 @@ impl ReportComment {
         let id = id.map_or_else(
             || {
                 format!(
-                    "{branch}/{testbed}/{adapter}{build_time}{file_size}",
+                    "{branch}/{testbed}/{adapter}{build_time}{file_size}{rusage}",
                     branch = self.json_report.branch.slug,
                     testbed = self.json_report.testbed.slug,
                     adapter = self.json_report.adapter,
@@ -597,6 +642,11 @@ impl ReportComment {
                     } else {
                         ""
                     },
+                    rusage = if self.sub_adapter.rusage {
+                        "-rusage"
+                    } else {
+                        ""
+                    },
                 )
             },
             ToString::to_string,
@@ -620,8 +670,27 @@ impl ReportComment {
         false
     }
 
+    // What the testbed hardware change means for this report, if its hardware changed
+    fn hardware_change_note(&self) -> Option<&'static str> {
+        if !self.json_report.environment_change {
+            return None;
+        }
+        Some(match self.json_report.hardware_change.unwrap_or_default() {
+            JsonHardwareChange::Warn => {
+                "Any Alerts for this report may be due to the change in environment rather than a change in performance."
+            },
+            JsonHardwareChange::Refuse => {
+                "This report was not compared to the historical metrics, so it does not have any Alerts."
+            },
+        })
+    }
+
+    // Suppressed alerts are silenced, so only check for active alerts
     pub fn has_alert(&self) -> bool {
-        !self.json_report.alerts.is_empty()
+        self.json_report
+            .alerts
+            .iter()
+            .any(|alert| matches!(alert.status, AlertStatus::Active))
     }
 
     pub fn find_alert(&self, result: &JsonReportResult, measure: &Measure) -> Option<&JsonAlert> {
@@ -631,6 +700,19 @@ impl ReportComment {
         })
     }
 
+    // Unlike `find_alert`, this also matches the iteration of the alert
+    fn find_iteration_alert(
+        &self,
+        result: &JsonReportResult,
+        report_measure: &JsonReportMeasure,
+    ) -> Option<&JsonAlert> {
+        self.json_report.alerts.iter().find(|alert| {
+            alert.iteration == result.iteration
+                && alert.benchmark.uuid == result.benchmark.uuid
+                && alert.threshold.measure.uuid == report_measure.measure.uuid
+        })
+    }
+
     #[cfg_attr(not(feature = "plus"), expect(clippy::unused_self))]
     fn is_bencher_cloud(&self) -> bool {
         #[cfg(feature = "plus")]
@@ -747,6 +829,7 @@ enum Resource {
     Measure(Slug),
     Threshold(ThresholdUuid),
     Alert(AlertUuid),
+    Metric(MetricUuid),
 }
 
 impl Resource {
@@ -759,6 +842,7 @@ impl Resource {
             Resource::Measure(_) => "measures",
             Resource::Threshold(_) => "thresholds",
             Resource::Alert(_) => "alerts",
+            Resource::Metric(_) => "metrics",
         }
     }
 
@@ -771,6 +855,7 @@ impl Resource {
             | Resource::Measure(slug) => slug.into(),
             Resource::Threshold(uuid) => uuid.to_string(),
             Resource::Alert(uuid) => uuid.to_string(),
+            Resource::Metric(uuid) => uuid.to_string(),
         }
     }
 }
@@ -810,6 +895,18 @@ impl Measure {
     }
 }
 
+// Scale the units for an alert to fit its smallest value
+fn alert_units(alert: &JsonAlert) -> Units {
+    let mut min = alert.metric.value;
+    if let Some(lower_limit) = alert.boundary.lower_limit {
+        min = min.min(lower_limit);
+    }
+    if let Some(upper_limit) = alert.boundary.upper_limit {
+        min = min.min(upper_limit);
+    }
+    Units::new(min.into(), alert.threshold.measure.units.clone())
+}
+
 fn alert_status(alert: &JsonAlert) -> &str {
     match alert.status {
         AlertStatus::Active => "🔔",
@@ -837,11 +934,7 @@ fn value_cell(
         }
 
         if let Some(baseline) = baseline {
-            let percent = if value.is_normal() && baseline.is_normal() {
-                ((value - baseline) / baseline) * 100.0
-            } else {
-                0.0.into()
-            };
+            let percent = delta_percent(value, baseline);
             let plus = if percent > 0.0.into() { "+" } else { "" };
             let percent = Units::format_float(percent.into());
             let baseline = Units::format_float((baseline / factor).into());
@@ -885,11 +978,7 @@ fn lower_limit_cell(
         return;
     };
 
-    let percent = if value.is_normal() && limit.is_normal() {
-        (limit / value) * 100.0
-    } else {
-        0.0.into()
-    };
+    let percent = lower_limit_percent(value, limit);
 
     limit_cell(html, limit, percent, factor, units_symbol, bold);
 }
@@ -907,11 +996,7 @@ fn upper_limit_cell(
         return;
     };
 
-    let percent = if value.is_normal() && limit.is_normal() {
-        (value / limit) * 100.0
-    } else {
-        0.0.into()
-    };
+    let percent = upper_limit_percent(value, limit);
 
     limit_cell(html, limit, percent, factor, units_symbol, bold);
 }
@@ -954,6 +1039,77 @@ fn limit_cell(
     html.push_str("</td>");
 }
 
+// A one line summary of an alert, with its boundary limit and the delta from its baseline
+fn alert_message(alert: &JsonAlert) -> String {
+    let units = alert_units(alert);
+    let factor = units.scale_factor();
+    let units_symbol = units.scale_units_symbol();
+    let value = alert.metric.value;
+
+    let (boundary, limit) = match alert.limit {
+        BoundaryLimit::Lower => ("below the lower", alert.boundary.lower_limit),
+        BoundaryLimit::Upper => ("above the upper", alert.boundary.upper_limit),
+    };
+    let mut message = format!(
+        "{benchmark} ({measure}): {value} is {boundary} boundary",
+        benchmark = alert.benchmark.name,
+        measure = alert.threshold.measure.name,
+        value = format_value(value, factor, &units_symbol),
+    );
+    if let Some(limit) = limit {
+        message.push_str(&format!(
+            " limit of {limit}",
+            limit = format_value(limit, factor, &units_symbol)
+        ));
+    }
+    if let Some(baseline) = alert.boundary.baseline {
+        let percent = delta_percent(value, baseline);
+        let plus = if percent > 0.0.into() { "+" } else { "" };
+        message.push_str(&format!(
+            " ({plus}{percent}% from the baseline of {baseline})",
+            percent = Units::format_float(percent.into()),
+            baseline = format_value(baseline, factor, &units_symbol),
+        ));
+    }
+    message
+}
+
+fn format_value(value: OrderedFloat<f64>, factor: OrderedFloat<f64>, units_symbol: &str) -> String {
+    let value = Units::format_float((value / factor).into());
+    if units_symbol.is_empty() {
+        value
+    } else {
+        format!("{value} {units_symbol}")
+    }
+}
+
+// The percent change of a value from its baseline
+fn delta_percent(value: OrderedFloat<f64>, baseline: OrderedFloat<f64>) -> OrderedFloat<f64> {
+    if value.is_normal() && baseline.is_normal() {
+        ((value - baseline) / baseline) * 100.0
+    } else {
+        0.0.into()
+    }
+}
+
+// How close a value is to its lower limit, as a percent
+fn lower_limit_percent(value: OrderedFloat<f64>, limit: OrderedFloat<f64>) -> OrderedFloat<f64> {
+    if value.is_normal() && limit.is_normal() {
+        (limit / value) * 100.0
+    } else {
+        0.0.into()
+    }
+}
+
+// How close a value is to its upper limit, as a percent
+fn upper_limit_percent(value: OrderedFloat<f64>, limit: OrderedFloat<f64>) -> OrderedFloat<f64> {
+    if value.is_normal() && limit.is_normal() {
+        (value / limit) * 100.0
+    } else {
+        0.0.into()
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct BoundaryLimits {
     min: OrderedFloat<f64>,
@@ -1048,3 +1204,186 @@ fn boundary_limits_map(
     }
     map
 }
+
+#[cfg(test)]
+pub mod test {
+    use bencher_json::JsonReport;
+    use serde_json::{Value, json};
+
+    use super::{ReportComment, SubAdapter};
+
+    pub const CONSOLE_URL: &str = "https://console.example.com";
+    pub const REPORT_UUID: &str = "00000001-0000-4000-8000-000000000000";
+    const PROJECT_UUID: &str = "00000002-0000-4000-8000-000000000000";
+    const CREATED: &str = "2024-01-01T00:00:00Z";
+    const START_TIME: &str = "2024-01-01T00:00:00Z";
+    const END_TIME: &str = "2024-01-01T00:01:00Z";
+
+    /// A fixture UUID that is unique for each kind of resource and index
+    pub fn uuid(kind: u32, index: usize) -> String {
+        format!("{kind:08x}-0000-4000-8000-{index:012x}")
+    }
+
+    /// A public report with a single iteration, where each benchmark has a latency of 150 ns.
+    /// The first `alerts` benchmarks have an active upper boundary alert.
+    pub fn json_report(benchmarks: &[&str], alerts: usize) -> JsonReport {
+        let results = benchmarks
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                json!({
+                    "iteration": 0,
+                    "benchmark": benchmark(index, name),
+                    "measures": [{
+                        "measure": measure(),
+                        "metric": metric(index),
+                        "threshold": {
+                            "uuid": uuid(10, 0),
+                            "project": PROJECT_UUID,
+                            "model": model(),
+                            "created": CREATED,
+                        },
+                        "boundary": boundary(),
+                    }],
+                })
+            })
+            .collect::<Vec<Value>>();
+        let alerts = benchmarks
+            .iter()
+            .take(alerts)
+            .enumerate()
+            .map(|(index, name)| alert(index, name))
+            .collect::<Vec<Value>>();
+
+        serde_json::from_value(json!({
+            "uuid": REPORT_UUID,
+            "project": {
+                "uuid": PROJECT_UUID,
+                "organization": uuid(12, 0),
+                "name": "Project",
+                "slug": "project",
+                "visibility": "public",
+                "created": CREATED,
+                "modified": CREATED,
+                "claimed": CREATED,
+            },
+            "branch": branch(),
+            "testbed": testbed(),
+            "start_time": START_TIME,
+            "end_time": END_TIME,
+            "adapter": "json",
+            "environment_change": false,
+            "hardware_change": null,
+            "results": [results],
+            "alerts": alerts,
+            "created": CREATED,
+        }))
+        .unwrap()
+    }
+
+    pub fn report_comment(json_report: JsonReport) -> ReportComment {
+        ReportComment::new(
+            CONSOLE_URL.parse().unwrap(),
+            json_report,
+            SubAdapter {
+                build_time: false,
+                file_size: false,
+                rusage: false,
+            },
+            "test".to_owned(),
+        )
+    }
+
+    fn branch() -> Value {
+        json!({
+            "uuid": uuid(3, 0),
+            "project": PROJECT_UUID,
+            "name": "main",
+            "slug": "main",
+            "head": { "uuid": uuid(4, 0), "created": CREATED },
+            "created": CREATED,
+            "modified": CREATED,
+        })
+    }
+
+    fn testbed() -> Value {
+        json!({
+            "uuid": uuid(5, 0),
+            "project": PROJECT_UUID,
+            "name": "localhost",
+            "slug": "localhost",
+            "created": CREATED,
+            "modified": CREATED,
+        })
+    }
+
+    fn measure() -> Value {
+        json!({
+            "uuid": uuid(6, 0),
+            "project": PROJECT_UUID,
+            "name": "Latency",
+            "slug": "latency",
+            "units": "nanoseconds (ns)",
+            "created": CREATED,
+            "modified": CREATED,
+        })
+    }
+
+    fn model() -> Value {
+        json!({
+            "uuid": uuid(7, 0),
+            "test": "t_test",
+            "upper_boundary": 0.99,
+            "created": CREATED,
+        })
+    }
+
+    fn benchmark(index: usize, name: &str) -> Value {
+        json!({
+            "uuid": uuid(8, index),
+            "project": PROJECT_UUID,
+            "name": name,
+            "slug": format!("benchmark-{index}"),
+            "created": CREATED,
+            "modified": CREATED,
+        })
+    }
+
+    fn metric(index: usize) -> Value {
+        json!({ "uuid": uuid(9, index), "value": 150.0 })
+    }
+
+    fn boundary() -> Value {
+        json!({
+            "baseline": 100.0,
+            "lower_limit": 80.0,
+            "upper_limit": 120.0,
+        })
+    }
+
+    fn alert(index: usize, name: &str) -> Value {
+        json!({
+            "uuid": uuid(11, index),
+            "report": REPORT_UUID,
+            "iteration": 0,
+            "benchmark": benchmark(index, name),
+            "metric": metric(index),
+            "threshold": {
+                "uuid": uuid(10, 0),
+                "project": PROJECT_UUID,
+                "branch": branch(),
+                "testbed": testbed(),
+                "measure": measure(),
+                "model": model(),
+                "created": CREATED,
+                "modified": CREATED,
+            },
+            "boundary": boundary(),
+            "limit": "upper",
+            "status": "active",
+            "environment_change": false,
+            "created": CREATED,
+            "modified": CREATED,
+        })
+    }
+}
@@ -0,0 +1,586 @@
+use std::collections::BTreeMap;
+
+use bencher_json::{
+    JsonAlert, Units,
+    project::{
+        boundary::BoundaryLimit,
+        report::{JsonReportIteration, JsonReportMeasure, JsonReportResult},
+    },
+};
+use ordered_float::OrderedFloat;
+
+use crate::{
+    BoundaryLimits, Measure, ReportComment, Resource, alert_status, alert_units,
+    boundary_limits_map, delta_percent, lower_limit_percent, upper_limit_percent,
+};
+
+const EMPTY_MARKDOWN_CELL: &str = " |";
+
+impl ReportComment {
+    /// Render the report as markdown, for forges and chat tools that do not render the GitHub flavored HTML well.
+    /// Tables are used for the alerts and benchmark results,
+    /// and the benchmark results are in a collapsible `<details>` section.
+    pub fn markdown(&self, require_threshold: bool) -> String {
+        self.markdown_inner(require_threshold, true, true)
+    }
+
+    /// Render the report as markdown for a forge comment, ending with the Bencher tag.
+    /// Leave out the benchmark results and then the alerts table
+    /// if the markdown would be longer than the maximum length.
+    pub fn markdown_with_max_length(
+        &self,
+        require_threshold: bool,
+        id: Option<&str>,
+        max_length: usize,
+    ) -> String {
+        // DO NOT MOVE: The Bencher tag must be the last thing in the markdown for updates to work
+        let bencher_tag = self.bencher_tag(id);
+        let max_length = max_length.saturating_sub(bencher_tag.len());
+        let mut markdown = self.markdown_inner(require_threshold, true, true);
+        if markdown.len() > max_length {
+            markdown = self.markdown_inner(require_threshold, false, true);
+        }
+        if markdown.len() > max_length {
+            markdown = self.markdown_inner(require_threshold, false, false);
+        }
+        markdown.push_str(&bencher_tag);
+        markdown
+    }
+
+    fn markdown_inner(
+        &self,
+        require_threshold: bool,
+        include_benchmarks: bool,
+        include_alerts: bool,
+    ) -> String {
+        let mut markdown = String::new();
+        let markdown_mut = &mut markdown;
+        self.markdown_header(markdown_mut);
+        self.markdown_report_table(markdown_mut);
+        self.markdown_no_benchmarks(markdown_mut);
+        self.markdown_no_threshold(markdown_mut, require_threshold);
+        self.markdown_environment_change(markdown_mut);
+        self.markdown_alerts(markdown_mut, include_alerts);
+        if include_benchmarks {
+            self.markdown_benchmark_details(markdown_mut, require_threshold);
+        }
+        self.markdown_footer(markdown_mut);
+        markdown
+    }
+
+    fn markdown_header(&self, markdown: &mut String) {
+        markdown.push_str(&format!(
+            "## 🐰 [Bencher Report]({url})\n\n",
+            url = self.resource_url(Resource::Report(self.json_report.uuid)),
+        ));
+    }
+
+    fn markdown_report_table(&self, markdown: &mut String) {
+        markdown.push_str("| Branch | Testbed |\n");
+        markdown.push_str("| --- | --- |\n");
+        markdown.push_str(&format!(
+            "| [{branch}]({branch_url}) | [{testbed}]({testbed_url}) |\n\n",
+            branch = markdown_escape(self.json_report.branch.name.as_ref()),
+            branch_url = self.resource_url(Resource::Branch(self.json_report.branch.slug.clone())),
+            testbed = markdown_escape(self.json_report.testbed.name.as_ref()),
+            testbed_url =
+                self.resource_url(Resource::Testbed(self.json_report.testbed.slug.clone())),
+        ));
+    }
+
+    fn markdown_no_benchmarks(&self, markdown: &mut String) {
+        if self.benchmark_count == 0 {
+            markdown.push_str("> ⚠️ **WARNING:** No benchmarks found!\n\n");
+        }
+    }
+
+    fn markdown_no_threshold(&self, markdown: &mut String, require_threshold: bool) {
+        if self.benchmark_count == 0 || self.missing_threshold.is_empty() || require_threshold {
+            return;
+        }
+
+        markdown.push_str("> ⚠️ **WARNING:** No Threshold found!\n>\n");
+        markdown.push_str("> Without a Threshold, no Alerts will ever be generated.\n>\n");
+        for Measure { name, slug, units } in &self.missing_threshold {
+            let url = self.resource_url(Resource::Measure(slug.clone()));
+            markdown.push_str(&format!(
+                "> - [{name} ({units})]({url})\n",
+                name = markdown_escape(name.as_ref()),
+                units = markdown_escape(units.as_ref()),
+            ));
+        }
+        markdown.push_str(">\n");
+        markdown.push_str(&format!(
+            "> [Click here to create a new Threshold]({console_url}console/projects/{project}/thresholds/add{utm})\n",
+            console_url = self.console_url,
+            project = self.project_slug,
+            utm = self.utm_query(),
+        ));
+        markdown.push_str(&format!(
+            "> For more information, see [the Threshold documentation](https://bencher.dev/docs/explanation/thresholds/{utm}).\n",
+            utm = self.utm_query(),
+        ));
+        markdown.push_str(&format!(
+            "> To only post results if a Threshold exists, set [the `--ci-only-thresholds` flag](https://bencher.dev/docs/explanation/bencher-run/{utm}#--ci-only-thresholds).\n\n",
+            utm = self.utm_query(),
+        ));
+    }
+
+    fn markdown_environment_change(&self, markdown: &mut String) {
+        let Some(note) = self.hardware_change_note() else {
+            return;
+        };
+
+        markdown.push_str("> ⚠️ **WARNING:** Testbed hardware changed!\n>\n");
+        markdown.push_str(&format!(
+            "> The hardware for the [{testbed}]({url}) Testbed has changed since its previous report.\n",
+            testbed = markdown_escape(self.json_report.testbed.name.as_ref()),
+            url = self.resource_url(Resource::Testbed(self.json_report.testbed.slug.clone())),
+        ));
+        markdown.push_str(&format!("> {note}\n\n"));
+    }
+
+    fn markdown_alerts(&self, markdown: &mut String, include_alerts: bool) {
+        if self.json_report.alerts.is_empty() {
+            return;
+        }
+        let alerts_len = self.json_report.alerts.len();
+        markdown.push_str(&format!(
+            "### 🚨 {alerts_len} {alert}\n\n",
+            alert = if alerts_len == 1 { "Alert" } else { "Alerts" },
+        ));
+        if include_alerts {
+            self.markdown_alerts_table(markdown);
+        }
+    }
+
+    fn markdown_alerts_table(&self, markdown: &mut String) {
+        let has_lower = self.has_lower_boundary_alert();
+        let has_upper = self.has_upper_boundary_alert();
+
+        markdown.push('|');
+        if self.multiple_iterations {
+            markdown.push_str(" Iteration |");
+        }
+        markdown.push_str(" Benchmark | Measure (Units) | View | Benchmark Result (Result Δ%) |");
+        if has_lower {
+            markdown.push_str(" Lower Boundary (Limit %) |");
+        }
+        if has_upper {
+            markdown.push_str(" Upper Boundary (Limit %) |");
+        }
+        markdown.push('\n');
+        markdown.push('|');
+        let columns = 4
+            + usize::from(self.multiple_iterations)
+            + usize::from(has_lower)
+            + usize::from(has_upper);
+        markdown.push_str(&" --- |".repeat(columns));
+        markdown.push('\n');
+
+        for alert in &self.json_report.alerts {
+            let units = alert_units(alert);
+            let factor = units.scale_factor();
+            let units_symbol = units.scale_units_symbol();
+
+            markdown.push('|');
+            if self.multiple_iterations {
+                markdown.push_str(&format!(" {} |", alert.iteration));
+            }
+            markdown.push_str(&format!(
+                " [{benchmark}]({url}) |",
+                benchmark = markdown_escape(alert.benchmark.name.as_ref()),
+                url = self.resource_url(Resource::Benchmark(alert.benchmark.slug.clone())),
+            ));
+            markdown.push_str(&format!(
+                " [{measure} ({units})]({url}) |",
+                measure = markdown_escape(alert.threshold.measure.name.as_ref()),
+                units = markdown_escape(&units.scale_units()),
+                url = self.resource_url(Resource::Measure(alert.threshold.measure.slug.clone())),
+            ));
+            self.markdown_alerts_table_view_cell(markdown, alert);
+            value_markdown_cell(
+                markdown,
+                alert.metric.value,
+                alert.boundary.baseline,
+                factor,
+                &units_symbol,
+                true,
+            );
+            if has_lower {
+                limit_markdown_cell(
+                    markdown,
+                    alert.metric.value,
+                    alert.boundary.lower_limit,
+                    BoundaryLimit::Lower,
+                    factor,
+                    &units_symbol,
+                    alert.limit == BoundaryLimit::Lower,
+                );
+            }
+            if has_upper {
+                limit_markdown_cell(
+                    markdown,
+                    alert.metric.value,
+                    alert.boundary.upper_limit,
+                    BoundaryLimit::Upper,
+                    factor,
+                    &units_symbol,
+                    alert.limit == BoundaryLimit::Upper,
+                );
+            }
+            markdown.push('\n');
+        }
+        markdown.push('\n');
+    }
+
+    fn markdown_alerts_table_view_cell(&self, markdown: &mut String, alert: &JsonAlert) {
+        markdown.push_str(&format!(
+            " 📈 [plot]({plot_url}) 🚷 [threshold]({threshold_url}) 🚨 [alert ({status})]({alert_url})",
+            plot_url = self.alert_perf_url(alert),
+            threshold_url = self.resource_url(Resource::Threshold(alert.threshold.uuid)),
+            status = alert_status(alert),
+            alert_url = self.resource_url(Resource::Alert(alert.uuid)),
+        ));
+        if alert.environment_change {
+            markdown.push_str(" 🖥️ environment change");
+        }
+        if let Some(change_point) = alert.boundary.change_point {
+            markdown.push_str(&format!(
+                " 🔀 [change point]({url})",
+                url = self.resource_url(Resource::Metric(change_point)),
+            ));
+        }
+        markdown.push_str(" |");
+    }
+
+    fn markdown_benchmark_details(&self, markdown: &mut String, require_threshold: bool) {
+        if self.benchmark_count == 0 {
+            return;
+        }
+
+        // The blank lines are needed for the markdown inside of the HTML to be rendered
+        markdown.push_str("<details><summary>Click to view all benchmark results</summary>\n\n");
+        for (i, iteration) in self.json_report.results.iter().enumerate() {
+            if self.multiple_iterations {
+                markdown.push_str(&format!("#### Iteration {i}\n\n"));
+            }
+            self.markdown_iteration_table(markdown, iteration, require_threshold);
+        }
+        markdown.push_str("</details>\n\n");
+    }
+
+    fn markdown_iteration_table(
+        &self,
+        markdown: &mut String,
+        iteration: &JsonReportIteration,
+        require_threshold: bool,
+    ) {
+        let mbl = boundary_limits_map(iteration, require_threshold);
+        self.markdown_iteration_table_header(markdown, &mbl);
+        self.markdown_iteration_table_body(markdown, iteration, &mbl);
+        markdown.push('\n');
+    }
+
+    fn markdown_iteration_table_header(
+        &self,
+        markdown: &mut String,
+        mbl: &BTreeMap<Measure, BoundaryLimits>,
+    ) {
+        let mut columns = 1;
+        markdown.push_str("| Benchmark |");
+        for (measure, boundary_limits) in mbl {
+            let units = Units::new(boundary_limits.min.into(), measure.units.clone()).scale_units();
+
+            markdown.push_str(&format!(
+                " [{name}]({url}) |",
+                name = markdown_escape(measure.name.as_ref()),
+                url = self.resource_url(Resource::Measure(measure.slug.clone())),
+            ));
+            let units = markdown_escape(&units);
+            if boundary_limits.has_limit() {
+                markdown.push_str(&format!(" Benchmark Result {units} (Result Δ%) |"));
+            } else {
+                markdown.push_str(&format!(" {units} |"));
+            }
+            columns += 2;
+
+            if boundary_limits.lower {
+                markdown.push_str(&format!(" Lower Boundary {units} (Limit %) |"));
+                columns += 1;
+            }
+            if boundary_limits.upper {
+                markdown.push_str(&format!(" Upper Boundary {units} (Limit %) |"));
+                columns += 1;
+            }
+        }
+        markdown.push('\n');
+        markdown.push('|');
+        markdown.push_str(&" --- |".repeat(columns));
+        markdown.push('\n');
+    }
+
+    fn markdown_iteration_table_body(
+        &self,
+        markdown: &mut String,
+        iteration: &JsonReportIteration,
+        mbl: &BTreeMap<Measure, BoundaryLimits>,
+    ) {
+        for result in iteration {
+            markdown.push_str(&format!(
+                "| [{name}]({url}) |",
+                name = markdown_escape(result.benchmark.name.as_ref()),
+                url = self.resource_url(Resource::Benchmark(result.benchmark.slug.clone())),
+            ));
+            for (measure, boundary_limits) in mbl {
+                let (factor, units_symbol) = {
+                    let units = Units::new(boundary_limits.min.into(), measure.units.clone());
+                    (units.scale_factor(), units.scale_units_symbol())
+                };
+
+                let report_measure = result
+                    .measures
+                    .iter()
+                    .find(|m| m.measure.slug == measure.slug);
+                let alert = self.find_alert(result, measure);
+
+                let Some(report_measure) = report_measure else {
+                    let columns =
+                        2 + usize::from(boundary_limits.lower) + usize::from(boundary_limits.upper);
+                    markdown.push_str(&EMPTY_MARKDOWN_CELL.repeat(columns));
+                    continue;
+                };
+                self.markdown_iteration_table_view_cell(
+                    markdown,
+                    result,
+                    report_measure,
+                    *boundary_limits,
+                    alert,
+                );
+                value_markdown_cell(
+                    markdown,
+                    report_measure.metric.value,
+                    report_measure.boundary.and_then(|b| b.baseline),
+                    factor,
+                    &units_symbol,
+                    alert.is_some(),
+                );
+                if boundary_limits.lower {
+                    limit_markdown_cell(
+                        markdown,
+                        report_measure.metric.value,
+                        report_measure.boundary.and_then(|b| b.lower_limit),
+                        BoundaryLimit::Lower,
+                        factor,
+                        &units_symbol,
+                        alert.is_some_and(|a| a.limit == BoundaryLimit::Lower),
+                    );
+                }
+                if boundary_limits.upper {
+                    limit_markdown_cell(
+                        markdown,
+                        report_measure.metric.value,
+                        report_measure.boundary.and_then(|b| b.upper_limit),
+                        BoundaryLimit::Upper,
+                        factor,
+                        &units_symbol,
+                        alert.is_some_and(|a| a.limit == BoundaryLimit::Upper),
+                    );
+                }
+            }
+            markdown.push('\n');
+        }
+    }
+
+    fn markdown_iteration_table_view_cell(
+        &self,
+        markdown: &mut String,
+        result: &JsonReportResult,
+        report_measure: &JsonReportMeasure,
+        boundary_limits: BoundaryLimits,
+        alert: Option<&JsonAlert>,
+    ) {
+        markdown.push_str(&format!(
+            " 📈 [view plot]({url})",
+            url = self.perf_url(
+                &result.benchmark,
+                &report_measure.measure,
+                Some(boundary_limits)
+            )
+        ));
+        if let Some(threshold) = &report_measure.threshold {
+            markdown.push_str(&format!(
+                " 🚷 [view threshold]({url})",
+                url = self.resource_url(Resource::Threshold(threshold.uuid)),
+            ));
+        } else {
+            markdown.push_str(" ⚠️ NO THRESHOLD");
+        }
+        if let Some(alert) = alert {
+            markdown.push_str(&format!(
+                " 🚨 [view alert ({status})]({url})",
+                url = self.resource_url(Resource::Alert(alert.uuid)),
+                status = alert_status(alert),
+            ));
+        }
+        markdown.push_str(" |");
+    }
+
+    fn markdown_footer(&self, markdown: &mut String) {
+        markdown.push_str(&format!(
+            "[🐰 View full continuous benchmarking report in Bencher]({url})\n",
+            url = self.resource_url(Resource::Report(self.json_report.uuid)),
+        ));
+    }
+}
+
+fn value_markdown_cell(
+    markdown: &mut String,
+    value: OrderedFloat<f64>,
+    baseline: Option<OrderedFloat<f64>>,
+    factor: OrderedFloat<f64>,
+    units_symbol: &str,
+    bold: bool,
+) {
+    let mut cell = Units::format_float((value / factor).into());
+    if !units_symbol.is_empty() {
+        cell.push_str(&format!(" {units_symbol}"));
+    }
+    if bold {
+        cell = format!("**{cell}**");
+    }
+    if let Some(baseline) = baseline {
+        let percent = delta_percent(value, baseline);
+        let plus = if percent > 0.0.into() { "+" } else { "" };
+        cell.push_str(&format!(
+            " ({plus}{percent}%)",
+            percent = Units::format_float(percent.into())
+        ));
+    }
+    markdown.push_str(&format!(" {cell} |"));
+}
+
+fn limit_markdown_cell(
+    markdown: &mut String,
+    value: OrderedFloat<f64>,
+    limit: Option<OrderedFloat<f64>>,
+    boundary_limit: BoundaryLimit,
+    factor: OrderedFloat<f64>,
+    units_symbol: &str,
+    bold: bool,
+) {
+    let Some(limit) = limit else {
+        markdown.push_str(EMPTY_MARKDOWN_CELL);
+        return;
+    };
+    let percent = match boundary_limit {
+        BoundaryLimit::Lower => lower_limit_percent(value, limit),
+        BoundaryLimit::Upper => upper_limit_percent(value, limit),
+    };
+
+    let mut cell = Units::format_float((limit / factor).into());
+    if !units_symbol.is_empty() {
+        cell.push_str(&format!(" {units_symbol}"));
+    }
+    if bold {
+        cell = format!("**{cell}**");
+    }
+    cell.push_str(&format!(
+        " ({percent}%)",
+        percent = Units::format_float(percent.into())
+    ));
+    markdown.push_str(&format!(" {cell} |"));
+}
+
+// Escape the characters that would break a markdown table or link
+fn markdown_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '|' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '#'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::markdown_escape;
+    use crate::test::{json_report, report_comment};
+
+    const ESCAPED_BENCHMARK: &str = r"bench\|\`vec\`\<u8\>";
+
+    fn assert_details_closed(markdown: &str) {
+        assert_eq!(
+            markdown.matches("<details>").count(),
+            markdown.matches("</details>").count(),
+            "{markdown}"
+        );
+    }
+
+    #[test]
+    fn test_markdown_escape() {
+        assert_eq!(markdown_escape("bench_fast"), r"bench\_fast");
+        assert_eq!(markdown_escape("a|b"), r"a\|b");
+        assert_eq!(markdown_escape("`code`"), r"\`code\`");
+        assert_eq!(markdown_escape("Vec<u8>"), r"Vec\<u8\>");
+        assert_eq!(markdown_escape(r"[link](url) \ #"), r"\[link\](url) \\ \#");
+        assert_eq!(markdown_escape("plain text"), "plain text");
+    }
+
+    #[test]
+    fn test_markdown_benchmark_name() {
+        let comment = report_comment(json_report(&["bench|`vec`<u8>"], 1));
+        let markdown = comment.markdown(false);
+        // Once in the alerts table and once in the benchmark results table
+        assert_eq!(markdown.matches(ESCAPED_BENCHMARK).count(), 2, "{markdown}");
+        assert!(!markdown.contains("bench|"), "{markdown}");
+        assert!(markdown.contains("### 🚨 1 Alert\n\n"), "{markdown}");
+        assert_details_closed(&markdown);
+    }
+
+    #[test]
+    fn test_markdown_with_max_length() {
+        let comment = report_comment(json_report(&["bench_1", "bench_2", "bench_3"], 2));
+        let bencher_tag = comment.bencher_tag(None);
+        let full = comment.markdown_inner(false, true, true);
+        let without_benchmarks = comment.markdown_inner(false, false, true);
+        let without_alerts = comment.markdown_inner(false, false, false);
+        assert!(full.len() > without_benchmarks.len());
+        assert!(without_benchmarks.len() > without_alerts.len());
+
+        // Everything fits
+        let markdown = comment.markdown_with_max_length(false, None, usize::MAX);
+        assert_eq!(markdown, format!("{full}{bencher_tag}"));
+        assert!(markdown.contains("<details>"), "{markdown}");
+        assert_details_closed(&markdown);
+
+        // The benchmark results are left out, along with their whole collapsible section
+        let max_length = without_benchmarks.len() + bencher_tag.len();
+        for max_length in [full.len() + bencher_tag.len() - 1, max_length] {
+            let markdown = comment.markdown_with_max_length(false, None, max_length);
+            assert_eq!(markdown, format!("{without_benchmarks}{bencher_tag}"));
+            assert!(markdown.len() <= max_length);
+            assert!(!markdown.contains("<details>"), "{markdown}");
+            assert!(markdown.contains("| Benchmark |"), "{markdown}");
+            assert_details_closed(&markdown);
+        }
+
+        // The alerts table is left out, but the alert count is kept
+        let markdown = comment.markdown_with_max_length(false, None, max_length - 1);
+        assert_eq!(markdown, format!("{without_alerts}{bencher_tag}"));
+        assert!(markdown.len() < max_length);
+        assert!(markdown.contains("### 🚨 2 Alerts\n\n"), "{markdown}");
+        assert!(!markdown.contains("| Benchmark |"), "{markdown}");
+        assert_details_closed(&markdown);
+
+        // The Bencher tag is always last
+        assert!(markdown.ends_with(&bencher_tag), "{markdown}");
+    }
+}
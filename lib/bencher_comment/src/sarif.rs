@@ -0,0 +1,244 @@
+use bencher_json::project::{
+    alert::AlertStatus,
+    boundary::BoundaryLimit,
+    report::{JsonReportMeasure, JsonReportResult},
+};
+use serde_json::{Value, json};
+
+use crate::{ReportComment, Resource, alert_message};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const LOWER_BOUNDARY_RULE: &str = "bencher/lower-boundary";
+const UPPER_BOUNDARY_RULE: &str = "bencher/upper-boundary";
+
+impl ReportComment {
+    /// Render the report as a SARIF log.
+    /// Each benchmark and measure is a result,
+    /// which is a failure if it has an active alert and a pass otherwise.
+    pub fn sarif(&self) -> Result<String, serde_json::Error> {
+        let results = self
+            .json_report
+            .results
+            .iter()
+            .flat_map(|iteration| {
+                iteration.iter().flat_map(|result| {
+                    result
+                        .measures
+                        .iter()
+                        .map(move |report_measure| self.sarif_result(result, report_measure))
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let sarif = json!({
+            "$schema": SARIF_SCHEMA,
+            "version": SARIF_VERSION,
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "Bencher",
+                        "informationUri": "https://bencher.dev",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": [
+                            sarif_rule(LOWER_BOUNDARY_RULE, "LowerBoundary", "The benchmark result is below the lower boundary of its Threshold."),
+                            sarif_rule(UPPER_BOUNDARY_RULE, "UpperBoundary", "The benchmark result is above the upper boundary of its Threshold."),
+                        ],
+                    },
+                },
+                "automationDetails": {
+                    "id": format!(
+                        "bencher/{project}/{branch}/{testbed}/{report}",
+                        project = self.json_report.project.slug,
+                        branch = self.json_report.branch.slug,
+                        testbed = self.json_report.testbed.slug,
+                        report = self.json_report.uuid,
+                    ),
+                },
+                "properties": {
+                    "report": self.resource_url(Resource::Report(self.json_report.uuid)).as_str(),
+                },
+                "results": results,
+            }],
+        });
+        serde_json::to_string_pretty(&sarif)
+    }
+
+    fn sarif_result(&self, result: &JsonReportResult, report_measure: &JsonReportMeasure) -> Value {
+        let name = format!(
+            "{benchmark} ({measure})",
+            benchmark = result.benchmark.name,
+            measure = report_measure.measure.name,
+        );
+        let locations = json!([{
+            "logicalLocations": [{
+                "name": name,
+                "fullyQualifiedName": format!(
+                    "{benchmark}/{measure}",
+                    benchmark = result.benchmark.slug,
+                    measure = report_measure.measure.slug,
+                ),
+                "kind": "function",
+            }],
+        }]);
+        let fingerprint = format!(
+            "{benchmark}:{measure}",
+            benchmark = result.benchmark.uuid,
+            measure = report_measure.measure.uuid,
+        );
+        let alert = self
+            .find_iteration_alert(result, report_measure)
+            .filter(|alert| matches!(alert.status, AlertStatus::Active));
+        let Some(alert) = alert else {
+            return json!({
+                "kind": "pass",
+                "level": "none",
+                "message": { "text": format!("{name}: no alert") },
+                "locations": locations,
+                "partialFingerprints": { "bencherMeasure/v1": fingerprint },
+                "properties": {
+                    "iteration": result.iteration,
+                    "benchmark": result.benchmark.uuid,
+                    "measure": report_measure.measure.uuid,
+                    "value": report_measure.metric.value,
+                    "plot": self.perf_url(&result.benchmark, &report_measure.measure, None).as_str(),
+                },
+            });
+        };
+
+        json!({
+            "ruleId": match alert.limit {
+                BoundaryLimit::Lower => LOWER_BOUNDARY_RULE,
+                BoundaryLimit::Upper => UPPER_BOUNDARY_RULE,
+            },
+            "kind": "fail",
+            "level": "error",
+            "message": { "text": alert_message(alert) },
+            "locations": locations,
+            "partialFingerprints": { "bencherMeasure/v1": fingerprint },
+            "properties": {
+                "iteration": result.iteration,
+                "benchmark": result.benchmark.uuid,
+                "measure": report_measure.measure.uuid,
+                "value": report_measure.metric.value,
+                "baseline": alert.boundary.baseline,
+                "limit": match alert.limit {
+                    BoundaryLimit::Lower => alert.boundary.lower_limit,
+                    BoundaryLimit::Upper => alert.boundary.upper_limit,
+                },
+                "plot": self.alert_perf_url(alert).as_str(),
+                "alert": self.resource_url(Resource::Alert(alert.uuid)).as_str(),
+            },
+        })
+    }
+}
+
+fn sarif_rule(id: &str, name: &str, description: &str) -> Value {
+    json!({
+        "id": id,
+        "name": name,
+        "shortDescription": { "text": description },
+        "helpUri": "https://bencher.dev/docs/explanation/thresholds/",
+        "defaultConfiguration": { "level": "error" },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::project::boundary::BoundaryLimit;
+    use pretty_assertions::assert_eq;
+    use serde_json::Value;
+
+    use super::{LOWER_BOUNDARY_RULE, UPPER_BOUNDARY_RULE};
+    use crate::test::{json_report, report_comment, uuid};
+
+    fn sarif_results(sarif: &str) -> Vec<Value> {
+        let sarif = serde_json::from_str::<Value>(sarif).unwrap();
+        assert_eq!(sarif.get("version").and_then(Value::as_str), Some("2.1.0"));
+        sarif
+            .pointer("/runs/0/results")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap()
+    }
+
+    fn field<'a>(result: &'a Value, pointer: &str) -> Option<&'a str> {
+        result.pointer(pointer).and_then(Value::as_str)
+    }
+
+    #[test]
+    fn test_sarif() {
+        let mut json_report = json_report(&["bench_1", "bench_2", "bench_3"], 2);
+        if let Some(alert) = json_report.alerts.first_mut() {
+            alert.limit = BoundaryLimit::Lower;
+        }
+        let sarif = report_comment(json_report).sarif().unwrap();
+        let results = sarif_results(&sarif);
+
+        // One result for each benchmark and measure
+        assert_eq!(results.len(), 3, "{sarif}");
+        let results = results
+            .iter()
+            .map(|result| {
+                (
+                    field(result, "/locations/0/logicalLocations/0/name"),
+                    field(result, "/ruleId"),
+                    field(result, "/kind"),
+                    field(result, "/level"),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            results,
+            [
+                (
+                    Some("bench_1 (Latency)"),
+                    Some(LOWER_BOUNDARY_RULE),
+                    Some("fail"),
+                    Some("error")
+                ),
+                (
+                    Some("bench_2 (Latency)"),
+                    Some(UPPER_BOUNDARY_RULE),
+                    Some("fail"),
+                    Some("error")
+                ),
+                (Some("bench_3 (Latency)"), None, Some("pass"), Some("none")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sarif_result() {
+        let sarif = report_comment(json_report(&["bench_1"], 1))
+            .sarif()
+            .unwrap();
+        let results = sarif_results(&sarif);
+        let result = results.first().unwrap();
+
+        assert_eq!(
+            field(result, "/message/text"),
+            Some(
+                "bench_1 (Latency): 150.00 ns is above the upper boundary limit of 120.00 ns (+50.00% from the baseline of 100.00 ns)"
+            )
+        );
+        assert_eq!(
+            field(result, "/locations/0/logicalLocations/0/fullyQualifiedName"),
+            Some("benchmark-0/latency")
+        );
+        assert_eq!(
+            field(result, "/partialFingerprints/bencherMeasure~1v1"),
+            Some(format!("{}:{}", uuid(8, 0), uuid(6, 0)).as_str())
+        );
+        assert_eq!(
+            field(result, "/properties/alert"),
+            Some(
+                format!(
+                    "https://console.example.com/perf/project/alerts/{}",
+                    uuid(11, 0)
+                )
+                .as_str()
+            )
+        );
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use bencher_endpoint::Registrar;
 #[cfg(feature = "plus")]
-use bencher_json::system::config::{JsonLitestream, JsonPlus};
+use bencher_json::system::config::JsonPlus;
 use bencher_json::{
     JsonConfig,
     system::config::{
@@ -11,13 +11,14 @@ use bencher_json::{
     },
 };
 use bencher_rbac::init_rbac;
-use bencher_schema::context::{ApiContext, Database, DbConnection};
 #[cfg(feature = "plus")]
 use bencher_schema::{context::RateLimiting, model::server::QueryServer};
+use bencher_schema::{
+    context::{ApiContext, Database, DbConnection, DbPragmas, Messenger},
+    model::user::notification::QueryNotification,
+};
 use bencher_token::TokenKey;
 use diesel::Connection as _;
-#[cfg(feature = "plus")]
-use diesel::connection::SimpleConnection as _;
 use dropshot::{
     ApiDescription, ConfigDropshot, ConfigLogging, ConfigLoggingIfExists, ConfigLoggingLevel,
     ConfigTls, HttpServer,
@@ -52,6 +53,10 @@ pub enum ConfigTxError {
     DatabaseConnection(String, diesel::ConnectionError),
     #[error("Failed to parse data store: {0}")]
     DataStore(bencher_schema::context::DataStoreError),
+    #[error("Scheduled database backups require a data store")]
+    BackupDataStore,
+    #[error("Scheduled database backup interval must be greater than zero")]
+    BackupInterval,
     #[error("Failed to register endpoint: {0}")]
     Register(dropshot::ApiDescriptionRegisterError),
     #[error("Failed to create server: {0}")]
@@ -183,10 +188,21 @@ async fn into_context(
         .map_err(|e| ConfigTxError::DatabaseConnection(database_path.to_string(), e))?;
 
     #[cfg(feature = "plus")]
-    if let Some(litestream) = plus.as_ref().and_then(|plus| plus.litestream.as_ref()) {
-        info!(&log, "Configuring Litestream");
-        run_litestream(&mut database_connection, litestream)?;
-    }
+    let litestream_busy_timeout =
+        plus.as_ref()
+            .and_then(|plus| plus.litestream.as_ref())
+            .map(|litestream| {
+                info!(&log, "Configuring Litestream");
+                litestream.busy_timeout.unwrap_or(DEFAULT_BUSY_TIMEOUT)
+            });
+    #[cfg(not(feature = "plus"))]
+    let litestream_busy_timeout = None;
+    let pragmas = DbPragmas {
+        litestream_busy_timeout,
+    };
+    pragmas
+        .apply(&mut database_connection)
+        .map_err(ConfigTxError::Pragma)?;
 
     info!(&log, "Running database migrations");
     bencher_schema::run_migrations(&mut database_connection)?;
@@ -196,11 +212,24 @@ async fn into_context(
     } else {
         None
     };
+    if let Some(interval) = json_database
+        .backup
+        .as_ref()
+        .and_then(|backup| backup.interval)
+    {
+        if interval == 0 {
+            return Err(ConfigTxError::BackupInterval);
+        } else if data_store.is_none() {
+            return Err(ConfigTxError::BackupDataStore);
+        }
+    }
 
     let database = Database {
         path: json_database.file,
         connection: Arc::new(tokio::sync::Mutex::new(database_connection)),
+        pragmas,
         data_store,
+        backup: json_database.backup,
     };
 
     info!(&log, "Loading secret key");
@@ -208,6 +237,7 @@ async fn into_context(
         security.issuer.unwrap_or_else(|| console_url.to_string()),
         &security.secret_key,
     );
+    let allow_private_webhooks = security.allow_private_webhooks.unwrap_or_default();
 
     #[cfg(feature = "plus")]
     let rate_limiting = plus.as_ref().and_then(|plus| plus.rate_limiting);
@@ -216,6 +246,7 @@ async fn into_context(
     #[cfg(feature = "plus")]
     let Plus {
         github,
+        oidc,
         stats,
         biller,
         licensor,
@@ -237,19 +268,25 @@ async fn into_context(
     .map_err(Box::new)
     .map_err(ConfigTxError::RateLimiting)?;
 
+    let messenger = Messenger::from(smtp);
+    spawn_background_tasks(log, &database, &messenger, &console_url, &restart_tx);
+
     debug!(&log, "Creating API context");
     Ok(ApiContext {
         console_url,
         token_key,
         rbac,
-        messenger: smtp.into(),
+        messenger,
         database,
         restart_tx,
+        allow_private_webhooks,
         #[cfg(feature = "plus")]
         rate_limiting,
         #[cfg(feature = "plus")]
         github,
         #[cfg(feature = "plus")]
+        oidc,
+        #[cfg(feature = "plus")]
         stats,
         #[cfg(feature = "plus")]
         biller,
@@ -262,6 +299,28 @@ async fn into_context(
     })
 }
 
+// These tasks run until the server is restarted
+fn spawn_background_tasks(
+    log: &Logger,
+    database: &Database,
+    messenger: &Messenger,
+    console_url: &url::Url,
+    restart_tx: &Sender<()>,
+) {
+    if let Some(interval) = database.backup.as_ref().and_then(|backup| backup.interval) {
+        info!(&log, "Scheduling database backups every {interval} seconds");
+        database.spawn_backups(log.clone(), restart_tx.clone());
+    }
+
+    QueryNotification::spawn_digests(
+        log.clone(),
+        database.connection.clone(),
+        messenger.clone(),
+        console_url.clone(),
+        restart_tx.clone(),
+    );
+}
+
 // Set the diesel `DATABASE_URL` env var to the database path
 fn diesel_database_url(log: &Logger, database_path: &str) {
     if let Ok(database_url) = std::env::var(DATABASE_URL) {
@@ -284,43 +343,6 @@ fn diesel_database_url(log: &Logger, database_path: &str) {
     }
 }
 
-#[cfg(feature = "plus")]
-fn run_litestream(
-    database: &mut DbConnection,
-    litestream: &JsonLitestream,
-) -> Result<(), ConfigTxError> {
-    // Enable WAL mode
-    // https://litestream.io/tips/#wal-journal-mode
-    // https://sqlite.org/wal.html
-    database
-        .batch_execute("PRAGMA journal_mode = WAL")
-        .map_err(ConfigTxError::Pragma)?;
-    // Disable auto-checkpoints
-    // https://litestream.io/tips/#disable-autocheckpoints-for-high-write-load-servers
-    // https://sqlite.org/wal.html#automatic_checkpoint
-    database
-        .batch_execute("PRAGMA wal_autocheckpoint = 0")
-        .map_err(ConfigTxError::Pragma)?;
-    // Enable busy timeout
-    // https://litestream.io/tips/#busy-timeout
-    // https://www.sqlite.org/pragma.html#pragma_busy_timeout
-    let busy_timeout = format!(
-        "PRAGMA busy_timeout = {}",
-        litestream.busy_timeout.unwrap_or(DEFAULT_BUSY_TIMEOUT)
-    );
-    database
-        .batch_execute(&busy_timeout)
-        .map_err(ConfigTxError::Pragma)?;
-    // Relax synchronous mode because we are using WAL mode
-    // https://litestream.io/tips/#synchronous-pragma
-    // https://www.sqlite.org/pragma.html#pragma_synchronous
-    database
-        .batch_execute("PRAGMA synchronous = NORMAL")
-        .map_err(ConfigTxError::Pragma)?;
-
-    Ok(())
-}
-
 #[expect(clippy::needless_pass_by_value)]
 fn into_config_dropshot(server: JsonServer) -> ConfigDropshot {
     let JsonServer {
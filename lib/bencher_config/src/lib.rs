@@ -211,6 +211,7 @@ impl Default for Config {
             security: JsonSecurity {
                 issuer: Some(DEFAULT_CONSOLE_URL.to_string()),
                 secret_key: DEFAULT_SECRET_KEY.clone(),
+                allow_private_webhooks: None,
             },
             server: JsonServer {
                 bind_address: *DEFAULT_BIND_ADDRESS,
@@ -220,6 +221,7 @@ impl Default for Config {
             database: JsonDatabase {
                 file: DEFAULT_DB_PATH.into(),
                 data_store: None,
+                backup: None,
             },
             smtp: None,
             logging: JsonLogging {
@@ -7,12 +7,14 @@ use bencher_json::{
     system::config::{JsonCloud, JsonPlus},
 };
 use bencher_license::Licensor;
+use bencher_oidc::Oidc;
 use bencher_schema::context::{Indexer, StatsSettings};
 use tokio::runtime::Handle;
 use url::Url;
 
 pub struct Plus {
     pub github: Option<GitHub>,
+    pub oidc: Option<Oidc>,
     pub indexer: Option<Indexer>,
     pub stats: StatsSettings,
     pub biller: Option<Biller>,
@@ -25,6 +27,8 @@ pub enum PlusError {
     LicenseSelfHosted(bencher_license::LicenseError),
     #[error("Failed to handle Bencher Cloud licensing: {0}")]
     LicenseCloud(bencher_license::LicenseError),
+    #[error("Invalid OpenID Connect issuer URL: {0}")]
+    OidcIssuer(bencher_json::ValidError),
     #[error("Tried to init Bencher Cloud for other Console URL: {0}")]
     BencherCloud(Url),
     #[error("Failed to setup billing: {0}")]
@@ -38,6 +42,7 @@ impl Plus {
         let Some(plus) = plus else {
             return Ok(Self {
                 github: None,
+                oidc: None,
                 indexer: None,
                 stats: StatsSettings::default(),
                 biller: None,
@@ -49,6 +54,25 @@ impl Plus {
             .github
             .map(|github| GitHub::new(github.client_id, github.client_secret));
 
+        let oidc = plus
+            .oidc
+            .map(|oidc| {
+                Url::try_from(oidc.issuer)
+                    .map(|issuer| {
+                        Oidc::new(
+                            issuer,
+                            oidc.client_id,
+                            oidc.client_secret,
+                            oidc.scopes,
+                            oidc.email_claim,
+                            oidc.name_claim,
+                            oidc.trust_unverified_email.unwrap_or_default(),
+                        )
+                    })
+                    .map_err(PlusError::OidcIssuer)
+            })
+            .transpose()?;
+
         let stats = plus.stats.map(Into::into).unwrap_or_default();
 
         let Some(JsonCloud {
@@ -60,6 +84,7 @@ impl Plus {
         else {
             return Ok(Self {
                 github,
+                oidc,
                 indexer: None,
                 stats,
                 biller: None,
@@ -84,6 +109,7 @@ impl Plus {
 
         Ok(Self {
             github,
+            oidc,
             indexer,
             stats,
             biller,
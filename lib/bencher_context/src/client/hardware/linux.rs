@@ -0,0 +1,234 @@
+use std::{collections::HashSet, fs};
+
+const CPUINFO: &str = "/proc/cpuinfo";
+const MEMINFO: &str = "/proc/meminfo";
+const OSRELEASE: &str = "/proc/sys/kernel/osrelease";
+const SCALING_GOVERNOR: &str = "/sys/devices/system/cpu/cpu0/cpufreq/scaling_governor";
+const SYS_VENDOR: &str = "/sys/class/dmi/id/sys_vendor";
+const PRODUCT_NAME: &str = "/sys/class/dmi/id/product_name";
+const HYPERVISOR_TYPE: &str = "/sys/hypervisor/type";
+
+const BARE_METAL: &str = "none";
+const UNKNOWN_HYPERVISOR: &str = "vm";
+const KILOBYTE: u64 = 1024;
+
+impl super::Hardware {
+    pub fn current() -> Self {
+        let cpuinfo = fs::read_to_string(CPUINFO).ok();
+        let cpuinfo = cpuinfo.as_deref().map(CpuInfo::parse).unwrap_or_default();
+        Self {
+            cpu: cpuinfo.model,
+            cores: cpuinfo.cores,
+            threads: cpuinfo.threads,
+            memory: read_trimmed(MEMINFO).as_deref().and_then(parse_mem_total),
+            kernel: read_trimmed(OSRELEASE),
+            governor: read_trimmed(SCALING_GOVERNOR),
+            virtualization: cpuinfo.hypervisor.map(|hypervisor| {
+                if hypervisor {
+                    hypervisor_vendor(
+                        read_trimmed(SYS_VENDOR).as_deref(),
+                        read_trimmed(PRODUCT_NAME).as_deref(),
+                        read_trimmed(HYPERVISOR_TYPE).as_deref(),
+                    )
+                } else {
+                    BARE_METAL
+                }
+                .to_owned()
+            }),
+        }
+    }
+}
+
+fn read_trimmed(path: &str) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty())
+}
+
+#[derive(Debug, Default)]
+struct CpuInfo {
+    model: Option<String>,
+    cores: Option<usize>,
+    threads: Option<usize>,
+    /// Whether the `hypervisor` CPU flag is set, if the CPU flags are available
+    hypervisor: Option<bool>,
+}
+
+impl CpuInfo {
+    fn parse(cpuinfo: &str) -> Self {
+        let mut model = None;
+        let mut threads = 0;
+        let mut cores = HashSet::new();
+        let mut physical_id = None;
+        let mut hypervisor = None;
+        for line in cpuinfo.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "processor" => {
+                    threads += 1;
+                    physical_id = None;
+                },
+                // ARM CPUs may only list the `Hardware` and not the `model name`
+                "model name" | "Hardware" if model.is_none() && !value.is_empty() => {
+                    model = Some(value.to_owned());
+                },
+                "physical id" => physical_id = Some(value.to_owned()),
+                "core id" => {
+                    cores.insert((physical_id.clone(), value.to_owned()));
+                },
+                "flags" if hypervisor.is_none() => {
+                    hypervisor = Some(value.split_whitespace().any(|flag| flag == "hypervisor"));
+                },
+                _ => {},
+            }
+        }
+        Self {
+            model,
+            cores: (!cores.is_empty()).then_some(cores.len()),
+            threads: (threads > 0).then_some(threads),
+            hypervisor,
+        }
+    }
+}
+
+// MemTotal:        6158152 kB
+fn parse_mem_total(meminfo: &str) -> Option<u64> {
+    meminfo.lines().find_map(|line| {
+        let kilobytes = line.strip_prefix("MemTotal:")?.trim().strip_suffix("kB")?;
+        kilobytes.trim().parse::<u64>().ok()?.checked_mul(KILOBYTE)
+    })
+}
+
+fn hypervisor_vendor(
+    sys_vendor: Option<&str>,
+    product_name: Option<&str>,
+    hypervisor_type: Option<&str>,
+) -> &'static str {
+    const VENDORS: [(&str, &str); 9] = [
+        ("Amazon EC2", "amazon"),
+        ("Google", "google"),
+        ("Microsoft Corporation", "hyper-v"),
+        ("VMware", "vmware"),
+        ("innotek GmbH", "virtualbox"),
+        ("Xen", "xen"),
+        ("KVM", "kvm"),
+        ("QEMU", "qemu"),
+        ("OpenStack", "openstack"),
+    ];
+    if hypervisor_type == Some("xen") {
+        return "xen";
+    }
+    [sys_vendor, product_name]
+        .into_iter()
+        .flatten()
+        .find_map(|dmi| {
+            VENDORS
+                .iter()
+                .find_map(|(prefix, vendor)| dmi.starts_with(prefix).then_some(*vendor))
+        })
+        .unwrap_or(UNKNOWN_HYPERVISOR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CpuInfo, hypervisor_vendor, parse_mem_total};
+
+    const CPUINFO_X86: &str = "processor	: 0
+vendor_id	: GenuineIntel
+model name	: Intel(R) Xeon(R) Platinum 8375C CPU @ 2.90GHz
+physical id	: 0
+siblings	: 4
+core id		: 0
+cpu cores	: 2
+flags		: fpu vme de pse tsc msr hypervisor lahf_lm
+
+processor	: 1
+vendor_id	: GenuineIntel
+model name	: Intel(R) Xeon(R) Platinum 8375C CPU @ 2.90GHz
+physical id	: 0
+siblings	: 4
+core id		: 1
+cpu cores	: 2
+flags		: fpu vme de pse tsc msr hypervisor lahf_lm
+
+processor	: 2
+vendor_id	: GenuineIntel
+model name	: Intel(R) Xeon(R) Platinum 8375C CPU @ 2.90GHz
+physical id	: 0
+siblings	: 4
+core id		: 0
+cpu cores	: 2
+flags		: fpu vme de pse tsc msr hypervisor lahf_lm
+
+processor	: 3
+vendor_id	: GenuineIntel
+model name	: Intel(R) Xeon(R) Platinum 8375C CPU @ 2.90GHz
+physical id	: 0
+siblings	: 4
+core id		: 1
+cpu cores	: 2
+flags		: fpu vme de pse tsc msr hypervisor lahf_lm
+";
+
+    const CPUINFO_ARM: &str = "processor	: 0
+BogoMIPS	: 243.75
+Features	: fp asimd evtstrm aes pmull sha1 sha2 crc32 atomics
+CPU implementer	: 0x41
+
+processor	: 1
+BogoMIPS	: 243.75
+Features	: fp asimd evtstrm aes pmull sha1 sha2 crc32 atomics
+CPU implementer	: 0x41
+
+Hardware	: BCM2835
+";
+
+    #[test]
+    fn test_cpuinfo_x86() {
+        let cpuinfo = CpuInfo::parse(CPUINFO_X86);
+        assert_eq!(
+            cpuinfo.model.as_deref(),
+            Some("Intel(R) Xeon(R) Platinum 8375C CPU @ 2.90GHz")
+        );
+        assert_eq!(cpuinfo.cores, Some(2));
+        assert_eq!(cpuinfo.threads, Some(4));
+        assert_eq!(cpuinfo.hypervisor, Some(true));
+    }
+
+    #[test]
+    fn test_cpuinfo_arm() {
+        let cpuinfo = CpuInfo::parse(CPUINFO_ARM);
+        assert_eq!(cpuinfo.model.as_deref(), Some("BCM2835"));
+        assert_eq!(cpuinfo.cores, None);
+        assert_eq!(cpuinfo.threads, Some(2));
+        assert_eq!(cpuinfo.hypervisor, None);
+    }
+
+    #[test]
+    fn test_mem_total() {
+        assert_eq!(
+            parse_mem_total("MemTotal:        6158152 kB\nMemFree:          361884 kB\n"),
+            Some(6_158_152 * 1024)
+        );
+        assert_eq!(parse_mem_total("MemFree:          361884 kB\n"), None);
+    }
+
+    #[test]
+    fn test_hypervisor_vendor() {
+        assert_eq!(
+            hypervisor_vendor(Some("Amazon EC2"), Some("c5.xlarge"), None),
+            "amazon"
+        );
+        assert_eq!(
+            hypervisor_vendor(Some("Microsoft Corporation"), Some("Virtual Machine"), None),
+            "hyper-v"
+        );
+        assert_eq!(hypervisor_vendor(Some("Red Hat"), Some("KVM"), None), "kvm");
+        assert_eq!(hypervisor_vendor(None, None, Some("xen")), "xen");
+        assert_eq!(hypervisor_vendor(None, None, None), "vm");
+    }
+}
@@ -0,0 +1,22 @@
+#[cfg(target_os = "linux")]
+mod linux;
+
+/// The hardware profile of the testbed.
+/// Any value that cannot be detected is left empty.
+#[derive(Debug, Clone, Default)]
+pub struct Hardware {
+    pub cpu: Option<String>,
+    pub cores: Option<usize>,
+    pub threads: Option<usize>,
+    pub memory: Option<u64>,
+    pub kernel: Option<String>,
+    pub governor: Option<String>,
+    pub virtualization: Option<String>,
+}
+
+#[cfg(not(target_os = "linux"))]
+impl Hardware {
+    pub fn current() -> Self {
+        Self::default()
+    }
+}
@@ -5,9 +5,11 @@ use gix::Repository;
 use crate::{ContextPath, RunContext};
 
 mod fingerprint;
+mod hardware;
 mod operating_system;
 
 use fingerprint::Fingerprint;
+use hardware::Hardware;
 use operating_system::OperatingSystem;
 
 const ROOT: &str = "root";
@@ -64,6 +66,37 @@ fn testbed_context(context: &mut RunContext) {
     if let Some(fingerprint) = Fingerprint::current() {
         context.insert(ContextPath::TESTBED_FINGERPRINT, fingerprint.to_string());
     }
+
+    let Hardware {
+        cpu,
+        cores,
+        threads,
+        memory,
+        kernel,
+        governor,
+        virtualization,
+    } = Hardware::current();
+    if let Some(cpu) = cpu {
+        context.insert(ContextPath::TESTBED_CPU, cpu);
+    }
+    if let Some(cores) = cores {
+        context.insert(ContextPath::TESTBED_CPU_CORES, cores.to_string());
+    }
+    if let Some(threads) = threads {
+        context.insert(ContextPath::TESTBED_CPU_THREADS, threads.to_string());
+    }
+    if let Some(governor) = governor {
+        context.insert(ContextPath::TESTBED_CPU_GOVERNOR, governor);
+    }
+    if let Some(memory) = memory {
+        context.insert(ContextPath::TESTBED_MEMORY, memory.to_string());
+    }
+    if let Some(kernel) = kernel {
+        context.insert(ContextPath::TESTBED_KERNEL, kernel);
+    }
+    if let Some(virtualization) = virtualization {
+        context.insert(ContextPath::TESTBED_VIRTUALIZATION, virtualization);
+    }
 }
 
 fn find_repo() -> Option<Repository> {
@@ -35,4 +35,11 @@ impl ContextPath {
     pub const BRANCH_HASH: &str = "/branch/hash";
     pub const TESTBED_OS: &str = "/testbed/os";
     pub const TESTBED_FINGERPRINT: &str = "/testbed/fingerprint";
+    pub const TESTBED_CPU: &str = "/testbed/cpu";
+    pub const TESTBED_CPU_CORES: &str = "/testbed/cpu/cores";
+    pub const TESTBED_CPU_THREADS: &str = "/testbed/cpu/threads";
+    pub const TESTBED_CPU_GOVERNOR: &str = "/testbed/cpu/governor";
+    pub const TESTBED_MEMORY: &str = "/testbed/memory";
+    pub const TESTBED_KERNEL: &str = "/testbed/kernel";
+    pub const TESTBED_VIRTUALIZATION: &str = "/testbed/virtualization";
 }
@@ -37,6 +37,37 @@ impl RunContext {
             .map(String::as_str)
     }
 
+    pub fn testbed_cpu(&self) -> Option<&str> {
+        self.get(ContextPath::TESTBED_CPU).map(String::as_str)
+    }
+
+    pub fn testbed_cpu_cores(&self) -> Option<&str> {
+        self.get(ContextPath::TESTBED_CPU_CORES).map(String::as_str)
+    }
+
+    pub fn testbed_cpu_threads(&self) -> Option<&str> {
+        self.get(ContextPath::TESTBED_CPU_THREADS)
+            .map(String::as_str)
+    }
+
+    pub fn testbed_cpu_governor(&self) -> Option<&str> {
+        self.get(ContextPath::TESTBED_CPU_GOVERNOR)
+            .map(String::as_str)
+    }
+
+    pub fn testbed_memory(&self) -> Option<&str> {
+        self.get(ContextPath::TESTBED_MEMORY).map(String::as_str)
+    }
+
+    pub fn testbed_kernel(&self) -> Option<&str> {
+        self.get(ContextPath::TESTBED_KERNEL).map(String::as_str)
+    }
+
+    pub fn testbed_virtualization(&self) -> Option<&str> {
+        self.get(ContextPath::TESTBED_VIRTUALIZATION)
+            .map(String::as_str)
+    }
+
     pub fn name(&self) -> Option<ResourceName> {
         self.repo_name()
             .map_or_else(|| "Project".to_owned(), truncate_name)
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 // Do not typeshare this type in order to obfuscate the u64
 // https://github.com/1Password/typeshare/issues/24
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct BigInt(pub u64);
 
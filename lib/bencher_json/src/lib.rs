@@ -2,10 +2,10 @@ use std::sync::LazyLock;
 
 pub use bencher_context::RunContext;
 pub use bencher_valid::{
-    BenchmarkName, Boundary, BranchName, CdfBoundary, DateTime, DateTimeMillis, Email, GitHash,
-    Index, IqrBoundary, Jwt, Model, ModelTest, NameId, NameIdKind, NonEmpty, PercentageBoundary,
-    ResourceId, ResourceIdKind, ResourceName, SampleSize, Sanitize, Search, Secret, Slug, Units,
-    Url, UserName, ValidError, Window,
+    BenchmarkName, Boundary, BranchName, CdfBoundary, DateTime, DateTimeMillis, Email,
+    EnvironmentChange, GitHash, Index, IqrBoundary, Jwt, Model, ModelTest, NameId, NameIdKind,
+    NonEmpty, PercentageBoundary, ResourceId, ResourceIdKind, ResourceName, SampleSize, Sanitize,
+    Search, Secret, Slug, Smoothing, Units, Url, UserName, ValidError, Window,
 };
 #[cfg(feature = "plus")]
 pub use bencher_valid::{
@@ -52,8 +52,16 @@ pub use project::{
     perf::{JsonPerf, JsonPerfQuery, ReportBenchmarkUuid},
     plot::{JsonNewPlot, JsonPlot, JsonPlots, PlotUuid},
     report::{JsonNewReport, JsonReport, JsonReports, ReportUuid},
-    testbed::{JsonNewTestbed, JsonTestbed, JsonTestbeds, TestbedUuid},
+    testbed::{JsonNewTestbed, JsonTestbed, JsonTestbedHardware, JsonTestbeds, TestbedUuid},
     threshold::{JsonNewThreshold, JsonThreshold, JsonThresholds, ThresholdUuid},
+    trust_policy::{
+        JsonNewTrustPolicy, JsonTrustPolicies, JsonTrustPolicy, TrustPolicyUuid, TrustProvider,
+    },
+    webhook::{
+        JsonNewWebhook, JsonWebhook, JsonWebhookDeliveries, JsonWebhookDelivery, JsonWebhookEvents,
+        JsonWebhooks, WebhookDeliveryStatus, WebhookDeliveryUuid, WebhookEvent, WebhookFormat,
+        WebhookUuid,
+    },
 };
 pub use run::JsonNewRun;
 #[cfg(feature = "plus")]
@@ -64,16 +72,18 @@ pub use system::{
     server::{JsonServer, JsonServerStats, ServerUuid},
 };
 pub use system::{
-    auth::{JsonAccept, JsonAuthAck, JsonAuthUser, JsonConfirm, JsonLogin, JsonSignup},
+    auth::{JsonAccept, JsonAuthAck, JsonAuthUser, JsonCiAuth, JsonConfirm, JsonLogin, JsonSignup},
     backup::{JsonBackup, JsonBackupCreated},
     config::JsonConfig,
     restart::JsonRestart,
+    restore::{JsonRestore, JsonRestored},
     spec::JsonSpec,
     version::JsonApiVersion,
 };
 pub use user::{
     JsonPubUser, JsonUpdateUser, JsonUser, JsonUsers, UserUuid,
-    token::{JsonNewToken, JsonToken, JsonTokens, TokenUuid},
+    notification::{JsonNotification, JsonUpdateNotification, NotificationMode},
+    token::{JsonNewToken, JsonToken, JsonTokenPermissions, JsonTokens, TokenUuid},
 };
 
 pub const BENCHER_CONSOLE_PORT: u16 = 3000;
@@ -27,6 +27,8 @@ pub struct JsonAlert {
     pub boundary: JsonBoundary,
     pub limit: BoundaryLimit,
     pub status: AlertStatus,
+    /// Whether the alert was raised for a report where the testbed hardware changed.
+    pub environment_change: bool,
     pub created: DateTime,
     pub modified: DateTime,
 }
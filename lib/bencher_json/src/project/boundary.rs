@@ -3,6 +3,8 @@ use ordered_float::OrderedFloat;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::MetricUuid;
+
 crate::typed_uuid::typed_uuid!(BoundaryUuid);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +20,12 @@ pub struct JsonBoundary {
     pub baseline: Option<OrderedFloat<f64>>,
     pub lower_limit: Option<OrderedFloat<f64>>,
     pub upper_limit: Option<OrderedFloat<f64>>,
+    /// The first metric after a detected change point, if any.
+    /// This is only set by the change point threshold model.
+    pub change_point: Option<MetricUuid>,
+    /// The significance level of the boundary test after correcting for multiple comparisons, if any.
+    /// This is only set for statistical threshold model tests when the report used a correction.
+    pub significance: Option<OrderedFloat<f64>>,
 }
 
 const LOWER_BOOL: bool = false;
@@ -95,6 +95,74 @@ pub mod iai {
     );
 }
 
+pub mod criterion {
+    create_measure!(
+        ByteThroughput,
+        "Byte Throughput",
+        "byte-throughput",
+        "bytes / second (B/s)"
+    );
+}
+
+pub mod go {
+    create_measure!(
+        BytesPerOp,
+        "Bytes per Operation",
+        "bytes-per-op",
+        "bytes / operation (B/op)"
+    );
+    create_measure!(
+        AllocsPerOp,
+        "Allocations per Operation",
+        "allocs-per-op",
+        "allocations / operation (allocs/op)"
+    );
+    create_measure!(
+        MegabytesPerSecond,
+        "Megabytes per Second",
+        "megabytes-per-second",
+        "megabytes / second (MB/s)"
+    );
+}
+
+pub mod google {
+    use bencher_valid::NANOSECONDS;
+
+    create_measure!(CpuTime, "CPU Time", "cpu-time", NANOSECONDS);
+}
+
+pub mod rusage {
+    use bencher_valid::{BYTES, NANOSECONDS};
+
+    create_measure!(UserTime, "User Time", "user-time", NANOSECONDS);
+    create_measure!(SystemTime, "System Time", "system-time", NANOSECONDS);
+    create_measure!(MaxRss, "Max RSS", "max-rss", BYTES);
+    create_measure!(
+        VoluntaryContextSwitches,
+        "Voluntary Context Switches",
+        "voluntary-context-switches",
+        "switches"
+    );
+    create_measure!(
+        InvoluntaryContextSwitches,
+        "Involuntary Context Switches",
+        "involuntary-context-switches",
+        "switches"
+    );
+    create_measure!(ReadBytes, "Read Bytes", "read-bytes", BYTES);
+    create_measure!(WriteBytes, "Write Bytes", "write-bytes", BYTES);
+}
+
+pub mod perf {
+    use bencher_valid::NANOSECONDS;
+
+    create_measure!(Cycles, "Cycles", "cycles", "cycles");
+    create_measure!(Instructions, "Instructions", "instructions", "instructions");
+    create_measure!(BranchMisses, "Branch Misses", "branch-misses", "misses");
+    create_measure!(CacheMisses, "Cache Misses", "cache-misses", "misses");
+    create_measure!(TaskClock, "Task Clock", "task-clock", NANOSECONDS);
+}
+
 pub mod iai_callgrind {
     use bencher_valid::BYTES;
 
@@ -26,6 +26,8 @@ pub mod plot;
 pub mod report;
 pub mod testbed;
 pub mod threshold;
+pub mod trust_policy;
+pub mod webhook;
 
 crate::typed_uuid::typed_uuid!(ProjectUuid);
 
@@ -301,7 +303,7 @@ mod visibility {
 }
 
 #[typeshare::typeshare]
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, derive_more::Display)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, derive_more::Display)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ProjectPermission {
@@ -1,4 +1,6 @@
-use bencher_valid::{Boundary, DateTime, ModelTest, SampleSize, Window};
+use bencher_valid::{
+    Boundary, DateTime, EnvironmentChange, ModelTest, SampleSize, Smoothing, Window,
+};
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -16,6 +18,10 @@ pub struct JsonModel {
     pub window: Option<Window>,
     pub lower_boundary: Option<Boundary>,
     pub upper_boundary: Option<Boundary>,
+    pub smoothing: Option<Smoothing>,
+    pub trend: Option<Smoothing>,
+    pub season: Option<SampleSize>,
+    pub environment_change: Option<EnvironmentChange>,
     pub created: DateTime,
     pub replaced: Option<DateTime>,
 }
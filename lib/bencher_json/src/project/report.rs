@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     JsonAlert, JsonBenchmark, JsonBoundary, JsonBranch, JsonMeasure, JsonMetric, JsonProject,
-    JsonPubUser, JsonTestbed, NameId,
+    JsonPubUser, JsonTestbed, JsonTestbedHardware, NameId,
     urlencoded::{UrlEncodedError, from_urlencoded, to_urlencoded},
 };
 
@@ -51,6 +51,8 @@ pub struct JsonNewReport {
     pub results: Vec<String>,
     /// Settings for how to handle the report.
     pub settings: Option<JsonReportSettings>,
+    /// The hardware profile of the testbed when the report was run.
+    pub hardware: Option<JsonTestbedHardware>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +80,18 @@ pub struct JsonReportSettings {
     /// Fold multiple results into a single result using the selected operation.
     /// This can be useful for taking the min, max, mean, or median of the benchmark results.
     pub fold: Option<JsonFold>,
+    /// Treat the results of all iterations of a benchmark as a single sample for threshold detection.
+    /// This creates at most one boundary and alert per benchmark and measure,
+    /// instead of one for each iteration.
+    /// This cannot be used with `fold`.
+    pub sample: Option<bool>,
+    /// Correct for multiple comparisons across all of the threshold boundary tests in the report.
+    /// Only the statistical threshold model tests are corrected.
+    /// This can be useful for reducing false positive alerts when a report has many benchmarks.
+    pub correction: Option<JsonCorrection>,
+    /// How to handle a change in the testbed hardware profile since the previous report for the testbed.
+    /// By default, the report is annotated with a warning and is still compared to the historical metrics.
+    pub hardware_change: Option<JsonHardwareChange>,
 }
 
 const MAGIC_INT: i32 = 0;
@@ -106,6 +120,7 @@ const RUBY_INT: i32 = 90;
 const RUBY_BENCHMARK_INT: i32 = 91;
 const SHELL_INT: i32 = 100;
 const SHELL_HYPERFINE_INT: i32 = 101;
+const SHELL_PERF_STAT_INT: i32 = 102;
 
 #[typeshare::typeshare]
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
@@ -142,6 +157,7 @@ pub enum Adapter {
     RubyBenchmark = RUBY_BENCHMARK_INT,
     Shell = SHELL_INT,
     ShellHyperfine = SHELL_HYPERFINE_INT,
+    ShellPerfStat = SHELL_PERF_STAT_INT,
 }
 
 impl fmt::Display for Adapter {
@@ -173,6 +189,7 @@ impl fmt::Display for Adapter {
             Self::RubyBenchmark => write!(f, "ruby_benchmark"),
             Self::Shell => write!(f, "shell"),
             Self::ShellHyperfine => write!(f, "shell_hyperfine"),
+            Self::ShellPerfStat => write!(f, "shell_perf_stat"),
         }
     }
 }
@@ -184,7 +201,7 @@ mod adapter {
         GO_BENCH_INT, GO_INT, JAVA_INT, JAVA_JMH_INT, JS_BENCHMARK_INT, JS_INT, JS_TIME_INT,
         JSON_INT, MAGIC_INT, PYTHON_ASV_INT, PYTHON_INT, PYTHON_PYTEST_INT, RUBY_BENCHMARK_INT,
         RUBY_INT, RUST_BENCH_INT, RUST_CRITERION_INT, RUST_IAI_CALLGRIND_INT, RUST_IAI_INT,
-        RUST_INT, SHELL_HYPERFINE_INT, SHELL_INT,
+        RUST_INT, SHELL_HYPERFINE_INT, SHELL_INT, SHELL_PERF_STAT_INT,
     };
 
     #[derive(Debug, thiserror::Error)]
@@ -229,6 +246,7 @@ mod adapter {
                 Self::RubyBenchmark => RUBY_BENCHMARK_INT.to_sql(out),
                 Self::Shell => SHELL_INT.to_sql(out),
                 Self::ShellHyperfine => SHELL_HYPERFINE_INT.to_sql(out),
+                Self::ShellPerfStat => SHELL_PERF_STAT_INT.to_sql(out),
             }
         }
     }
@@ -266,6 +284,7 @@ mod adapter {
                 RUBY_BENCHMARK_INT => Ok(Self::RubyBenchmark),
                 SHELL_INT => Ok(Self::Shell),
                 SHELL_HYPERFINE_INT => Ok(Self::ShellHyperfine),
+                SHELL_PERF_STAT_INT => Ok(Self::ShellPerfStat),
                 value => Err(Box::new(AdapterError::Invalid(value))),
             }
         }
@@ -291,6 +310,78 @@ pub enum JsonFold {
     Median,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum JsonCorrection {
+    /// Control the family-wise error rate.
+    Bonferroni,
+    /// Control the false discovery rate.
+    BenjaminiHochberg,
+}
+
+const WARN_INT: i32 = 0;
+const REFUSE_INT: i32 = 1;
+
+/// How to handle a report when the testbed hardware has changed since its previous report.
+/// If the report is still compared to the historical metrics,
+/// then the threshold model `environment_change` decides how to handle any alerts.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
+#[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Integer))]
+#[serde(rename_all = "snake_case")]
+#[repr(i32)]
+pub enum JsonHardwareChange {
+    /// Warn about the change and compare the report to the historical metrics.
+    #[default]
+    Warn = WARN_INT,
+    /// Refuse to compare the report to the historical metrics.
+    Refuse = REFUSE_INT,
+}
+
+#[cfg(feature = "db")]
+mod hardware_change {
+    use super::{JsonHardwareChange, REFUSE_INT, WARN_INT};
+
+    #[derive(Debug, thiserror::Error)]
+    pub enum HardwareChangeError {
+        #[error("Invalid hardware change value: {0}")]
+        Invalid(i32),
+    }
+
+    impl<DB> diesel::serialize::ToSql<diesel::sql_types::Integer, DB> for JsonHardwareChange
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::serialize::ToSql<diesel::sql_types::Integer, DB>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            match self {
+                Self::Warn => WARN_INT.to_sql(out),
+                Self::Refuse => REFUSE_INT.to_sql(out),
+            }
+        }
+    }
+
+    impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Integer, DB> for JsonHardwareChange
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            match i32::from_sql(bytes)? {
+                WARN_INT => Ok(Self::Warn),
+                REFUSE_INT => Ok(Self::Refuse),
+                value => Err(Box::new(HardwareChangeError::Invalid(value))),
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct JsonReports(pub Vec<JsonReport>);
@@ -309,6 +400,11 @@ pub struct JsonReport {
     pub start_time: DateTime,
     pub end_time: DateTime,
     pub adapter: Adapter,
+    pub hardware: Option<JsonTestbedHardware>,
+    /// Whether the testbed hardware changed since its previous report.
+    pub environment_change: bool,
+    /// How the report was handled if the testbed hardware changed since its previous report.
+    pub hardware_change: Option<JsonHardwareChange>,
     pub results: JsonReportResults,
     pub alerts: JsonReportAlerts,
     pub created: DateTime,
@@ -1,12 +1,14 @@
 use std::fmt;
 use std::sync::LazyLock;
 
+#[cfg(feature = "server")]
+use bencher_context::RunContext;
 use bencher_valid::{DateTime, NameId, ResourceName, Slug};
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::ProjectUuid;
+use crate::{BigInt, ProjectUuid};
 
 pub const TESTBED_LOCALHOST_STR: &str = "localhost";
 #[expect(clippy::expect_used)]
@@ -68,6 +70,8 @@ pub struct JsonTestbed {
     pub project: ProjectUuid,
     pub name: ResourceName,
     pub slug: Slug,
+    /// The current hardware profile of the testbed, as detected by its most recent report.
+    pub hardware: Option<JsonTestbedHardware>,
     pub created: DateTime,
     pub modified: DateTime,
     pub archived: Option<DateTime>,
@@ -79,6 +83,108 @@ impl fmt::Display for JsonTestbed {
     }
 }
 
+/// The hardware profile of a testbed, as detected when the report was run.
+/// Any value that could not be detected is left empty.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
+#[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Text))]
+pub struct JsonTestbedHardware {
+    /// The operating system.
+    pub os: Option<String>,
+    /// The CPU model name.
+    pub cpu: Option<String>,
+    /// The number of physical CPU cores.
+    pub cores: Option<u32>,
+    /// The number of logical CPU threads.
+    pub threads: Option<u32>,
+    /// The CPU frequency scaling governor.
+    pub governor: Option<String>,
+    /// The total memory in bytes.
+    pub memory: Option<BigInt>,
+    /// The kernel version.
+    pub kernel: Option<String>,
+    /// The hypervisor, if virtualized, or `none` for bare metal.
+    pub virtualization: Option<String>,
+}
+
+impl JsonTestbedHardware {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Whether any value that was detected in both hardware profiles is different.
+    pub fn is_changed(&self, other: &Self) -> bool {
+        fn changed<T: PartialEq>(lhs: Option<&T>, rhs: Option<&T>) -> bool {
+            matches!((lhs, rhs), (Some(lhs), Some(rhs)) if lhs != rhs)
+        }
+
+        changed(self.os.as_ref(), other.os.as_ref())
+            || changed(self.cpu.as_ref(), other.cpu.as_ref())
+            || changed(self.cores.as_ref(), other.cores.as_ref())
+            || changed(self.threads.as_ref(), other.threads.as_ref())
+            || changed(self.governor.as_ref(), other.governor.as_ref())
+            || changed(self.memory.as_ref(), other.memory.as_ref())
+            || changed(self.kernel.as_ref(), other.kernel.as_ref())
+            || changed(self.virtualization.as_ref(), other.virtualization.as_ref())
+    }
+}
+
+#[cfg(feature = "server")]
+impl From<&RunContext> for JsonTestbedHardware {
+    fn from(context: &RunContext) -> Self {
+        Self {
+            os: context.testbed_os().map(ToOwned::to_owned),
+            cpu: context.testbed_cpu().map(ToOwned::to_owned),
+            cores: context
+                .testbed_cpu_cores()
+                .and_then(|cores| cores.parse().ok()),
+            threads: context
+                .testbed_cpu_threads()
+                .and_then(|threads| threads.parse().ok()),
+            governor: context.testbed_cpu_governor().map(ToOwned::to_owned),
+            memory: context
+                .testbed_memory()
+                .and_then(|memory| memory.parse::<u64>().ok())
+                .map(Into::into),
+            kernel: context.testbed_kernel().map(ToOwned::to_owned),
+            virtualization: context.testbed_virtualization().map(ToOwned::to_owned),
+        }
+    }
+}
+
+#[cfg(feature = "db")]
+mod testbed_hardware {
+    use super::JsonTestbedHardware;
+
+    impl<DB> diesel::serialize::ToSql<diesel::sql_types::Text, DB> for JsonTestbedHardware
+    where
+        DB: diesel::backend::Backend,
+        for<'a> String: diesel::serialize::ToSql<diesel::sql_types::Text, DB>
+            + Into<<DB::BindCollector<'a> as diesel::query_builder::BindCollector<'a, DB>>::Buffer>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            out.set_value(serde_json::to_string(self)?);
+            Ok(diesel::serialize::IsNull::No)
+        }
+    }
+
+    impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Text, DB> for JsonTestbedHardware
+    where
+        DB: diesel::backend::Backend,
+        String: diesel::deserialize::FromSql<diesel::sql_types::Text, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            let hardware = String::from_sql(bytes)?;
+            Ok(serde_json::from_str(&hardware)?)
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct JsonUpdateTestbed {
@@ -152,6 +152,7 @@ pub struct JsonRemoveModel {
 }
 
 impl<'de> Deserialize<'de> for JsonUpdateThreshold {
+    #[expect(clippy::too_many_lines)]
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
@@ -162,6 +163,10 @@ impl<'de> Deserialize<'de> for JsonUpdateThreshold {
         const WINDOW_FIELD: &str = "window";
         const LOWER_BOUNDARY_FIELD: &str = "lower_boundary";
         const UPPER_BOUNDARY_FIELD: &str = "upper_boundary";
+        const SMOOTHING_FIELD: &str = "smoothing";
+        const TREND_FIELD: &str = "trend";
+        const SEASON_FIELD: &str = "season";
+        const ENVIRONMENT_CHANGE_FIELD: &str = "environment_change";
 
         const FIELDS: &[&str] = &[
             TEST_FIELD,
@@ -170,6 +175,10 @@ impl<'de> Deserialize<'de> for JsonUpdateThreshold {
             WINDOW_FIELD,
             LOWER_BOUNDARY_FIELD,
             UPPER_BOUNDARY_FIELD,
+            SMOOTHING_FIELD,
+            TREND_FIELD,
+            SEASON_FIELD,
+            ENVIRONMENT_CHANGE_FIELD,
         ];
 
         #[derive(Deserialize)]
@@ -181,6 +190,10 @@ impl<'de> Deserialize<'de> for JsonUpdateThreshold {
             Window,
             LowerBoundary,
             UpperBoundary,
+            Smoothing,
+            Trend,
+            Season,
+            EnvironmentChange,
         }
 
         struct UpdateThresholdVisitor;
@@ -202,6 +215,10 @@ impl<'de> Deserialize<'de> for JsonUpdateThreshold {
                 let mut window = None;
                 let mut lower_boundary = None;
                 let mut upper_boundary = None;
+                let mut smoothing = None;
+                let mut trend = None;
+                let mut season = None;
+                let mut environment_change = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -241,6 +258,30 @@ impl<'de> Deserialize<'de> for JsonUpdateThreshold {
                             }
                             upper_boundary = Some(map.next_value()?);
                         },
+                        Field::Smoothing => {
+                            if smoothing.is_some() {
+                                return Err(de::Error::duplicate_field(SMOOTHING_FIELD));
+                            }
+                            smoothing = Some(map.next_value()?);
+                        },
+                        Field::Trend => {
+                            if trend.is_some() {
+                                return Err(de::Error::duplicate_field(TREND_FIELD));
+                            }
+                            trend = Some(map.next_value()?);
+                        },
+                        Field::Season => {
+                            if season.is_some() {
+                                return Err(de::Error::duplicate_field(SEASON_FIELD));
+                            }
+                            season = Some(map.next_value()?);
+                        },
+                        Field::EnvironmentChange => {
+                            if environment_change.is_some() {
+                                return Err(de::Error::duplicate_field(ENVIRONMENT_CHANGE_FIELD));
+                            }
+                            environment_change = Some(map.next_value()?);
+                        },
                     }
                 }
 
@@ -253,6 +294,10 @@ impl<'de> Deserialize<'de> for JsonUpdateThreshold {
                             window,
                             lower_boundary,
                             upper_boundary,
+                            smoothing,
+                            trend,
+                            season,
+                            environment_change,
                         },
                     })),
                     Some(None) => Ok(Self::Value::Remove(JsonRemoveModel { test: () })),
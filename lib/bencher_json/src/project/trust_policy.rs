@@ -0,0 +1,125 @@
+use bencher_valid::{DateTime, NonEmpty, Url};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{ProjectUuid, UserUuid};
+
+crate::typed_uuid::typed_uuid!(TrustPolicyUuid);
+
+pub const GITHUB_ACTIONS_ISSUER: &str = "https://token.actions.githubusercontent.com";
+pub const GITLAB_CI_ISSUER: &str = "https://gitlab.com";
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewTrustPolicy {
+    /// The CI provider that issues the OpenID Connect ID tokens.
+    pub provider: TrustProvider,
+    /// The OpenID Connect issuer URL for a self-hosted CI provider.
+    /// If not provided, the issuer for the hosted CI provider is used.
+    /// The issuer URL must use `https`.
+    pub issuer: Option<Url>,
+    /// The repository that is trusted, for example `bencherdev/bencher`.
+    /// For GitHub Actions this is matched against the `repository` claim,
+    /// and for GitLab CI/CD this is matched against the `project_path` claim.
+    pub repository: NonEmpty,
+    /// The git ref pattern that is trusted, matched against the `ref` claim.
+    /// A `*` matches any sequence of characters.
+    /// If not provided, all git refs are trusted.
+    pub git_ref: Option<NonEmpty>,
+    /// The workflow file path pattern that is trusted, for example `.github/workflows/bench.yml`.
+    /// A `*` matches any sequence of characters.
+    /// If not provided, all workflows are trusted.
+    pub workflow: Option<NonEmpty>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonTrustPolicies(pub Vec<JsonTrustPolicy>);
+
+crate::from_vec!(JsonTrustPolicies[JsonTrustPolicy]);
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonTrustPolicy {
+    pub uuid: TrustPolicyUuid,
+    pub project: ProjectUuid,
+    /// The user that the short-lived CI tokens are issued for.
+    pub user: UserUuid,
+    pub provider: TrustProvider,
+    pub issuer: Url,
+    pub repository: NonEmpty,
+    pub git_ref: Option<NonEmpty>,
+    pub workflow: Option<NonEmpty>,
+    pub created: DateTime,
+}
+
+const GITHUB_ACTIONS_INT: i32 = 0;
+const GITLAB_CI_INT: i32 = 1;
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
+#[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Integer))]
+#[serde(rename_all = "snake_case")]
+#[repr(i32)]
+pub enum TrustProvider {
+    #[display("GitHub Actions")]
+    GithubActions = GITHUB_ACTIONS_INT,
+    #[display("GitLab CI/CD")]
+    GitlabCi = GITLAB_CI_INT,
+}
+
+impl TrustProvider {
+    /// The OpenID Connect issuer for the hosted CI provider
+    pub fn issuer(self) -> &'static str {
+        match self {
+            Self::GithubActions => GITHUB_ACTIONS_ISSUER,
+            Self::GitlabCi => GITLAB_CI_ISSUER,
+        }
+    }
+}
+
+#[cfg(feature = "db")]
+mod trust_provider {
+    use super::{GITHUB_ACTIONS_INT, GITLAB_CI_INT, TrustProvider};
+
+    #[derive(Debug, thiserror::Error)]
+    pub enum TrustProviderError {
+        #[error("Invalid trust policy provider value: {0}")]
+        Invalid(i32),
+    }
+
+    impl<DB> diesel::serialize::ToSql<diesel::sql_types::Integer, DB> for TrustProvider
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::serialize::ToSql<diesel::sql_types::Integer, DB>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            match self {
+                Self::GithubActions => GITHUB_ACTIONS_INT.to_sql(out),
+                Self::GitlabCi => GITLAB_CI_INT.to_sql(out),
+            }
+        }
+    }
+
+    impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Integer, DB> for TrustProvider
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            match i32::from_sql(bytes)? {
+                GITHUB_ACTIONS_INT => Ok(Self::GithubActions),
+                GITLAB_CI_INT => Ok(Self::GitlabCi),
+                value => Err(Box::new(TrustProviderError::Invalid(value))),
+            }
+        }
+    }
+}
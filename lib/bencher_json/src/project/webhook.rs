@@ -0,0 +1,466 @@
+use bencher_valid::{DateTime, Secret, Url};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{JsonAlert, JsonReport, JsonThreshold, ProjectUuid};
+
+crate::typed_uuid::typed_uuid!(WebhookUuid);
+crate::typed_uuid::typed_uuid!(WebhookDeliveryUuid);
+
+/// The HTTP header with the webhook event type.
+pub const WEBHOOK_EVENT_HEADER: &str = "X-Bencher-Event";
+/// The HTTP header with the webhook delivery UUID.
+pub const WEBHOOK_DELIVERY_HEADER: &str = "X-Bencher-Delivery";
+/// The HTTP header with the Unix timestamp of the webhook delivery attempt, in seconds.
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "X-Bencher-Timestamp";
+/// The HTTP header with the HMAC-SHA256 signature of the webhook timestamp and payload.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Bencher-Signature-256";
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewWebhook {
+    /// The URL that the webhook payloads are sent to.
+    /// Unless the server allows private webhooks,
+    /// the URL host must not be a loopback, link-local, or private network address.
+    /// For the `matrix` format, this is the URL to send a message to a room, without the transaction ID:
+    /// `https://{homeserver}/_matrix/client/v3/rooms/{room_id}/send/m.room.message`
+    pub url: Url,
+    /// The format of the webhook payloads.
+    /// If not provided, the payloads are sent as `json`.
+    pub format: Option<WebhookFormat>,
+    /// For the `json` format, the secret used to sign the webhook payloads.
+    /// Each delivery attempt is sent with its Unix timestamp in the `X-Bencher-Timestamp` header.
+    /// The hex encoded HMAC-SHA256 signature of the timestamp, a `.`, and the payload
+    /// is sent in the `X-Bencher-Signature-256` header, prefixed with `sha256=`.
+    /// If not provided, the webhook payloads are not signed.
+    /// For the `matrix` format, the access token for the Matrix user that sends the messages.
+    /// This is required for the `matrix` format and not allowed for the `slack` and `teams` formats.
+    pub secret: Option<Secret>,
+    /// The events that the webhook is subscribed to.
+    /// There must be at least one event.
+    /// Chat formats can not be subscribed to the `threshold.updated` event.
+    pub events: JsonWebhookEvents,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonWebhooks(pub Vec<JsonWebhook>);
+
+crate::from_vec!(JsonWebhooks[JsonWebhook]);
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonWebhook {
+    pub uuid: WebhookUuid,
+    pub project: ProjectUuid,
+    pub url: Url,
+    pub format: WebhookFormat,
+    /// Whether the webhook payloads are signed with a secret.
+    pub signed: bool,
+    pub events: JsonWebhookEvents,
+    pub created: DateTime,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
+#[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Text))]
+pub struct JsonWebhookEvents(pub Vec<WebhookEvent>);
+
+crate::from_vec!(JsonWebhookEvents[WebhookEvent]);
+
+impl JsonWebhookEvents {
+    pub fn contains(&self, event: WebhookEvent) -> bool {
+        self.0.contains(&event)
+    }
+}
+
+#[cfg(feature = "db")]
+mod webhook_events {
+    use super::JsonWebhookEvents;
+
+    impl<DB> diesel::serialize::ToSql<diesel::sql_types::Text, DB> for JsonWebhookEvents
+    where
+        DB: diesel::backend::Backend,
+        for<'a> String: diesel::serialize::ToSql<diesel::sql_types::Text, DB>
+            + Into<<DB::BindCollector<'a> as diesel::query_builder::BindCollector<'a, DB>>::Buffer>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            out.set_value(serde_json::to_string(self)?);
+            Ok(diesel::serialize::IsNull::No)
+        }
+    }
+
+    impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Text, DB> for JsonWebhookEvents
+    where
+        DB: diesel::backend::Backend,
+        String: diesel::deserialize::FromSql<diesel::sql_types::Text, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            let events = String::from_sql(bytes)?;
+            Ok(serde_json::from_str(&events)?)
+        }
+    }
+}
+
+const JSON_INT: i32 = 0;
+const SLACK_INT: i32 = 1;
+const TEAMS_INT: i32 = 2;
+const MATRIX_INT: i32 = 3;
+
+#[typeshare::typeshare]
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, derive_more::Display, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
+#[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Integer))]
+#[serde(rename_all = "snake_case")]
+#[repr(i32)]
+pub enum WebhookFormat {
+    /// A signed JSON payload with the full event data.
+    #[default]
+    #[display("json")]
+    Json = JSON_INT,
+    /// A Slack Block Kit message for a Slack incoming webhook.
+    #[display("slack")]
+    Slack = SLACK_INT,
+    /// A `MessageCard` for a Microsoft Teams incoming webhook.
+    #[display("teams")]
+    Teams = TEAMS_INT,
+    /// An `m.room.message` event for a Matrix room.
+    #[display("matrix")]
+    Matrix = MATRIX_INT,
+}
+
+impl WebhookFormat {
+    /// Whether the payloads are chat messages rather than the full event data.
+    pub fn is_chat(self) -> bool {
+        !matches!(self, Self::Json)
+    }
+}
+
+#[cfg(feature = "db")]
+mod webhook_format {
+    use super::{JSON_INT, MATRIX_INT, SLACK_INT, TEAMS_INT, WebhookFormat};
+
+    #[derive(Debug, thiserror::Error)]
+    pub enum WebhookFormatError {
+        #[error("Invalid webhook format value: {0}")]
+        Invalid(i32),
+    }
+
+    impl<DB> diesel::serialize::ToSql<diesel::sql_types::Integer, DB> for WebhookFormat
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::serialize::ToSql<diesel::sql_types::Integer, DB>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            match self {
+                Self::Json => JSON_INT.to_sql(out),
+                Self::Slack => SLACK_INT.to_sql(out),
+                Self::Teams => TEAMS_INT.to_sql(out),
+                Self::Matrix => MATRIX_INT.to_sql(out),
+            }
+        }
+    }
+
+    impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Integer, DB> for WebhookFormat
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            match i32::from_sql(bytes)? {
+                JSON_INT => Ok(Self::Json),
+                SLACK_INT => Ok(Self::Slack),
+                TEAMS_INT => Ok(Self::Teams),
+                MATRIX_INT => Ok(Self::Matrix),
+                value => Err(Box::new(WebhookFormatError::Invalid(value))),
+            }
+        }
+    }
+}
+
+const ALERT_CREATED_INT: i32 = 0;
+const ALERT_DISMISSED_INT: i32 = 1;
+const REPORT_CREATED_INT: i32 = 10;
+const THRESHOLD_UPDATED_INT: i32 = 20;
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
+#[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Integer))]
+#[repr(i32)]
+pub enum WebhookEvent {
+    /// An alert was raised for a new report.
+    #[serde(rename = "alert.created")]
+    #[display("alert.created")]
+    AlertCreated = ALERT_CREATED_INT,
+    /// An alert was dismissed by a user.
+    #[serde(rename = "alert.dismissed")]
+    #[display("alert.dismissed")]
+    AlertDismissed = ALERT_DISMISSED_INT,
+    /// A new report was created.
+    #[serde(rename = "report.created")]
+    #[display("report.created")]
+    ReportCreated = REPORT_CREATED_INT,
+    /// The model for a threshold was updated by a user.
+    #[serde(rename = "threshold.updated")]
+    #[display("threshold.updated")]
+    ThresholdUpdated = THRESHOLD_UPDATED_INT,
+}
+
+#[cfg(feature = "db")]
+mod webhook_event {
+    use super::{
+        ALERT_CREATED_INT, ALERT_DISMISSED_INT, REPORT_CREATED_INT, THRESHOLD_UPDATED_INT,
+        WebhookEvent,
+    };
+
+    #[derive(Debug, thiserror::Error)]
+    pub enum WebhookEventError {
+        #[error("Invalid webhook event value: {0}")]
+        Invalid(i32),
+    }
+
+    impl<DB> diesel::serialize::ToSql<diesel::sql_types::Integer, DB> for WebhookEvent
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::serialize::ToSql<diesel::sql_types::Integer, DB>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            match self {
+                Self::AlertCreated => ALERT_CREATED_INT.to_sql(out),
+                Self::AlertDismissed => ALERT_DISMISSED_INT.to_sql(out),
+                Self::ReportCreated => REPORT_CREATED_INT.to_sql(out),
+                Self::ThresholdUpdated => THRESHOLD_UPDATED_INT.to_sql(out),
+            }
+        }
+    }
+
+    impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Integer, DB> for WebhookEvent
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            match i32::from_sql(bytes)? {
+                ALERT_CREATED_INT => Ok(Self::AlertCreated),
+                ALERT_DISMISSED_INT => Ok(Self::AlertDismissed),
+                REPORT_CREATED_INT => Ok(Self::ReportCreated),
+                THRESHOLD_UPDATED_INT => Ok(Self::ThresholdUpdated),
+                value => Err(Box::new(WebhookEventError::Invalid(value))),
+            }
+        }
+    }
+}
+
+/// The payload that is sent to a webhook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonWebhookPayload {
+    /// The UUID of the webhook delivery.
+    pub uuid: WebhookDeliveryUuid,
+    pub project: ProjectUuid,
+    #[serde(flatten)]
+    pub event: JsonWebhookEventData,
+    pub created: DateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(tag = "event", content = "data")]
+pub enum JsonWebhookEventData {
+    #[serde(rename = "alert.created")]
+    AlertCreated(Box<JsonAlert>),
+    #[serde(rename = "alert.dismissed")]
+    AlertDismissed(Box<JsonAlert>),
+    #[serde(rename = "report.created")]
+    ReportCreated(Box<JsonReport>),
+    #[serde(rename = "threshold.updated")]
+    ThresholdUpdated(Box<JsonThreshold>),
+}
+
+impl JsonWebhookEventData {
+    pub fn event(&self) -> WebhookEvent {
+        match self {
+            Self::AlertCreated(_) => WebhookEvent::AlertCreated,
+            Self::AlertDismissed(_) => WebhookEvent::AlertDismissed,
+            Self::ReportCreated(_) => WebhookEvent::ReportCreated,
+            Self::ThresholdUpdated(_) => WebhookEvent::ThresholdUpdated,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonWebhookDeliveries(pub Vec<JsonWebhookDelivery>);
+
+crate::from_vec!(JsonWebhookDeliveries[JsonWebhookDelivery]);
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonWebhookDelivery {
+    pub uuid: WebhookDeliveryUuid,
+    pub webhook: WebhookUuid,
+    pub event: WebhookEvent,
+    pub status: WebhookDeliveryStatus,
+    /// The number of delivery attempts that have been made.
+    pub attempts: u32,
+    /// The HTTP status code of the response to the last delivery attempt, if any.
+    pub response_status: Option<u16>,
+    /// The error for the last delivery attempt, if it failed.
+    pub error: Option<String>,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+const PENDING_INT: i32 = 0;
+const DELIVERED_INT: i32 = 1;
+const FAILED_INT: i32 = 2;
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
+#[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Integer))]
+#[serde(rename_all = "snake_case")]
+#[repr(i32)]
+pub enum WebhookDeliveryStatus {
+    /// The delivery has not succeeded yet and will be retried.
+    #[display("pending")]
+    Pending = PENDING_INT,
+    /// The webhook responded with a successful status code.
+    #[display("delivered")]
+    Delivered = DELIVERED_INT,
+    /// All of the delivery attempts failed.
+    #[display("failed")]
+    Failed = FAILED_INT,
+}
+
+#[cfg(feature = "db")]
+mod webhook_delivery_status {
+    use super::{DELIVERED_INT, FAILED_INT, PENDING_INT, WebhookDeliveryStatus};
+
+    #[derive(Debug, thiserror::Error)]
+    pub enum WebhookDeliveryStatusError {
+        #[error("Invalid webhook delivery status value: {0}")]
+        Invalid(i32),
+    }
+
+    impl<DB> diesel::serialize::ToSql<diesel::sql_types::Integer, DB> for WebhookDeliveryStatus
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::serialize::ToSql<diesel::sql_types::Integer, DB>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            match self {
+                Self::Pending => PENDING_INT.to_sql(out),
+                Self::Delivered => DELIVERED_INT.to_sql(out),
+                Self::Failed => FAILED_INT.to_sql(out),
+            }
+        }
+    }
+
+    impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Integer, DB> for WebhookDeliveryStatus
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            match i32::from_sql(bytes)? {
+                PENDING_INT => Ok(Self::Pending),
+                DELIVERED_INT => Ok(Self::Delivered),
+                FAILED_INT => Ok(Self::Failed),
+                value => Err(Box::new(WebhookDeliveryStatusError::Invalid(value))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{JsonWebhookEvents, WebhookDeliveryStatus, WebhookEvent, WebhookFormat};
+
+    #[test]
+    fn test_webhook_event_serde() {
+        for (event, name) in [
+            (WebhookEvent::AlertCreated, "alert.created"),
+            (WebhookEvent::AlertDismissed, "alert.dismissed"),
+            (WebhookEvent::ReportCreated, "report.created"),
+            (WebhookEvent::ThresholdUpdated, "threshold.updated"),
+        ] {
+            // The event header uses the display name, so it must match the payload
+            assert_eq!(event.to_string(), name);
+            let json = serde_json::to_string(&event).unwrap();
+            assert_eq!(json, format!("\"{name}\""));
+            assert_eq!(serde_json::from_str::<WebhookEvent>(&json).unwrap(), event);
+        }
+        assert!(serde_json::from_str::<WebhookEvent>("\"alert_created\"").is_err());
+    }
+
+    #[test]
+    fn test_webhook_events() {
+        let events: JsonWebhookEvents =
+            serde_json::from_str(r#"["report.created", "alert.created"]"#).unwrap();
+        assert!(events.contains(WebhookEvent::ReportCreated));
+        assert!(events.contains(WebhookEvent::AlertCreated));
+        assert!(!events.contains(WebhookEvent::AlertDismissed));
+        assert!(!events.contains(WebhookEvent::ThresholdUpdated));
+    }
+
+    #[test]
+    fn test_webhook_format() {
+        assert_eq!(WebhookFormat::default(), WebhookFormat::Json);
+        assert!(!WebhookFormat::Json.is_chat());
+        for (format, name) in [
+            (WebhookFormat::Json, "json"),
+            (WebhookFormat::Slack, "slack"),
+            (WebhookFormat::Teams, "teams"),
+            (WebhookFormat::Matrix, "matrix"),
+        ] {
+            assert_eq!(format.to_string(), name);
+            assert_eq!(
+                serde_json::from_str::<WebhookFormat>(&format!("\"{name}\"")).unwrap(),
+                format
+            );
+            assert_eq!(format.is_chat(), format != WebhookFormat::Json);
+        }
+    }
+
+    #[test]
+    fn test_webhook_delivery_status() {
+        for (status, name) in [
+            (WebhookDeliveryStatus::Pending, "pending"),
+            (WebhookDeliveryStatus::Delivered, "delivered"),
+            (WebhookDeliveryStatus::Failed, "failed"),
+        ] {
+            assert_eq!(status.to_string(), name);
+            assert_eq!(
+                serde_json::to_string(&status).unwrap(),
+                format!("\"{name}\"")
+            );
+        }
+    }
+}
@@ -14,7 +14,7 @@ use crate::{
 
 #[cfg(feature = "server")]
 use crate::{
-    JsonNewReport,
+    JsonNewReport, JsonTestbedHardware,
     project::{branch::DEFAULT_BRANCH, testbed::DEFAULT_TESTBED},
 };
 
@@ -99,6 +99,10 @@ impl From<JsonNewRun> for JsonNewReport {
         // TODO eventually there should be a `ReportContext` type
         // this type should include user defined context and system context
         // Some of the Bencher provided context should be filtered out, like the full fingerprint
+        let hardware = context
+            .as_ref()
+            .map(JsonTestbedHardware::from)
+            .filter(|hardware| !hardware.is_empty());
         Self {
             branch,
             hash,
@@ -109,6 +113,7 @@ impl From<JsonNewRun> for JsonNewReport {
             end_time,
             results,
             settings,
+            hardware,
         }
     }
 }
@@ -1,6 +1,6 @@
-use bencher_valid::{DateTime, Email, Jwt, Slug, UserName};
+use bencher_valid::{DateTime, Email, Jwt, ResourceId, Secret, Slug, UserName};
 #[cfg(feature = "plus")]
-use bencher_valid::{PlanLevel, Secret};
+use bencher_valid::{PlanLevel, Url};
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -44,6 +44,14 @@ pub struct JsonOAuth {
     pub claim: Option<OrganizationUuid>,
 }
 
+#[cfg(feature = "plus")]
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonOAuthUrl {
+    pub url: Url,
+}
+
 #[typeshare::typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -74,3 +82,14 @@ pub struct JsonAuthUser {
     pub creation: DateTime,
     pub expiration: DateTime,
 }
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonCiAuth {
+    /// The slug or UUID for the project to create a CI token for.
+    pub project: ResourceId,
+    /// The OpenID Connect ID token issued by the CI provider.
+    /// The audience (`aud`) of the ID token must be the Bencher Console URL.
+    pub id_token: Secret,
+}
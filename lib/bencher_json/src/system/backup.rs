@@ -19,15 +19,38 @@ pub struct JsonBackup {
     pub rm: Option<bool>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum JsonDataStore {
     AwsS3,
+    Local,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct JsonBackupCreated {
     pub created: DateTime,
+    /// The database backup that was saved to the data store, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup: Option<JsonBackupFile>,
+}
+
+/// The manifest of all database backups in a data store
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonBackupManifest {
+    pub backups: Vec<JsonBackupFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonBackupFile {
+    /// The file name of the database backup in the data store.
+    pub file_name: String,
+    pub created: DateTime,
+    /// The size of the database backup in bytes.
+    pub size: u64,
+    /// The hex encoded SHA-256 checksum of the database backup.
+    pub sha256: String,
 }
@@ -11,6 +11,8 @@ pub struct JsonDatabase {
     pub file: PathBuf,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data_store: Option<DataStore>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup: Option<JsonDatabaseBackup>,
 }
 
 impl Sanitize for JsonDatabase {
@@ -30,6 +32,10 @@ pub enum DataStore {
         // https://docs.aws.amazon.com/AmazonS3/latest/userguide/using-access-points.html
         access_point: String,
     },
+    Local {
+        // A local directory, such as an NFS mount
+        path: PathBuf,
+    },
 }
 
 impl Sanitize for DataStore {
@@ -38,6 +44,32 @@ impl Sanitize for DataStore {
             Self::AwsS3 {
                 secret_access_key, ..
             } => secret_access_key.sanitize(),
+            Self::Local { .. } => {},
         }
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonDatabaseBackup {
+    /// The interval between scheduled backups to the data store, in seconds.
+    /// If not set, backups are only created on demand.
+    pub interval: Option<u64>,
+    /// Compress scheduled backups with gzip.
+    pub compress: Option<bool>,
+    /// Which backups to keep in the data store.
+    /// If not set, all backups are kept.
+    pub retention: Option<JsonBackupRetention>,
+    /// The maximum size of an uploaded database backup to restore, in bytes.
+    /// Defaults to 8 GiB.
+    pub max_upload_size: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonBackupRetention {
+    /// The number of most recent days to keep the latest backup for.
+    pub daily: u32,
+    /// The number of most recent weeks to keep the latest backup for.
+    pub weekly: u32,
+}
@@ -12,7 +12,7 @@ mod server;
 mod smtp;
 
 pub use console::JsonConsole;
-pub use database::{DataStore, JsonDatabase};
+pub use database::{DataStore, JsonBackupRetention, JsonDatabase, JsonDatabaseBackup};
 pub use logging::{IfExists, JsonLogging, LogLevel, ServerLog};
 #[cfg(feature = "plus")]
 pub use plus::{
@@ -8,12 +8,14 @@ use serde::{Deserialize, Serialize};
 pub mod cloud;
 pub mod github;
 pub mod litestream;
+pub mod oidc;
 pub mod rate_limiting;
 pub mod stats;
 
 pub use cloud::JsonCloud;
 pub use github::JsonGitHub;
 pub use litestream::JsonLitestream;
+pub use oidc::JsonOidc;
 pub use rate_limiting::JsonRateLimiting;
 pub use stats::JsonStats;
 
@@ -24,6 +26,8 @@ pub struct JsonPlus {
     pub rate_limiting: Option<JsonRateLimiting>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub github: Option<JsonGitHub>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oidc: Option<JsonOidc>,
     #[serde(alias = "disaster_recovery", skip_serializing_if = "Option::is_none")]
     pub litestream: Option<JsonLitestream>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -35,6 +39,7 @@ pub struct JsonPlus {
 impl Sanitize for JsonPlus {
     fn sanitize(&mut self) {
         self.github.sanitize();
+        self.oidc.sanitize();
         self.litestream.sanitize();
         self.cloud.sanitize();
     }
@@ -0,0 +1,36 @@
+use bencher_valid::{NonEmpty, Sanitize, Secret, Url};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonOidc {
+    /// The OpenID Connect issuer URL used for discovery
+    pub issuer: Url,
+    pub client_id: NonEmpty,
+    pub client_secret: Secret,
+    /// The scopes to request in addition to `openid`
+    /// Defaults to `email` and `profile`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<NonEmpty>>,
+    /// The claim to use for the user email
+    /// Defaults to `email`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_claim: Option<NonEmpty>,
+    /// The claim to use for the user name
+    /// Defaults to `name`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name_claim: Option<NonEmpty>,
+    /// Trust the email claim even if the provider does not set `email_verified` to `true`
+    /// Only enable this if the provider verifies every email it issues
+    /// Defaults to `false`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trust_unverified_email: Option<bool>,
+}
+
+impl Sanitize for JsonOidc {
+    fn sanitize(&mut self) {
+        self.client_secret.sanitize();
+    }
+}
@@ -9,6 +9,10 @@ pub struct JsonSecurity {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub issuer: Option<String>,
     pub secret_key: Secret,
+    /// Allow webhooks and CI trust policy issuers to use loopback, link-local, and private network addresses.
+    /// This should only be enabled for a self-hosted server that is on a trusted network.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_private_webhooks: Option<bool>,
 }
 
 impl Sanitize for JsonSecurity {
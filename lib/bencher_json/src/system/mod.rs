@@ -3,6 +3,7 @@ pub mod backup;
 pub mod config;
 pub mod payment;
 pub mod restart;
+pub mod restore;
 pub mod server;
 pub mod spec;
 pub mod version;
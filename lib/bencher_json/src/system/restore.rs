@@ -0,0 +1,29 @@
+use bencher_valid::DateTime;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::backup::JsonBackupFile;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonRestore {
+    /// The file name of the database backup in the data store to restore.
+    /// Defaults to the latest backup in the data store manifest, if not specified.
+    pub backup: Option<String>,
+    /// The delay in seconds before the server restarts.
+    /// Defaults to 3 seconds, if not specified.
+    pub delay: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonRestored {
+    pub restored: DateTime,
+    /// The database backup from the data store that was restored, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup: Option<JsonBackupFile>,
+    /// The file name that the previous database was kept aside as,
+    /// in the same directory as the database.
+    pub previous: String,
+}
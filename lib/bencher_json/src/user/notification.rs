@@ -0,0 +1,92 @@
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{ProjectUuid, UserUuid};
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNotification {
+    pub user: UserUuid,
+    pub project: ProjectUuid,
+    /// How the user is notified by email when an alert is generated for the project.
+    pub alerts: NotificationMode,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonUpdateNotification {
+    /// How the user should be notified by email when an alert is generated for the project.
+    pub alerts: Option<NotificationMode>,
+}
+
+const IMMEDIATE_INT: i32 = 0;
+const DIGEST_INT: i32 = 1;
+const OFF_INT: i32 = 2;
+
+#[typeshare::typeshare]
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, derive_more::Display, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
+#[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Integer))]
+#[serde(rename_all = "snake_case")]
+#[repr(i32)]
+pub enum NotificationMode {
+    /// Send an email as soon as an alert is generated.
+    #[display("immediate")]
+    Immediate = IMMEDIATE_INT,
+    /// Send a daily digest email with all of the alerts generated in the last day.
+    #[display("digest")]
+    Digest = DIGEST_INT,
+    /// Do not send any emails.
+    #[default]
+    #[display("off")]
+    Off = OFF_INT,
+}
+
+#[cfg(feature = "db")]
+mod notification_mode {
+    use super::{DIGEST_INT, IMMEDIATE_INT, NotificationMode, OFF_INT};
+
+    #[derive(Debug, thiserror::Error)]
+    pub enum NotificationModeError {
+        #[error("Invalid notification mode value: {0}")]
+        Invalid(i32),
+    }
+
+    impl<DB> diesel::serialize::ToSql<diesel::sql_types::Integer, DB> for NotificationMode
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::serialize::ToSql<diesel::sql_types::Integer, DB>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            match self {
+                Self::Immediate => IMMEDIATE_INT.to_sql(out),
+                Self::Digest => DIGEST_INT.to_sql(out),
+                Self::Off => OFF_INT.to_sql(out),
+            }
+        }
+    }
+
+    impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Integer, DB> for NotificationMode
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            match i32::from_sql(bytes)? {
+                IMMEDIATE_INT => Ok(Self::Immediate),
+                DIGEST_INT => Ok(Self::Digest),
+                OFF_INT => Ok(Self::Off),
+                value => Err(Box::new(NotificationModeError::Invalid(value))),
+            }
+        }
+    }
+}
@@ -1,9 +1,9 @@
-use bencher_valid::{DateTime, Jwt, ResourceName};
+use bencher_valid::{DateTime, Jwt, NonEmpty, ResourceName};
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::UserUuid;
+use crate::{ProjectUuid, ResourceId, UserUuid, project::ProjectPermission};
 
 crate::typed_uuid::typed_uuid!(TokenUuid);
 
@@ -17,6 +17,21 @@ pub struct JsonNewToken {
     /// The time-to-live (TTL) for the token in seconds.
     /// If not provided, the token will not expire for over 128 years.
     pub ttl: Option<u32>,
+    /// The slug or UUID of a project to scope the token to.
+    /// A scoped token can only access this one project.
+    pub project: Option<ResourceId>,
+    /// The project permissions granted to a scoped token.
+    /// Requires `project` to be set.
+    /// If not provided, a scoped token is only granted the `view` permission.
+    pub permissions: Option<Vec<ProjectPermission>>,
+    /// Create a CI token.
+    /// A CI token can only be used to create runs for the `project` and to view the reports that it created.
+    /// Requires `project` to be set and can not be used with `permissions`.
+    pub ci: Option<bool>,
+    /// The branch name pattern that a CI token is limited to.
+    /// A `*` matches any sequence of characters.
+    /// Requires `ci` to be set.
+    pub branch: Option<NonEmpty>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +50,61 @@ pub struct JsonToken {
     pub token: Jwt,
     pub creation: DateTime,
     pub expiration: DateTime,
+    /// The project that the token is scoped to, if any.
+    pub project: Option<ProjectUuid>,
+    /// The project permissions granted to the token, if it is scoped.
+    pub permissions: Option<JsonTokenPermissions>,
+    /// The last time that the token was used, to within a minute.
+    pub last_used: Option<DateTime>,
+    /// The IP address that the token was last used from.
+    pub last_used_ip: Option<String>,
+    /// The time that the token was revoked.
+    /// A revoked token can no longer be used.
+    pub revoked: Option<DateTime>,
+    /// Whether the token is a CI token.
+    pub ci: bool,
+    /// The branch name pattern that a CI token is limited to, if any.
+    pub branch: Option<NonEmpty>,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
+#[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Text))]
+pub struct JsonTokenPermissions(pub Vec<ProjectPermission>);
+
+crate::from_vec!(JsonTokenPermissions[ProjectPermission]);
+
+#[cfg(feature = "db")]
+mod token_permissions {
+    use super::JsonTokenPermissions;
+
+    impl<DB> diesel::serialize::ToSql<diesel::sql_types::Text, DB> for JsonTokenPermissions
+    where
+        DB: diesel::backend::Backend,
+        for<'a> String: diesel::serialize::ToSql<diesel::sql_types::Text, DB>
+            + Into<<DB::BindCollector<'a> as diesel::query_builder::BindCollector<'a, DB>>::Buffer>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            out.set_value(serde_json::to_string(self)?);
+            Ok(diesel::serialize::IsNull::No)
+        }
+    }
+
+    impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Text, DB> for JsonTokenPermissions
+    where
+        DB: diesel::backend::Backend,
+        String: diesel::deserialize::FromSql<diesel::sql_types::Text, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            let permissions = String::from_sql(bytes)?;
+            Ok(serde_json::from_str(&permissions)?)
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,185 @@
+use jsonwebtoken::{DecodingKey, Validation, decode};
+use serde::Deserialize;
+
+use crate::OidcError;
+
+/// The claims of an ID token issued to a CI job
+/// GitHub Actions: <https://docs.github.com/en/actions/security-for-github-actions/security-hardening-your-deployments/about-security-hardening-with-openid-connect#understanding-the-oidc-token>
+/// GitLab CI/CD: <https://docs.gitlab.com/ci/secrets/id_token_authentication/#token-payload>
+#[derive(Debug, Clone, Deserialize)]
+pub struct CiClaims {
+    pub sub: String,
+    /// GitHub Actions repository, for example `bencherdev/bencher`
+    pub repository: Option<String>,
+    /// GitLab CI/CD project path, for example `bencherdev/bencher`
+    pub project_path: Option<String>,
+    /// The git ref, for example `refs/heads/main` for GitHub Actions or `main` for GitLab CI/CD
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+    /// GitHub Actions workflow ref, for example `bencherdev/bencher/.github/workflows/bench.yml@refs/heads/main`
+    pub workflow_ref: Option<String>,
+    /// GitLab CI/CD config ref URI, for example `gitlab.com/bencherdev/bencher//.gitlab-ci.yml@refs/heads/main`
+    pub ci_config_ref_uri: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct IssuerClaim {
+    iss: String,
+}
+
+/// Get the issuer of an ID token without verifying it.
+/// This must only be used to find the issuer to verify the ID token against.
+pub fn unverified_issuer(id_token: &str) -> Result<String, OidcError> {
+    let mut validation = Validation::default();
+    validation.insecure_disable_signature_validation();
+    validation.validate_aud = false;
+    validation.validate_exp = false;
+    validation.required_spec_claims.clear();
+    decode::<IssuerClaim>(id_token, &DecodingKey::from_secret(&[]), &validation)
+        .map(|token_data| token_data.claims.iss)
+        .map_err(OidcError::IdToken)
+}
+
+impl CiClaims {
+    /// The repository for GitHub Actions or the project path for GitLab CI/CD
+    pub fn repository(&self) -> Option<&str> {
+        self.repository.as_deref().or(self.project_path.as_deref())
+    }
+
+    pub fn git_ref(&self) -> Option<&str> {
+        self.git_ref.as_deref()
+    }
+
+    /// The path to the workflow file for GitHub Actions or the CI/CD config file for GitLab CI/CD
+    pub fn workflow(&self) -> Option<&str> {
+        if let Some(workflow_ref) = self.workflow_ref.as_deref() {
+            // `{repository}/{path}@{ref}`
+            let (workflow, _) = workflow_ref.split_once('@')?;
+            self.repository
+                .as_deref()
+                .and_then(|repository| workflow.strip_prefix(repository))
+                .and_then(|path| path.strip_prefix('/'))
+        } else if let Some(ci_config_ref_uri) = self.ci_config_ref_uri.as_deref() {
+            // `{host}/{project_path}//{path}@{ref}`
+            let (config, _) = ci_config_ref_uri.split_once('@')?;
+            config.split_once("//").map(|(_, path)| path)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oauth2::reqwest;
+    use serde_json::{Value, json};
+
+    use super::{CiClaims, unverified_issuer};
+    use crate::{
+        Issuer, OidcError,
+        mock::{CLIENT_ID, mock_oidc_server},
+    };
+
+    async fn mock_id_token(claims: Value) -> (Issuer, String) {
+        let issuer = mock_oidc_server(claims, json!({})).await;
+        let bytes = reqwest::get(format!("{issuer}token"))
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+        let token: Value = serde_json::from_slice(&bytes).unwrap();
+        let id_token = token
+            .get("id_token")
+            .and_then(Value::as_str)
+            .unwrap()
+            .to_owned();
+        (Issuer::new(issuer), id_token)
+    }
+
+    #[tokio::test]
+    async fn test_ci_github_actions() {
+        let (issuer, id_token) = mock_id_token(json!({
+            "repository": "bencherdev/bencher",
+            "ref": "refs/heads/main",
+            "workflow_ref": "bencherdev/bencher/.github/workflows/bench.yml@refs/heads/main",
+        }))
+        .await;
+
+        assert_eq!(unverified_issuer(&id_token).unwrap(), issuer.as_str());
+        let claims = issuer
+            .verify_ci_id_token(CLIENT_ID, &id_token, |_| true)
+            .await
+            .unwrap();
+        assert_eq!(claims.repository(), Some("bencherdev/bencher"));
+        assert_eq!(claims.git_ref(), Some("refs/heads/main"));
+        assert_eq!(claims.workflow(), Some(".github/workflows/bench.yml"));
+    }
+
+    #[tokio::test]
+    async fn test_ci_gitlab_ci() {
+        let (issuer, id_token) = mock_id_token(json!({
+            "project_path": "bencherdev/bencher",
+            "ref": "main",
+            "ci_config_ref_uri": "gitlab.com/bencherdev/bencher//.gitlab-ci.yml@refs/heads/main",
+        }))
+        .await;
+
+        let claims = issuer
+            .verify_ci_id_token(CLIENT_ID, &id_token, |_| true)
+            .await
+            .unwrap();
+        assert_eq!(claims.repository(), Some("bencherdev/bencher"));
+        assert_eq!(claims.git_ref(), Some("main"));
+        assert_eq!(claims.workflow(), Some(".gitlab-ci.yml"));
+    }
+
+    #[tokio::test]
+    async fn test_ci_bad_audience() {
+        let (issuer, id_token) = mock_id_token(json!({
+            "repository": "bencherdev/bencher",
+            "aud": "https://other.example.com",
+        }))
+        .await;
+
+        let err = issuer
+            .verify_ci_id_token(CLIENT_ID, &id_token, |_| true)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, OidcError::IdToken(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_ci_forbidden_url() {
+        let (issuer, id_token) = mock_id_token(json!({
+            "repository": "bencherdev/bencher",
+        }))
+        .await;
+
+        let err = issuer
+            .verify_ci_id_token(CLIENT_ID, &id_token, |_| false)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, OidcError::ForbiddenUrl(_)), "{err}");
+
+        // The JWKS URL is checked after discovery
+        let issuer_url = issuer.as_str().to_owned();
+        let err = issuer
+            .verify_ci_id_token(CLIENT_ID, &id_token, |url| {
+                url.as_str().trim_end_matches('/') == issuer_url
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, OidcError::ForbiddenUrl(_)), "{err}");
+    }
+
+    #[test]
+    fn test_ci_workflow_missing() {
+        let claims: CiClaims = serde_json::from_value(json!({
+            "sub": "repo:bencherdev/bencher:ref:refs/heads/main",
+            "repository": "bencherdev/bencher",
+        }))
+        .unwrap();
+        assert_eq!(claims.workflow(), None);
+    }
+}
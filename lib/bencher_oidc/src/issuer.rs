@@ -0,0 +1,138 @@
+use jsonwebtoken::{DecodingKey, Validation, decode, decode_header, jwk::JwkSet};
+use oauth2::reqwest;
+use serde::{Deserialize, de::DeserializeOwned};
+use url::Url;
+
+use crate::{CiClaims, OidcError, http_client};
+
+const DISCOVERY_PATH: &str = ".well-known/openid-configuration";
+
+/// An OpenID Connect issuer
+#[derive(Debug, Clone)]
+pub struct Issuer {
+    url: Url,
+}
+
+/// The subset of the OpenID Connect provider metadata that Bencher uses
+/// <https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderMetadata>
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProviderMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: Option<Url>,
+    pub token_endpoint: Option<Url>,
+    pub userinfo_endpoint: Option<Url>,
+    pub jwks_uri: Url,
+}
+
+impl Issuer {
+    pub fn new(url: Url) -> Self {
+        Self { url }
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.url.as_str().trim_end_matches('/')
+    }
+
+    /// Fetch the provider metadata for the issuer
+    pub async fn discover(
+        &self,
+        http_client: &reqwest::Client,
+    ) -> Result<ProviderMetadata, OidcError> {
+        let discovery_url = format!("{issuer}/{DISCOVERY_PATH}", issuer = self.as_str());
+        let metadata: ProviderMetadata = get_json(http_client, &discovery_url).await?;
+        // The issuer in the metadata must exactly match the issuer used to discover it
+        if metadata.issuer.trim_end_matches('/') == self.as_str() {
+            Ok(metadata)
+        } else {
+            Err(OidcError::IssuerMismatch {
+                expected: self.as_str().to_owned(),
+                actual: metadata.issuer,
+            })
+        }
+    }
+
+    /// Verify an ID token issued to a CI job by the issuer
+    ///
+    /// Both the issuer URL and the discovered JWKS URL must pass `is_allowed_url`
+    /// before they are requested.
+    pub async fn verify_ci_id_token<F>(
+        &self,
+        audience: &str,
+        id_token: &str,
+        is_allowed_url: F,
+    ) -> Result<CiClaims, OidcError>
+    where
+        F: Fn(&Url) -> bool,
+    {
+        if !is_allowed_url(&self.url) {
+            return Err(OidcError::ForbiddenUrl(self.url.clone()));
+        }
+        let http_client = http_client()?;
+        let metadata = self.discover(&http_client).await?;
+        if !is_allowed_url(&metadata.jwks_uri) {
+            return Err(OidcError::ForbiddenUrl(metadata.jwks_uri));
+        }
+        metadata
+            .verify_id_token(&http_client, audience, id_token)
+            .await
+    }
+}
+
+impl ProviderMetadata {
+    /// Verify the signature and standard claims of an ID token
+    /// using the JSON Web Key Set (JWKS) of the provider.
+    pub async fn verify_id_token<C>(
+        &self,
+        http_client: &reqwest::Client,
+        audience: &str,
+        id_token: &str,
+    ) -> Result<C, OidcError>
+    where
+        C: DeserializeOwned,
+    {
+        let header = decode_header(id_token).map_err(OidcError::IdToken)?;
+        let jwks: JwkSet = get_json(http_client, self.jwks_uri.as_str()).await?;
+        let jwk = if let Some(kid) = header.kid.as_deref() {
+            jwks.find(kid)
+        } else if let [jwk] = jwks.keys.as_slice() {
+            Some(jwk)
+        } else {
+            None
+        }
+        .ok_or_else(|| OidcError::NoJwk(header.kid.clone()))?;
+        let decoding_key = DecodingKey::from_jwk(jwk).map_err(OidcError::Jwk)?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_audience(&[audience]);
+        validation.set_issuer(&[self.issuer.as_str()]);
+        validation.set_required_spec_claims(&["aud", "exp", "iss", "sub"]);
+        decode::<C>(id_token, &decoding_key, &validation)
+            .map(|token_data| token_data.claims)
+            .map_err(OidcError::IdToken)
+    }
+}
+
+pub(crate) async fn get_json<T>(http_client: &reqwest::Client, url: &str) -> Result<T, OidcError>
+where
+    T: DeserializeOwned,
+{
+    let bytes = http_client
+        .get(url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| OidcError::Request {
+            url: url.to_owned(),
+            error: e,
+        })?
+        .bytes()
+        .await
+        .map_err(|e| OidcError::Request {
+            url: url.to_owned(),
+            error: e,
+        })?;
+    serde_json::from_slice(&bytes).map_err(|e| OidcError::Json {
+        url: url.to_owned(),
+        error: e,
+    })
+}
@@ -0,0 +1,422 @@
+use bencher_valid::{Email, NonEmpty, Secret, UserName};
+use oauth2::{
+    AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken, EndpointNotSet, EndpointSet,
+    ExtraTokenFields, RedirectUrl, Scope, StandardRevocableToken, StandardTokenResponse,
+    TokenResponse as _, TokenUrl,
+    basic::{
+        BasicErrorResponse, BasicRevocationErrorResponse, BasicTokenIntrospectionResponse,
+        BasicTokenType,
+    },
+    reqwest,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use url::Url;
+
+mod ci;
+mod issuer;
+#[cfg(test)]
+mod mock;
+
+pub use ci::{CiClaims, unverified_issuer};
+pub use issuer::{Issuer, ProviderMetadata};
+
+const OPENID_SCOPE: &str = "openid";
+const DEFAULT_SCOPES: [&str; 2] = ["email", "profile"];
+const DEFAULT_EMAIL_CLAIM: &str = "email";
+const DEFAULT_NAME_CLAIM: &str = "name";
+const EMAIL_VERIFIED_CLAIM: &str = "email_verified";
+
+type OidcClient = oauth2::Client<
+    BasicErrorResponse,
+    OidcTokenResponse,
+    BasicTokenIntrospectionResponse,
+    StandardRevocableToken,
+    BasicRevocationErrorResponse,
+    EndpointNotSet,
+    EndpointNotSet,
+    EndpointNotSet,
+    EndpointNotSet,
+    EndpointSet,
+>;
+type OidcTokenResponse = StandardTokenResponse<IdTokenFields, BasicTokenType>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdTokenFields {
+    id_token: Option<String>,
+}
+
+impl ExtraTokenFields for IdTokenFields {}
+
+/// A generic OpenID Connect provider used for authentication
+#[derive(Debug, Clone)]
+pub struct Oidc {
+    issuer: Issuer,
+    client_id: ClientId,
+    client_secret: ClientSecret,
+    scopes: Vec<Scope>,
+    email_claim: String,
+    name_claim: String,
+    trust_unverified_email: bool,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum OidcError {
+    #[error("Failed to create a reqwest client: {0}")]
+    Reqwest(reqwest::Error),
+    #[error("Failed to request ({url}): {error}")]
+    Request { url: String, error: reqwest::Error },
+    #[error("Failed to parse JSON from ({url}): {error}")]
+    Json {
+        url: String,
+        error: serde_json::Error,
+    },
+    #[error("The discovered issuer ({actual}) does not match the configured issuer ({expected})")]
+    IssuerMismatch { expected: String, actual: String },
+    #[error("The OpenID Connect provider URL is not allowed: {0}")]
+    ForbiddenUrl(Url),
+    #[error("The OpenID Connect provider does not have an {0} endpoint")]
+    MissingEndpoint(&'static str),
+    #[error("Invalid OpenID Connect provider endpoint URL: {0}")]
+    BadEndpoint(url::ParseError),
+    #[error("Failed to exchange code for access token: {0}")]
+    Exchange(
+        oauth2::RequestTokenError<
+            oauth2::HttpClientError<reqwest::Error>,
+            oauth2::StandardErrorResponse<oauth2::basic::BasicErrorResponseType>,
+        >,
+    ),
+    #[error("No JSON Web Key found for ID token key ID ({0:?})")]
+    NoJwk(Option<String>),
+    #[error("Invalid JSON Web Key: {0}")]
+    Jwk(jsonwebtoken::errors::Error),
+    #[error("Invalid ID token: {0}")]
+    IdToken(jsonwebtoken::errors::Error),
+    #[error("The user info is missing the email claim ({0})")]
+    MissingEmail(String),
+    #[error("The email for the user ({0}) has not been verified")]
+    UnverifiedEmail(String),
+    #[error("Failed to parse the email for the user: {0}")]
+    BadEmail(bencher_valid::ValidError),
+    #[error("Failed to parse the name for the user: {0}")]
+    BadName(bencher_valid::ValidError),
+}
+
+impl Oidc {
+    pub fn new(
+        issuer: Url,
+        client_id: NonEmpty,
+        client_secret: Secret,
+        scopes: Option<Vec<NonEmpty>>,
+        email_claim: Option<NonEmpty>,
+        name_claim: Option<NonEmpty>,
+        trust_unverified_email: bool,
+    ) -> Self {
+        let scopes = scopes.map_or_else(
+            || DEFAULT_SCOPES.iter().map(|&s| s.to_owned()).collect(),
+            |scopes| scopes.into_iter().map(Into::into).collect::<Vec<String>>(),
+        );
+        let scopes = std::iter::once(OPENID_SCOPE.to_owned())
+            .chain(scopes.into_iter().filter(|scope| scope != OPENID_SCOPE))
+            .map(Scope::new)
+            .collect();
+        Self {
+            issuer: Issuer::new(issuer),
+            client_id: ClientId::new(client_id.into()),
+            client_secret: ClientSecret::new(client_secret.into()),
+            scopes,
+            email_claim: email_claim.map_or_else(|| DEFAULT_EMAIL_CLAIM.to_owned(), Into::into),
+            name_claim: name_claim.map_or_else(|| DEFAULT_NAME_CLAIM.to_owned(), Into::into),
+            trust_unverified_email,
+        }
+    }
+
+    pub fn issuer(&self) -> &Issuer {
+        &self.issuer
+    }
+
+    /// Create the URL to redirect the user to in order to authenticate with the provider
+    pub async fn authorization_url(
+        &self,
+        redirect_url: Url,
+        state: Option<String>,
+    ) -> Result<Url, OidcError> {
+        let http_client = http_client()?;
+        let metadata = self.issuer.discover(&http_client).await?;
+        let auth_url = metadata
+            .authorization_endpoint
+            .ok_or(OidcError::MissingEndpoint("authorization"))
+            .and_then(|url| AuthUrl::new(url.into()).map_err(OidcError::BadEndpoint))?;
+        let (url, _state) = oauth2::basic::BasicClient::new(self.client_id.clone())
+            .set_auth_uri(auth_url)
+            .set_redirect_uri(RedirectUrl::from_url(redirect_url))
+            .authorize_url(|| state.map_or_else(CsrfToken::new_random, CsrfToken::new))
+            .add_scopes(self.scopes.iter().cloned())
+            .url();
+        Ok(url)
+    }
+
+    /// Exchange an authorization code for the name and verified email of the user
+    pub async fn oauth_user(
+        &self,
+        redirect_url: Url,
+        code: Secret,
+    ) -> Result<(UserName, Email), OidcError> {
+        let http_client = http_client()?;
+        let metadata = self.issuer.discover(&http_client).await?;
+        let token_url = metadata
+            .token_endpoint
+            .clone()
+            .ok_or(OidcError::MissingEndpoint("token"))
+            .and_then(|url| TokenUrl::new(url.into()).map_err(OidcError::BadEndpoint))?;
+        let oidc_client: OidcClient = oauth2::Client::new(self.client_id.clone())
+            .set_client_secret(self.client_secret.clone())
+            .set_token_uri(token_url)
+            .set_redirect_uri(RedirectUrl::from_url(redirect_url));
+
+        let token = oidc_client
+            .exchange_code(AuthorizationCode::new(code.into()))
+            .request_async(&http_client)
+            .await
+            .map_err(OidcError::Exchange)?;
+
+        let mut claims = if let Some(id_token) = token.extra_fields().id_token.as_deref() {
+            metadata
+                .verify_id_token::<Map<String, Value>>(
+                    &http_client,
+                    self.client_id.as_str(),
+                    id_token,
+                )
+                .await?
+        } else {
+            Map::new()
+        };
+        // Fall back to the user info endpoint if the ID token does not have the email claim
+        if !claims.contains_key(&self.email_claim) {
+            if let Some(userinfo_endpoint) = metadata.userinfo_endpoint.as_ref() {
+                let userinfo = http_client
+                    .get(userinfo_endpoint.as_str())
+                    .bearer_auth(token.access_token().secret())
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .map_err(|e| OidcError::Request {
+                        url: userinfo_endpoint.to_string(),
+                        error: e,
+                    })?
+                    .bytes()
+                    .await
+                    .map_err(|e| OidcError::Request {
+                        url: userinfo_endpoint.to_string(),
+                        error: e,
+                    })?;
+                let userinfo: Map<String, Value> =
+                    serde_json::from_slice(&userinfo).map_err(|e| OidcError::Json {
+                        url: userinfo_endpoint.to_string(),
+                        error: e,
+                    })?;
+                claims.extend(userinfo);
+            }
+        }
+
+        self.user_from_claims(&claims)
+    }
+
+    fn user_from_claims(
+        &self,
+        claims: &Map<String, Value>,
+    ) -> Result<(UserName, Email), OidcError> {
+        let email = claims
+            .get(&self.email_claim)
+            .and_then(Value::as_str)
+            .ok_or_else(|| OidcError::MissingEmail(self.email_claim.clone()))?;
+        // The email is used to look up an existing user,
+        // so it must be verified unless the provider is explicitly trusted.
+        if !self.trust_unverified_email
+            && claims.get(EMAIL_VERIFIED_CLAIM).and_then(Value::as_bool) != Some(true)
+        {
+            return Err(OidcError::UnverifiedEmail(email.to_owned()));
+        }
+        let email: Email = email.parse().map_err(OidcError::BadEmail)?;
+
+        let name = claims
+            .get(&self.name_claim)
+            .and_then(Value::as_str)
+            .and_then(|name| name.parse().ok());
+        let name = if let Some(name) = name {
+            name
+        } else {
+            // Fall back to the local part of the email
+            email
+                .as_ref()
+                .split('@')
+                .next()
+                .unwrap_or_default()
+                .parse()
+                .map_err(OidcError::BadName)?
+        };
+
+        Ok((name, email))
+    }
+}
+
+fn http_client() -> Result<reqwest::Client, OidcError> {
+    reqwest::ClientBuilder::new()
+        // Following redirects opens the client up to SSRF vulnerabilities.
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .map_err(OidcError::Reqwest)
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+    use url::Url;
+
+    use super::{Oidc, OidcError};
+    use crate::mock::{CLIENT_ID, mock_oidc_server};
+
+    fn oidc(issuer: Url) -> Oidc {
+        Oidc::new(
+            issuer,
+            CLIENT_ID.parse().unwrap(),
+            "secret".parse().unwrap(),
+            None,
+            None,
+            None,
+            false,
+        )
+    }
+
+    fn redirect_url() -> Url {
+        "http://localhost:3000/auth/oidc".parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_oidc_authorization_url() {
+        let issuer = mock_oidc_server(json!({}), json!({})).await;
+        let url = oidc(issuer.clone())
+            .authorization_url(redirect_url(), Some("plan".to_owned()))
+            .await
+            .unwrap();
+
+        assert_eq!(url.path(), "/authorize");
+        let query = url.query_pairs().collect::<Vec<_>>();
+        assert!(query.contains(&("client_id".into(), CLIENT_ID.into())));
+        assert!(query.contains(&("response_type".into(), "code".into())));
+        assert!(query.contains(&("scope".into(), "openid email profile".into())));
+        assert!(query.contains(&("state".into(), "plan".into())));
+        assert!(query.contains(&("redirect_uri".into(), redirect_url().to_string().into())));
+    }
+
+    #[tokio::test]
+    async fn test_oidc_user() {
+        let issuer = mock_oidc_server(
+            json!({ "email": "muriel@courage.dog", "email_verified": true, "name": "Muriel Bagge" }),
+            json!({}),
+        )
+        .await;
+        let (name, email) = oidc(issuer)
+            .oauth_user(redirect_url(), "code".parse().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(name.as_ref(), "Muriel Bagge");
+        assert_eq!(email.as_ref(), "muriel@courage.dog");
+    }
+
+    #[tokio::test]
+    async fn test_oidc_user_claims() {
+        let issuer = mock_oidc_server(
+            json!({ "upn": "eustace@courage.dog", "nickname": "Eustace Bagge" }),
+            json!({}),
+        )
+        .await;
+        let oidc = Oidc::new(
+            issuer,
+            CLIENT_ID.parse().unwrap(),
+            "secret".parse().unwrap(),
+            Some(vec!["email".parse().unwrap()]),
+            Some("upn".parse().unwrap()),
+            Some("nickname".parse().unwrap()),
+            true,
+        );
+        let (name, email) = oidc
+            .oauth_user(redirect_url(), "code".parse().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(name.as_ref(), "Eustace Bagge");
+        assert_eq!(email.as_ref(), "eustace@courage.dog");
+    }
+
+    #[tokio::test]
+    async fn test_oidc_userinfo() {
+        let issuer = mock_oidc_server(
+            json!({}),
+            json!({ "email": "courage@courage.dog", "email_verified": true }),
+        )
+        .await;
+        let (name, email) = oidc(issuer)
+            .oauth_user(redirect_url(), "code".parse().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(name.as_ref(), "courage");
+        assert_eq!(email.as_ref(), "courage@courage.dog");
+    }
+
+    #[tokio::test]
+    async fn test_oidc_unverified_email() {
+        let issuer = mock_oidc_server(
+            json!({ "email": "muriel@courage.dog", "email_verified": false }),
+            json!({}),
+        )
+        .await;
+        let err = oidc(issuer)
+            .oauth_user(redirect_url(), "code".parse().unwrap())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, OidcError::UnverifiedEmail(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_oidc_missing_email_verified() {
+        let issuer = mock_oidc_server(json!({ "email": "muriel@courage.dog" }), json!({})).await;
+        let err = oidc(issuer)
+            .oauth_user(redirect_url(), "code".parse().unwrap())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, OidcError::UnverifiedEmail(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_oidc_bad_audience() {
+        let issuer = mock_oidc_server(
+            json!({ "email": "muriel@courage.dog", "aud": "not-bencher" }),
+            json!({}),
+        )
+        .await;
+        let err = oidc(issuer)
+            .oauth_user(redirect_url(), "code".parse().unwrap())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, OidcError::IdToken(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_oidc_bad_issuer() {
+        let issuer = mock_oidc_server(json!({}), json!({})).await;
+        let mut other_issuer = issuer.clone();
+        other_issuer.set_path("/other");
+        let err = oidc(other_issuer)
+            .authorization_url(redirect_url(), None)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, OidcError::IssuerMismatch { .. }), "{err}");
+    }
+}
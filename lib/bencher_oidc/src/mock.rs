@@ -0,0 +1,76 @@
+use jsonwebtoken::{EncodingKey, Header, encode};
+use serde_json::{Value, json};
+use tokio::{
+    io::{AsyncReadExt as _, AsyncWriteExt as _},
+    net::TcpListener,
+};
+use url::Url;
+
+pub const CLIENT_ID: &str = "bencher";
+const KEY_ID: &str = "mock";
+const SECRET: &[u8] = b"bencher";
+// Base64 URL encoding of `SECRET`
+const SECRET_BASE64: &str = "YmVuY2hlcg";
+
+/// Start a mock OpenID Connect provider that issues an ID token with the given claims
+/// and serves the given user info.
+pub async fn mock_oidc_server(id_token_claims: Value, userinfo: Value) -> Url {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let issuer = format!("http://{}", listener.local_addr().unwrap());
+    let mut id_token_claims = id_token_claims;
+    if let Some(claims) = id_token_claims.as_object_mut() {
+        claims.entry("iss").or_insert_with(|| issuer.clone().into());
+        claims.entry("sub").or_insert_with(|| "mock-user".into());
+        claims.entry("aud").or_insert_with(|| CLIENT_ID.into());
+        claims.entry("exp").or_insert_with(|| u32::MAX.into());
+    }
+    let header = Header {
+        kid: Some(KEY_ID.to_owned()),
+        ..Default::default()
+    };
+    let id_token = encode(&header, &id_token_claims, &EncodingKey::from_secret(SECRET)).unwrap();
+
+    let metadata = json!({
+        "issuer": issuer,
+        "authorization_endpoint": format!("{issuer}/authorize"),
+        "token_endpoint": format!("{issuer}/token"),
+        "userinfo_endpoint": format!("{issuer}/userinfo"),
+        "jwks_uri": format!("{issuer}/jwks"),
+    });
+    let jwks = json!({
+        "keys": [{ "kty": "oct", "kid": KEY_ID, "alg": "HS256", "k": SECRET_BASE64 }]
+    });
+    let token = json!({
+        "access_token": "mock-access-token",
+        "token_type": "Bearer",
+        "id_token": id_token,
+    });
+
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                return;
+            };
+            let mut buf = vec![0; 8192];
+            let len = stream.read(&mut buf).await.unwrap();
+            buf.truncate(len);
+            let request = String::from_utf8_lossy(&buf).into_owned();
+            let path = request.split_whitespace().nth(1).unwrap_or_default();
+            let body = match path {
+                path if path.ends_with("/.well-known/openid-configuration") => &metadata,
+                "/jwks" => &jwks,
+                "/token" => &token,
+                "/userinfo" => &userinfo,
+                _ => &Value::Null,
+            }
+            .to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+
+    issuer.parse().unwrap()
+}
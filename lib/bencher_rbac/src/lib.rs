@@ -8,7 +8,7 @@ pub mod user;
 pub use organization::Organization;
 pub use project::Project;
 pub use server::Server;
-pub use user::User;
+pub use user::{Scope, User};
 
 const VIEW_PERM: &str = "view";
 const CREATE_PERM: &str = "create";
@@ -26,6 +26,7 @@ pub const POLAR: &str = include_str!("../bencher.polar");
 pub fn init_rbac() -> Result<Oso, Box<oso::OsoError>> {
     let mut oso = Oso::new();
     oso.register_class(User::get_polar_class())?;
+    oso.register_class(Scope::get_polar_class())?;
     oso.register_class(ClassBuilder::with_constructor(|| Server {}).build())?;
     oso.register_class(
         Organization::get_polar_class_builder()
@@ -66,6 +67,7 @@ mod test {
             locked: false,
             organizations: HashMap::new(),
             projects: HashMap::new(),
+            scope: None,
         };
 
         assert!(
@@ -82,6 +84,7 @@ mod test {
             locked: false,
             organizations: HashMap::new(),
             projects: HashMap::new(),
+            scope: None,
         };
 
         assert!(
@@ -98,6 +101,7 @@ mod test {
             locked: true,
             organizations: HashMap::new(),
             projects: HashMap::new(),
+            scope: None,
         };
 
         assert!(
@@ -114,6 +118,7 @@ mod test {
             locked: true,
             organizations: HashMap::new(),
             projects: HashMap::new(),
+            scope: None,
         };
 
         assert!(
@@ -135,6 +140,7 @@ mod test {
                 org_id.to_string() => OrgRole::Leader
             },
             projects: HashMap::new(),
+            scope: None,
         };
 
         let org_member = User {
@@ -144,6 +150,7 @@ mod test {
                 org_id.to_string() => OrgRole::Member
             },
             projects: HashMap::new(),
+            scope: None,
         };
 
         let proj_member = User {
@@ -155,6 +162,7 @@ mod test {
             projects: literally::hmap! {
                 proj_id.to_string() => ProjRole::Developer
             },
+            scope: None,
         };
 
         let org = Organization {
@@ -345,4 +353,105 @@ mod test {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn test_rbac_scope() {
+        let oso = &*OSO;
+
+        let org_id = Uuid::new_v4();
+        let proj_id = Uuid::new_v4();
+        let other_proj_id = Uuid::new_v4();
+
+        let org = Organization {
+            id: org_id.to_string(),
+        };
+        let proj = Project {
+            id: proj_id.to_string(),
+            organization_id: org_id.to_string(),
+        };
+        let other_proj = Project {
+            id: other_proj_id.to_string(),
+            organization_id: org_id.to_string(),
+        };
+
+        let scoped_leader = User {
+            admin: false,
+            locked: false,
+            organizations: literally::hmap! {
+                org_id.to_string() => OrgRole::Leader
+            },
+            projects: HashMap::new(),
+            scope: Some(Scope {
+                project: proj_id.to_string(),
+                permissions: vec![ProjPerm::View, ProjPerm::Create],
+            }),
+        };
+
+        assert!(
+            oso.is_allowed(scoped_leader.clone(), ProjPerm::View, proj.clone())
+                .unwrap()
+        );
+        assert!(
+            oso.is_allowed(scoped_leader.clone(), ProjPerm::Create, proj.clone())
+                .unwrap()
+        );
+        assert!(
+            !oso.is_allowed(scoped_leader.clone(), ProjPerm::Delete, proj.clone())
+                .unwrap()
+        );
+        assert!(
+            !oso.is_allowed(scoped_leader.clone(), ProjPerm::Manage, proj.clone())
+                .unwrap()
+        );
+        assert!(
+            !oso.is_allowed(scoped_leader.clone(), ProjPerm::View, other_proj.clone())
+                .unwrap()
+        );
+        assert!(
+            !oso.is_allowed(scoped_leader.clone(), OrgPerm::View, org.clone())
+                .unwrap()
+        );
+        assert!(
+            !oso.is_allowed(scoped_leader, SvrPerm::Session, Server {})
+                .unwrap()
+        );
+
+        let scoped_admin = User {
+            admin: true,
+            locked: false,
+            organizations: HashMap::new(),
+            projects: HashMap::new(),
+            scope: Some(Scope {
+                project: proj_id.to_string(),
+                permissions: vec![ProjPerm::View],
+            }),
+        };
+
+        assert!(
+            oso.is_allowed(scoped_admin.clone(), ProjPerm::View, proj.clone())
+                .unwrap()
+        );
+        assert!(
+            !oso.is_allowed(scoped_admin.clone(), ProjPerm::Create, proj.clone())
+                .unwrap()
+        );
+        assert!(
+            !oso.is_allowed(scoped_admin.clone(), SvrPerm::Administer, Server {})
+                .unwrap()
+        );
+        assert!(!oso.is_allowed(scoped_admin, OrgPerm::View, org).unwrap());
+
+        let scoped_user = User {
+            admin: false,
+            locked: false,
+            organizations: HashMap::new(),
+            projects: HashMap::new(),
+            scope: Some(Scope {
+                project: proj_id.to_string(),
+                permissions: vec![ProjPerm::View],
+            }),
+        };
+
+        assert!(!oso.is_allowed(scoped_user, ProjPerm::View, proj).unwrap());
+    }
 }
@@ -13,6 +13,17 @@ pub struct User {
     pub organizations: OrganizationRoles,
     #[polar(attribute)]
     pub projects: ProjectRoles,
+    #[polar(attribute)]
+    pub scope: Option<Scope>,
+}
+
+/// A scoped API token is restricted to a subset of the permissions for a single project.
+#[derive(Debug, Clone, PolarClass)]
+pub struct Scope {
+    #[polar(attribute)]
+    pub project: String,
+    #[polar(attribute)]
+    pub permissions: Vec<crate::project::Permission>,
 }
 
 pub type OrganizationRoles = HashMap<String, crate::organization::Role>;
@@ -0,0 +1,42 @@
+PRAGMA foreign_keys = off;
+-- token
+CREATE TABLE up_token (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    user_id INTEGER NOT NULL,
+    project_id INTEGER,
+    name TEXT NOT NULL,
+    jwt TEXT NOT NULL,
+    permissions TEXT,
+    creation BIGINT NOT NULL,
+    expiration BIGINT NOT NULL,
+    last_used BIGINT,
+    last_used_ip TEXT,
+    revoked BIGINT,
+    FOREIGN KEY (user_id) REFERENCES user (id),
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE
+    SET NULL
+);
+INSERT INTO up_token(
+        id,
+        uuid,
+        user_id,
+        name,
+        jwt,
+        creation,
+        expiration
+    )
+SELECT id,
+    uuid,
+    user_id,
+    name,
+    jwt,
+    creation,
+    expiration
+FROM token;
+DROP TABLE token;
+ALTER TABLE up_token
+    RENAME TO token;
+-- index
+CREATE INDEX index_token_jwt ON token(jwt);
+PRAGMA foreign_keys = on;
@@ -0,0 +1 @@
+DROP TABLE trust_policy;
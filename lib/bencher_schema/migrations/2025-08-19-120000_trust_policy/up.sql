@@ -0,0 +1,14 @@
+CREATE TABLE trust_policy (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    project_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    provider INTEGER NOT NULL,
+    issuer TEXT,
+    repository TEXT NOT NULL,
+    git_ref TEXT,
+    workflow TEXT,
+    created BIGINT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE
+);
@@ -0,0 +1,32 @@
+PRAGMA foreign_keys = off;
+CREATE TABLE down_webhook (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    project_id INTEGER NOT NULL,
+    url TEXT NOT NULL,
+    secret TEXT,
+    events TEXT NOT NULL,
+    created BIGINT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE
+);
+INSERT INTO down_webhook(
+        id,
+        uuid,
+        project_id,
+        url,
+        secret,
+        events,
+        created
+    )
+SELECT id,
+    uuid,
+    project_id,
+    url,
+    secret,
+    events,
+    created
+FROM webhook;
+DROP TABLE webhook;
+ALTER TABLE down_webhook
+    RENAME TO webhook;
+PRAGMA foreign_keys = on;
@@ -0,0 +1,35 @@
+PRAGMA foreign_keys = off;
+CREATE TABLE up_webhook (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    project_id INTEGER NOT NULL,
+    url TEXT NOT NULL,
+    format INTEGER NOT NULL,
+    secret TEXT,
+    events TEXT NOT NULL,
+    created BIGINT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE
+);
+INSERT INTO up_webhook(
+        id,
+        uuid,
+        project_id,
+        url,
+        format,
+        secret,
+        events,
+        created
+    )
+SELECT id,
+    uuid,
+    project_id,
+    url,
+    0,
+    secret,
+    events,
+    created
+FROM webhook;
+DROP TABLE webhook;
+ALTER TABLE up_webhook
+    RENAME TO webhook;
+PRAGMA foreign_keys = on;
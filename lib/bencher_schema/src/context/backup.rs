@@ -0,0 +1,1265 @@
+use std::{
+    collections::HashSet,
+    ffi::OsStr,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use async_compression::tokio::{bufread::GzipDecoder, write::GzipEncoder};
+use bencher_json::{
+    DateTime,
+    system::{
+        backup::{JsonBackupCreated, JsonBackupFile},
+        config::JsonBackupRetention,
+        restore::JsonRestored,
+    },
+};
+use chrono::{Datelike as _, Utc};
+use diesel::{
+    Connection as _, RunQueryDsl as _, connection::SimpleConnection as _,
+    migration::MigrationSource, sqlite::Sqlite,
+};
+use sha2::{Digest as _, Sha256};
+use slog::Logger;
+use tokio::{
+    fs::remove_file,
+    io::{AsyncReadExt as _, AsyncWriteExt as _, BufReader, BufWriter},
+    sync::mpsc::Sender,
+};
+
+use super::{
+    Database, DbConnection,
+    database::{DataStore, DataStoreError},
+};
+use crate::MIGRATIONS;
+
+const BUFFER_SIZE: usize = 1024;
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const IN_MEMORY: &str = ":memory:";
+const DEFAULT_MAX_UPLOAD_SIZE: u64 = 8 * 1024 * 1024 * 1024;
+
+#[derive(Debug, thiserror::Error)]
+pub enum BackupError {
+    #[error("Failed to batch execute: {0}")]
+    BatchExecute(diesel::result::Error),
+    #[error("Failed to create backup file: {0}")]
+    CreateBackupFile(std::io::Error),
+    #[error("Failed to read backup file: {0}")]
+    ReadBackupFile(std::io::Error),
+    #[error("Failed to create compressed file: {0}")]
+    CreateZipFile(std::io::Error),
+    #[error("Failed to write to compressed file: {0}")]
+    WriteZipFile(std::io::Error),
+    #[error("Failed to close compressed file: {0}")]
+    CloseZipFile(std::io::Error),
+    #[error("Failed to remove backup file: {0}")]
+    RmBackupFile(std::io::Error),
+    #[error("Failed to remove compressed file: {0}")]
+    RmZipFile(std::io::Error),
+    #[error("{0}")]
+    DataStore(DataStoreError),
+    #[error("No data store")]
+    NoDataStore,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RestoreError {
+    #[error("No data store")]
+    NoDataStore,
+    #[error("{0}")]
+    DataStore(DataStoreError),
+    #[error("No database backups in the data store manifest")]
+    NoBackups,
+    #[error("Database backup file name ({0}) must be a single file in the data store")]
+    FileName(String),
+    #[error("Failed to read database backup: {0}")]
+    ReadBackupFile(std::io::Error),
+    #[error(
+        "Checksum for database backup ({file_name}) does not match the data store manifest. Expected {expected} but found {actual}"
+    )]
+    Checksum {
+        file_name: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("Failed to decompress database backup: {0}")]
+    Decompress(std::io::Error),
+    #[error("Failed to remove database backup file: {0}")]
+    RmBackupFile(std::io::Error),
+    #[error("Failed to connect to database: {0}")]
+    Connection(diesel::ConnectionError),
+    #[error("Failed to run database pragma: {0}")]
+    Pragma(diesel::result::Error),
+    #[error("Database backup failed integrity check: {0}")]
+    IntegrityCheck(String),
+    #[error("Database backup is not a Bencher database: {0}")]
+    NotBencher(String),
+    #[error("Failed to load database migrations: {0}")]
+    Migrations(String),
+    #[error(
+        "Database backup has a migration ({0}) that is newer than this server. Upgrade the server before restoring this backup."
+    )]
+    Migration(String),
+    #[error("Failed to keep aside the current database: {0}")]
+    KeepAside(diesel::result::Error),
+    #[error("Failed to swap in the database backup: {0}")]
+    Swap(std::io::Error),
+}
+
+#[expect(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BackupOptions {
+    /// Compress the database backup with gzip.
+    pub compress: bool,
+    /// Save the database backup to the data store.
+    pub data_store: bool,
+    /// Remove the local copy of the database backup.
+    pub rm: bool,
+    /// Do not save the database backup to the data store
+    /// if it is the same as the latest backup in the data store.
+    pub skip_unchanged: bool,
+}
+
+pub enum RestoreSource {
+    /// A database backup in the data store.
+    /// Defaults to the latest backup in the data store manifest.
+    DataStore(Option<String>),
+    /// A database backup file next to the database, such as an upload.
+    File(PathBuf),
+}
+
+struct Backup {
+    file_path: PathBuf,
+    file_name: String,
+    created: DateTime,
+}
+
+impl Database {
+    pub async fn backup(&self, options: BackupOptions) -> Result<JsonBackupCreated, BackupError> {
+        // Create a database backup
+        let Backup {
+            file_path: backup_file_path,
+            file_name: backup_file_name,
+            created,
+        } = self.backup_database().await?;
+
+        // Compress the database backup
+        let (source_path, file_name) = if options.compress {
+            compress_database(backup_file_path, &backup_file_name).await?
+        } else {
+            (backup_file_path, backup_file_name)
+        };
+
+        // Store the database backup in the data store
+        let backup = if options.data_store {
+            let data_store = self.data_store.as_ref().ok_or(BackupError::NoDataStore)?;
+            let retention = self.backup.as_ref().and_then(|backup| backup.retention);
+            save_backup(
+                data_store,
+                &source_path,
+                file_name,
+                created,
+                options.skip_unchanged,
+                retention,
+            )
+            .await?
+        } else {
+            None
+        };
+
+        // Remove the remaining database backup
+        if options.rm {
+            remove_file(source_path)
+                .await
+                .map_err(BackupError::RmZipFile)?;
+        }
+
+        Ok(JsonBackupCreated { created, backup })
+    }
+
+    async fn backup_database(&self) -> Result<Backup, BackupError> {
+        let date_time = Utc::now();
+        let (file_path, file_name) = self.sibling_path("backup", date_time);
+        let file_path_str = file_path.to_string_lossy();
+        let query = format!("VACUUM INTO '{file_path_str}'");
+
+        self.connection
+            .lock()
+            .await
+            .batch_execute(&query)
+            .map_err(BackupError::BatchExecute)?;
+
+        Ok(Backup {
+            file_path,
+            file_name,
+            created: date_time.into(),
+        })
+    }
+
+    /// Create a timestamped path next to the database, such as `backup-bencher-2025-01-01-00-00-00-000000000.db`
+    /// The timestamp includes nanoseconds, so paths created at the same time do not collide.
+    fn sibling_path(&self, prefix: &str, date_time: chrono::DateTime<Utc>) -> (PathBuf, String) {
+        let mut file_path = self.path.clone();
+
+        let file_stem = file_path
+            .file_stem()
+            .unwrap_or_else(|| OsStr::new("bencher"))
+            .to_string_lossy();
+        let file_extension = file_path
+            .extension()
+            .unwrap_or_else(|| OsStr::new("db"))
+            .to_string_lossy();
+        let file_name = format!(
+            "{prefix}-{file_stem}-{}.{file_extension}",
+            date_time.format("%Y-%m-%d-%H-%M-%S-%9f")
+        );
+        file_path.set_file_name(&file_name);
+
+        (file_path, file_name)
+    }
+
+    /// The path to save an uploaded database backup to before restoring it
+    pub fn upload_path(&self) -> PathBuf {
+        self.sibling_path("upload", Utc::now()).0
+    }
+
+    /// The maximum size of an uploaded database backup, in bytes
+    pub fn max_upload_size(&self) -> u64 {
+        self.backup
+            .as_ref()
+            .and_then(|backup| backup.max_upload_size)
+            .unwrap_or(DEFAULT_MAX_UPLOAD_SIZE)
+    }
+
+    /// Restore the database from a backup.
+    /// The backup must be a Bencher database at a migration version that is compatible with this server.
+    /// The current database is kept aside, and the backup is swapped in its place.
+    /// The server should be restarted afterwards.
+    pub async fn restore(&self, source: RestoreSource) -> Result<JsonRestored, RestoreError> {
+        let date_time = Utc::now();
+        let (restore_path, _) = self.sibling_path("restore", date_time);
+        let (source_path, backup) = match source {
+            RestoreSource::DataStore(file_name) => {
+                let (download_path, _) = self.sibling_path("download", date_time);
+                match self.download_backup(file_name, &download_path).await {
+                    Ok(backup) => (download_path, backup),
+                    Err(e) => {
+                        let _rm = remove_file(download_path).await;
+                        return Err(e);
+                    },
+                }
+            },
+            RestoreSource::File(source_path) => (source_path, None),
+        };
+
+        let previous = match self.restore_database(&source_path, &restore_path).await {
+            Ok(previous) => previous,
+            Err(e) => {
+                // Clean up whatever is left of the database backup
+                let _rm = remove_file(source_path).await;
+                let _rm = remove_file(restore_path).await;
+                return Err(e);
+            },
+        };
+
+        Ok(JsonRestored {
+            restored: date_time.into(),
+            backup,
+            previous,
+        })
+    }
+
+    async fn download_backup(
+        &self,
+        file_name: Option<String>,
+        download_path: &Path,
+    ) -> Result<Option<JsonBackupFile>, RestoreError> {
+        let data_store = self.data_store.as_ref().ok_or(RestoreError::NoDataStore)?;
+        let mut manifest = data_store
+            .get_manifest()
+            .await
+            .map_err(RestoreError::DataStore)?;
+        // Backups saved before the manifest existed can still be restored by name,
+        // but they can not be verified against a checksum.
+        let (file_name, backup) = if let Some(file_name) = file_name {
+            let backup = manifest
+                .backups
+                .into_iter()
+                .rfind(|backup| backup.file_name == file_name);
+            (file_name, backup)
+        } else {
+            let backup = manifest.backups.pop().ok_or(RestoreError::NoBackups)?;
+            (backup.file_name.clone(), Some(backup))
+        };
+        // The file name is joined to the data store path,
+        // so it must not be able to reach outside of the data store.
+        if !is_file_name(&file_name) {
+            return Err(RestoreError::FileName(file_name));
+        }
+
+        data_store
+            .download(&file_name, download_path)
+            .await
+            .map_err(RestoreError::DataStore)?;
+
+        if let Some(backup) = &backup {
+            let (_, sha256) = checksum(download_path)
+                .await
+                .map_err(RestoreError::ReadBackupFile)?;
+            if sha256 != backup.sha256 {
+                return Err(RestoreError::Checksum {
+                    file_name,
+                    expected: backup.sha256.clone(),
+                    actual: sha256,
+                });
+            }
+        }
+
+        Ok(backup)
+    }
+
+    async fn restore_database(
+        &self,
+        source_path: &Path,
+        restore_path: &Path,
+    ) -> Result<String, RestoreError> {
+        decompress_database(source_path, restore_path).await?;
+        validate_database(restore_path)?;
+
+        let (previous_path, previous) = self.sibling_path("previous", Utc::now());
+        let mut connection = self.connection.lock().await;
+
+        // Keep aside a copy of the current database
+        let query = format!("VACUUM INTO '{}'", previous_path.to_string_lossy());
+        connection
+            .batch_execute(&query)
+            .map_err(RestoreError::KeepAside)?;
+
+        // Close the current database connection before the swap,
+        // so its write-ahead log is checkpointed into the current database
+        // and never picked up by the restored database.
+        *connection = DbConnection::establish(IN_MEMORY).map_err(RestoreError::Connection)?;
+        let swapped = tokio::fs::rename(restore_path, &self.path)
+            .await
+            .map_err(RestoreError::Swap);
+        *connection = DbConnection::establish(&self.path.to_string_lossy())
+            .map_err(RestoreError::Connection)?;
+        self.pragmas
+            .apply(&mut connection)
+            .map_err(RestoreError::Pragma)?;
+        swapped?;
+
+        Ok(previous)
+    }
+
+    /// Spawn a task that backs up the database to the data store on the configured interval.
+    /// The task stops once the server restarts.
+    pub fn spawn_backups(&self, log: Logger, restart_tx: Sender<()>) {
+        let Some(backup) = self.backup.as_ref() else {
+            return;
+        };
+        let Some(interval) = backup.interval else {
+            return;
+        };
+        let options = BackupOptions {
+            compress: backup.compress.unwrap_or_default(),
+            data_store: true,
+            rm: true,
+            skip_unchanged: true,
+        };
+        let database = self.clone();
+        tokio::spawn(async move {
+            let interval = Duration::from_secs(interval);
+            loop {
+                tokio::select! {
+                    () = tokio::time::sleep(interval) => {},
+                    () = restart_tx.closed() => return,
+                }
+                match database.backup(options).await {
+                    Ok(JsonBackupCreated {
+                        backup: Some(backup),
+                        ..
+                    }) => slog::info!(
+                        log,
+                        "Saved scheduled database backup {} ({})",
+                        backup.file_name,
+                        backup.sha256
+                    ),
+                    Ok(JsonBackupCreated { backup: None, .. }) => slog::info!(
+                        log,
+                        "Skipped scheduled database backup with no changes since the latest backup"
+                    ),
+                    Err(e) => slog::error!(log, "Failed to run scheduled database backup: {e}"),
+                }
+            }
+        });
+    }
+}
+
+async fn compress_database(
+    backup_file_path: PathBuf,
+    backup_file_name: &str,
+) -> Result<(PathBuf, String), BackupError> {
+    let backup_file = tokio::fs::File::open(&backup_file_path)
+        .await
+        .map_err(BackupError::CreateBackupFile)?;
+    let mut backup_data = BufReader::with_capacity(BUFFER_SIZE, backup_file);
+
+    let compress_file_name = format!("{backup_file_name}.gz");
+    let mut compress_file_path = backup_file_path.clone();
+    compress_file_path.set_file_name(&compress_file_name);
+    let compress_file = tokio::fs::File::create(&compress_file_path)
+        .await
+        .map_err(BackupError::CreateZipFile)?;
+    let compress_data = BufWriter::with_capacity(BUFFER_SIZE, compress_file);
+
+    let mut encoder = GzipEncoder::new(compress_data);
+    tokio::io::copy(&mut backup_data, &mut encoder)
+        .await
+        .map_err(BackupError::WriteZipFile)?;
+    encoder
+        .shutdown()
+        .await
+        .map_err(BackupError::CloseZipFile)?;
+
+    remove_file(backup_file_path)
+        .await
+        .map_err(BackupError::RmBackupFile)?;
+
+    Ok((compress_file_path, compress_file_name))
+}
+
+async fn save_backup(
+    data_store: &DataStore,
+    source_path: &Path,
+    file_name: String,
+    created: DateTime,
+    skip_unchanged: bool,
+    retention: Option<JsonBackupRetention>,
+) -> Result<Option<JsonBackupFile>, BackupError> {
+    let (size, sha256) = checksum(source_path)
+        .await
+        .map_err(BackupError::ReadBackupFile)?;
+    let mut manifest = data_store
+        .get_manifest()
+        .await
+        .map_err(BackupError::DataStore)?;
+    if skip_unchanged
+        && manifest
+            .backups
+            .last()
+            .is_some_and(|latest| latest.sha256 == sha256)
+    {
+        return Ok(None);
+    }
+
+    data_store
+        .backup(source_path, &file_name)
+        .await
+        .map_err(BackupError::DataStore)?;
+    let backup = JsonBackupFile {
+        file_name,
+        created,
+        size,
+        sha256,
+    };
+    manifest.backups.push(backup.clone());
+
+    // Update the manifest before pruning,
+    // so it never lists a backup that has already been removed.
+    let pruned = if let Some(retention) = retention {
+        let (keep, prune) = retain(manifest.backups, retention);
+        manifest.backups = keep;
+        prune
+    } else {
+        Vec::new()
+    };
+    data_store
+        .put_manifest(&manifest)
+        .await
+        .map_err(BackupError::DataStore)?;
+    for backup in pruned {
+        data_store
+            .remove(&backup.file_name)
+            .await
+            .map_err(BackupError::DataStore)?;
+    }
+
+    Ok(Some(backup))
+}
+
+/// A file name is a single path component, such as `backup-bencher-2025-01-01-00-00-00.db`
+fn is_file_name(file_name: &str) -> bool {
+    Path::new(file_name).file_name() == Some(OsStr::new(file_name))
+}
+
+async fn checksum(path: &Path) -> Result<(u64, String), std::io::Error> {
+    let file = tokio::fs::File::open(path).await?;
+    let mut data = BufReader::with_capacity(BUFFER_SIZE, file);
+    let mut hasher = Sha256::new();
+    let mut size = 0;
+    let mut buffer = [0; BUFFER_SIZE];
+    loop {
+        let data_size = data.read(&mut buffer).await?;
+        let Some(chunk) = buffer.get(..data_size).filter(|chunk| !chunk.is_empty()) else {
+            break;
+        };
+        hasher.update(chunk);
+        size += data_size as u64;
+    }
+    Ok((size, format!("{:x}", hasher.finalize())))
+}
+
+async fn decompress_database(source_path: &Path, restore_path: &Path) -> Result<(), RestoreError> {
+    let mut source_file = tokio::fs::File::open(source_path)
+        .await
+        .map_err(RestoreError::ReadBackupFile)?;
+    let mut magic = [0; 2];
+    let is_gzip = match source_file.read_exact(&mut magic).await {
+        Ok(_) => magic == GZIP_MAGIC,
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => false,
+        Err(e) => return Err(RestoreError::ReadBackupFile(e)),
+    };
+    drop(source_file);
+
+    if !is_gzip {
+        return tokio::fs::rename(source_path, restore_path)
+            .await
+            .map_err(RestoreError::ReadBackupFile);
+    }
+
+    let source_file = tokio::fs::File::open(source_path)
+        .await
+        .map_err(RestoreError::ReadBackupFile)?;
+    let mut decoder = GzipDecoder::new(BufReader::with_capacity(BUFFER_SIZE, source_file));
+    let restore_file = tokio::fs::File::create(restore_path)
+        .await
+        .map_err(RestoreError::Decompress)?;
+    let mut restore_data = BufWriter::with_capacity(BUFFER_SIZE, restore_file);
+    tokio::io::copy(&mut decoder, &mut restore_data)
+        .await
+        .map_err(RestoreError::Decompress)?;
+    restore_data
+        .shutdown()
+        .await
+        .map_err(RestoreError::Decompress)?;
+
+    remove_file(source_path)
+        .await
+        .map_err(RestoreError::RmBackupFile)
+}
+
+#[derive(diesel::QueryableByName)]
+struct QuickCheck {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    quick_check: String,
+}
+
+#[derive(diesel::QueryableByName)]
+struct AppliedMigration {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    version: String,
+}
+
+fn validate_database(restore_path: &Path) -> Result<(), RestoreError> {
+    let mut conn = DbConnection::establish(&restore_path.to_string_lossy())
+        .map_err(RestoreError::Connection)?;
+
+    let quick_check = diesel::sql_query("PRAGMA quick_check")
+        .load::<QuickCheck>(&mut conn)
+        .map_err(|e| RestoreError::IntegrityCheck(e.to_string()))?;
+    if quick_check.iter().any(|check| check.quick_check != "ok") {
+        return Err(RestoreError::IntegrityCheck(
+            quick_check
+                .into_iter()
+                .map(|check| check.quick_check)
+                .collect::<Vec<_>>()
+                .join(", "),
+        ));
+    }
+
+    // Query the migrations table directly,
+    // as the migration harness would create it if it did not already exist.
+    let applied_migrations = diesel::sql_query("SELECT version FROM __diesel_schema_migrations")
+        .load::<AppliedMigration>(&mut conn)
+        .map_err(|e| RestoreError::NotBencher(e.to_string()))?;
+    if applied_migrations.is_empty() {
+        return Err(RestoreError::NotBencher(
+            "No database migrations have been run".to_owned(),
+        ));
+    }
+    let migrations = MigrationSource::<Sqlite>::migrations(&MIGRATIONS)
+        .map_err(|e| RestoreError::Migrations(e.to_string()))?
+        .iter()
+        .map(|migration| migration.name().version().to_string())
+        .collect::<HashSet<_>>();
+    // Any pending migrations are run when the server restarts,
+    // but a migration from a newer server version can not be undone.
+    for applied_migration in applied_migrations {
+        if !migrations.contains(&applied_migration.version) {
+            return Err(RestoreError::Migration(applied_migration.version));
+        }
+    }
+
+    Ok(())
+}
+
+/// Split the backups, which are ordered from oldest to newest, into those to keep and those to prune.
+/// The latest backup is always kept, along with the latest backup
+/// for each of the most recent `daily` days and `weekly` weeks.
+fn retain(
+    backups: Vec<JsonBackupFile>,
+    retention: JsonBackupRetention,
+) -> (Vec<JsonBackupFile>, Vec<JsonBackupFile>) {
+    let JsonBackupRetention { daily, weekly } = retention;
+    let daily = usize::try_from(daily).unwrap_or(usize::MAX);
+    let weekly = usize::try_from(weekly).unwrap_or(usize::MAX);
+
+    let mut days = HashSet::new();
+    let mut weeks = HashSet::new();
+    let mut keep = Vec::new();
+    let mut prune = Vec::new();
+    for (index, backup) in backups.into_iter().rev().enumerate() {
+        let date = backup.created.into_inner().date_naive();
+        let week = date.iso_week();
+        let is_daily = days.len() < daily && days.insert(date);
+        let is_weekly = weeks.len() < weekly && weeks.insert((week.year(), week.week()));
+        if index == 0 || is_daily || is_weekly {
+            keep.push(backup);
+        } else {
+            prune.push(backup);
+        }
+    }
+    keep.reverse();
+
+    (keep, prune)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        path::{Path, PathBuf},
+        sync::Arc,
+    };
+
+    use bencher_json::system::{
+        backup::{JsonBackupFile, JsonBackupManifest},
+        config::{DataStore as DataStoreConfig, JsonBackupRetention},
+    };
+    use chrono::{TimeZone as _, Utc};
+    use diesel::{Connection as _, RunQueryDsl as _, connection::SimpleConnection as _};
+
+    use super::{
+        BackupOptions, DataStore, Database, DbConnection, GZIP_MAGIC, RestoreError, RestoreSource,
+        compress_database, is_file_name, retain, save_backup, validate_database,
+    };
+    use crate::{context::DbPragmas, run_migrations};
+
+    const BACKUP_DATA: &[u8] = b"bencher";
+    const BACKUP_SHA256: &str = "d3587f7692cc55740006f099dceb60aa0ff5a69b74390f075cec06340a48bd6d";
+
+    #[derive(diesel::QueryableByName)]
+    struct ForeignKeys {
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        foreign_keys: i32,
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("bencher_{name}_{}", uuid::Uuid::new_v4()))
+    }
+
+    fn local_data_store(dir: &Path) -> DataStore {
+        DataStore::try_from(DataStoreConfig::Local {
+            path: dir.join("data_store"),
+        })
+        .unwrap()
+    }
+
+    fn bencher_database(path: &Path) -> DbConnection {
+        let mut conn = DbConnection::establish(&path.to_string_lossy()).unwrap();
+        run_migrations(&mut conn).unwrap();
+        conn
+    }
+
+    fn database(dir: &Path) -> Database {
+        let path = dir.join("bencher.db");
+        let connection = bencher_database(&path);
+        Database {
+            path,
+            connection: Arc::new(tokio::sync::Mutex::new(connection)),
+            pragmas: DbPragmas::default(),
+            data_store: Some(local_data_store(dir)),
+            backup: None,
+        }
+    }
+
+    /// Create a Bencher database backup that can be told apart from the current database
+    fn backup_database(path: &Path) {
+        bencher_database(path)
+            .batch_execute("CREATE TABLE restored (id INTEGER PRIMARY KEY)")
+            .unwrap();
+    }
+
+    async fn is_restored(database: &Database) -> bool {
+        database
+            .connection
+            .lock()
+            .await
+            .batch_execute("SELECT id FROM restored")
+            .is_ok()
+    }
+
+    fn backup_file(year: i32, month: u32, day: u32, hour: u32) -> JsonBackupFile {
+        let created = Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap();
+        JsonBackupFile {
+            file_name: format!("backup-bencher-{}.db", created.format("%Y-%m-%d-%H")),
+            created: created.into(),
+            size: 0,
+            sha256: String::new(),
+        }
+    }
+
+    fn file_names(backups: &[JsonBackupFile]) -> Vec<&str> {
+        backups
+            .iter()
+            .map(|backup| backup.file_name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_retain_empty() {
+        let (keep, prune) = retain(
+            Vec::new(),
+            JsonBackupRetention {
+                daily: 7,
+                weekly: 4,
+            },
+        );
+        assert!(keep.is_empty(), "{keep:?}");
+        assert!(prune.is_empty(), "{prune:?}");
+    }
+
+    #[test]
+    fn test_retain_latest() {
+        let backups = vec![
+            backup_file(2025, 1, 1, 0),
+            backup_file(2025, 1, 2, 0),
+            backup_file(2025, 1, 3, 0),
+        ];
+        let (keep, prune) = retain(
+            backups,
+            JsonBackupRetention {
+                daily: 0,
+                weekly: 0,
+            },
+        );
+        assert_eq!(file_names(&keep), ["backup-bencher-2025-01-03-00.db"]);
+        assert_eq!(
+            file_names(&prune),
+            [
+                "backup-bencher-2025-01-02-00.db",
+                "backup-bencher-2025-01-01-00.db"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_retain_daily() {
+        // Two backups a day, for five days
+        let backups = (1..=5)
+            .flat_map(|day| [backup_file(2025, 1, day, 0), backup_file(2025, 1, day, 12)])
+            .collect();
+        let (keep, prune) = retain(
+            backups,
+            JsonBackupRetention {
+                daily: 3,
+                weekly: 0,
+            },
+        );
+        assert_eq!(
+            file_names(&keep),
+            [
+                "backup-bencher-2025-01-03-12.db",
+                "backup-bencher-2025-01-04-12.db",
+                "backup-bencher-2025-01-05-12.db"
+            ]
+        );
+        assert_eq!(prune.len(), 7);
+    }
+
+    #[test]
+    fn test_retain_daily_gaps() {
+        // Only days with a backup count towards the daily retention
+        let backups = vec![
+            backup_file(2025, 1, 1, 0),
+            backup_file(2025, 1, 10, 0),
+            backup_file(2025, 1, 20, 0),
+        ];
+        let (keep, prune) = retain(
+            backups,
+            JsonBackupRetention {
+                daily: 2,
+                weekly: 0,
+            },
+        );
+        assert_eq!(
+            file_names(&keep),
+            [
+                "backup-bencher-2025-01-10-00.db",
+                "backup-bencher-2025-01-20-00.db"
+            ]
+        );
+        assert_eq!(file_names(&prune), ["backup-bencher-2025-01-01-00.db"]);
+    }
+
+    #[test]
+    fn test_retain_weekly() {
+        // One backup a day for all of January 2025, which starts on a Wednesday
+        let backups = (1..=31).map(|day| backup_file(2025, 1, day, 0)).collect();
+        let (keep, prune) = retain(
+            backups,
+            JsonBackupRetention {
+                daily: 2,
+                weekly: 3,
+            },
+        );
+        assert_eq!(
+            file_names(&keep),
+            [
+                // The last Sunday of the third and second most recent weeks
+                "backup-bencher-2025-01-19-00.db",
+                "backup-bencher-2025-01-26-00.db",
+                "backup-bencher-2025-01-30-00.db",
+                "backup-bencher-2025-01-31-00.db"
+            ]
+        );
+        assert_eq!(prune.len(), 27);
+    }
+
+    #[test]
+    fn test_retain_weekly_new_year() {
+        // Monday, December 29, 2025 is in the first ISO week of 2026
+        let backups = vec![
+            backup_file(2025, 12, 28, 0),
+            backup_file(2025, 12, 29, 0),
+            backup_file(2026, 1, 2, 0),
+        ];
+        let (keep, prune) = retain(
+            backups,
+            JsonBackupRetention {
+                daily: 0,
+                weekly: 2,
+            },
+        );
+        assert_eq!(
+            file_names(&keep),
+            [
+                "backup-bencher-2025-12-28-00.db",
+                "backup-bencher-2026-01-02-00.db"
+            ]
+        );
+        assert_eq!(file_names(&prune), ["backup-bencher-2025-12-29-00.db"]);
+    }
+
+    #[tokio::test]
+    async fn test_local_data_store() {
+        let dir = temp_dir("local_data_store");
+        let data_store = local_data_store(&dir);
+
+        // The manifest is empty before any backups have been saved
+        let manifest = data_store.get_manifest().await.unwrap();
+        assert!(manifest.backups.is_empty(), "{manifest:?}");
+
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let source_path = dir.join("source.db");
+        tokio::fs::write(&source_path, BACKUP_DATA).await.unwrap();
+        data_store.backup(&source_path, "backup.db").await.unwrap();
+
+        let target_path = dir.join("target.db");
+        data_store
+            .download("backup.db", &target_path)
+            .await
+            .unwrap();
+        assert_eq!(tokio::fs::read(&target_path).await.unwrap(), BACKUP_DATA);
+
+        let mut manifest = data_store.get_manifest().await.unwrap();
+        manifest.backups.push(backup_file(2025, 1, 1, 0));
+        data_store.put_manifest(&manifest).await.unwrap();
+        let manifest = data_store.get_manifest().await.unwrap();
+        assert_eq!(manifest.backups, [backup_file(2025, 1, 1, 0)]);
+
+        data_store.remove("backup.db").await.unwrap();
+        let download = data_store.download("backup.db", &target_path).await;
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+        assert!(download.is_err(), "{download:?}");
+    }
+
+    #[tokio::test]
+    async fn test_save_backup() {
+        let dir = temp_dir("save_backup");
+        let data_store = local_data_store(&dir);
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let source_path = dir.join("source.db");
+        tokio::fs::write(&source_path, BACKUP_DATA).await.unwrap();
+
+        let first = backup_file(2025, 1, 1, 0);
+        let backup = save_backup(
+            &data_store,
+            &source_path,
+            first.file_name.clone(),
+            first.created,
+            true,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(backup.file_name, first.file_name);
+        assert_eq!(backup.size, 7);
+        assert_eq!(backup.sha256, BACKUP_SHA256);
+        let manifest = data_store.get_manifest().await.unwrap();
+        assert_eq!(manifest.backups, [backup.clone()]);
+        let stored = tokio::fs::read(dir.join("data_store").join(&first.file_name))
+            .await
+            .unwrap();
+        assert_eq!(stored, BACKUP_DATA);
+
+        // An unchanged backup is skipped
+        let second = backup_file(2025, 1, 2, 0);
+        let skipped = save_backup(
+            &data_store,
+            &source_path,
+            second.file_name.clone(),
+            second.created,
+            true,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(skipped.is_none(), "{skipped:?}");
+        let manifest = data_store.get_manifest().await.unwrap();
+        assert_eq!(manifest.backups, [backup.clone()]);
+        assert!(
+            !dir.join("data_store").join(&second.file_name).exists(),
+            "Skipped backup was saved"
+        );
+
+        // Unless it is not skipped
+        let unchanged = save_backup(
+            &data_store,
+            &source_path,
+            second.file_name.clone(),
+            second.created,
+            false,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(unchanged.sha256, BACKUP_SHA256);
+        let manifest = data_store.get_manifest().await.unwrap();
+        assert_eq!(manifest.backups, [backup, unchanged]);
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_save_backup_retention() {
+        let dir = temp_dir("save_backup_retention");
+        let data_store = local_data_store(&dir);
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let source_path = dir.join("source.db");
+        let retention = JsonBackupRetention {
+            daily: 2,
+            weekly: 0,
+        };
+
+        let mut saved = Vec::new();
+        for (day, data) in [b"day 1", b"day 2", b"day 3"].into_iter().enumerate() {
+            tokio::fs::write(&source_path, data).await.unwrap();
+            let day = u32::try_from(day).unwrap() + 1;
+            let file = backup_file(2025, 1, day, 0);
+            let backup = save_backup(
+                &data_store,
+                &source_path,
+                file.file_name,
+                file.created,
+                true,
+                Some(retention),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+            saved.push(backup);
+        }
+
+        // The manifest only lists the retained backups,
+        // and the pruned backup is removed from the data store.
+        let manifest = data_store.get_manifest().await.unwrap();
+        assert_eq!(
+            file_names(&manifest.backups),
+            [
+                "backup-bencher-2025-01-02-00.db",
+                "backup-bencher-2025-01-03-00.db"
+            ]
+        );
+        assert_eq!(manifest.backups, saved.split_off(1));
+        let data_store_dir = dir.join("data_store");
+        assert!(
+            !data_store_dir
+                .join("backup-bencher-2025-01-01-00.db")
+                .exists(),
+            "Pruned backup was not removed"
+        );
+        assert!(
+            data_store_dir
+                .join("backup-bencher-2025-01-02-00.db")
+                .exists(),
+            "Retained backup was removed"
+        );
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[test]
+    fn test_is_file_name() {
+        assert!(is_file_name("backup-bencher-2025-01-01-00-00-00.db"));
+        assert!(is_file_name("backup-bencher-2025-01-01-00-00-00.db.gz"));
+        assert!(!is_file_name(""));
+        assert!(!is_file_name("."));
+        assert!(!is_file_name(".."));
+        assert!(!is_file_name("../bencher.db"));
+        assert!(!is_file_name("backups/bencher.db"));
+        assert!(!is_file_name("bencher.db/"));
+        assert!(!is_file_name("/etc/passwd"));
+    }
+
+    #[test]
+    fn test_validate_database() {
+        let dir = temp_dir("validate_database");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let bencher_path = dir.join("bencher.db");
+        drop(bencher_database(&bencher_path));
+        let bencher = validate_database(&bencher_path);
+
+        let empty_path = dir.join("empty.db");
+        DbConnection::establish(&empty_path.to_string_lossy())
+            .unwrap()
+            .batch_execute("CREATE TABLE other (id INTEGER PRIMARY KEY)")
+            .unwrap();
+        let empty = validate_database(&empty_path);
+
+        let not_sqlite_path = dir.join("not_sqlite.db");
+        std::fs::write(&not_sqlite_path, "This is not a SQLite database.").unwrap();
+        let not_sqlite = validate_database(&not_sqlite_path);
+
+        let newer_path = dir.join("newer.db");
+        bencher_database(&newer_path)
+            .batch_execute(
+                "INSERT INTO __diesel_schema_migrations (version) VALUES ('99991231000000')",
+            )
+            .unwrap();
+        let newer = validate_database(&newer_path);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(bencher.is_ok(), "{bencher:?}");
+        assert!(
+            matches!(empty, Err(RestoreError::NotBencher(_))),
+            "{empty:?}"
+        );
+        assert!(not_sqlite.is_err(), "{not_sqlite:?}");
+        assert!(
+            matches!(&newer, Err(RestoreError::Migration(version)) if version == "99991231000000"),
+            "{newer:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_file() {
+        let dir = temp_dir("restore_file");
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let database = database(&dir);
+        let upload_path = dir.join("upload.db");
+        backup_database(&upload_path);
+
+        let restored = database
+            .restore(RestoreSource::File(upload_path.clone()))
+            .await
+            .unwrap();
+        assert!(restored.backup.is_none(), "{restored:?}");
+        assert!(is_restored(&database).await, "Database was not restored");
+        assert!(!upload_path.exists(), "Upload was not removed");
+        // The restored database connection has the same pragmas as a new server connection
+        let foreign_keys = diesel::sql_query("PRAGMA foreign_keys")
+            .get_result::<ForeignKeys>(&mut *database.connection.lock().await)
+            .unwrap();
+        assert_eq!(foreign_keys.foreign_keys, 1);
+        assert!(
+            dir.join(&restored.previous).exists(),
+            "Previous database was not kept aside"
+        );
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_restore_file_gzip() {
+        let dir = temp_dir("restore_file_gzip");
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let database = database(&dir);
+        let upload_path = dir.join("upload.db");
+        backup_database(&upload_path);
+        let (upload_path, _) = compress_database(upload_path, "upload.db").await.unwrap();
+        let compressed = tokio::fs::read(&upload_path).await.unwrap();
+        assert!(
+            compressed.starts_with(&GZIP_MAGIC),
+            "Upload was not compressed"
+        );
+
+        database
+            .restore(RestoreSource::File(upload_path.clone()))
+            .await
+            .unwrap();
+        assert!(is_restored(&database).await, "Database was not restored");
+        assert!(!upload_path.exists(), "Upload was not removed");
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_restore_file_not_bencher() {
+        let dir = temp_dir("restore_file_not_bencher");
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let database = database(&dir);
+        let upload_path = dir.join("upload.db");
+        DbConnection::establish(&upload_path.to_string_lossy())
+            .unwrap()
+            .batch_execute("CREATE TABLE restored (id INTEGER PRIMARY KEY)")
+            .unwrap();
+
+        let restored = database
+            .restore(RestoreSource::File(upload_path.clone()))
+            .await;
+        assert!(
+            matches!(restored, Err(RestoreError::NotBencher(_))),
+            "{restored:?}"
+        );
+        // The current database is left in place
+        assert!(!is_restored(&database).await, "Database was restored");
+        assert!(
+            database
+                .connection
+                .lock()
+                .await
+                .batch_execute("SELECT id FROM user")
+                .is_ok(),
+            "Current database was changed"
+        );
+        assert!(!upload_path.exists(), "Upload was not removed");
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_restore_data_store() {
+        let dir = temp_dir("restore_data_store");
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let database = database(&dir);
+        database
+            .connection
+            .lock()
+            .await
+            .batch_execute("CREATE TABLE restored (id INTEGER PRIMARY KEY)")
+            .unwrap();
+        let created = database
+            .backup(BackupOptions {
+                compress: true,
+                data_store: true,
+                rm: true,
+                skip_unchanged: false,
+            })
+            .await
+            .unwrap();
+        let backup = created.backup.unwrap();
+        assert!(
+            Path::new(&backup.file_name)
+                .extension()
+                .is_some_and(|extension| extension == "gz"),
+            "{backup:?}"
+        );
+        database
+            .connection
+            .lock()
+            .await
+            .batch_execute("DROP TABLE restored")
+            .unwrap();
+        assert!(!is_restored(&database).await, "Database was not changed");
+
+        // Restore the latest backup in the data store manifest
+        let restored = database
+            .restore(RestoreSource::DataStore(None))
+            .await
+            .unwrap();
+        assert_eq!(restored.backup, Some(backup));
+        assert!(is_restored(&database).await, "Database was not restored");
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_restore_data_store_checksum() {
+        let dir = temp_dir("restore_data_store_checksum");
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let database = database(&dir);
+        let data_store = database.data_store.as_ref().unwrap();
+        let backup_path = dir.join("backup.db");
+        backup_database(&backup_path);
+        let backup = backup_file(2025, 1, 1, 0);
+        data_store
+            .backup(&backup_path, &backup.file_name)
+            .await
+            .unwrap();
+        data_store
+            .put_manifest(&JsonBackupManifest {
+                backups: vec![JsonBackupFile {
+                    sha256: BACKUP_SHA256.to_owned(),
+                    ..backup.clone()
+                }],
+            })
+            .await
+            .unwrap();
+
+        let restored = database
+            .restore(RestoreSource::DataStore(Some(backup.file_name.clone())))
+            .await;
+        assert!(
+            matches!(&restored, Err(RestoreError::Checksum { file_name, expected, .. }) if *file_name == backup.file_name && expected == BACKUP_SHA256),
+            "{restored:?}"
+        );
+        assert!(!is_restored(&database).await, "Database was restored");
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_restore_data_store_file_name() {
+        let dir = temp_dir("restore_data_store_file_name");
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let database = database(&dir);
+        // A Bencher database outside of the data store
+        backup_database(&dir.join("outside.db"));
+
+        let restored = database
+            .restore(RestoreSource::DataStore(Some("../outside.db".to_owned())))
+            .await;
+        assert!(
+            matches!(&restored, Err(RestoreError::FileName(file_name)) if file_name == "../outside.db"),
+            "{restored:?}"
+        );
+        assert!(!is_restored(&database).await, "Database was restored");
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
@@ -1,16 +1,28 @@
 use std::{
+    io::ErrorKind,
     path::{Path, PathBuf},
     sync::Arc,
 };
 
-use bencher_json::{Secret, system::config::DataStore as DataStoreConfig};
+use aws_sdk_s3::operation::get_object::GetObjectError;
+use bencher_json::{
+    Secret,
+    system::{
+        backup::JsonBackupManifest,
+        config::{DataStore as DataStoreConfig, JsonDatabaseBackup},
+    },
+};
+use diesel::connection::SimpleConnection as _;
 
 pub type DbConnection = diesel::SqliteConnection;
 
+#[derive(Clone)]
 pub struct Database {
     pub path: PathBuf,
     pub connection: Arc<tokio::sync::Mutex<DbConnection>>,
+    pub pragmas: DbPragmas,
     pub data_store: Option<DataStore>,
+    pub backup: Option<JsonDatabaseBackup>,
 }
 
 #[macro_export]
@@ -24,10 +36,48 @@ macro_rules! yield_connection_lock {
     }};
 }
 
+/// The pragmas that are set on every new connection to the database
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DbPragmas {
+    /// The busy timeout in milliseconds when the database is replicated with Litestream
+    pub litestream_busy_timeout: Option<u32>,
+}
+
+impl DbPragmas {
+    pub fn apply(self, connection: &mut DbConnection) -> Result<(), diesel::result::Error> {
+        // Foreign key constraints are disabled by default for each new connection
+        // https://www.sqlite.org/foreignkeys.html#fk_enable
+        connection.batch_execute("PRAGMA foreign_keys = ON")?;
+
+        let Some(busy_timeout) = self.litestream_busy_timeout else {
+            return Ok(());
+        };
+        // Enable WAL mode
+        // https://litestream.io/tips/#wal-journal-mode
+        // https://sqlite.org/wal.html
+        connection.batch_execute("PRAGMA journal_mode = WAL")?;
+        // Disable auto-checkpoints
+        // https://litestream.io/tips/#disable-autocheckpoints-for-high-write-load-servers
+        // https://sqlite.org/wal.html#automatic_checkpoint
+        connection.batch_execute("PRAGMA wal_autocheckpoint = 0")?;
+        // Enable busy timeout
+        // https://litestream.io/tips/#busy-timeout
+        // https://www.sqlite.org/pragma.html#pragma_busy_timeout
+        connection.batch_execute(&format!("PRAGMA busy_timeout = {busy_timeout}"))?;
+        // Relax synchronous mode because we are using WAL mode
+        // https://litestream.io/tips/#synchronous-pragma
+        // https://www.sqlite.org/pragma.html#pragma_synchronous
+        connection.batch_execute("PRAGMA synchronous = NORMAL")
+    }
+}
+
+#[derive(Clone)]
 pub enum DataStore {
     AwsS3(AwsS3),
+    Local(Local),
 }
 
+#[derive(Clone)]
 pub struct AwsS3 {
     client: aws_sdk_s3::Client,
     arn: String,
@@ -40,8 +90,15 @@ pub enum DataStoreError {
     DataStore(String),
     #[error("Failed to use AWS S3: {0}")]
     AwsS3(String),
+    #[error("Failed to use local data store ({0}): {1}")]
+    Local(PathBuf, std::io::Error),
+    #[error("Failed to parse backup manifest: {0}")]
+    Manifest(serde_json::Error),
 }
 
+// The manifest of all backups is kept alongside the backups in the data store
+const MANIFEST_FILE_NAME: &str = "bencher-backups.json";
+
 impl TryFrom<DataStoreConfig> for DataStore {
     type Error = DataStoreError;
 
@@ -52,6 +109,7 @@ impl TryFrom<DataStoreConfig> for DataStore {
                 secret_access_key,
                 access_point,
             } => AwsS3::new(access_key_id, secret_access_key, &access_point).map(Self::AwsS3),
+            DataStoreConfig::Local { path } => Ok(Self::Local(Local { path })),
         }
     }
 }
@@ -60,6 +118,45 @@ impl DataStore {
     pub async fn backup(&self, source_path: &Path, file_name: &str) -> Result<(), DataStoreError> {
         match self {
             Self::AwsS3(aws_s3) => aws_s3.backup(source_path, file_name).await,
+            Self::Local(local) => local.backup(source_path, file_name).await,
+        }
+    }
+
+    pub async fn download(
+        &self,
+        file_name: &str,
+        target_path: &Path,
+    ) -> Result<(), DataStoreError> {
+        match self {
+            Self::AwsS3(aws_s3) => aws_s3.download(file_name, target_path).await,
+            Self::Local(local) => local.download(file_name, target_path).await,
+        }
+    }
+
+    pub async fn remove(&self, file_name: &str) -> Result<(), DataStoreError> {
+        match self {
+            Self::AwsS3(aws_s3) => aws_s3.remove(file_name).await,
+            Self::Local(local) => local.remove(file_name).await,
+        }
+    }
+
+    /// Get the backup manifest, which is empty if no backups have been saved yet
+    pub async fn get_manifest(&self) -> Result<JsonBackupManifest, DataStoreError> {
+        let manifest = match self {
+            Self::AwsS3(aws_s3) => aws_s3.get(MANIFEST_FILE_NAME).await?,
+            Self::Local(local) => local.get(MANIFEST_FILE_NAME).await?,
+        };
+        manifest.map_or_else(
+            || Ok(JsonBackupManifest::default()),
+            |manifest| serde_json::from_slice(&manifest).map_err(DataStoreError::Manifest),
+        )
+    }
+
+    pub async fn put_manifest(&self, manifest: &JsonBackupManifest) -> Result<(), DataStoreError> {
+        let manifest = serde_json::to_vec_pretty(manifest).map_err(DataStoreError::Manifest)?;
+        match self {
+            Self::AwsS3(aws_s3) => aws_s3.put(MANIFEST_FILE_NAME, manifest).await,
+            Self::Local(local) => local.put(MANIFEST_FILE_NAME, manifest).await,
         }
     }
 }
@@ -115,13 +212,15 @@ impl AwsS3 {
         })
     }
 
-    async fn backup(&self, source_path: &Path, file_name: &str) -> Result<(), DataStoreError> {
-        let key = if let Some(bucket_path) = &self.path {
+    fn key(&self, file_name: &str) -> String {
+        if let Some(bucket_path) = &self.path {
             bucket_path.join(file_name).to_string_lossy().to_string()
         } else {
             file_name.to_owned()
-        };
+        }
+    }
 
+    async fn backup(&self, source_path: &Path, file_name: &str) -> Result<(), DataStoreError> {
         let body = aws_sdk_s3::primitives::ByteStream::from_path(source_path)
             .await
             .map_err(|e| DataStoreError::AwsS3(e.to_string()))?;
@@ -129,7 +228,7 @@ impl AwsS3 {
         self.client
             .put_object()
             .bucket(self.arn.clone())
-            .key(key)
+            .key(self.key(file_name))
             .body(body)
             .send()
             .await
@@ -137,4 +236,123 @@ impl AwsS3 {
 
         Ok(())
     }
+
+    async fn download(&self, file_name: &str, target_path: &Path) -> Result<(), DataStoreError> {
+        let output = self
+            .client
+            .get_object()
+            .bucket(self.arn.clone())
+            .key(self.key(file_name))
+            .send()
+            .await
+            .map_err(|e| DataStoreError::AwsS3(e.to_string()))?;
+        let mut target_file = tokio::fs::File::create(target_path)
+            .await
+            .map_err(|e| DataStoreError::Local(target_path.to_path_buf(), e))?;
+        tokio::io::copy(&mut output.body.into_async_read(), &mut target_file)
+            .await
+            .map_err(|e| DataStoreError::AwsS3(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get(&self, file_name: &str) -> Result<Option<Vec<u8>>, DataStoreError> {
+        let output = match self
+            .client
+            .get_object()
+            .bucket(self.arn.clone())
+            .key(self.key(file_name))
+            .send()
+            .await
+        {
+            Ok(output) => output,
+            Err(e)
+                if e.as_service_error()
+                    .is_some_and(GetObjectError::is_no_such_key) =>
+            {
+                return Ok(None);
+            },
+            Err(e) => return Err(DataStoreError::AwsS3(e.to_string())),
+        };
+        output
+            .body
+            .collect()
+            .await
+            .map(|data| Some(data.to_vec()))
+            .map_err(|e| DataStoreError::AwsS3(e.to_string()))
+    }
+
+    async fn put(&self, file_name: &str, data: Vec<u8>) -> Result<(), DataStoreError> {
+        self.client
+            .put_object()
+            .bucket(self.arn.clone())
+            .key(self.key(file_name))
+            .body(data.into())
+            .send()
+            .await
+            .map_err(|e| DataStoreError::AwsS3(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn remove(&self, file_name: &str) -> Result<(), DataStoreError> {
+        self.client
+            .delete_object()
+            .bucket(self.arn.clone())
+            .key(self.key(file_name))
+            .send()
+            .await
+            .map_err(|e| DataStoreError::AwsS3(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct Local {
+    path: PathBuf,
+}
+
+impl Local {
+    async fn backup(&self, source_path: &Path, file_name: &str) -> Result<(), DataStoreError> {
+        tokio::fs::create_dir_all(&self.path)
+            .await
+            .map_err(|e| DataStoreError::Local(self.path.clone(), e))?;
+        let file_path = self.path.join(file_name);
+        tokio::fs::copy(source_path, &file_path)
+            .await
+            .map_err(|e| DataStoreError::Local(file_path, e))?;
+        Ok(())
+    }
+
+    async fn download(&self, file_name: &str, target_path: &Path) -> Result<(), DataStoreError> {
+        let file_path = self.path.join(file_name);
+        tokio::fs::copy(&file_path, target_path)
+            .await
+            .map_err(|e| DataStoreError::Local(file_path, e))?;
+        Ok(())
+    }
+
+    async fn get(&self, file_name: &str) -> Result<Option<Vec<u8>>, DataStoreError> {
+        let file_path = self.path.join(file_name);
+        match tokio::fs::read(&file_path).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(DataStoreError::Local(file_path, e)),
+        }
+    }
+
+    async fn put(&self, file_name: &str, data: Vec<u8>) -> Result<(), DataStoreError> {
+        tokio::fs::create_dir_all(&self.path)
+            .await
+            .map_err(|e| DataStoreError::Local(self.path.clone(), e))?;
+        let file_path = self.path.join(file_name);
+        tokio::fs::write(&file_path, data)
+            .await
+            .map_err(|e| DataStoreError::Local(file_path, e))
+    }
+
+    async fn remove(&self, file_name: &str) -> Result<(), DataStoreError> {
+        let file_path = self.path.join(file_name);
+        tokio::fs::remove_file(&file_path)
+            .await
+            .map_err(|e| DataStoreError::Local(file_path, e))
+    }
 }
@@ -0,0 +1,194 @@
+use std::fmt::Write as _;
+
+use bencher_json::project::boundary::BoundaryLimit;
+use slog::Logger;
+
+use super::FmtBody;
+
+#[derive(Debug)]
+pub struct AlertBody {
+    pub name: String,
+    pub project: String,
+    /// If set, then the alerts are a daily digest rather than from a single report.
+    pub digest: bool,
+    pub alerts: Vec<AlertSummary>,
+    pub settings: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct AlertSummary {
+    pub benchmark: String,
+    pub measure: String,
+    pub units: String,
+    pub limit: BoundaryLimit,
+    pub boundary: Option<f64>,
+    pub value: f64,
+    pub perf_url: String,
+}
+
+impl AlertBody {
+    pub fn subject(&self) -> String {
+        let Self {
+            project,
+            digest,
+            alerts,
+            ..
+        } = self;
+        if *digest {
+            format!("Daily alert digest for {project}")
+        } else if let [alert] = alerts.as_slice() {
+            format!("Bencher alert for {project}: {}", alert.benchmark)
+        } else {
+            format!("{} Bencher alerts for {project}", alerts.len())
+        }
+    }
+
+    fn summary(&self) -> String {
+        let Self {
+            project,
+            digest,
+            alerts,
+            ..
+        } = self;
+        let count = alerts.len();
+        let alerts = if count == 1 { "alert" } else { "alerts" };
+        if *digest {
+            format!("{count} {alerts} generated for {project} in the last day.")
+        } else {
+            format!("{count} new {alerts} generated for {project}.")
+        }
+    }
+}
+
+impl FmtBody for AlertBody {
+    fn text(&self) -> String {
+        let Self {
+            name,
+            alerts,
+            settings,
+            ..
+        } = self;
+        let mut text = format!("Ahoy {name},\n{}\n", self.summary());
+        for alert in alerts {
+            let AlertSummary {
+                benchmark,
+                measure,
+                units,
+                perf_url,
+                ..
+            } = alert;
+            let _ = write!(
+                text,
+                "\nBenchmark: {benchmark}\nMeasure: {measure}\nLimit: {limit}\nValue: {value} {units}\nView: {perf_url}\n",
+                limit = alert.limit_text(),
+                value = format_number(alert.value),
+            );
+        }
+        let _ = write!(text, "\n{settings}\n\n🐰 Bencher\n");
+        text
+    }
+
+    fn html(&self, _log: &Logger) -> String {
+        let Self {
+            name,
+            alerts,
+            settings,
+            ..
+        } = self;
+        let rows = alerts.iter().fold(String::new(), |mut rows, alert| {
+            let AlertSummary {
+                benchmark,
+                measure,
+                units,
+                perf_url,
+                ..
+            } = alert;
+            let _ = write!(
+                rows,
+                "
+                <tr>
+                    <td>{benchmark}</td>
+                    <td>{measure}</td>
+                    <td>{limit}</td>
+                    <td>{value} {units}</td>
+                    <td><a href=\"{perf_url}\">View</a></td>
+                </tr>",
+                benchmark = escape(benchmark),
+                measure = escape(measure),
+                units = escape(units),
+                limit = alert.limit_text(),
+                value = format_number(alert.value),
+                perf_url = escape(perf_url),
+            );
+            rows
+        });
+        format!(
+            "<!doctype html>
+<html>
+    <head>
+        <meta charset=\"utf-8\" />
+        <meta name=\"viewport\" content=\"width=device-width, initial-scale=1, shrink-to-fit=no\" />
+        <meta name=\"theme-color\" content=\"#ffffff\" />
+        <title>{subject}</title>
+    </head>
+    <body>
+        <p>Ahoy {name},</p>
+        <p>{summary}</p>
+        <table>
+            <thead>
+                <tr>
+                    <th>Benchmark</th>
+                    <th>Measure</th>
+                    <th>Limit</th>
+                    <th>Value</th>
+                    <th></th>
+                </tr>
+            </thead>
+            <tbody>{rows}
+            </tbody>
+        </table>
+        <br />
+        <p>{settings}</p>
+        <p>🐰 Bencher</p>
+    </body>
+</html>",
+            subject = escape(&self.subject()),
+            name = escape(name),
+            summary = escape(&self.summary()),
+            settings = escape(settings),
+        )
+    }
+}
+
+impl AlertSummary {
+    fn limit_text(&self) -> String {
+        let side = match self.limit {
+            BoundaryLimit::Lower => "Lower",
+            BoundaryLimit::Upper => "Upper",
+        };
+        if let Some(boundary) = self.boundary {
+            format!("{side} Boundary {}", format_number(boundary))
+        } else {
+            format!("{side} Boundary")
+        }
+    }
+}
+
+fn format_number(number: f64) -> String {
+    format!("{number:.3}")
+}
+
+fn escape(text: &str) -> String {
+    text.chars()
+        .fold(String::with_capacity(text.len()), |mut escaped, c| {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&#39;"),
+                _ => escaped.push(c),
+            }
+            escaped
+        })
+}
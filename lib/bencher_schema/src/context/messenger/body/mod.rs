@@ -1,7 +1,9 @@
+mod alert;
 mod button;
 mod new_user;
 mod server_stats;
 
+pub use alert::{AlertBody, AlertSummary};
 pub use button::ButtonBody;
 pub use new_user::NewUserBody;
 #[cfg(feature = "plus")]
@@ -15,6 +17,7 @@ pub trait FmtBody {
 
 #[derive(Debug)]
 pub enum Body {
+    Alert(Box<AlertBody>),
     Button(Box<ButtonBody>),
     NewUser(NewUserBody),
     #[cfg(feature = "plus")]
@@ -24,6 +27,7 @@ pub enum Body {
 impl FmtBody for Body {
     fn text(&self) -> String {
         match self {
+            Self::Alert(body) => body.text(),
             Self::Button(body) => body.text(),
             Self::NewUser(body) => body.text(),
             #[cfg(feature = "plus")]
@@ -33,6 +37,7 @@ impl FmtBody for Body {
 
     fn html(&self, log: &Logger) -> String {
         match self {
+            Self::Alert(body) => body.html(log),
             Self::Button(body) => body.html(log),
             Self::NewUser(body) => body.html(log),
             #[cfg(feature = "plus")]
@@ -5,7 +5,7 @@ mod message;
 use bencher_json::system::config::JsonSmtp;
 #[cfg(feature = "plus")]
 pub use body::ServerStatsBody;
-pub use body::{Body, ButtonBody, NewUserBody};
+pub use body::{AlertBody, AlertSummary, Body, ButtonBody, NewUserBody};
 pub use email::Email;
 pub use message::Message;
 use slog::{Logger, info};
@@ -4,6 +4,8 @@ use bencher_billing::Biller;
 use bencher_github::GitHub;
 #[cfg(feature = "plus")]
 use bencher_license::Licensor;
+#[cfg(feature = "plus")]
+use bencher_oidc::Oidc;
 use bencher_token::TokenKey;
 use tokio::sync::mpsc::Sender;
 use url::Url;
@@ -11,6 +13,7 @@ use url::Url;
 #[cfg(feature = "plus")]
 use crate::model::project::QueryProject;
 
+mod backup;
 mod database;
 mod indexer;
 mod messenger;
@@ -20,12 +23,15 @@ mod rbac;
 #[cfg(feature = "plus")]
 mod stats;
 
-pub use database::{DataStoreError, Database, DbConnection};
+pub use backup::{BackupError, BackupOptions, RestoreError, RestoreSource};
+pub use database::{DataStore, DataStoreError, Database, DbConnection, DbPragmas};
 #[cfg(feature = "plus")]
 pub use indexer::{IndexError, Indexer};
 #[cfg(feature = "plus")]
 pub use messenger::ServerStatsBody;
-pub use messenger::{Body, ButtonBody, Email, Message, Messenger, NewUserBody};
+pub use messenger::{
+    AlertBody, AlertSummary, Body, ButtonBody, Email, Message, Messenger, NewUserBody,
+};
 #[cfg(feature = "plus")]
 pub use rate_limiting::{RateLimiting, RateLimitingError};
 pub use rbac::{Rbac, RbacError};
@@ -39,11 +45,14 @@ pub struct ApiContext {
     pub messenger: Messenger,
     pub database: Database,
     pub restart_tx: Sender<()>,
+    pub allow_private_webhooks: bool,
     #[cfg(feature = "plus")]
     pub rate_limiting: RateLimiting,
     #[cfg(feature = "plus")]
     pub github: Option<GitHub>,
     #[cfg(feature = "plus")]
+    pub oidc: Option<Oidc>,
+    #[cfg(feature = "plus")]
     pub indexer: Option<Indexer>,
     #[cfg(feature = "plus")]
     pub stats: StatsSettings,
@@ -33,6 +33,10 @@ pub enum BencherResource {
     Alert,
     User,
     Token,
+    Notification,
+    TrustPolicy,
+    Webhook,
+    WebhookDelivery,
     #[cfg(feature = "plus")]
     Plan,
     #[cfg(feature = "plus")]
@@ -70,6 +74,10 @@ impl fmt::Display for BencherResource {
                 Self::Alert => "Alert",
                 Self::User => "User",
                 Self::Token => "Token",
+                Self::Notification => "Notification",
+                Self::TrustPolicy => "Trust Policy",
+                Self::Webhook => "Webhook",
+                Self::WebhookDelivery => "Webhook Delivery",
                 #[cfg(feature = "plus")]
                 Self::Plan => "Plan",
                 #[cfg(feature = "plus")]
@@ -136,6 +144,17 @@ where
     ))
 }
 
+pub fn payload_too_large_error<E>(error: E) -> HttpError
+where
+    E: fmt::Display,
+{
+    cors_headers(HttpError::for_client_error(
+        None,
+        ClientErrorStatusCode::PAYLOAD_TOO_LARGE,
+        error.to_string(),
+    ))
+}
+
 pub fn conflict_error<E>(error: E) -> HttpError
 where
     E: fmt::Display,
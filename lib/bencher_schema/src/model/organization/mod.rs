@@ -68,6 +68,7 @@ impl QueryOrganization {
         context: &ApiContext,
         auth_user: &AuthUser,
     ) -> Result<Self, HttpError> {
+        auth_user.check_is_unscoped()?;
         // The user's organization should be created with the user's UUID.
         let user_uuid = auth_user.user.uuid;
         if let Ok(query_organization) = Self::from_uuid(conn_lock!(context), user_uuid.into()) {
@@ -118,6 +119,7 @@ impl QueryOrganization {
         auth_user: &AuthUser,
         insert_organization: InsertOrganization,
     ) -> Result<Self, HttpError> {
+        auth_user.check_is_unscoped()?;
         #[cfg(feature = "plus")]
         InsertOrganization::rate_limit(context, auth_user).await?;
         let query_organization = Self::create_inner(context, insert_organization).await?;
@@ -73,6 +73,7 @@ impl QueryMeasure {
         Ok(query_measure.id)
     }
 
+    #[expect(clippy::too_many_lines)]
     async fn get_or_create_inner(
         context: &ApiContext,
         project_id: ProjectId,
@@ -93,6 +94,11 @@ impl QueryMeasure {
             .or_else(|| built_in::default::Throughput::from_str(measure_str))
             .or_else(|| built_in::json::BuildTime::from_str(measure_str))
             .or_else(|| built_in::json::FileSize::from_str(measure_str))
+            .or_else(|| built_in::criterion::ByteThroughput::from_str(measure_str))
+            .or_else(|| built_in::go::BytesPerOp::from_str(measure_str))
+            .or_else(|| built_in::go::AllocsPerOp::from_str(measure_str))
+            .or_else(|| built_in::go::MegabytesPerSecond::from_str(measure_str))
+            .or_else(|| built_in::google::CpuTime::from_str(measure_str))
             .or_else(|| built_in::iai::Instructions::from_str(measure_str))
             .or_else(|| built_in::iai::L1Accesses::from_str(measure_str))
             .or_else(|| built_in::iai::L2Accesses::from_str(measure_str))
@@ -161,6 +167,20 @@ impl QueryMeasure {
             .or_else(|| built_in::iai_callgrind::DrdContexts::from_str(measure_str))
             .or_else(|| built_in::iai_callgrind::DrdSuppressedErrors::from_str(measure_str))
             .or_else(|| built_in::iai_callgrind::DrdSuppressedContexts::from_str(measure_str))
+            // rusage
+            .or_else(|| built_in::rusage::UserTime::from_str(measure_str))
+            .or_else(|| built_in::rusage::SystemTime::from_str(measure_str))
+            .or_else(|| built_in::rusage::MaxRss::from_str(measure_str))
+            .or_else(|| built_in::rusage::VoluntaryContextSwitches::from_str(measure_str))
+            .or_else(|| built_in::rusage::InvoluntaryContextSwitches::from_str(measure_str))
+            .or_else(|| built_in::rusage::ReadBytes::from_str(measure_str))
+            .or_else(|| built_in::rusage::WriteBytes::from_str(measure_str))
+            // perf stat
+            .or_else(|| built_in::perf::Cycles::from_str(measure_str))
+            .or_else(|| built_in::perf::Instructions::from_str(measure_str))
+            .or_else(|| built_in::perf::BranchMisses::from_str(measure_str))
+            .or_else(|| built_in::perf::CacheMisses::from_str(measure_str))
+            .or_else(|| built_in::perf::TaskClock::from_str(measure_str))
         {
             measure
         } else {
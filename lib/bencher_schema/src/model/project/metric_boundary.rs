@@ -37,6 +37,8 @@ pub struct QueryMetricBoundary {
     pub baseline: Option<f64>,
     pub lower_limit: Option<f64>,
     pub upper_limit: Option<f64>,
+    pub change_point: Option<MetricUuid>,
+    pub significance: Option<f64>,
 }
 
 impl QueryMetricBoundary {
@@ -56,6 +58,8 @@ impl QueryMetricBoundary {
             baseline,
             lower_limit,
             upper_limit,
+            change_point,
+            significance,
         } = self;
         let query_metric = QueryMetric {
             id: metric_id,
@@ -78,6 +82,8 @@ impl QueryMetricBoundary {
                 baseline,
                 lower_limit,
                 upper_limit,
+                change_point,
+                significance,
             })
         } else {
             None
@@ -43,6 +43,8 @@ pub mod project_role;
 pub mod report;
 pub mod testbed;
 pub mod threshold;
+pub mod trust_policy;
+pub mod webhook;
 
 crate::macros::typed_id::typed_id!(ProjectId);
 
@@ -1,9 +1,10 @@
 use bencher_json::{
-    DateTime, JsonNewReport, JsonReport, ReportUuid,
+    DateTime, JsonNewReport, JsonReport, JsonTestbedHardware, ReportUuid,
     project::report::{
-        Adapter, Iteration, JsonReportAlerts, JsonReportMeasure, JsonReportResult,
-        JsonReportResults,
+        Adapter, Iteration, JsonHardwareChange, JsonReportAlerts, JsonReportMeasure,
+        JsonReportResult, JsonReportResults,
     },
+    project::webhook::JsonWebhookEventData,
 };
 use diesel::{
     ExpressionMethods as _, NullableExpressionMethods as _, QueryDsl as _, RunQueryDsl as _,
@@ -28,8 +29,11 @@ use crate::{
             measure::QueryMeasure,
             testbed::{QueryTestbed, TestbedId},
             threshold::{QueryThreshold, alert::QueryAlert, model::QueryModel},
+            webhook::QueryWebhook,
+        },
+        user::{
+            QueryUser, UserId, auth::AuthUser, notification::QueryNotification, token::TokenId,
         },
-        user::{QueryUser, UserId, auth::AuthUser},
     },
     schema::{self, report as report_table},
     view,
@@ -54,11 +58,15 @@ pub struct QueryReport {
     pub id: ReportId,
     pub uuid: ReportUuid,
     pub user_id: Option<UserId>,
+    pub token_id: Option<TokenId>,
     pub project_id: ProjectId,
     pub head_id: HeadId,
     pub version_id: VersionId,
     pub testbed_id: TestbedId,
     pub adapter: Adapter,
+    pub hardware: Option<JsonTestbedHardware>,
+    pub environment_change: bool,
+    pub hardware_change: Option<JsonHardwareChange>,
     pub start_time: DateTime,
     pub end_time: DateTime,
     pub created: DateTime,
@@ -126,15 +134,29 @@ impl QueryReport {
         let json_settings = json_report.settings.take().unwrap_or_default();
         let adapter = json_settings.adapter.unwrap_or_default();
 
+        // Check to see if the testbed hardware has changed since its previous report
+        // and update the testbed hardware profile if this is its most recent report.
+        let query_testbed = QueryTestbed::get(conn_lock!(context), testbed_id)?;
+        let environment_change = query_testbed.update_hardware(
+            conn_lock!(context),
+            json_report.hardware.as_ref(),
+            json_report.end_time,
+        )?;
+        let hardware_change =
+            environment_change.then(|| json_settings.hardware_change.unwrap_or_default());
+
         // Create a new report and add it to the database
         let insert_report = InsertReport::from_json(
             auth_user.map(|u| u.id),
+            auth_user.and_then(|u| u.token.as_ref().map(|t| t.id)),
             project_id,
             head_id,
             version_id,
             testbed_id,
             &json_report,
             adapter,
+            environment_change,
+            hardware_change,
         );
 
         diesel::insert_into(schema::report::table)
@@ -159,6 +181,7 @@ impl QueryReport {
         // Process and record the report results
         let mut report_results =
             ReportResults::new(project_id, branch_id, head_id, testbed_id, query_report.id);
+        report_results.hardware_change = hardware_change;
         let results_array = json_report
             .results
             .iter()
@@ -184,7 +207,30 @@ impl QueryReport {
         // Don't return the error from processing the report until after the metrics usage has been checked
         processed_report?;
         // If the report was processed successfully, then return the report with the results
-        query_report.into_json(log, context).await
+        let json_report = query_report.into_json(log, context).await?;
+        Self::send_webhook_events(log, context, query_project, &json_report).await;
+        QueryNotification::send_alerts(log, context, query_project, &json_report).await;
+        Ok(json_report)
+    }
+
+    // Notify the project webhooks of the new report and any alerts that it raised
+    async fn send_webhook_events(
+        log: &Logger,
+        context: &ApiContext,
+        query_project: &QueryProject,
+        json_report: &JsonReport,
+    ) {
+        let webhook_events = std::iter::once(JsonWebhookEventData::ReportCreated(Box::new(
+            json_report.clone(),
+        )))
+        .chain(
+            json_report
+                .alerts
+                .iter()
+                .map(|alert| JsonWebhookEventData::AlertCreated(Box::new(alert.clone()))),
+        )
+        .collect();
+        QueryWebhook::send_events(log, context, query_project, webhook_events).await;
     }
 
     pub async fn into_json(
@@ -201,9 +247,13 @@ impl QueryReport {
             version_id,
             testbed_id,
             adapter,
+            hardware,
+            environment_change,
+            hardware_change,
             start_time,
             end_time,
             created,
+            ..
         } = self;
 
         let query_project = QueryProject::get(conn_lock!(context), project_id)?;
@@ -229,6 +279,9 @@ impl QueryReport {
             start_time,
             end_time,
             adapter,
+            hardware,
+            environment_change,
+            hardware_change,
             results,
             alerts,
             created,
@@ -289,6 +342,10 @@ async fn get_report_results(
                 schema::model::window,
                 schema::model::lower_boundary,
                 schema::model::upper_boundary,
+                schema::model::smoothing,
+                schema::model::trend,
+                schema::model::season,
+                schema::model::environment_change,
                 schema::model::created,
                 schema::model::replaced,
             )
@@ -399,6 +456,7 @@ async fn get_report_alerts(
         .select((
             schema::report::uuid,
             schema::report::created,
+            schema::report::environment_change,
             schema::report_benchmark::iteration,
             QueryAlert::as_select(),
             QueryBenchmark::as_select(),
@@ -408,6 +466,7 @@ async fn get_report_alerts(
         .load::<(
             ReportUuid,
             DateTime,
+            bool,
             Iteration,
             QueryAlert,
             QueryBenchmark,
@@ -420,6 +479,7 @@ async fn get_report_alerts(
     for (
         report_uuid,
         created,
+        environment_change,
         iteration,
         query_alert,
         query_benchmark,
@@ -433,6 +493,7 @@ async fn get_report_alerts(
                 project,
                 report_uuid,
                 created,
+                environment_change,
                 head_id,
                 version_id,
                 iteration,
@@ -452,11 +513,15 @@ async fn get_report_alerts(
 pub struct InsertReport {
     pub uuid: ReportUuid,
     pub user_id: Option<UserId>,
+    pub token_id: Option<TokenId>,
     pub project_id: ProjectId,
     pub head_id: HeadId,
     pub version_id: VersionId,
     pub testbed_id: TestbedId,
     pub adapter: Adapter,
+    pub hardware: Option<JsonTestbedHardware>,
+    pub environment_change: bool,
+    pub hardware_change: Option<JsonHardwareChange>,
     pub start_time: DateTime,
     pub end_time: DateTime,
     pub created: DateTime,
@@ -466,23 +531,31 @@ impl InsertReport {
     #[cfg(feature = "plus")]
     crate::macros::rate_limit::fn_rate_limit!(report, Report);
 
+    #[expect(clippy::too_many_arguments)]
     pub fn from_json(
         user_id: Option<UserId>,
+        token_id: Option<TokenId>,
         project_id: ProjectId,
         head_id: HeadId,
         version_id: VersionId,
         testbed_id: TestbedId,
         report: &JsonNewReport,
         adapter: Adapter,
+        environment_change: bool,
+        hardware_change: Option<JsonHardwareChange>,
     ) -> Self {
         Self {
             uuid: ReportUuid::new(),
             user_id,
+            token_id,
             project_id,
             head_id,
             version_id,
             testbed_id,
             adapter,
+            hardware: report.hardware.clone(),
+            environment_change,
+            hardware_change,
             start_time: report.start_time,
             end_time: report.end_time,
             created: DateTime::now(),
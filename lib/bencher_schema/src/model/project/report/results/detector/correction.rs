@@ -0,0 +1,54 @@
+use bencher_boundary::Correction;
+use bencher_json::project::report::JsonCorrection;
+use dropshot::HttpError;
+use slog::{Logger, debug};
+
+use crate::context::ApiContext;
+
+use super::DetectorBoundary;
+
+/// The boundary checks for every benchmark and measure in a report,
+/// which are corrected for multiple comparisons before they are recorded.
+#[derive(Debug)]
+pub struct ReportCorrection {
+    correction: JsonCorrection,
+    boundaries: Vec<DetectorBoundary>,
+}
+
+impl ReportCorrection {
+    pub fn new(correction: JsonCorrection) -> Self {
+        Self {
+            correction,
+            boundaries: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, detector_boundary: DetectorBoundary) {
+        self.boundaries.push(detector_boundary);
+    }
+
+    pub async fn detect(self, log: &Logger, context: &ApiContext) -> Result<(), HttpError> {
+        let Self {
+            correction,
+            boundaries,
+        } = self;
+        // Only the statistical threshold model tests count towards the number of comparisons.
+        let normalized = boundaries
+            .iter()
+            .filter_map(DetectorBoundary::normalized)
+            .collect::<Vec<_>>();
+        let correction = Correction::new(correction, &normalized);
+        debug!(
+            log,
+            "Multiple comparisons correction for {} boundary checks: {correction:?}",
+            normalized.len()
+        );
+        for mut detector_boundary in boundaries {
+            if let Some(correction) = correction {
+                detector_boundary.correct(log, correction)?;
+            }
+            detector_boundary.insert(context).await?;
+        }
+        Ok(())
+    }
+}
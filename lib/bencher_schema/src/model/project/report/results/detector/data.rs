@@ -1,4 +1,5 @@
 use bencher_boundary::MetricsData;
+use bencher_json::MetricUuid;
 use chrono::offset::Utc;
 use diesel::{ExpressionMethods as _, JoinOnDsl as _, QueryDsl as _, RunQueryDsl as _};
 use dropshot::HttpError;
@@ -8,13 +9,16 @@ use crate::{
     context::DbConnection,
     error::not_found_error,
     model::project::{
-        benchmark::BenchmarkId, branch::head::HeadId, measure::MeasureId, testbed::TestbedId,
+        benchmark::BenchmarkId, branch::head::HeadId, measure::MeasureId, metric::MetricId,
+        testbed::TestbedId,
     },
     schema,
 };
 
-use super::threshold::ThresholdModel;
+use super::{DetectorSample, threshold::ThresholdModel};
 
+/// The historical metrics data for a sample, which does not include the sample itself.
+#[expect(clippy::too_many_arguments)]
 pub fn metrics_data(
     log: &Logger,
     conn: &mut DbConnection,
@@ -23,7 +27,8 @@ pub fn metrics_data(
     benchmark_id: BenchmarkId,
     measure_id: MeasureId,
     model: &ThresholdModel,
-) -> Result<MetricsData, HttpError> {
+    sample: &DetectorSample,
+) -> Result<(Vec<MetricUuid>, MetricsData), HttpError> {
     let mut query = schema::metric::table
         .inner_join(
             schema::report_benchmark::table
@@ -47,6 +52,12 @@ pub fn metrics_data(
         .filter(schema::metric::measure_id.eq(measure_id))
         .into_boxed();
 
+    // Split a report sample from the historical data by its report, not by its position,
+    // as a backdated or concurrent report may not be the most recent.
+    if let DetectorSample::Report { report_id, .. } = sample {
+        query = query.filter(schema::report::id.ne(*report_id));
+    }
+
     if let Some(window) = model.window {
         let now = Utc::now().timestamp();
         if let Some(start_time) = now.checked_sub(window.into()) {
@@ -67,15 +78,32 @@ pub fn metrics_data(
     ));
 
     if let Some(max_sample_size) = model.max_sample_size {
-        query = query.limit(max_sample_size.into());
+        query = match sample {
+            DetectorSample::Metric(_) => query.limit(max_sample_size.into()),
+            // A report sample counts as a single datum towards the max sample size.
+            DetectorSample::Report { .. } => {
+                query.limit(i64::from(max_sample_size).saturating_sub(1))
+            },
+        };
     }
 
-    let data = query
-        .select(schema::metric::value)
-        .load::<f64>(conn)
+    let (uuids, data): (Vec<_>, Vec<_>) = query
+        .select((
+            schema::metric::id,
+            schema::metric::uuid,
+            schema::metric::value,
+        ))
+        .load::<(MetricId, MetricUuid, f64)>(conn)
         .map_err(not_found_error)?
         .into_iter()
-        .collect();
+        // A single metric is most likely included in its own historical data,
+        // so it is removed after the max sample size has been applied.
+        .filter(|(id, _, _)| match sample {
+            DetectorSample::Metric(query_metric) => *id != query_metric.id,
+            DetectorSample::Report { .. } => true,
+        })
+        .map(|(_, uuid, value)| (uuid, value))
+        .unzip();
 
-    Ok(MetricsData { data })
+    Ok((uuids, MetricsData { data }))
 }
@@ -1,5 +1,8 @@
-use bencher_boundary::MetricsBoundary;
-use bencher_json::BoundaryUuid;
+use bencher_boundary::{Correction, MetricsBoundary, MetricsData};
+use bencher_json::{
+    BoundaryUuid, EnvironmentChange, MetricUuid, Model,
+    project::{alert::AlertStatus, report::JsonHardwareChange},
+};
 use diesel::RunQueryDsl as _;
 use dropshot::HttpError;
 use slog::Logger;
@@ -7,19 +10,22 @@ use slog::Logger;
 use crate::{
     conn_lock,
     context::{ApiContext, DbConnection},
-    error::{bad_request_error, resource_conflict_err},
+    error::{bad_request_error, issue_error, resource_conflict_err},
     model::project::{
         benchmark::BenchmarkId,
         branch::{BranchId, head::HeadId},
         measure::MeasureId,
-        metric::QueryMetric,
+        metric::{MetricId, QueryMetric},
+        report::ReportId,
         testbed::TestbedId,
         threshold::{alert::InsertAlert, boundary::InsertBoundary},
     },
     schema,
 };
 
+pub mod correction;
 pub mod data;
+pub mod sample;
 pub mod threshold;
 
 use data::metrics_data;
@@ -31,6 +37,8 @@ pub struct Detector {
     pub testbed_id: TestbedId,
     pub measure_id: MeasureId,
     pub threshold: Threshold,
+    /// The status of any alerts, which depends on whether the testbed hardware has changed since its previous report.
+    pub alert_status: AlertStatus,
 }
 
 impl Detector {
@@ -40,14 +48,18 @@ impl Detector {
         head_id: HeadId,
         testbed_id: TestbedId,
         measure_id: MeasureId,
+        hardware_change: Option<JsonHardwareChange>,
     ) -> Option<Self> {
         // Check to see if there is a threshold for the branch/testbed/measure grouping.
         // If not, then there will be nothing to detect.
-        Threshold::new(conn, branch_id, testbed_id, measure_id).map(|threshold| Self {
+        let threshold = Threshold::new(conn, branch_id, testbed_id, measure_id)?;
+        let alert_status = alert_status(hardware_change, threshold.model.environment_change)?;
+        Some(Self {
             head_id,
             testbed_id,
             measure_id,
             threshold,
+            alert_status,
         })
     }
 
@@ -56,11 +68,11 @@ impl Detector {
         log: &Logger,
         context: &ApiContext,
         benchmark_id: BenchmarkId,
-        query_metric: &QueryMetric,
+        sample: DetectorSample,
         ignore_benchmark: bool,
-    ) -> Result<(), HttpError> {
+    ) -> Result<DetectorBoundary, HttpError> {
         // Query the historical population/sample data for the benchmark
-        let metrics_data = metrics_data(
+        let (history_uuids, metrics_data) = metrics_data(
             log,
             conn_lock!(context),
             self.head_id,
@@ -68,29 +80,167 @@ impl Detector {
             benchmark_id,
             self.measure_id,
             &self.threshold.model,
+            &sample,
         )?;
+        let metrics = sample.into_metrics();
+        // The metrics for the sample are ordered from most recent to least recent,
+        // followed by the historical metrics data.
+        let metric_uuids = metrics
+            .iter()
+            .map(|metric| metric.uuid)
+            .chain(history_uuids)
+            .collect();
+        let sample = metrics
+            .iter()
+            .map(|metric| metric.value)
+            .collect::<Vec<_>>();
 
-        // Check to see if the metric has a boundary check for the given threshold model.
-        let boundary = MetricsBoundary::new(
-            log,
-            query_metric.value,
-            &metrics_data,
-            self.threshold.model.test,
-            self.threshold.model.min_sample_size,
-            self.threshold.model.lower_boundary,
-            self.threshold.model.upper_boundary,
-        )
-        .map_err(bad_request_error)?;
+        // Check to see if the metric sample has a boundary check for the given threshold model.
+        // The boundary is recorded for the most recent metric in the sample.
+        let boundary =
+            MetricsBoundary::new(log, &sample, &metrics_data, self.threshold.model.model())
+                .map_err(bad_request_error)?;
+
+        let Some(metric_id) = metrics.first().map(|metric| metric.id) else {
+            return Err(issue_error(
+                "Failed to find sample metric",
+                "Failed to find any metrics for a detector sample even though they were just created.",
+                "empty detector sample",
+            ));
+        };
+
+        Ok(DetectorBoundary {
+            threshold: self.threshold.clone(),
+            metric_id,
+            ignore_benchmark,
+            sample,
+            metric_uuids,
+            // Only the statistical threshold model tests are rechecked after a correction for multiple comparisons,
+            // so the historical metrics data is only kept for them.
+            metrics_data: boundary.p_value.is_some().then_some(metrics_data),
+            boundary,
+            significance: None,
+            alert_status: self.alert_status,
+        })
+    }
+}
+
+/// The status of any alerts for a report checked against a threshold,
+/// or `None` if the report should not be checked against the threshold at all.
+///
+/// When the testbed hardware has changed since its previous report,
+/// the report `hardware_change` setting decides whether the report is compared to the historical metrics.
+/// If it is, then the threshold model `environment_change` setting decides whether its alerts are active or silenced.
+/// Either way, the alerts are annotated as an environment change.
+fn alert_status(
+    hardware_change: Option<JsonHardwareChange>,
+    environment_change: Option<EnvironmentChange>,
+) -> Option<AlertStatus> {
+    match (hardware_change, environment_change.unwrap_or_default()) {
+        (None, _) | (Some(JsonHardwareChange::Warn), EnvironmentChange::Annotate) => {
+            Some(AlertStatus::Active)
+        },
+        (Some(JsonHardwareChange::Warn), EnvironmentChange::Suppress) => {
+            Some(AlertStatus::Silenced)
+        },
+        (Some(JsonHardwareChange::Refuse), _) => None,
+    }
+}
+
+/// The metrics from a report that are checked against their historical metrics data.
+#[derive(Debug)]
+pub enum DetectorSample {
+    /// A single metric.
+    Metric(QueryMetric),
+    /// All of the iterations for a benchmark and measure from a report,
+    /// ordered from the most recent iteration to the least recent.
+    Report {
+        report_id: ReportId,
+        metrics: Vec<QueryMetric>,
+    },
+}
+
+impl DetectorSample {
+    fn into_metrics(self) -> Vec<QueryMetric> {
+        match self {
+            Self::Metric(query_metric) => vec![query_metric],
+            Self::Report { metrics, .. } => metrics,
+        }
+    }
+}
+
+/// A boundary check for a metric sample that has not yet been recorded.
+#[derive(Debug)]
+pub struct DetectorBoundary {
+    threshold: Threshold,
+    metric_id: MetricId,
+    ignore_benchmark: bool,
+    sample: Vec<f64>,
+    metric_uuids: Vec<MetricUuid>,
+    metrics_data: Option<MetricsData>,
+    boundary: MetricsBoundary,
+    significance: Option<f64>,
+    alert_status: AlertStatus,
+}
+
+impl DetectorBoundary {
+    // The p-value of the boundary check normalized by the significance level of the threshold model.
+    // This is only set for statistical threshold model tests.
+    fn normalized(&self) -> Option<f64> {
+        let Model {
+            lower_boundary,
+            upper_boundary,
+            ..
+        } = self.threshold.model.model();
+        self.boundary
+            .p_value
+            .and_then(|p_value| p_value.normalized(lower_boundary, upper_boundary))
+    }
+
+    // Recheck the boundary at the significance level of the threshold model after correcting for multiple comparisons.
+    fn correct(&mut self, log: &Logger, correction: Correction) -> Result<(), HttpError> {
+        if self.normalized().is_none() {
+            return Ok(());
+        }
+        let Some(metrics_data) = self.metrics_data.take() else {
+            return Ok(());
+        };
+        let model = self.threshold.model.model();
+        let corrected_model = correction.model(model).map_err(bad_request_error)?;
+        self.boundary = MetricsBoundary::new(log, &self.sample, &metrics_data, corrected_model)
+            .map_err(bad_request_error)?;
+        self.significance = correction.significance(&model);
+        Ok(())
+    }
+
+    pub async fn insert(self, context: &ApiContext) -> Result<(), HttpError> {
+        let Self {
+            threshold,
+            metric_id,
+            ignore_benchmark,
+            metric_uuids,
+            boundary,
+            significance,
+            alert_status,
+            ..
+        } = self;
+        let lower_limit = boundary.limits.lower.map(Into::into);
+        let upper_limit = boundary.limits.upper.map(Into::into);
+        let change_point = boundary
+            .change_point
+            .and_then(|index| metric_uuids.get(index).copied());
 
         let boundary_uuid = BoundaryUuid::new();
         let insert_boundary = InsertBoundary {
             uuid: boundary_uuid,
-            threshold_id: self.threshold.id,
-            model_id: self.threshold.model.id,
-            metric_id: query_metric.id,
+            threshold_id: threshold.id,
+            model_id: threshold.model.id,
+            metric_id,
             baseline: boundary.limits.baseline,
-            lower_limit: boundary.limits.lower.map(Into::into),
-            upper_limit: boundary.limits.upper.map(Into::into),
+            lower_limit,
+            upper_limit,
+            change_point,
+            significance,
         };
 
         diesel::insert_into(schema::boundary::table)
@@ -101,11 +251,57 @@ impl Detector {
         // If the boundary check detects an outlier then create an alert for it on the given side.
         // As long as the benchmark is not being ignored.
         if ignore_benchmark {
-            Ok(())
-        } else if let Some(boundary_limit) = boundary.outlier {
-            InsertAlert::from_boundary(conn_lock!(context), boundary_uuid, boundary_limit)
-        } else {
-            Ok(())
+            return Ok(());
+        }
+        if let Some(boundary_limit) = boundary.outlier {
+            InsertAlert::from_boundary(
+                conn_lock!(context),
+                boundary_uuid,
+                boundary_limit,
+                alert_status,
+            )?;
         }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{
+        EnvironmentChange,
+        project::{alert::AlertStatus, report::JsonHardwareChange},
+    };
+
+    use super::alert_status;
+
+    #[test]
+    fn test_alert_status_hardware_change() {
+        for environment_change in [
+            None,
+            Some(EnvironmentChange::Annotate),
+            Some(EnvironmentChange::Suppress),
+        ] {
+            // Without a hardware change, the threshold model setting does not apply
+            assert!(matches!(
+                alert_status(None, environment_change),
+                Some(AlertStatus::Active)
+            ));
+            // Refusing to compare the report takes precedence over the threshold model setting
+            assert!(alert_status(Some(JsonHardwareChange::Refuse), environment_change).is_none());
+        }
+
+        let warn = Some(JsonHardwareChange::Warn);
+        assert!(matches!(
+            alert_status(warn, None),
+            Some(AlertStatus::Active)
+        ));
+        assert!(matches!(
+            alert_status(warn, Some(EnvironmentChange::Annotate)),
+            Some(AlertStatus::Active)
+        ));
+        assert!(matches!(
+            alert_status(warn, Some(EnvironmentChange::Suppress)),
+            Some(AlertStatus::Silenced)
+        ));
     }
 }
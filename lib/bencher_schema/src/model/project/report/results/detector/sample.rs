@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use bencher_json::MetricUuid;
+use dropshot::HttpError;
+use slog::Logger;
+
+use crate::{
+    conn_lock,
+    context::ApiContext,
+    error::issue_error,
+    model::project::{
+        benchmark::BenchmarkId, measure::MeasureId, metric::QueryMetric, report::ReportId,
+    },
+};
+
+use super::{Detector, DetectorBoundary, DetectorSample, correction::ReportCorrection};
+
+/// The samples for every benchmark and measure in a report,
+/// where all of the iterations for a benchmark and measure are treated as a single sample.
+#[derive(Debug, Default)]
+pub struct ReportSamples {
+    index: HashMap<(BenchmarkId, MeasureId), usize>,
+    samples: Vec<ReportSample>,
+}
+
+#[derive(Debug)]
+struct ReportSample {
+    detector: Detector,
+    benchmark_id: BenchmarkId,
+    ignore_benchmark: bool,
+    // The metrics for every iteration, in iteration order.
+    metric_uuids: Vec<MetricUuid>,
+}
+
+impl ReportSamples {
+    pub fn push(
+        &mut self,
+        detector: Detector,
+        benchmark_id: BenchmarkId,
+        ignore_benchmark: bool,
+        metric_uuid: MetricUuid,
+    ) {
+        let key = (benchmark_id, detector.measure_id);
+        if let Some(sample) = self
+            .index
+            .get(&key)
+            .and_then(|&index| self.samples.get_mut(index))
+        {
+            sample.ignore_benchmark |= ignore_benchmark;
+            sample.metric_uuids.push(metric_uuid);
+        } else {
+            self.index.insert(key, self.samples.len());
+            self.samples.push(ReportSample {
+                detector,
+                benchmark_id,
+                ignore_benchmark,
+                metric_uuids: vec![metric_uuid],
+            });
+        }
+    }
+
+    pub async fn detect(
+        self,
+        log: &Logger,
+        context: &ApiContext,
+        report_id: ReportId,
+        mut correction: Option<&mut ReportCorrection>,
+    ) -> Result<(), HttpError> {
+        for sample in self.samples {
+            let detector_boundary = sample.detect(log, context, report_id).await?;
+            if let Some(correction) = correction.as_deref_mut() {
+                correction.push(detector_boundary);
+            } else {
+                detector_boundary.insert(context).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ReportSample {
+    async fn detect(
+        self,
+        log: &Logger,
+        context: &ApiContext,
+        report_id: ReportId,
+    ) -> Result<DetectorBoundary, HttpError> {
+        let Self {
+            detector,
+            benchmark_id,
+            ignore_benchmark,
+            metric_uuids,
+        } = self;
+        // The sample is ordered from the most recent iteration to the least recent.
+        let mut metrics = Vec::with_capacity(metric_uuids.len());
+        for metric_uuid in metric_uuids.into_iter().rev() {
+            let query_metric = QueryMetric::from_uuid(conn_lock!(context), metric_uuid).map_err(|e| {
+                issue_error(
+                    "Failed to find metric",
+                    &format!("Failed to find new metric ({metric_uuid}) for report sample even though it was just created."),
+                    e,
+                )
+            })?;
+            metrics.push(query_metric);
+        }
+        detector
+            .detect(
+                log,
+                context,
+                benchmark_id,
+                DetectorSample::Report { report_id, metrics },
+                ignore_benchmark,
+            )
+            .await
+    }
+}
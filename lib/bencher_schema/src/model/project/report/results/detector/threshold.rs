@@ -1,4 +1,4 @@
-use bencher_json::{Boundary, ModelTest, SampleSize, Window};
+use bencher_json::{Boundary, EnvironmentChange, Model, ModelTest, SampleSize, Smoothing, Window};
 use diesel::{
     ExpressionMethods as _, JoinOnDsl as _, NullableExpressionMethods as _, QueryDsl as _,
     RunQueryDsl as _, SelectableHelper as _,
@@ -33,6 +33,40 @@ pub struct ThresholdModel {
     pub window: Option<Window>,
     pub lower_boundary: Option<Boundary>,
     pub upper_boundary: Option<Boundary>,
+    pub smoothing: Option<Smoothing>,
+    pub trend: Option<Smoothing>,
+    pub season: Option<SampleSize>,
+    pub environment_change: Option<EnvironmentChange>,
+}
+
+impl ThresholdModel {
+    pub fn model(&self) -> Model {
+        let Self {
+            test,
+            min_sample_size,
+            max_sample_size,
+            window,
+            lower_boundary,
+            upper_boundary,
+            smoothing,
+            trend,
+            season,
+            environment_change,
+            ..
+        } = *self;
+        Model {
+            test,
+            min_sample_size,
+            max_sample_size,
+            window,
+            lower_boundary,
+            upper_boundary,
+            smoothing,
+            trend,
+            season,
+            environment_change,
+        }
+    }
 }
 
 impl Threshold {
@@ -61,6 +95,10 @@ impl Threshold {
                     window,
                     lower_boundary,
                     upper_boundary,
+                    smoothing,
+                    trend,
+                    season,
+                    environment_change,
                     ..
                 } = query_model;
                 let model = ThresholdModel {
@@ -71,6 +109,10 @@ impl Threshold {
                     window,
                     lower_boundary,
                     upper_boundary,
+                    smoothing,
+                    trend,
+                    season,
+                    environment_change,
                 };
                 Self {
                     id: threshold_id,
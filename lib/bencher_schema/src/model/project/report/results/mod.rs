@@ -6,7 +6,7 @@ use bencher_adapter::{
 };
 use bencher_json::{
     BenchmarkName, MeasureNameId,
-    project::report::{Adapter, Iteration, JsonReportSettings},
+    project::report::{Adapter, Iteration, JsonHardwareChange, JsonReportSettings},
 };
 use diesel::RunQueryDsl as _;
 use dropshot::HttpError;
@@ -30,7 +30,7 @@ use crate::{
 
 pub mod detector;
 
-use detector::Detector;
+use detector::{Detector, DetectorSample, correction::ReportCorrection, sample::ReportSamples};
 
 use super::ReportId;
 
@@ -44,6 +44,12 @@ pub struct ReportResults {
     pub benchmark_cache: HashMap<BenchmarkName, BenchmarkId>,
     pub measure_cache: HashMap<MeasureNameId, MeasureId>,
     pub detector_cache: HashMap<MeasureId, Option<Detector>>,
+    /// If set, then all of the iterations of a benchmark and measure are detected as a single sample.
+    pub samples: Option<ReportSamples>,
+    /// If set, then all of the boundary checks in the report are corrected for multiple comparisons.
+    pub correction: Option<ReportCorrection>,
+    /// If set, then the testbed hardware has changed since its previous report.
+    pub hardware_change: Option<JsonHardwareChange>,
 }
 
 impl ReportResults {
@@ -63,6 +69,9 @@ impl ReportResults {
             benchmark_cache: HashMap::new(),
             measure_cache: HashMap::new(),
             detector_cache: HashMap::new(),
+            samples: None,
+            correction: None,
+            hardware_change: None,
         }
     }
 
@@ -83,7 +92,27 @@ impl ReportResults {
                 ))
             })?;
 
+        match self.hardware_change {
+            Some(JsonHardwareChange::Warn) => slog::warn!(
+                log,
+                "Testbed ({}) hardware has changed since its previous report",
+                self.testbed_id
+            ),
+            Some(JsonHardwareChange::Refuse) => slog::warn!(
+                log,
+                "Testbed ({}) hardware has changed since its previous report, skipping threshold detection",
+                self.testbed_id
+            ),
+            None => {},
+        }
+        self.correction = settings.correction.map(ReportCorrection::new);
         if let Some(fold) = settings.fold {
+            // A folded report only has a single iteration, so there is nothing to sample.
+            if settings.sample.unwrap_or_default() {
+                return Err(bad_request_error(format!(
+                    "Cannot treat a folded report ({fold:?}) as a single sample. Either fold the results or sample them, but not both."
+                )));
+            }
             let results = results_array.fold(fold);
             self.results(
                 log,
@@ -95,6 +124,9 @@ impl ReportResults {
             )
             .await?;
         } else {
+            if settings.sample.unwrap_or_default() {
+                self.samples = Some(ReportSamples::default());
+            }
             for (iteration, results) in results_array.inner.into_iter().enumerate() {
                 self.results(
                     log,
@@ -106,6 +138,16 @@ impl ReportResults {
                 )
                 .await?;
             }
+            // Detect each sample once all of its iterations have been recorded.
+            if let Some(samples) = self.samples.take() {
+                samples
+                    .detect(log, context, self.report_id, self.correction.as_mut())
+                    .await?;
+            }
+        }
+        // Correct and record all of the boundary checks once the entire report has been checked.
+        if let Some(correction) = self.correction.take() {
+            correction.detect(log, context).await?;
         }
 
         Ok(())
@@ -177,6 +219,10 @@ impl ReportResults {
             let Some(detector) = self.detector(context, measure_id).await else {
                 continue;
             };
+            if let Some(samples) = self.samples.as_mut() {
+                samples.push(detector, benchmark_id, ignore_benchmark, insert_metric.uuid);
+                continue;
+            }
             let query_metric = QueryMetric::from_uuid(conn_lock!(context), insert_metric.uuid).map_err(|e| {
                     issue_error(
                         "Failed to find metric",
@@ -184,9 +230,20 @@ impl ReportResults {
                         e,
                     )
                 })?;
-            detector
-                .detect(log, context, benchmark_id, &query_metric, ignore_benchmark)
+            let detector_boundary = detector
+                .detect(
+                    log,
+                    context,
+                    benchmark_id,
+                    DetectorSample::Metric(query_metric),
+                    ignore_benchmark,
+                )
                 .await?;
+            if let Some(correction) = self.correction.as_mut() {
+                correction.push(detector_boundary);
+            } else {
+                detector_boundary.insert(context).await?;
+            }
         }
 
         Ok(())
@@ -235,6 +292,7 @@ impl ReportResults {
                 self.head_id,
                 self.testbed_id,
                 measure_id,
+                self.hardware_change,
             );
             self.detector_cache.insert(measure_id, detector.clone());
             detector
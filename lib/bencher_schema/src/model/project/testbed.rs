@@ -1,15 +1,15 @@
 use bencher_json::{
-    DateTime, JsonNewTestbed, JsonTestbed, NameId, NameIdKind, ResourceName, Slug, TestbedUuid,
-    project::testbed::JsonUpdateTestbed,
+    DateTime, JsonNewTestbed, JsonTestbed, JsonTestbedHardware, NameId, NameIdKind, ResourceName,
+    Slug, TestbedUuid, project::testbed::JsonUpdateTestbed,
 };
-use diesel::{ExpressionMethods as _, QueryDsl as _, RunQueryDsl as _};
+use diesel::{ExpressionMethods as _, OptionalExtension as _, QueryDsl as _, RunQueryDsl as _};
 use dropshot::HttpError;
 
-use super::{ProjectId, QueryProject};
+use super::{ProjectId, QueryProject, report::ReportId};
 use crate::{
     conn_lock,
     context::{ApiContext, DbConnection},
-    error::{BencherResource, assert_parentage, resource_conflict_err},
+    error::{BencherResource, assert_parentage, resource_conflict_err, resource_not_found_err},
     macros::{
         fn_get::{fn_from_uuid, fn_get, fn_get_id, fn_get_uuid},
         name_id::{fn_eq_name_id, fn_from_name_id},
@@ -32,6 +32,7 @@ pub struct QueryTestbed {
     pub project_id: ProjectId,
     pub name: ResourceName,
     pub slug: Slug,
+    pub hardware: Option<JsonTestbedHardware>,
     pub created: DateTime,
     pub modified: DateTime,
     pub archived: Option<DateTime>,
@@ -112,12 +113,62 @@ impl QueryTestbed {
         Self::from_uuid(conn_lock!(context), project_id, insert_testbed.uuid)
     }
 
+    /// Update the hardware profile of the testbed with the hardware detected for a new report,
+    /// as long as the new report is the most recent report for the testbed with a hardware profile.
+    /// Returns `true` if the hardware has changed since the previous report with a hardware profile.
+    /// Both are ordered by report end time, so a backdated report does not change the profile of the testbed.
+    pub fn update_hardware(
+        &self,
+        conn: &mut DbConnection,
+        hardware: Option<&JsonTestbedHardware>,
+        end_time: DateTime,
+    ) -> Result<bool, HttpError> {
+        let Some(hardware) = hardware else {
+            return Ok(false);
+        };
+
+        let is_changed = schema::report::table
+            .filter(schema::report::testbed_id.eq(self.id))
+            .filter(schema::report::hardware.is_not_null())
+            .filter(schema::report::end_time.le(end_time))
+            .order((
+                schema::report::end_time.desc(),
+                schema::report::created.desc(),
+            ))
+            .select(schema::report::hardware)
+            .first::<Option<JsonTestbedHardware>>(conn)
+            .optional()
+            .map_err(resource_not_found_err!(Report, (self, end_time)))?
+            .flatten()
+            .is_some_and(|previous| previous.is_changed(hardware));
+
+        let is_most_recent = schema::report::table
+            .filter(schema::report::testbed_id.eq(self.id))
+            .filter(schema::report::hardware.is_not_null())
+            .filter(schema::report::end_time.gt(end_time))
+            .select(schema::report::id)
+            .first::<ReportId>(conn)
+            .optional()
+            .map_err(resource_not_found_err!(Report, (self, end_time)))?
+            .is_none();
+        if is_most_recent && self.hardware.as_ref() != Some(hardware) {
+            let update_testbed = UpdateTestbed::hardware(hardware.clone());
+            diesel::update(schema::testbed::table.filter(schema::testbed::id.eq(self.id)))
+                .set(&update_testbed)
+                .execute(conn)
+                .map_err(resource_conflict_err!(Testbed, self))?;
+        }
+
+        Ok(is_changed)
+    }
+
     pub fn into_json_for_project(self, project: &QueryProject) -> JsonTestbed {
         let Self {
             uuid,
             project_id,
             name,
             slug,
+            hardware,
             created,
             modified,
             archived,
@@ -134,6 +185,7 @@ impl QueryTestbed {
             project: project.uuid,
             name,
             slug,
+            hardware,
             created,
             modified,
             archived,
@@ -148,6 +200,7 @@ pub struct InsertTestbed {
     pub project_id: ProjectId,
     pub name: ResourceName,
     pub slug: Slug,
+    pub hardware: Option<JsonTestbedHardware>,
     pub created: DateTime,
     pub modified: DateTime,
     pub archived: Option<DateTime>,
@@ -166,6 +219,7 @@ impl InsertTestbed {
             project_id,
             name,
             slug,
+            hardware: None,
             created: timestamp,
             modified: timestamp,
             archived: None,
@@ -182,6 +236,7 @@ impl InsertTestbed {
 pub struct UpdateTestbed {
     pub name: Option<ResourceName>,
     pub slug: Option<Slug>,
+    pub hardware: Option<Option<JsonTestbedHardware>>,
     pub modified: DateTime,
     pub archived: Option<Option<DateTime>>,
 }
@@ -198,6 +253,7 @@ impl From<JsonUpdateTestbed> for UpdateTestbed {
         Self {
             name,
             slug,
+            hardware: None,
             modified,
             archived,
         }
@@ -213,4 +269,14 @@ impl UpdateTestbed {
         }
         .into()
     }
+
+    fn hardware(hardware: JsonTestbedHardware) -> Self {
+        Self {
+            name: None,
+            slug: None,
+            hardware: Some(Some(hardware)),
+            modified: DateTime::now(),
+            archived: None,
+        }
+    }
 }
@@ -91,6 +91,7 @@ impl QueryAlert {
         let (
             report_uuid,
             created,
+            environment_change,
             head_id,
             version_id,
             iteration,
@@ -111,6 +112,7 @@ impl QueryAlert {
             .select((
                 schema::report::uuid,
                 schema::report::created,
+                schema::report::environment_change,
                 schema::report::head_id,
                 schema::report::version_id,
                 schema::report_benchmark::iteration,
@@ -121,6 +123,7 @@ impl QueryAlert {
             .first::<(
                 ReportUuid,
                 DateTime,
+                bool,
                 HeadId,
                 VersionId,
                 Iteration,
@@ -135,6 +138,7 @@ impl QueryAlert {
             &project,
             report_uuid,
             created,
+            environment_change,
             head_id,
             version_id,
             iteration,
@@ -152,6 +156,7 @@ impl QueryAlert {
         project: &QueryProject,
         report_uuid: ReportUuid,
         created: DateTime,
+        environment_change: bool,
         head_id: HeadId,
         version_id: VersionId,
         iteration: Iteration,
@@ -184,6 +189,7 @@ impl QueryAlert {
             boundary: query_boundary.into_json(),
             limit: boundary_limit,
             status,
+            environment_change,
             created,
             modified,
         })
@@ -221,12 +227,13 @@ impl InsertAlert {
         conn: &mut DbConnection,
         boundary_uuid: BoundaryUuid,
         boundary_limit: BoundaryLimit,
+        status: AlertStatus,
     ) -> Result<(), HttpError> {
         let insert_alert = InsertAlert {
             uuid: AlertUuid::new(),
             boundary_id: QueryBoundary::get_id(conn, boundary_uuid)?,
             boundary_limit,
-            status: AlertStatus::default(),
+            status,
             modified: DateTime::now(),
         };
 
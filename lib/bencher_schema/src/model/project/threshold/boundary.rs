@@ -1,4 +1,4 @@
-use bencher_json::{BoundaryUuid, project::boundary::JsonBoundary};
+use bencher_json::{BoundaryUuid, MetricUuid, project::boundary::JsonBoundary};
 use diesel::{ExpressionMethods as _, QueryDsl as _, RunQueryDsl as _};
 use dropshot::HttpError;
 
@@ -26,6 +26,8 @@ pub struct QueryBoundary {
     pub baseline: Option<f64>,
     pub lower_limit: Option<f64>,
     pub upper_limit: Option<f64>,
+    pub change_point: Option<MetricUuid>,
+    pub significance: Option<f64>,
 }
 
 impl QueryBoundary {
@@ -45,6 +47,8 @@ impl QueryBoundary {
             baseline: self.baseline.map(Into::into),
             lower_limit: self.lower_limit.map(Into::into),
             upper_limit: self.upper_limit.map(Into::into),
+            change_point: self.change_point,
+            significance: self.significance.map(Into::into),
         }
     }
 }
@@ -59,4 +63,6 @@ pub struct InsertBoundary {
     pub baseline: Option<f64>,
     pub lower_limit: Option<f64>,
     pub upper_limit: Option<f64>,
+    pub change_point: Option<MetricUuid>,
+    pub significance: Option<f64>,
 }
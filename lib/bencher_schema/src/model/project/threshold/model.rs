@@ -1,5 +1,6 @@
 use bencher_json::{
-    Boundary, DateTime, JsonModel, Model, ModelTest, ModelUuid, SampleSize, Window,
+    Boundary, DateTime, EnvironmentChange, JsonModel, Model, ModelTest, ModelUuid, SampleSize,
+    Smoothing, Window,
 };
 use diesel::{
     ExpressionMethods as _, JoinOnDsl as _, QueryDsl as _, RunQueryDsl as _, SelectableHelper as _,
@@ -30,6 +31,10 @@ pub struct QueryModel {
     pub window: Option<Window>,
     pub lower_boundary: Option<Boundary>,
     pub upper_boundary: Option<Boundary>,
+    pub smoothing: Option<Smoothing>,
+    pub trend: Option<Smoothing>,
+    pub season: Option<SampleSize>,
+    pub environment_change: Option<EnvironmentChange>,
     pub created: DateTime,
     pub replaced: Option<DateTime>,
 }
@@ -63,6 +68,10 @@ impl QueryModel {
             window,
             lower_boundary,
             upper_boundary,
+            smoothing,
+            trend,
+            season,
+            environment_change,
             ..
         } = self;
         Model {
@@ -72,6 +81,10 @@ impl QueryModel {
             window,
             lower_boundary,
             upper_boundary,
+            smoothing,
+            trend,
+            season,
+            environment_change,
         }
     }
 
@@ -85,6 +98,10 @@ impl QueryModel {
             window,
             lower_boundary,
             upper_boundary,
+            smoothing,
+            trend,
+            season,
+            environment_change,
             created,
             replaced,
             ..
@@ -103,6 +120,10 @@ impl QueryModel {
             window,
             lower_boundary,
             upper_boundary,
+            smoothing,
+            trend,
+            season,
+            environment_change,
             created,
             replaced,
         }
@@ -120,6 +141,10 @@ pub struct InsertModel {
     pub window: Option<Window>,
     pub lower_boundary: Option<Boundary>,
     pub upper_boundary: Option<Boundary>,
+    pub smoothing: Option<Smoothing>,
+    pub trend: Option<Smoothing>,
+    pub season: Option<SampleSize>,
+    pub environment_change: Option<EnvironmentChange>,
     pub created: DateTime,
     pub replaced: Option<DateTime>,
 }
@@ -176,6 +201,10 @@ impl InsertModel {
             window,
             lower_boundary,
             upper_boundary,
+            smoothing,
+            trend,
+            season,
+            environment_change,
         } = model;
         Self {
             uuid: ModelUuid::new(),
@@ -186,6 +215,10 @@ impl InsertModel {
             window,
             lower_boundary,
             upper_boundary,
+            smoothing,
+            trend,
+            season,
+            environment_change,
             created: DateTime::now(),
             replaced: None,
         }
@@ -199,6 +232,10 @@ impl InsertModel {
             window,
             lower_boundary,
             upper_boundary,
+            smoothing,
+            trend,
+            season,
+            environment_change,
             created,
             replaced,
             ..
@@ -212,6 +249,10 @@ impl InsertModel {
             window,
             lower_boundary,
             upper_boundary,
+            smoothing,
+            trend,
+            season,
+            environment_change,
             created,
             replaced,
         }
@@ -0,0 +1,232 @@
+use bencher_json::{
+    DateTime, JsonNewTrustPolicy, JsonTrustPolicy, NonEmpty, TrustPolicyUuid, TrustProvider, Url,
+};
+use bencher_oidc::CiClaims;
+use diesel::{BelongingToDsl as _, ExpressionMethods as _, QueryDsl as _, RunQueryDsl as _};
+use dropshot::HttpError;
+
+use super::{ProjectId, QueryProject, webhook::delivery};
+use crate::{
+    context::DbConnection,
+    error::{BencherResource, assert_parentage, bad_request_error, resource_not_found_err},
+    model::user::{QueryUser, UserId, auth::AuthUser, token::is_pattern_match},
+    schema::trust_policy as trust_policy_table,
+};
+
+crate::macros::typed_id::typed_id!(TrustPolicyId);
+
+#[derive(
+    Debug, Clone, diesel::Queryable, diesel::Identifiable, diesel::Associations, diesel::Selectable,
+)]
+#[diesel(table_name = trust_policy_table)]
+#[diesel(belongs_to(QueryProject, foreign_key = project_id))]
+pub struct QueryTrustPolicy {
+    pub id: TrustPolicyId,
+    pub uuid: TrustPolicyUuid,
+    pub project_id: ProjectId,
+    pub user_id: UserId,
+    pub provider: TrustProvider,
+    pub issuer: Option<Url>,
+    pub repository: NonEmpty,
+    pub git_ref: Option<NonEmpty>,
+    pub workflow: Option<NonEmpty>,
+    pub created: DateTime,
+}
+
+impl QueryTrustPolicy {
+    pub fn get_with_uuid(
+        conn: &mut DbConnection,
+        query_project: &QueryProject,
+        uuid: TrustPolicyUuid,
+    ) -> Result<Self, HttpError> {
+        Self::belonging_to(&query_project)
+            .filter(trust_policy_table::uuid.eq(uuid))
+            .first::<Self>(conn)
+            .map_err(resource_not_found_err!(TrustPolicy, (query_project, uuid)))
+    }
+
+    /// Get all of the trust policies for a project with the given OpenID Connect issuer
+    pub fn all_for_issuer(
+        conn: &mut DbConnection,
+        query_project: &QueryProject,
+        issuer: &str,
+    ) -> Result<Vec<Self>, HttpError> {
+        Ok(Self::belonging_to(&query_project)
+            .order(trust_policy_table::created.asc())
+            .load::<Self>(conn)
+            .map_err(resource_not_found_err!(TrustPolicy, query_project))?
+            .into_iter()
+            .filter(|policy| policy.issuer() == issuer.trim_end_matches('/'))
+            .collect())
+    }
+
+    /// The OpenID Connect issuer for the trust policy
+    pub fn issuer(&self) -> &str {
+        self.issuer
+            .as_ref()
+            .map_or_else(|| self.provider.issuer(), AsRef::as_ref)
+            .trim_end_matches('/')
+    }
+
+    /// Whether an OpenID Connect provider URL may be requested to verify a CI ID token.
+    /// The URL must use `https`, and unless `allow_private` is set,
+    /// its host must not be a private network address.
+    pub fn is_allowed_url(url: &url::Url, allow_private: bool) -> bool {
+        url.scheme() == "https" && (allow_private || !delivery::is_private_host(url))
+    }
+
+    /// Check that the claims of a verified CI ID token match the trust policy
+    pub fn is_trusted(&self, claims: &CiClaims) -> bool {
+        let is_match = |pattern: Option<&NonEmpty>, claim: Option<&str>| {
+            pattern.is_none_or(|pattern| {
+                claim.is_some_and(|claim| is_pattern_match(pattern.as_ref(), claim))
+            })
+        };
+        claims.repository() == Some(self.repository.as_ref())
+            && is_match(self.git_ref.as_ref(), claims.git_ref())
+            && is_match(self.workflow.as_ref(), claims.workflow())
+    }
+
+    pub fn into_json(self, conn: &mut DbConnection) -> Result<JsonTrustPolicy, HttpError> {
+        let query_project = QueryProject::get(conn, self.project_id)?;
+        self.into_json_for_project(conn, &query_project)
+    }
+
+    pub fn into_json_for_project(
+        self,
+        conn: &mut DbConnection,
+        query_project: &QueryProject,
+    ) -> Result<JsonTrustPolicy, HttpError> {
+        let issuer = self.issuer().parse().map_err(|e| {
+            crate::error::issue_error(
+                "Failed to parse trust policy issuer",
+                &format!(
+                    "Failed to parse issuer for trust policy ({uuid})",
+                    uuid = self.uuid
+                ),
+                e,
+            )
+        })?;
+        let user = QueryUser::get_uuid(conn, self.user_id)?;
+        let Self {
+            uuid,
+            project_id,
+            provider,
+            repository,
+            git_ref,
+            workflow,
+            created,
+            ..
+        } = self;
+        assert_parentage(
+            BencherResource::Project,
+            query_project.id,
+            BencherResource::TrustPolicy,
+            project_id,
+        );
+        Ok(JsonTrustPolicy {
+            uuid,
+            project: query_project.uuid,
+            user,
+            provider,
+            issuer,
+            repository,
+            git_ref,
+            workflow,
+            created,
+        })
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = trust_policy_table)]
+pub struct InsertTrustPolicy {
+    pub uuid: TrustPolicyUuid,
+    pub project_id: ProjectId,
+    pub user_id: UserId,
+    pub provider: TrustProvider,
+    pub issuer: Option<Url>,
+    pub repository: NonEmpty,
+    pub git_ref: Option<NonEmpty>,
+    pub workflow: Option<NonEmpty>,
+    pub created: DateTime,
+}
+
+impl InsertTrustPolicy {
+    /// The issuer must use `https`, and unless `allow_private` is set,
+    /// its host must not be a private network address.
+    /// The issuer and its JWKS URL are also checked again before each ID token is verified.
+    pub fn from_json(
+        query_project: &QueryProject,
+        auth_user: &AuthUser,
+        trust_policy: JsonNewTrustPolicy,
+        allow_private: bool,
+    ) -> Result<Self, HttpError> {
+        let JsonNewTrustPolicy {
+            provider,
+            issuer,
+            repository,
+            git_ref,
+            workflow,
+        } = trust_policy;
+        if let Some(issuer) = issuer.as_ref() {
+            let issuer_url = url::Url::try_from(issuer.clone()).map_err(|e| {
+                bad_request_error(format!("Invalid trust policy issuer ({issuer}): {e}"))
+            })?;
+            if issuer_url.scheme() != "https" {
+                return Err(bad_request_error(format!(
+                    "Invalid trust policy issuer ({issuer}): The URL scheme must be `https`"
+                )));
+            }
+            if !allow_private && delivery::is_private_host(&issuer_url) {
+                return Err(bad_request_error(format!(
+                    "Invalid trust policy issuer ({issuer}): The URL host must not be a loopback, link-local, or private network address"
+                )));
+            }
+        }
+        Ok(Self {
+            uuid: TrustPolicyUuid::new(),
+            project_id: query_project.id,
+            user_id: auth_user.user.id,
+            provider,
+            issuer,
+            repository,
+            git_ref,
+            workflow,
+            created: DateTime::now(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::QueryTrustPolicy;
+
+    #[test]
+    fn test_is_allowed_url() {
+        for (url, allow_private, expected) in [
+            ("https://token.actions.githubusercontent.com", false, true),
+            (
+                "https://gitlab.example.com/oauth/discovery/keys",
+                false,
+                true,
+            ),
+            ("http://gitlab.example.com", false, false),
+            ("http://gitlab.example.com", true, false),
+            ("https://localhost", false, false),
+            ("https://127.0.0.1", false, false),
+            ("https://10.0.0.1/jwks", false, false),
+            ("https://169.254.169.254", false, false),
+            ("https://[::1]", false, false),
+            ("https://10.0.0.1/jwks", true, true),
+            ("https://localhost", true, true),
+        ] {
+            let url = url.parse().unwrap();
+            assert_eq!(
+                QueryTrustPolicy::is_allowed_url(&url, allow_private),
+                expected,
+                "{url}"
+            );
+        }
+    }
+}
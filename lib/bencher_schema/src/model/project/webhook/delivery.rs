@@ -0,0 +1,827 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
+
+use bencher_comment::ChatMessage;
+use bencher_json::{
+    DateTime, JsonWebhookDelivery, Secret, WebhookDeliveryStatus, WebhookDeliveryUuid,
+    WebhookEvent, WebhookFormat,
+    project::webhook::{
+        JsonWebhookPayload, WEBHOOK_DELIVERY_HEADER, WEBHOOK_EVENT_HEADER,
+        WEBHOOK_SIGNATURE_HEADER, WEBHOOK_TIMESTAMP_HEADER,
+    },
+};
+use diesel::{ExpressionMethods as _, QueryDsl as _, RunQueryDsl as _};
+use dropshot::HttpError;
+use hmac::{Hmac, Mac as _};
+use sha2::Sha256;
+use slog::Logger;
+use tokio::sync::Mutex;
+use url::Host;
+
+use super::{QueryWebhook, WebhookId};
+use crate::{
+    context::DbConnection,
+    error::{BencherResource, assert_parentage, issue_error, resource_conflict_err},
+    schema::webhook_delivery as webhook_delivery_table,
+    yield_connection_lock,
+};
+
+crate::macros::typed_id::typed_id!(WebhookDeliveryId);
+
+// The maximum number of attempts to deliver a webhook payload
+const MAX_ATTEMPTS: u32 = 5;
+// The delay before the first retry, which doubles after each failed attempt
+const INITIAL_BACKOFF: Duration = Duration::from_secs(10);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(
+    Debug, Clone, diesel::Queryable, diesel::Identifiable, diesel::Associations, diesel::Selectable,
+)]
+#[diesel(table_name = webhook_delivery_table)]
+#[diesel(belongs_to(QueryWebhook, foreign_key = webhook_id))]
+pub struct QueryWebhookDelivery {
+    pub id: WebhookDeliveryId,
+    pub uuid: WebhookDeliveryUuid,
+    pub webhook_id: WebhookId,
+    pub event: WebhookEvent,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    pub response_status: Option<i32>,
+    pub error: Option<String>,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl QueryWebhookDelivery {
+    pub fn into_json_for_webhook(self, query_webhook: &QueryWebhook) -> JsonWebhookDelivery {
+        let Self {
+            uuid,
+            webhook_id,
+            event,
+            status,
+            attempts,
+            response_status,
+            error,
+            created,
+            modified,
+            ..
+        } = self;
+        assert_parentage(
+            BencherResource::Webhook,
+            query_webhook.id,
+            BencherResource::WebhookDelivery,
+            webhook_id,
+        );
+        JsonWebhookDelivery {
+            uuid,
+            webhook: query_webhook.uuid,
+            event,
+            status,
+            attempts: u32::try_from(attempts).unwrap_or_default(),
+            response_status: response_status.and_then(|status| u16::try_from(status).ok()),
+            error,
+            created,
+            modified,
+        }
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = webhook_delivery_table)]
+pub struct InsertWebhookDelivery {
+    pub uuid: WebhookDeliveryUuid,
+    pub webhook_id: WebhookId,
+    pub event: WebhookEvent,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl InsertWebhookDelivery {
+    pub fn new(webhook_id: WebhookId, payload: &JsonWebhookPayload) -> Self {
+        Self {
+            uuid: payload.uuid,
+            webhook_id,
+            event: payload.event.event(),
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            created: payload.created,
+            modified: payload.created,
+        }
+    }
+
+    pub fn insert(&self, conn: &mut DbConnection) -> Result<WebhookDeliveryId, HttpError> {
+        diesel::insert_into(webhook_delivery_table::table)
+            .values(self)
+            .execute(conn)
+            .map_err(resource_conflict_err!(WebhookDelivery, self))?;
+        webhook_delivery_table::table
+            .filter(webhook_delivery_table::uuid.eq(self.uuid))
+            .select(webhook_delivery_table::id)
+            .first(conn)
+            .map_err(resource_conflict_err!(WebhookDelivery, self))
+    }
+}
+
+#[derive(Debug, diesel::AsChangeset)]
+#[diesel(table_name = webhook_delivery_table)]
+#[diesel(treat_none_as_null = true)]
+struct UpdateWebhookDelivery {
+    status: WebhookDeliveryStatus,
+    attempts: i32,
+    response_status: Option<i32>,
+    error: Option<String>,
+    modified: DateTime,
+}
+
+#[derive(Debug, thiserror::Error)]
+enum DeliveryError {
+    #[error("Webhook URL has no host")]
+    NoHost,
+    #[error("Failed to resolve webhook URL host ({host}): {error}")]
+    Resolve { host: String, error: std::io::Error },
+    #[error("Webhook URL host ({0}) did not resolve to any addresses")]
+    NoAddress(String),
+    #[error(
+        "Webhook URL host ({host}) resolved to a loopback, link-local, or private network address ({address})"
+    )]
+    PrivateAddress { host: String, address: IpAddr },
+    #[error("Failed to create webhook client: {0}")]
+    Client(reqwest::Error),
+}
+
+impl DeliveryError {
+    // A host that resolves to a private network address is not retried,
+    // as it is not allowed to be delivered to at all.
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Resolve { .. } | Self::NoAddress(_) | Self::Client(_) => true,
+            Self::NoHost | Self::PrivateAddress { .. } => false,
+        }
+    }
+}
+
+/// A webhook payload that is ready to be delivered
+pub struct DeliveryRequest {
+    url: url::Url,
+    uuid: WebhookDeliveryUuid,
+    event: WebhookEvent,
+    format: WebhookFormat,
+    signing_secret: Option<Secret>,
+    access_token: Option<Secret>,
+    body: String,
+    allow_private: bool,
+    backoff: Duration,
+}
+
+impl DeliveryRequest {
+    /// Chat webhooks are sent the chat message for the event instead of the full payload.
+    /// Unless `allow_private` is set, the webhook URL host must not resolve to a private network address.
+    pub fn new(
+        query_webhook: &QueryWebhook,
+        payload: &JsonWebhookPayload,
+        chat_message: Option<&ChatMessage>,
+        allow_private: bool,
+    ) -> Result<Self, HttpError> {
+        let mut url = url::Url::try_from(query_webhook.url.clone()).map_err(|e| {
+            issue_error(
+                "Failed to parse webhook URL",
+                &format!("Failed to parse URL for webhook ({query_webhook:?})"),
+                e,
+            )
+        })?;
+        let format = query_webhook.format;
+        let body = match (format, chat_message) {
+            (WebhookFormat::Json, _) => serde_json::to_string(payload),
+            (WebhookFormat::Slack, Some(chat_message)) => {
+                serde_json::to_string(&chat_message.slack())
+            },
+            (WebhookFormat::Teams, Some(chat_message)) => {
+                serde_json::to_string(&chat_message.teams())
+            },
+            (WebhookFormat::Matrix, Some(chat_message)) => {
+                serde_json::to_string(&chat_message.matrix())
+            },
+            (WebhookFormat::Slack | WebhookFormat::Teams | WebhookFormat::Matrix, None) => {
+                return Err(issue_error(
+                    "Missing webhook chat message",
+                    &format!("Missing chat message for webhook ({query_webhook:?})"),
+                    format,
+                ));
+            },
+        }
+        .map_err(|e| {
+            issue_error(
+                "Failed to serialize webhook payload",
+                &format!("Failed to serialize payload for webhook ({query_webhook:?})"),
+                e,
+            )
+        })?;
+
+        let (signing_secret, access_token) = match format {
+            // Each delivery attempt is signed along with its timestamp
+            WebhookFormat::Json => (query_webhook.secret.clone(), None),
+            WebhookFormat::Slack | WebhookFormat::Teams => (None, None),
+            WebhookFormat::Matrix => {
+                // The delivery UUID is used as the Matrix transaction ID,
+                // so retries of the same delivery are only ever posted to the room once.
+                url.path_segments_mut()
+                    .map_err(|()| {
+                        issue_error(
+                            "Invalid Matrix webhook URL",
+                            &format!("Failed to add transaction ID to URL for webhook ({query_webhook:?})"),
+                            "URL can not be a base",
+                        )
+                    })?
+                    .pop_if_empty()
+                    .push(&payload.uuid.to_string());
+                (None, query_webhook.secret.clone())
+            },
+        };
+
+        Ok(Self {
+            url,
+            uuid: payload.uuid,
+            event: payload.event.event(),
+            format,
+            signing_secret,
+            access_token,
+            body,
+            allow_private,
+            backoff: INITIAL_BACKOFF,
+        })
+    }
+
+    /// Deliver the payload, retrying with exponential backoff until it succeeds or runs out of attempts.
+    /// Each attempt is recorded in the webhook delivery log.
+    pub async fn deliver(
+        self,
+        log: &Logger,
+        db_connection: &Mutex<DbConnection>,
+        delivery_id: WebhookDeliveryId,
+    ) {
+        let mut backoff = self.backoff;
+        for attempt in 1..=MAX_ATTEMPTS {
+            let (response_status, error, is_retryable) = match self.client().await {
+                Ok(client) => {
+                    let (response_status, error) = self.attempt(&client).await;
+                    (response_status, error, true)
+                },
+                Err(e) => (None, Some(e.to_string()), e.is_retryable()),
+            };
+            let status = if error.is_none() {
+                WebhookDeliveryStatus::Delivered
+            } else if attempt == MAX_ATTEMPTS || !is_retryable {
+                WebhookDeliveryStatus::Failed
+            } else {
+                WebhookDeliveryStatus::Pending
+            };
+            if let Some(error) = error.as_ref() {
+                slog::warn!(log, "Webhook delivery attempt failed"; "uuid" => %self.uuid, "attempt" => attempt, "error" => error);
+            } else {
+                slog::info!(log, "Webhook delivered"; "uuid" => %self.uuid, "attempt" => attempt);
+            }
+
+            let update_delivery = UpdateWebhookDelivery {
+                status,
+                attempts: i32::try_from(attempt).unwrap_or(i32::MAX),
+                response_status: response_status.map(Into::into),
+                error,
+                modified: DateTime::now(),
+            };
+            if let Err(e) = yield_connection_lock!(db_connection, |conn| diesel::update(
+                webhook_delivery_table::table.filter(webhook_delivery_table::id.eq(delivery_id))
+            )
+            .set(&update_delivery)
+            .execute(conn))
+            {
+                slog::error!(
+                    log,
+                    "Failed to update webhook delivery ({delivery_id}): {e}"
+                );
+            }
+
+            if status != WebhookDeliveryStatus::Pending {
+                return;
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = backoff.saturating_mul(2);
+        }
+    }
+
+    // Resolve the webhook URL host before each attempt,
+    // so a host can not be pointed at a private network address after the webhook was created.
+    // The client only connects to the addresses that were checked.
+    async fn client(&self) -> Result<reqwest::Client, DeliveryError> {
+        // Do not follow redirects, so a webhook can only ever reach the URL it was created with
+        let client_builder = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none());
+        let client_builder = if self.allow_private {
+            client_builder
+        } else {
+            match self.url.host().ok_or(DeliveryError::NoHost)? {
+                Host::Domain(domain) => {
+                    let port = self
+                        .url
+                        .port_or_known_default()
+                        .ok_or(DeliveryError::NoHost)?;
+                    let addresses = tokio::net::lookup_host((domain, port))
+                        .await
+                        .map_err(|error| DeliveryError::Resolve {
+                            host: domain.to_owned(),
+                            error,
+                        })?
+                        .collect::<Vec<SocketAddr>>();
+                    if addresses.is_empty() {
+                        return Err(DeliveryError::NoAddress(domain.to_owned()));
+                    }
+                    if let Some(address) = addresses
+                        .iter()
+                        .map(SocketAddr::ip)
+                        .find(|address| is_private_address(*address))
+                    {
+                        return Err(DeliveryError::PrivateAddress {
+                            host: domain.to_owned(),
+                            address,
+                        });
+                    }
+                    client_builder.resolve_to_addrs(domain, &addresses)
+                },
+                Host::Ipv4(address) if is_private_address(address.into()) => {
+                    return Err(DeliveryError::PrivateAddress {
+                        host: address.to_string(),
+                        address: address.into(),
+                    });
+                },
+                Host::Ipv6(address) if is_private_address(address.into()) => {
+                    return Err(DeliveryError::PrivateAddress {
+                        host: address.to_string(),
+                        address: address.into(),
+                    });
+                },
+                Host::Ipv4(_) | Host::Ipv6(_) => client_builder,
+            }
+        };
+        client_builder.build().map_err(DeliveryError::Client)
+    }
+
+    async fn attempt(&self, client: &reqwest::Client) -> (Option<u16>, Option<String>) {
+        let request = match self.format {
+            WebhookFormat::Json | WebhookFormat::Slack | WebhookFormat::Teams => {
+                client.post(self.url.clone())
+            },
+            WebhookFormat::Matrix => client.put(self.url.clone()),
+        };
+        let mut request = request
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .header(WEBHOOK_EVENT_HEADER, self.event.to_string())
+            .header(WEBHOOK_DELIVERY_HEADER, self.uuid.to_string());
+        if let Some(secret) = self.signing_secret.as_ref() {
+            let timestamp = DateTime::now().timestamp().to_string();
+            let signature = match sign(secret.as_ref(), &timestamp, &self.body) {
+                Ok(signature) => signature,
+                Err(e) => return (None, Some(format!("Failed to sign webhook payload: {e}"))),
+            };
+            request = request
+                .header(WEBHOOK_TIMESTAMP_HEADER, timestamp)
+                .header(WEBHOOK_SIGNATURE_HEADER, signature);
+        }
+        if let Some(access_token) = self.access_token.as_ref() {
+            request = request.bearer_auth(access_token.as_ref());
+        }
+        match request.body(self.body.clone()).send().await {
+            Ok(response) => {
+                let status = response.status();
+                let error = (!status.is_success())
+                    .then(|| format!("Webhook responded with status code {status}"));
+                (Some(status.as_u16()), error)
+            },
+            Err(e) => (
+                e.status().map(|status| status.as_u16()),
+                Some(e.to_string()),
+            ),
+        }
+    }
+}
+
+/// Sign a webhook timestamp and payload with HMAC-SHA256.
+/// The signed content is the timestamp, a `.`, and then the payload,
+/// so a signed payload can not be replayed with a different timestamp.
+/// The signature is hex encoded and prefixed with `sha256=`.
+fn sign(secret: &str, timestamp: &str, body: &str) -> Result<String, hmac::digest::InvalidLength> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())?;
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    Ok(format!("sha256={:x}", mac.finalize().into_bytes()))
+}
+
+/// Whether a webhook URL host is a loopback, link-local, or private network address,
+/// without resolving it.
+pub fn is_private_host(url: &url::Url) -> bool {
+    match url.host() {
+        Some(Host::Domain(domain)) => {
+            domain.eq_ignore_ascii_case("localhost")
+                || domain.to_ascii_lowercase().ends_with(".localhost")
+        },
+        Some(Host::Ipv4(address)) => is_private_address(address.into()),
+        Some(Host::Ipv6(address)) => is_private_address(address.into()),
+        None => false,
+    }
+}
+
+/// Whether an address is a loopback, link-local, private, or unique local network address,
+/// or any other address that a webhook should not be able to reach on the server's network.
+fn is_private_address(address: IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => {
+            let [first, second, ..] = address.octets();
+            address.is_loopback()
+                || address.is_private()
+                || address.is_link_local()
+                || address.is_unspecified()
+                || address.is_broadcast()
+                // Shared address space for carrier-grade NAT (100.64.0.0/10)
+                || (first == 100 && (second & 0b1100_0000) == 64)
+        },
+        IpAddr::V6(address) => {
+            if let Some(address) = address.to_ipv4_mapped() {
+                return is_private_address(address.into());
+            }
+            address.is_loopback()
+                || address.is_unspecified()
+                || address.is_unique_local()
+                || address.is_unicast_link_local()
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::{BufRead as _, BufReader, Read as _, Write as _},
+        net::{IpAddr, TcpListener},
+        thread::{self, JoinHandle},
+        time::Duration,
+    };
+
+    use bencher_json::{
+        DateTime, Secret, WebhookDeliveryStatus, WebhookDeliveryUuid, WebhookEvent, WebhookFormat,
+    };
+    use diesel::{
+        Connection as _, ExpressionMethods as _, QueryDsl as _, RunQueryDsl as _,
+        SelectableHelper as _, connection::SimpleConnection as _,
+    };
+    use slog::Logger;
+    use tokio::sync::{Mutex, mpsc};
+
+    use super::{
+        DeliveryRequest, InsertWebhookDelivery, QueryWebhookDelivery, WebhookDeliveryId,
+        is_private_address, is_private_host, sign,
+    };
+    use crate::{
+        context::DbConnection, model::project::webhook::WebhookId, run_migrations,
+        schema::webhook_delivery as webhook_delivery_table,
+    };
+
+    const SECRET: &str = "bencher-secret";
+    const BODY: &str = r#"{"event":"report.created"}"#;
+
+    #[derive(Debug)]
+    struct MockRequest {
+        method: String,
+        timestamp: Option<String>,
+        signature: Option<String>,
+        event: Option<String>,
+        delivery: Option<String>,
+        body: String,
+    }
+
+    // A mock webhook receiver that responds to each request in order
+    fn mock_server(
+        statuses: Vec<u16>,
+    ) -> (
+        url::Url,
+        mpsc::UnboundedReceiver<MockRequest>,
+        JoinHandle<()>,
+    ) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/webhook", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        let (tx, rx) = mpsc::unbounded_channel();
+        let handle = thread::spawn(move || {
+            for status in statuses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut headers = Vec::new();
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    let Some((name, value)) = header.split_once(':') else {
+                        break;
+                    };
+                    let name = name.to_ascii_lowercase();
+                    let value = value.trim().to_owned();
+                    if name == "content-length" {
+                        content_length = value.parse().unwrap();
+                    }
+                    headers.push((name, value));
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let response = format!(
+                    "HTTP/1.1 {status} Mock\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+
+                let header = |name: &str| {
+                    headers
+                        .iter()
+                        .find(|(header, _)| header == name)
+                        .map(|(_, value)| value.clone())
+                };
+                tx.send(MockRequest {
+                    method: request_line.split_whitespace().next().unwrap().to_owned(),
+                    timestamp: header("x-bencher-timestamp"),
+                    signature: header("x-bencher-signature-256"),
+                    event: header("x-bencher-event"),
+                    delivery: header("x-bencher-delivery"),
+                    body: String::from_utf8(body).unwrap(),
+                })
+                .unwrap();
+            }
+        });
+        (url, rx, handle)
+    }
+
+    fn delivery_request(url: url::Url, allow_private: bool) -> DeliveryRequest {
+        DeliveryRequest {
+            url,
+            uuid: WebhookDeliveryUuid::new(),
+            event: WebhookEvent::ReportCreated,
+            format: WebhookFormat::Json,
+            signing_secret: Some(SECRET.parse::<Secret>().unwrap()),
+            access_token: None,
+            body: BODY.to_owned(),
+            allow_private,
+            backoff: Duration::from_millis(1),
+        }
+    }
+
+    // An in-memory delivery log with a single pending delivery
+    fn delivery_log(request: &DeliveryRequest) -> (Mutex<DbConnection>, WebhookDeliveryId) {
+        let mut conn = DbConnection::establish(":memory:").unwrap();
+        run_migrations(&mut conn).unwrap();
+        // The delivery log is tested without the webhook that it belongs to
+        conn.batch_execute("PRAGMA foreign_keys = OFF").unwrap();
+        let now = DateTime::now();
+        let delivery_id = InsertWebhookDelivery {
+            uuid: request.uuid,
+            webhook_id: WebhookId::default(),
+            event: request.event,
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            created: now,
+            modified: now,
+        }
+        .insert(&mut conn)
+        .unwrap();
+        (Mutex::new(conn), delivery_id)
+    }
+
+    async fn get_delivery(
+        db_connection: &Mutex<DbConnection>,
+        delivery_id: WebhookDeliveryId,
+    ) -> QueryWebhookDelivery {
+        webhook_delivery_table::table
+            .filter(webhook_delivery_table::id.eq(delivery_id))
+            .select(QueryWebhookDelivery::as_select())
+            .first(&mut *db_connection.lock().await)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_sign() {
+        assert_eq!(
+            sign(SECRET, "1700000000", BODY).unwrap(),
+            "sha256=f7dd8ff434a002fb827c5e337528a749249d1be61850d790f041b6e1e2c4c81b"
+        );
+        // The timestamp is part of the signature
+        assert_ne!(
+            sign(SECRET, "1700000001", BODY).unwrap(),
+            sign(SECRET, "1700000000", BODY).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_is_private_address() {
+        for address in [
+            "127.0.0.1",
+            "127.1.2.3",
+            "10.0.0.1",
+            "172.16.0.1",
+            "172.31.255.255",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "100.127.255.255",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fc00::1",
+            "fd12:3456:789a::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+        ] {
+            assert!(
+                is_private_address(address.parse::<IpAddr>().unwrap()),
+                "{address}"
+            );
+        }
+        for address in [
+            "1.1.1.1",
+            "8.8.8.8",
+            "172.32.0.1",
+            "100.128.0.1",
+            "192.169.0.1",
+            "2606:4700:4700::1111",
+            "::ffff:1.1.1.1",
+        ] {
+            assert!(
+                !is_private_address(address.parse::<IpAddr>().unwrap()),
+                "{address}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_private_host() {
+        for url in [
+            "http://localhost/webhook",
+            "http://LOCALHOST:8080/webhook",
+            "http://api.localhost/webhook",
+            "http://127.0.0.1/webhook",
+            "http://[::1]:3000/webhook",
+            "https://10.1.2.3/webhook",
+            "https://169.254.169.254/latest/meta-data",
+        ] {
+            assert!(is_private_host(&url.parse().unwrap()), "{url}");
+        }
+        for url in [
+            "https://example.com/webhook",
+            "https://localhost.example.com/webhook",
+            "https://8.8.8.8/webhook",
+            "https://[2606:4700:4700::1111]/webhook",
+        ] {
+            assert!(!is_private_host(&url.parse().unwrap()), "{url}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deliver_signed() {
+        let (url, mut requests, handle) = mock_server(vec![200]);
+        let request = delivery_request(url, true);
+        let uuid = request.uuid;
+        let (db_connection, delivery_id) = delivery_log(&request);
+        let log = Logger::root(slog::Discard, slog::o!());
+
+        let before = DateTime::now().timestamp();
+        request.deliver(&log, &db_connection, delivery_id).await;
+        let after = DateTime::now().timestamp();
+        handle.join().unwrap();
+
+        let mock_request = requests.recv().await.unwrap();
+        assert_eq!(mock_request.method, "POST");
+        assert_eq!(mock_request.body, BODY);
+        assert_eq!(mock_request.event.as_deref(), Some("report.created"));
+        assert_eq!(mock_request.delivery, Some(uuid.to_string()));
+        let timestamp = mock_request.timestamp.unwrap();
+        let timestamp_secs = timestamp.parse::<i64>().unwrap();
+        assert!(
+            (before..=after).contains(&timestamp_secs),
+            "{timestamp} not in {before}..={after}"
+        );
+        assert_eq!(
+            mock_request.signature,
+            Some(sign(SECRET, &timestamp, BODY).unwrap())
+        );
+
+        let delivery = get_delivery(&db_connection, delivery_id).await;
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Delivered);
+        assert_eq!(delivery.attempts, 1);
+        assert_eq!(delivery.response_status, Some(200));
+        assert_eq!(delivery.error, None);
+    }
+
+    #[tokio::test]
+    async fn test_deliver_retry() {
+        let (url, mut requests, handle) = mock_server(vec![500, 200]);
+        let mut request = delivery_request(url, true);
+        // Wait long enough between attempts to check the delivery log
+        request.backoff = Duration::from_millis(500);
+        let uuid = request.uuid;
+        let (db_connection, delivery_id) = delivery_log(&request);
+        let log = Logger::root(slog::Discard, slog::o!());
+
+        let delivery_task = async {
+            request.deliver(&log, &db_connection, delivery_id).await;
+        };
+        let check_task = async {
+            let first = requests.recv().await.unwrap();
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            let pending = get_delivery(&db_connection, delivery_id).await;
+            let second = requests.recv().await.unwrap();
+            (first, pending, second)
+        };
+        let ((), (first, pending, second)) = tokio::join!(delivery_task, check_task);
+        handle.join().unwrap();
+
+        assert_eq!(pending.status, WebhookDeliveryStatus::Pending);
+        assert_eq!(pending.attempts, 1);
+        assert_eq!(pending.response_status, Some(500));
+        assert_eq!(
+            pending.error.as_deref(),
+            Some("Webhook responded with status code 500 Internal Server Error")
+        );
+
+        // Each attempt is for the same delivery, but it is signed with its own timestamp
+        assert_eq!(first.delivery, Some(uuid.to_string()));
+        assert_eq!(second.delivery, Some(uuid.to_string()));
+        assert_eq!(
+            second.signature,
+            Some(sign(SECRET, second.timestamp.as_deref().unwrap(), BODY).unwrap())
+        );
+
+        let delivered = get_delivery(&db_connection, delivery_id).await;
+        assert_eq!(delivered.status, WebhookDeliveryStatus::Delivered);
+        assert_eq!(delivered.attempts, 2);
+        assert_eq!(delivered.response_status, Some(200));
+        assert_eq!(delivered.error, None);
+    }
+
+    #[tokio::test]
+    async fn test_deliver_failed() {
+        let (url, mut requests, handle) = mock_server(vec![500, 502, 503, 504, 404]);
+        let request = delivery_request(url, true);
+        let (db_connection, delivery_id) = delivery_log(&request);
+        let log = Logger::root(slog::Discard, slog::o!());
+
+        request.deliver(&log, &db_connection, delivery_id).await;
+        handle.join().unwrap();
+
+        let mut attempts = 0;
+        while requests.recv().await.is_some() {
+            attempts += 1;
+        }
+        assert_eq!(attempts, 5);
+        let delivery = get_delivery(&db_connection, delivery_id).await;
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Failed);
+        assert_eq!(delivery.attempts, 5);
+        assert_eq!(delivery.response_status, Some(404));
+        assert_eq!(
+            delivery.error.as_deref(),
+            Some("Webhook responded with status code 404 Not Found")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deliver_private_address() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let log = Logger::root(slog::Discard, slog::o!());
+
+        for (url, host) in [
+            (format!("http://127.0.0.1:{port}/webhook"), "127.0.0.1"),
+            (format!("http://localhost:{port}/webhook"), "localhost"),
+        ] {
+            let request = delivery_request(url.parse().unwrap(), false);
+            let (db_connection, delivery_id) = delivery_log(&request);
+
+            request.deliver(&log, &db_connection, delivery_id).await;
+
+            // A private network address is never connected to or retried
+            assert!(listener.accept().is_err(), "{url}");
+            let delivery = get_delivery(&db_connection, delivery_id).await;
+            assert_eq!(delivery.status, WebhookDeliveryStatus::Failed, "{url}");
+            assert_eq!(delivery.attempts, 1, "{url}");
+            assert_eq!(delivery.response_status, None, "{url}");
+            let error = delivery.error.unwrap();
+            assert!(
+                error.contains(&format!("Webhook URL host ({host})")),
+                "{error}"
+            );
+        }
+    }
+}
@@ -0,0 +1,516 @@
+use std::collections::{HashMap, hash_map::Entry};
+
+use bencher_comment::{ChatMessage, ReportComment, SubAdapter};
+use bencher_json::{
+    DateTime, JsonNewWebhook, JsonReport, JsonWebhook, JsonWebhookEvents, ReportUuid, Secret, Url,
+    WebhookDeliveryUuid, WebhookEvent, WebhookFormat, WebhookUuid,
+    project::webhook::{JsonWebhookEventData, JsonWebhookPayload},
+};
+use diesel::{BelongingToDsl as _, ExpressionMethods as _, QueryDsl as _, RunQueryDsl as _};
+use dropshot::HttpError;
+use slog::Logger;
+
+use super::{ProjectId, QueryProject, report::QueryReport};
+use crate::{
+    conn_lock,
+    context::{ApiContext, DbConnection},
+    error::{BencherResource, assert_parentage, bad_request_error, resource_not_found_err},
+    schema::{self, webhook as webhook_table},
+};
+
+pub mod delivery;
+
+use delivery::{DeliveryRequest, InsertWebhookDelivery};
+
+crate::macros::typed_id::typed_id!(WebhookId);
+
+#[derive(
+    Debug, Clone, diesel::Queryable, diesel::Identifiable, diesel::Associations, diesel::Selectable,
+)]
+#[diesel(table_name = webhook_table)]
+#[diesel(belongs_to(QueryProject, foreign_key = project_id))]
+pub struct QueryWebhook {
+    pub id: WebhookId,
+    pub uuid: WebhookUuid,
+    pub project_id: ProjectId,
+    pub url: Url,
+    pub format: WebhookFormat,
+    pub secret: Option<Secret>,
+    pub events: JsonWebhookEvents,
+    pub created: DateTime,
+}
+
+// The source for the links in chat messages
+const CHAT_SOURCE: &str = "webhook";
+
+impl QueryWebhook {
+    pub fn get_with_uuid(
+        conn: &mut DbConnection,
+        query_project: &QueryProject,
+        uuid: WebhookUuid,
+    ) -> Result<Self, HttpError> {
+        Self::belonging_to(&query_project)
+            .filter(webhook_table::uuid.eq(uuid))
+            .first::<Self>(conn)
+            .map_err(resource_not_found_err!(Webhook, (query_project, uuid)))
+    }
+
+    /// Send events to all of the webhooks for a project that are subscribed to them.
+    ///
+    /// The webhooks are delivered in the background, with retries.
+    /// Failing to queue a delivery is logged but never fails the request that caused the events.
+    pub async fn send_events(
+        log: &Logger,
+        context: &ApiContext,
+        query_project: &QueryProject,
+        events: Vec<JsonWebhookEventData>,
+    ) {
+        let webhooks = match Self::belonging_to(query_project).load::<Self>(conn_lock!(context)) {
+            Ok(webhooks) => webhooks,
+            Err(e) => {
+                slog::error!(
+                    log,
+                    "Failed to get webhooks for project ({query_project:?}): {e}"
+                );
+                #[cfg(feature = "sentry")]
+                sentry::capture_error(&e);
+                return;
+            },
+        };
+        if webhooks.is_empty() {
+            return;
+        }
+
+        // Chat messages are rendered from the report for each event, which may be shared by several events
+        let mut report_comments = HashMap::new();
+        for event_data in &events {
+            let event = event_data.event();
+            let subscribed = webhooks
+                .iter()
+                .filter(|webhook| webhook.events.contains(event))
+                .collect::<Vec<_>>();
+            let chat_message = if subscribed.iter().any(|webhook| webhook.format.is_chat()) {
+                Self::chat_message(
+                    log,
+                    context,
+                    query_project,
+                    &events,
+                    event_data,
+                    &mut report_comments,
+                )
+                .await
+            } else {
+                None
+            };
+
+            for query_webhook in subscribed {
+                if query_webhook.format.is_chat() && chat_message.is_none() {
+                    continue;
+                }
+                let payload = JsonWebhookPayload {
+                    uuid: WebhookDeliveryUuid::new(),
+                    project: query_project.uuid,
+                    event: event_data.clone(),
+                    created: DateTime::now(),
+                };
+                if let Err(e) = query_webhook
+                    .send(log, context, payload, chat_message.as_ref())
+                    .await
+                {
+                    slog::error!(log, "Failed to queue webhook delivery: {e}");
+                    #[cfg(feature = "sentry")]
+                    sentry::capture_error(&e);
+                }
+            }
+        }
+    }
+
+    // Render the chat message for an event, using the report that the event is for.
+    // If the message can not be rendered, the error is logged
+    // and the event is not sent to any chat webhooks.
+    async fn chat_message(
+        log: &Logger,
+        context: &ApiContext,
+        query_project: &QueryProject,
+        events: &[JsonWebhookEventData],
+        event_data: &JsonWebhookEventData,
+        report_comments: &mut HashMap<ReportUuid, ReportComment>,
+    ) -> Option<ChatMessage> {
+        let report_uuid = match event_data {
+            JsonWebhookEventData::ReportCreated(json_report) => json_report.uuid,
+            JsonWebhookEventData::AlertCreated(json_alert)
+            | JsonWebhookEventData::AlertDismissed(json_alert) => json_alert.report,
+            JsonWebhookEventData::ThresholdUpdated(_) => return None,
+        };
+
+        let report_comment = match report_comments.entry(report_uuid) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                // A new report is sent along with its alerts, so only get the report if it is not there
+                let json_report = if let Some(json_report) =
+                    events.iter().find_map(|event| match event {
+                        JsonWebhookEventData::ReportCreated(json_report)
+                            if json_report.uuid == report_uuid =>
+                        {
+                            Some(json_report.as_ref().clone())
+                        },
+                        JsonWebhookEventData::ReportCreated(_)
+                        | JsonWebhookEventData::AlertCreated(_)
+                        | JsonWebhookEventData::AlertDismissed(_)
+                        | JsonWebhookEventData::ThresholdUpdated(_) => None,
+                    }) {
+                    json_report
+                } else {
+                    match Self::get_report(log, context, query_project, report_uuid).await {
+                        Ok(json_report) => json_report,
+                        Err(e) => {
+                            slog::error!(
+                                log,
+                                "Failed to get report ({report_uuid}) for webhook chat message: {e}"
+                            );
+                            #[cfg(feature = "sentry")]
+                            sentry::capture_error(&e);
+                            return None;
+                        },
+                    }
+                };
+                let sub_adapter = SubAdapter {
+                    build_time: false,
+                    file_size: false,
+                    rusage: false,
+                };
+                entry.insert(ReportComment::new(
+                    context.console_url.clone(),
+                    json_report,
+                    sub_adapter,
+                    CHAT_SOURCE.to_owned(),
+                ))
+            },
+        };
+        match event_data {
+            JsonWebhookEventData::ReportCreated(_) => Some(report_comment.chat()),
+            JsonWebhookEventData::AlertCreated(json_alert)
+            | JsonWebhookEventData::AlertDismissed(json_alert) => {
+                Some(report_comment.chat_alert(json_alert))
+            },
+            JsonWebhookEventData::ThresholdUpdated(_) => None,
+        }
+    }
+
+    async fn get_report(
+        log: &Logger,
+        context: &ApiContext,
+        query_project: &QueryProject,
+        report_uuid: ReportUuid,
+    ) -> Result<JsonReport, HttpError> {
+        let query_report = QueryReport::belonging_to(query_project)
+            .filter(schema::report::uuid.eq(report_uuid))
+            .first::<QueryReport>(conn_lock!(context))
+            .map_err(resource_not_found_err!(
+                Report,
+                (query_project, report_uuid)
+            ))?;
+        // Separate out this query to prevent a deadlock when getting the conn_lock
+        query_report.into_json(log, context).await
+    }
+
+    async fn send(
+        &self,
+        log: &Logger,
+        context: &ApiContext,
+        payload: JsonWebhookPayload,
+        chat_message: Option<&ChatMessage>,
+    ) -> Result<(), HttpError> {
+        let request =
+            DeliveryRequest::new(self, &payload, chat_message, context.allow_private_webhooks)?;
+        let insert_delivery = InsertWebhookDelivery::new(self.id, &payload);
+        let delivery_id = insert_delivery.insert(conn_lock!(context))?;
+        slog::info!(log, "Queued webhook delivery"; "uuid" => %payload.uuid, "event" => %insert_delivery.event);
+
+        let log = log.clone();
+        let connection = context.database.connection.clone();
+        tokio::spawn(async move { request.deliver(&log, &connection, delivery_id).await });
+
+        Ok(())
+    }
+
+    pub fn into_json_for_project(self, query_project: &QueryProject) -> JsonWebhook {
+        let Self {
+            uuid,
+            project_id,
+            url,
+            format,
+            secret,
+            events,
+            created,
+            ..
+        } = self;
+        assert_parentage(
+            BencherResource::Project,
+            query_project.id,
+            BencherResource::Webhook,
+            project_id,
+        );
+        JsonWebhook {
+            uuid,
+            project: query_project.uuid,
+            url,
+            format,
+            // The secret for a Matrix webhook is an access token, not a signing secret
+            signed: secret.is_some() && !format.is_chat(),
+            events,
+            created,
+        }
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = webhook_table)]
+pub struct InsertWebhook {
+    pub uuid: WebhookUuid,
+    pub project_id: ProjectId,
+    pub url: Url,
+    pub format: WebhookFormat,
+    pub secret: Option<Secret>,
+    pub events: JsonWebhookEvents,
+    pub created: DateTime,
+}
+
+impl InsertWebhook {
+    /// Unless `allow_private` is set, the webhook URL host must not be a private network address.
+    /// The host is also resolved and checked again before each delivery.
+    pub fn from_json(
+        query_project: &QueryProject,
+        webhook: JsonNewWebhook,
+        allow_private: bool,
+    ) -> Result<Self, HttpError> {
+        let JsonNewWebhook {
+            url,
+            format,
+            secret,
+            events,
+        } = webhook;
+        let format = format.unwrap_or_default();
+        let webhook_url = url::Url::try_from(url.clone())
+            .map_err(|e| bad_request_error(format!("Invalid webhook URL ({url}): {e}")))?;
+        if !matches!(webhook_url.scheme(), "http" | "https") {
+            return Err(bad_request_error(format!(
+                "Invalid webhook URL ({url}): The URL scheme must be `http` or `https`"
+            )));
+        }
+        if !allow_private && delivery::is_private_host(&webhook_url) {
+            return Err(bad_request_error(format!(
+                "Invalid webhook URL ({url}): The URL host must not be a loopback, link-local, or private network address"
+            )));
+        }
+        let mut unique_events = Vec::with_capacity(events.0.len());
+        for event in events.0 {
+            if !unique_events.contains(&event) {
+                unique_events.push(event);
+            }
+        }
+        if unique_events.is_empty() {
+            return Err(bad_request_error(
+                "A webhook must be subscribed to at least one event",
+            ));
+        }
+        if format.is_chat() && unique_events.contains(&WebhookEvent::ThresholdUpdated) {
+            return Err(bad_request_error(format!(
+                "A `{format}` webhook can not be subscribed to the `{event}` event",
+                event = WebhookEvent::ThresholdUpdated
+            )));
+        }
+        match (format, secret.is_some()) {
+            (WebhookFormat::Slack | WebhookFormat::Teams, true) => {
+                return Err(bad_request_error(format!(
+                    "A `{format}` webhook can not have a secret. The webhook URL itself is the secret."
+                )));
+            },
+            (WebhookFormat::Matrix, false) => {
+                return Err(bad_request_error(
+                    "A `matrix` webhook must have a secret, which is the access token for the Matrix user that sends the messages",
+                ));
+            },
+            _ => {},
+        }
+        Ok(Self {
+            uuid: WebhookUuid::new(),
+            project_id: query_project.id,
+            url,
+            format,
+            secret,
+            events: unique_events.into(),
+            created: DateTime::now(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{
+        DateTime, JsonNewWebhook, ProjectUuid, WebhookEvent, WebhookFormat, project::Visibility,
+    };
+
+    use super::InsertWebhook;
+    use crate::model::{
+        organization::OrganizationId,
+        project::{ProjectId, QueryProject},
+    };
+
+    fn query_project() -> QueryProject {
+        QueryProject {
+            id: ProjectId::default(),
+            uuid: ProjectUuid::new(),
+            organization_id: OrganizationId::default(),
+            name: "Project".parse().unwrap(),
+            slug: "project".parse().unwrap(),
+            url: None,
+            visibility: Visibility::Public,
+            created: DateTime::now(),
+            modified: DateTime::now(),
+        }
+    }
+
+    fn new_webhook(
+        url: &str,
+        format: Option<WebhookFormat>,
+        secret: Option<&str>,
+        events: Vec<WebhookEvent>,
+    ) -> JsonNewWebhook {
+        JsonNewWebhook {
+            url: url.parse().unwrap(),
+            format,
+            secret: secret.map(|secret| secret.parse().unwrap()),
+            events: events.into(),
+        }
+    }
+
+    fn assert_bad_request(webhook: JsonNewWebhook, allow_private: bool, message: &str) {
+        let error = InsertWebhook::from_json(&query_project(), webhook, allow_private)
+            .unwrap_err()
+            .external_message;
+        assert!(error.contains(message), "{error}");
+    }
+
+    #[test]
+    fn test_from_json() {
+        let insert_webhook = InsertWebhook::from_json(
+            &query_project(),
+            new_webhook(
+                "https://example.com/webhook",
+                None,
+                Some("bencher-secret"),
+                vec![
+                    WebhookEvent::AlertCreated,
+                    WebhookEvent::ThresholdUpdated,
+                    WebhookEvent::AlertCreated,
+                ],
+            ),
+            false,
+        )
+        .unwrap();
+        assert_eq!(insert_webhook.format, WebhookFormat::Json);
+        assert!(insert_webhook.secret.is_some(), "{insert_webhook:?}");
+        // Duplicate events are removed
+        assert_eq!(
+            insert_webhook.events.0,
+            [WebhookEvent::AlertCreated, WebhookEvent::ThresholdUpdated]
+        );
+    }
+
+    #[test]
+    fn test_from_json_url() {
+        let events = || vec![WebhookEvent::ReportCreated];
+        assert_bad_request(
+            new_webhook("ftp://example.com/webhook", None, None, events()),
+            false,
+            "The URL scheme must be `http` or `https`",
+        );
+        for url in [
+            "http://localhost:8080/webhook",
+            "http://127.0.0.1/webhook",
+            "http://[::1]/webhook",
+            "http://192.168.1.1/webhook",
+            "http://169.254.169.254/latest/meta-data",
+        ] {
+            assert_bad_request(
+                new_webhook(url, None, None, events()),
+                false,
+                "The URL host must not be a loopback, link-local, or private network address",
+            );
+            // A self-hosted server may allow private network addresses
+            InsertWebhook::from_json(
+                &query_project(),
+                new_webhook(url, None, None, events()),
+                true,
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_from_json_events() {
+        assert_bad_request(
+            new_webhook("https://example.com/webhook", None, None, Vec::new()),
+            false,
+            "A webhook must be subscribed to at least one event",
+        );
+        assert_bad_request(
+            new_webhook(
+                "https://hooks.slack.com/services/T0/B0/X",
+                Some(WebhookFormat::Slack),
+                None,
+                vec![WebhookEvent::AlertCreated, WebhookEvent::ThresholdUpdated],
+            ),
+            false,
+            "A `slack` webhook can not be subscribed to the `threshold.updated` event",
+        );
+    }
+
+    #[test]
+    fn test_from_json_secret() {
+        let events = || vec![WebhookEvent::AlertCreated];
+        assert_bad_request(
+            new_webhook(
+                "https://hooks.slack.com/services/T0/B0/X",
+                Some(WebhookFormat::Slack),
+                Some("bencher-secret"),
+                events(),
+            ),
+            false,
+            "A `slack` webhook can not have a secret",
+        );
+        assert_bad_request(
+            new_webhook(
+                "https://example.webhook.office.com/webhookb2/bencher",
+                Some(WebhookFormat::Teams),
+                Some("bencher-secret"),
+                events(),
+            ),
+            false,
+            "A `teams` webhook can not have a secret",
+        );
+        assert_bad_request(
+            new_webhook(
+                "https://matrix.example.com/_matrix/client/v3/rooms/!room:example.com/send/m.room.message",
+                Some(WebhookFormat::Matrix),
+                None,
+                events(),
+            ),
+            false,
+            "A `matrix` webhook must have a secret",
+        );
+        let insert_webhook = InsertWebhook::from_json(
+            &query_project(),
+            new_webhook(
+                "https://matrix.example.com/_matrix/client/v3/rooms/!room:example.com/send/m.room.message",
+                Some(WebhookFormat::Matrix),
+                Some("syt_access_token"),
+                events(),
+            ),
+            false,
+        )
+        .unwrap();
+        assert_eq!(insert_webhook.format, WebhookFormat::Matrix);
+    }
+}
@@ -1,11 +1,11 @@
-use std::ops::Deref;
+use std::{net::IpAddr, ops::Deref};
 
 use async_trait::async_trait;
 #[cfg(feature = "plus")]
 use bencher_json::system::payment::JsonCustomer;
 use bencher_json::{Jwt, Sanitize};
 use bencher_rbac::{
-    Organization, Project, Server, User as RbacUser,
+    Organization, Project, Scope, Server, User as RbacUser,
     server::Permission,
     user::{OrganizationRoles, ProjectRoles},
 };
@@ -19,12 +19,12 @@ use oso::{PolarValue, ToPolar};
 use crate::{
     conn_lock,
     context::{ApiContext, DbConnection, Rbac},
-    error::{BEARER_TOKEN_FORMAT, bad_request_error},
+    error::{BEARER_TOKEN_FORMAT, bad_request_error, forbidden_error, unauthorized_error},
     model::{organization::OrganizationId, project::ProjectId},
     schema,
 };
 
-use super::QueryUser;
+use super::{QueryUser, token::QueryToken};
 
 #[derive(Debug, Clone)]
 pub struct AuthUser {
@@ -32,6 +32,7 @@ pub struct AuthUser {
     pub organizations: Vec<OrganizationId>,
     pub projects: Vec<OrgProjectId>,
     pub rbac: RbacUser,
+    pub token: Option<QueryToken>,
 }
 
 impl AuthUser {
@@ -58,27 +59,87 @@ impl AuthUser {
         })
     }
 
+    /// Authenticate a user who may be using a CI token.
+    /// This should only be used by the endpoints that accept CI tokens.
+    pub async fn from_pub_ci_token(
+        context: &ApiContext,
+        bearer_token: PubBearerToken,
+    ) -> Result<Option<Self>, HttpError> {
+        let Some(bearer_token) = bearer_token.0 else {
+            return Ok(None);
+        };
+        let Ok(claims) = context.token_key.validate_ci(&bearer_token) else {
+            return Self::from_token(context, bearer_token).await.map(Some);
+        };
+
+        // Hold the connection for all permissions related queries
+        let conn = conn_lock!(context);
+        let query_user = QueryUser::get_with_email(conn, claims.email())?;
+        let query_token =
+            QueryToken::authenticate(conn, &query_user, &bearer_token, bearer_token.remote_ip)?
+                .filter(|query_token| query_token.ci)
+                .ok_or_else(|| {
+                    unauthorized_error(format!(
+                        "CI token ({uuid}) was not found",
+                        uuid = claims.jti
+                    ))
+                })?;
+        let scope = query_token.scope()?;
+        Self::load(conn, query_user, Some(query_token), scope).map(Some)
+    }
+
     pub async fn from_token(
         context: &ApiContext,
         bearer_token: BearerToken,
     ) -> Result<Self, HttpError> {
-        let claims = context
-            .token_key
-            .validate_client(&bearer_token)
-            .map_err(|e| bad_request_error(format!("Failed to validate JSON Web Token: {e}")))?;
+        let claims = context.token_key.validate_client(&bearer_token).map_err(|e| {
+            if context.token_key.validate_ci(&bearer_token).is_ok() {
+                forbidden_error(
+                    "A CI token can only be used to create a run and view the reports that it created",
+                )
+            } else {
+                bad_request_error(format!("Failed to validate JSON Web Token: {e}"))
+            }
+        })?;
         let email = claims.email();
 
         // Hold the connection for all permissions related queries
         let conn = conn_lock!(context);
         let query_user = QueryUser::get_with_email(conn, email)?;
-        Self::load(conn, query_user)
+        let query_token = if claims.is_api_key() {
+            QueryToken::authenticate(conn, &query_user, &bearer_token, bearer_token.remote_ip)?
+        } else {
+            None
+        };
+        let scope = query_token
+            .as_ref()
+            .map(QueryToken::scope)
+            .transpose()?
+            .flatten();
+        Self::load(conn, query_user, query_token, scope)
+    }
+
+    /// Load the permissions for a user without a bearer token.
+    /// This is used to check that the user that created a trust policy still has access.
+    pub fn from_user(conn: &mut DbConnection, query_user: QueryUser) -> Result<Self, HttpError> {
+        Self::load(conn, query_user, None, None)
     }
 
     pub fn reload(&self, conn: &mut DbConnection) -> Result<Self, HttpError> {
-        Self::load(conn, self.user.clone())
+        Self::load(
+            conn,
+            self.user.clone(),
+            self.token.clone(),
+            self.rbac.scope.clone(),
+        )
     }
 
-    fn load(conn: &mut DbConnection, query_user: QueryUser) -> Result<Self, HttpError> {
+    fn load(
+        conn: &mut DbConnection,
+        query_user: QueryUser,
+        query_token: Option<QueryToken>,
+        scope: Option<Scope>,
+    ) -> Result<Self, HttpError> {
         query_user.check_is_locked()?;
 
         let (org_ids, org_roles) = Self::organization_roles(conn, &query_user)?;
@@ -89,6 +150,7 @@ impl AuthUser {
             locked: query_user.locked,
             organizations: org_roles,
             projects: proj_roles,
+            scope,
         };
 
         Ok(Self {
@@ -96,6 +158,7 @@ impl AuthUser {
             organizations: org_ids,
             projects: proj_ids,
             rbac,
+            token: query_token,
         })
     }
 
@@ -194,6 +257,23 @@ impl AuthUser {
         Ok((ids, roles))
     }
 
+    /// Check to see if the user was authenticated with an API token that is scoped to a single project
+    pub fn check_is_unscoped(&self) -> Result<(), HttpError> {
+        if self.rbac.scope.is_some() {
+            Err(forbidden_error(format!(
+                "User ({uuid}) is using an API token that is scoped to a single project. Please use an unscoped API token.",
+                uuid = self.user.uuid
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The CI token that the user was authenticated with, if any
+    pub fn ci_token(&self) -> Option<&QueryToken> {
+        self.token.as_ref().filter(|query_token| query_token.ci)
+    }
+
     pub fn is_admin(&self, rbac: &Rbac) -> bool {
         rbac.is_allowed_unwrap(self, Permission::Administer, Server {})
     }
@@ -270,11 +350,17 @@ impl SharedExtractor for Headers {
 }
 
 // https://github.com/oxidecomputer/cio/blob/master/dropshot-verify-request/src/bearer.rs
-pub struct BearerToken(Jwt);
+pub struct BearerToken {
+    jwt: Jwt,
+    remote_ip: Option<IpAddr>,
+}
 
 impl From<Jwt> for BearerToken {
     fn from(jwt: Jwt) -> Self {
-        Self(jwt)
+        Self {
+            jwt,
+            remote_ip: None,
+        }
     }
 }
 
@@ -282,7 +368,7 @@ impl Deref for BearerToken {
     type Target = Jwt;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.jwt
     }
 }
 
@@ -312,11 +398,14 @@ impl SharedExtractor for BearerToken {
             )));
         };
 
-        token
+        let jwt = token
             .trim()
             .parse::<Jwt>()
-            .map(Into::into)
-            .map_err(|e| bad_request_error(format!("Malformed JSON Web Token: {e}")))
+            .map_err(|e| bad_request_error(format!("Malformed JSON Web Token: {e}")))?;
+        Ok(Self {
+            jwt,
+            remote_ip: Some(rqctx.request.remote_addr().ip()),
+        })
     }
 
     fn metadata(_body_content_type: ApiEndpointBodyContentType) -> ExtractorMetadata {
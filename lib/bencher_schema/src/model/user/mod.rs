@@ -21,6 +21,7 @@ use crate::{
 
 pub mod admin;
 pub mod auth;
+pub mod notification;
 pub mod token;
 
 crate::macros::typed_id::typed_id!(UserId);
@@ -28,6 +29,7 @@ crate::macros::typed_id::typed_id!(UserId);
 #[macro_export]
 macro_rules! same_user {
     ($auth_user:ident, $rbac:expr, $user_uuid:expr) => {
+        $auth_user.check_is_unscoped()?;
         if !($auth_user.is_admin(&$rbac) || $auth_user.uuid() == $user_uuid) {
             return Err($crate::error::forbidden_error(format!("User is not admin and the authenticated user ({auth_user}) does not match the requested user ({requested_user})", auth_user = $auth_user.uuid(), requested_user = $user_uuid)));
         }
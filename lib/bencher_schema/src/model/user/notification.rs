@@ -0,0 +1,895 @@
+use std::{sync::Arc, time::Duration};
+
+use bencher_json::{
+    BenchmarkName, BenchmarkUuid, BranchUuid, DateTime, HeadUuid, JsonNotification, JsonReport,
+    MeasureUuid, NotificationMode, ReportUuid, ResourceName, TestbedUuid,
+    project::{
+        alert::AlertStatus,
+        boundary::BoundaryLimit,
+        perf::JsonPerfQuery,
+        plot::{LOWER_BOUNDARY, UPPER_BOUNDARY},
+    },
+    user::notification::JsonUpdateNotification,
+};
+use diesel::{
+    BoolExpressionMethods as _, ExpressionMethods as _, OptionalExtension as _, QueryDsl as _,
+    RunQueryDsl as _,
+};
+use dropshot::HttpError;
+use slog::Logger;
+use tokio::sync::{Mutex, mpsc::Sender};
+
+use super::{QueryUser, UserId};
+use crate::{
+    context::{AlertBody, AlertSummary, ApiContext, Body, DbConnection, Message, Messenger},
+    error::{resource_conflict_err, resource_not_found_err},
+    model::project::{ProjectId, QueryProject},
+    schema::{self, notification as notification_table},
+    yield_connection_lock,
+};
+
+crate::macros::typed_id::typed_id!(NotificationId);
+
+// How often to check for notifications that are due a daily digest
+const DIGEST_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+// How far back the perf link for an alert looks
+const PERF_HISTORY_DAYS: i64 = 30;
+
+#[derive(Debug, Clone, diesel::Queryable, diesel::Selectable)]
+#[diesel(table_name = notification_table)]
+pub struct QueryNotification {
+    pub id: NotificationId,
+    pub user_id: UserId,
+    pub project_id: ProjectId,
+    pub alerts: NotificationMode,
+    pub digested: Option<DateTime>,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl QueryNotification {
+    /// Get the notification settings for a user on a project.
+    /// If the user has never set them, then notifications are off.
+    pub fn get_json(
+        conn: &mut DbConnection,
+        query_user: &QueryUser,
+        query_project: &QueryProject,
+    ) -> Result<JsonNotification, HttpError> {
+        let alerts = Self::get_for_user(conn, query_user.id, query_project.id)?
+            .map(|query_notification| query_notification.alerts)
+            .unwrap_or_default();
+        Ok(JsonNotification {
+            user: query_user.uuid,
+            project: query_project.uuid,
+            alerts,
+        })
+    }
+
+    pub fn update(
+        conn: &mut DbConnection,
+        query_user: &QueryUser,
+        query_project: &QueryProject,
+        json_notification: &JsonUpdateNotification,
+    ) -> Result<JsonNotification, HttpError> {
+        let JsonUpdateNotification { alerts } = *json_notification;
+        let now = DateTime::now();
+        if let Some(query_notification) = Self::get_for_user(conn, query_user.id, query_project.id)?
+        {
+            let update_notification = UpdateNotification {
+                alerts,
+                modified: now,
+            };
+            diesel::update(
+                schema::notification::table
+                    .filter(schema::notification::id.eq(query_notification.id)),
+            )
+            .set(&update_notification)
+            .execute(conn)
+            .map_err(resource_conflict_err!(
+                Notification,
+                (&query_notification, &update_notification)
+            ))?;
+        } else {
+            let insert_notification = InsertNotification {
+                user_id: query_user.id,
+                project_id: query_project.id,
+                alerts: alerts.unwrap_or_default(),
+                digested: None,
+                created: now,
+                modified: now,
+            };
+            diesel::insert_into(schema::notification::table)
+                .values(&insert_notification)
+                .execute(conn)
+                .map_err(resource_conflict_err!(Notification, insert_notification))?;
+        }
+        Self::get_json(conn, query_user, query_project)
+    }
+
+    fn get_for_user(
+        conn: &mut DbConnection,
+        user_id: UserId,
+        project_id: ProjectId,
+    ) -> Result<Option<Self>, HttpError> {
+        schema::notification::table
+            .filter(schema::notification::user_id.eq(user_id))
+            .filter(schema::notification::project_id.eq(project_id))
+            .first::<Self>(conn)
+            .optional()
+            .map_err(resource_not_found_err!(Notification, (user_id, project_id)))
+    }
+
+    /// Check whether a user is a member of the project, either through its organization or directly.
+    /// Only members are allowed to receive notifications for a project.
+    pub fn is_member(
+        conn: &mut DbConnection,
+        user_id: UserId,
+        query_project: &QueryProject,
+    ) -> Result<bool, HttpError> {
+        let organization_roles = schema::organization_role::table
+            .filter(schema::organization_role::user_id.eq(user_id))
+            .filter(schema::organization_role::organization_id.eq(query_project.organization_id))
+            .count()
+            .get_result::<i64>(conn)
+            .map_err(resource_not_found_err!(User, (user_id, query_project)))?;
+        if organization_roles > 0 {
+            return Ok(true);
+        }
+        let project_roles = schema::project_role::table
+            .filter(schema::project_role::user_id.eq(user_id))
+            .filter(schema::project_role::project_id.eq(query_project.id))
+            .count()
+            .get_result::<i64>(conn)
+            .map_err(resource_not_found_err!(User, (user_id, query_project)))?;
+        Ok(project_roles > 0)
+    }
+
+    /// Email the active alerts for a new report to all of the project members
+    /// who want to be notified immediately.
+    ///
+    /// Failing to send the emails is logged but never fails the report.
+    pub async fn send_alerts(
+        log: &Logger,
+        context: &ApiContext,
+        query_project: &QueryProject,
+        json_report: &JsonReport,
+    ) {
+        if !json_report
+            .alerts
+            .iter()
+            .any(|alert| matches!(alert.status, AlertStatus::Active))
+        {
+            return;
+        }
+        if let Err(e) = Self::send_alerts_inner(
+            log,
+            &context.database.connection,
+            &context.messenger,
+            &context.console_url,
+            query_project,
+            json_report.uuid,
+        )
+        .await
+        {
+            slog::error!(
+                log,
+                "Failed to send alert notifications for report ({}): {e}",
+                json_report.uuid
+            );
+            #[cfg(feature = "sentry")]
+            sentry::capture_error(&e);
+        }
+    }
+
+    async fn send_alerts_inner(
+        log: &Logger,
+        db_connection: &Mutex<DbConnection>,
+        messenger: &Messenger,
+        console_url: &url::Url,
+        query_project: &QueryProject,
+        report_uuid: ReportUuid,
+    ) -> Result<(), HttpError> {
+        let recipients = yield_connection_lock!(db_connection, |conn| {
+            schema::notification::table
+                .inner_join(schema::user::table)
+                .filter(schema::notification::project_id.eq(query_project.id))
+                .filter(schema::notification::alerts.eq(NotificationMode::Immediate))
+                .filter(schema::user::locked.eq(false))
+                .select(schema::user::all_columns)
+                .load::<QueryUser>(conn)
+                .map_err(resource_not_found_err!(Notification, query_project))
+        })?;
+        if recipients.is_empty() {
+            return Ok(());
+        }
+
+        let alerts = yield_connection_lock!(db_connection, |conn| alert_summaries(
+            conn,
+            console_url,
+            query_project,
+            AlertWindow::Report(report_uuid),
+        ))?;
+        if alerts.is_empty() {
+            return Ok(());
+        }
+
+        for query_user in recipients {
+            let is_member = yield_connection_lock!(db_connection, |conn| Self::is_member(
+                conn,
+                query_user.id,
+                query_project
+            ))?;
+            if is_member {
+                send_email(
+                    log,
+                    messenger,
+                    &query_user,
+                    query_project,
+                    alerts.clone(),
+                    false,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Spawn a task that sends a daily digest of the active alerts
+    /// to all of the project members who want to be notified with a digest.
+    /// The task stops once the server restarts.
+    pub fn spawn_digests(
+        log: Logger,
+        db_connection: Arc<Mutex<DbConnection>>,
+        messenger: Messenger,
+        console_url: url::Url,
+        restart_tx: Sender<()>,
+    ) {
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    () = tokio::time::sleep(DIGEST_CHECK_INTERVAL) => {},
+                    () = restart_tx.closed() => return,
+                }
+                if let Err(e) =
+                    Self::send_digests(&log, &db_connection, &messenger, &console_url).await
+                {
+                    slog::error!(log, "Failed to send alert digests: {e}");
+                    #[cfg(feature = "sentry")]
+                    sentry::capture_error(&e);
+                }
+            }
+        });
+    }
+
+    async fn send_digests(
+        log: &Logger,
+        db_connection: &Mutex<DbConnection>,
+        messenger: &Messenger,
+        console_url: &url::Url,
+    ) -> Result<(), HttpError> {
+        let now = DateTime::now();
+        let day_ago = DateTime::from(now.into_inner() - chrono::Duration::days(1));
+        let notifications = yield_connection_lock!(db_connection, |conn| {
+            schema::notification::table
+                .filter(schema::notification::alerts.eq(NotificationMode::Digest))
+                .filter(
+                    schema::notification::digested
+                        .is_null()
+                        .or(schema::notification::digested.le(day_ago)),
+                )
+                .load::<Self>(conn)
+                .map_err(resource_not_found_err!(Notification, day_ago))
+        })?;
+
+        for query_notification in notifications {
+            let result = yield_connection_lock!(db_connection, |conn| query_notification
+                .send_digest(log, conn, messenger, console_url, now, day_ago));
+            if let Err(e) = result {
+                slog::error!(
+                    log,
+                    "Failed to send alert digest ({query_notification:?}): {e}"
+                );
+                #[cfg(feature = "sentry")]
+                sentry::capture_error(&e);
+            }
+        }
+        Ok(())
+    }
+
+    fn send_digest(
+        &self,
+        log: &Logger,
+        conn: &mut DbConnection,
+        messenger: &Messenger,
+        console_url: &url::Url,
+        now: DateTime,
+        day_ago: DateTime,
+    ) -> Result<(), HttpError> {
+        // Mark the digest as sent first, so a failure is not retried until the next day.
+        diesel::update(schema::notification::table.filter(schema::notification::id.eq(self.id)))
+            .set(schema::notification::digested.eq(now))
+            .execute(conn)
+            .map_err(resource_conflict_err!(Notification, self))?;
+
+        let query_user = QueryUser::get(conn, self.user_id)?;
+        if query_user.locked {
+            return Ok(());
+        }
+        let query_project = QueryProject::get(conn, self.project_id)?;
+        if !Self::is_member(conn, self.user_id, &query_project)? {
+            return Ok(());
+        }
+
+        // Include everything since the last digest, in case one was missed.
+        let since = self.digested.unwrap_or(day_ago);
+        let alerts = alert_summaries(conn, console_url, &query_project, AlertWindow::Since(since))?;
+        if !alerts.is_empty() {
+            send_email(log, messenger, &query_user, &query_project, alerts, true);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = notification_table)]
+pub struct InsertNotification {
+    pub user_id: UserId,
+    pub project_id: ProjectId,
+    pub alerts: NotificationMode,
+    pub digested: Option<DateTime>,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+#[derive(Debug, Clone, diesel::AsChangeset)]
+#[diesel(table_name = notification_table)]
+pub struct UpdateNotification {
+    pub alerts: Option<NotificationMode>,
+    pub modified: DateTime,
+}
+
+#[derive(Clone, Copy)]
+enum AlertWindow {
+    Report(ReportUuid),
+    Since(DateTime),
+}
+
+type AlertSummaryRow = (
+    BoundaryLimit,
+    Option<f64>,
+    Option<f64>,
+    f64,
+    BenchmarkUuid,
+    BenchmarkName,
+    MeasureUuid,
+    ResourceName,
+    ResourceName,
+    BranchUuid,
+    HeadUuid,
+    TestbedUuid,
+    ReportUuid,
+    DateTime,
+);
+
+fn alert_summaries(
+    conn: &mut DbConnection,
+    console_url: &url::Url,
+    query_project: &QueryProject,
+    window: AlertWindow,
+) -> Result<Vec<AlertSummary>, HttpError> {
+    let mut query = schema::alert::table
+        .inner_join(
+            schema::boundary::table
+                .inner_join(
+                    schema::metric::table.inner_join(
+                        schema::report_benchmark::table
+                            .inner_join(schema::report::table.inner_join(schema::head::table))
+                            .inner_join(schema::benchmark::table),
+                    ),
+                )
+                .inner_join(
+                    schema::threshold::table
+                        .inner_join(schema::branch::table)
+                        .inner_join(schema::testbed::table)
+                        .inner_join(schema::measure::table),
+                ),
+        )
+        .filter(schema::benchmark::project_id.eq(query_project.id))
+        .filter(schema::alert::status.eq(AlertStatus::Active))
+        .into_boxed();
+    query = match window {
+        AlertWindow::Report(report_uuid) => query.filter(schema::report::uuid.eq(report_uuid)),
+        AlertWindow::Since(since) => query.filter(schema::report::created.gt(since)),
+    };
+    let rows = query
+        .order((
+            schema::report::created.asc(),
+            schema::benchmark::name.asc(),
+            schema::measure::name.asc(),
+        ))
+        .select((
+            schema::alert::boundary_limit,
+            schema::boundary::lower_limit,
+            schema::boundary::upper_limit,
+            schema::metric::value,
+            schema::benchmark::uuid,
+            schema::benchmark::name,
+            schema::measure::uuid,
+            schema::measure::name,
+            schema::measure::units,
+            schema::branch::uuid,
+            schema::head::uuid,
+            schema::testbed::uuid,
+            schema::report::uuid,
+            schema::report::end_time,
+        ))
+        .load::<AlertSummaryRow>(conn)
+        .map_err(resource_not_found_err!(Alert, query_project))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| alert_summary(console_url, query_project, row))
+        .collect())
+}
+
+fn alert_summary(
+    console_url: &url::Url,
+    query_project: &QueryProject,
+    row: AlertSummaryRow,
+) -> AlertSummary {
+    let (
+        limit,
+        lower_limit,
+        upper_limit,
+        value,
+        benchmark_uuid,
+        benchmark,
+        measure_uuid,
+        measure,
+        units,
+        branch_uuid,
+        head_uuid,
+        testbed_uuid,
+        report_uuid,
+        end_time,
+    ) = row;
+
+    let mut perf_url = console_url.clone();
+    perf_url.set_path(&format!("/console/projects/{}/perf", query_project.slug));
+    let json_perf_query = JsonPerfQuery {
+        branches: vec![branch_uuid],
+        heads: vec![Some(head_uuid)],
+        testbeds: vec![testbed_uuid],
+        benchmarks: vec![benchmark_uuid],
+        measures: vec![measure_uuid],
+        start_time: Some(
+            (end_time.into_inner() - chrono::Duration::days(PERF_HISTORY_DAYS)).into(),
+        ),
+        end_time: Some(end_time),
+    };
+    let boundary_key = match limit {
+        BoundaryLimit::Lower => LOWER_BOUNDARY,
+        BoundaryLimit::Upper => UPPER_BOUNDARY,
+    };
+    let query_string = [
+        ("report", Some(report_uuid.to_string())),
+        (boundary_key, Some(true.to_string())),
+    ];
+    perf_url.set_query(Some(
+        &json_perf_query
+            .to_query_string(&query_string)
+            .unwrap_or_default(),
+    ));
+
+    AlertSummary {
+        benchmark: benchmark.to_string(),
+        measure: measure.to_string(),
+        units: units.to_string(),
+        limit,
+        boundary: match limit {
+            BoundaryLimit::Lower => lower_limit,
+            BoundaryLimit::Upper => upper_limit,
+        },
+        value,
+        perf_url: perf_url.to_string(),
+    }
+}
+
+fn send_email(
+    log: &Logger,
+    messenger: &Messenger,
+    query_user: &QueryUser,
+    query_project: &QueryProject,
+    alerts: Vec<AlertSummary>,
+    digest: bool,
+) {
+    let body = AlertBody {
+        name: query_user.name.to_string(),
+        project: query_project.name.to_string(),
+        digest,
+        alerts,
+        settings: format!(
+            "To change these emails, run: bencher notification update {user} {project} --alerts <immediate|digest|off>",
+            user = query_user.slug,
+            project = query_project.slug,
+        ),
+    };
+    let message = Message {
+        to_name: Some(query_user.name.to_string()),
+        to_email: query_user.email.to_string(),
+        subject: Some(body.subject()),
+        body: Some(Body::Alert(Box::new(body))),
+    };
+    messenger.send(log, message);
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fmt::Write as _,
+        sync::{Arc, Mutex as StdMutex},
+    };
+
+    use bencher_json::{DateTime, NotificationMode, ReportUuid};
+    use diesel::{
+        Connection as _, ExpressionMethods as _, QueryDsl as _, RunQueryDsl as _,
+        connection::SimpleConnection as _,
+    };
+    use slog::{Drain, Level, Logger, Never, OwnedKVList, Record};
+    use tokio::sync::Mutex;
+
+    use super::{InsertNotification, QueryNotification};
+    use crate::{
+        context::{DbConnection, Messenger},
+        model::{project::QueryProject, user::UserId},
+        run_migrations, schema,
+    };
+
+    const CONSOLE_URL: &str = "https://bencher.dev";
+    const ACTIVE_REPORT: &str = "11111111-1111-4111-8111-111111111111";
+    const OLD_REPORT: &str = "22222222-2222-4222-8222-222222222222";
+    const DISMISSED_REPORT: &str = "33333333-3333-4333-8333-333333333333";
+
+    const ORG_MEMBER: i32 = 1;
+    const PROJECT_MEMBER: i32 = 2;
+    const LOCKED_MEMBER: i32 = 3;
+    const NON_MEMBER: i32 = 4;
+    const DIGEST_MEMBER: i32 = 5;
+
+    // The standard out messenger logs each message at the info level
+    #[derive(Clone, Default)]
+    struct Outbox(Arc<StdMutex<Vec<String>>>);
+
+    impl Drain for Outbox {
+        type Ok = ();
+        type Err = Never;
+
+        fn log(&self, record: &Record<'_>, _values: &OwnedKVList) -> Result<(), Never> {
+            if record.level() == Level::Info {
+                self.0.lock().unwrap().push(record.msg().to_string());
+            }
+            Ok(())
+        }
+    }
+
+    impl Outbox {
+        fn logger(&self) -> Logger {
+            Logger::root(self.clone(), slog::o!())
+        }
+
+        fn messages(&self) -> Vec<String> {
+            self.0.lock().unwrap().clone()
+        }
+
+        fn sent_to(&self, user_id: i32) -> Vec<String> {
+            let to_email = format!("<{}>", email(user_id));
+            self.messages()
+                .into_iter()
+                .filter(|message| message.contains(&to_email))
+                .collect()
+        }
+
+        fn sent_one(&self, user_id: i32) -> String {
+            let mut sent = self.sent_to(user_id);
+            assert_eq!(sent.len(), 1, "{sent:?}");
+            sent.pop().unwrap()
+        }
+    }
+
+    fn email(user_id: i32) -> String {
+        format!("user{user_id}@bencher.dev")
+    }
+
+    fn seconds_ago(seconds: i64) -> i64 {
+        DateTime::now().timestamp() - seconds
+    }
+
+    // A project with a recent active alert, an older active alert, and a dismissed alert.
+    // Foreign keys are off, so only the rows that the notifications read are needed.
+    fn project_database() -> (Mutex<DbConnection>, QueryProject) {
+        let mut conn = DbConnection::establish(":memory:").unwrap();
+        run_migrations(&mut conn).unwrap();
+        conn.batch_execute("PRAGMA foreign_keys = OFF").unwrap();
+
+        let now = seconds_ago(0);
+        let hour_ago = seconds_ago(60 * 60);
+        let days_ago = seconds_ago(3 * 24 * 60 * 60);
+        let mut sql = format!(
+            "INSERT INTO project (id, uuid, organization_id, name, slug, visibility, created, modified)
+                VALUES (1, '44444444-4444-4444-8444-444444444444', 1, 'Project', 'project', 0, {now}, {now});
+            INSERT INTO organization_role (user_id, organization_id, role, created, modified)
+                VALUES ({ORG_MEMBER}, 1, 'leader', {now}, {now}),
+                    ({LOCKED_MEMBER}, 1, 'leader', {now}, {now}),
+                    ({DIGEST_MEMBER}, 1, 'leader', {now}, {now});
+            INSERT INTO project_role (user_id, project_id, role, created, modified)
+                VALUES ({PROJECT_MEMBER}, 1, 'maintainer', {now}, {now});
+            INSERT INTO branch (id, uuid, project_id, name, slug, head_id, created, modified)
+                VALUES (1, '55555555-5555-4555-8555-555555555555', 1, 'main', 'main', 1, {now}, {now});
+            INSERT INTO head (id, uuid, branch_id, created)
+                VALUES (1, '66666666-6666-4666-8666-666666666666', 1, {now});
+            INSERT INTO testbed (id, uuid, project_id, name, slug, created, modified)
+                VALUES (1, '77777777-7777-4777-8777-777777777777', 1, 'localhost', 'localhost', {now}, {now});
+            INSERT INTO measure (id, uuid, project_id, name, slug, units, created, modified)
+                VALUES (1, '88888888-8888-4888-8888-888888888888', 1, 'Latency', 'latency', 'nanoseconds (ns)', {now}, {now});
+            INSERT INTO threshold (id, uuid, project_id, branch_id, testbed_id, measure_id, created, modified)
+                VALUES (1, '99999999-9999-4999-8999-999999999999', 1, 1, 1, 1, {now}, {now});"
+        );
+        for id in [
+            ORG_MEMBER,
+            PROJECT_MEMBER,
+            LOCKED_MEMBER,
+            NON_MEMBER,
+            DIGEST_MEMBER,
+        ] {
+            write!(
+                sql,
+                "INSERT INTO user (id, uuid, name, slug, email, admin, locked, created, modified)
+                    VALUES ({id}, '00000000-0000-4000-8000-00000000000{id}', 'User {id}', 'user-{id}', '{user_email}', 0, {locked}, {now}, {now});",
+                user_email = email(id),
+                locked = i32::from(id == LOCKED_MEMBER),
+            )
+            .unwrap();
+        }
+        for (id, report_uuid, created, benchmark, status) in [
+            (1, ACTIVE_REPORT, hour_ago, "bench_recent", 0),
+            (2, OLD_REPORT, days_ago, "bench_old", 0),
+            (3, DISMISSED_REPORT, hour_ago, "bench_dismissed", 1),
+        ] {
+            write!(
+                sql,
+                "INSERT INTO benchmark (id, uuid, project_id, name, slug, created, modified)
+                    VALUES ({id}, '00000000-0000-4000-8000-00000000001{id}', 1, '{benchmark}', 'bench-{id}', {now}, {now});
+                INSERT INTO report (id, uuid, project_id, head_id, version_id, testbed_id, adapter, environment_change, start_time, end_time, created)
+                    VALUES ({id}, '{report_uuid}', 1, 1, 1, 1, 0, 0, {created}, {created}, {created});
+                INSERT INTO report_benchmark (id, uuid, report_id, iteration, benchmark_id)
+                    VALUES ({id}, '00000000-0000-4000-8000-00000000002{id}', {id}, 0, {id});
+                INSERT INTO metric (id, uuid, report_benchmark_id, measure_id, value)
+                    VALUES ({id}, '00000000-0000-4000-8000-00000000003{id}', {id}, 1, 2.0);
+                INSERT INTO boundary (id, uuid, metric_id, threshold_id, model_id, upper_limit)
+                    VALUES ({id}, '00000000-0000-4000-8000-00000000004{id}', {id}, 1, 1, 1.0);
+                INSERT INTO alert (id, uuid, boundary_id, boundary_limit, status, modified)
+                    VALUES ({id}, '00000000-0000-4000-8000-00000000005{id}', {id}, 1, {status}, {now});"
+            )
+            .unwrap();
+        }
+        conn.batch_execute(&sql).unwrap();
+
+        let query_project = schema::project::table
+            .first::<QueryProject>(&mut conn)
+            .unwrap();
+        (Mutex::new(conn), query_project)
+    }
+
+    fn notify(
+        conn: &mut DbConnection,
+        query_project: &QueryProject,
+        user_id: i32,
+        alerts: NotificationMode,
+        digested: Option<i64>,
+    ) {
+        let now = DateTime::now();
+        diesel::insert_into(schema::notification::table)
+            .values(&InsertNotification {
+                user_id: UserId(user_id),
+                project_id: query_project.id,
+                alerts,
+                digested: digested.map(|digested| DateTime::try_from(digested).unwrap()),
+                created: now,
+                modified: now,
+            })
+            .execute(conn)
+            .unwrap();
+    }
+
+    fn digested(conn: &mut DbConnection, user_id: i32) -> Option<DateTime> {
+        schema::notification::table
+            .filter(schema::notification::user_id.eq(UserId(user_id)))
+            .select(schema::notification::digested)
+            .first(conn)
+            .unwrap()
+    }
+
+    async fn send_alerts(
+        db_connection: &Mutex<DbConnection>,
+        query_project: &QueryProject,
+        report_uuid: &str,
+    ) -> Outbox {
+        let outbox = Outbox::default();
+        QueryNotification::send_alerts_inner(
+            &outbox.logger(),
+            db_connection,
+            &Messenger::StdOut,
+            &CONSOLE_URL.parse().unwrap(),
+            query_project,
+            report_uuid.parse::<ReportUuid>().unwrap(),
+        )
+        .await
+        .unwrap();
+        outbox
+    }
+
+    #[tokio::test]
+    async fn test_send_alerts() {
+        let (db_connection, query_project) = project_database();
+        {
+            let conn = &mut *db_connection.lock().await;
+            for user_id in [ORG_MEMBER, PROJECT_MEMBER, LOCKED_MEMBER, NON_MEMBER] {
+                notify(
+                    conn,
+                    &query_project,
+                    user_id,
+                    NotificationMode::Immediate,
+                    None,
+                );
+            }
+            notify(
+                conn,
+                &query_project,
+                DIGEST_MEMBER,
+                NotificationMode::Digest,
+                None,
+            );
+        }
+
+        let outbox = send_alerts(&db_connection, &query_project, ACTIVE_REPORT).await;
+        assert_eq!(outbox.messages().len(), 2, "{:?}", outbox.messages());
+        for user_id in [ORG_MEMBER, PROJECT_MEMBER] {
+            let message = outbox.sent_one(user_id);
+            assert!(
+                message.contains("Subject: Bencher alert for Project: bench_recent"),
+                "{message}"
+            );
+            assert!(!message.contains("bench_old"), "{message}");
+        }
+        // Locked users, non-members, and digest subscribers are never emailed immediately
+        for user_id in [LOCKED_MEMBER, NON_MEMBER, DIGEST_MEMBER] {
+            assert!(
+                outbox.sent_to(user_id).is_empty(),
+                "{:?}",
+                outbox.messages()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_alerts_inactive() {
+        let (db_connection, query_project) = project_database();
+        notify(
+            &mut *db_connection.lock().await,
+            &query_project,
+            ORG_MEMBER,
+            NotificationMode::Immediate,
+            None,
+        );
+
+        // A report without any active alerts is ignored
+        let outbox = send_alerts(&db_connection, &query_project, DISMISSED_REPORT).await;
+        assert!(outbox.messages().is_empty(), "{:?}", outbox.messages());
+    }
+
+    #[tokio::test]
+    async fn test_send_alerts_off() {
+        let (db_connection, query_project) = project_database();
+        notify(
+            &mut *db_connection.lock().await,
+            &query_project,
+            ORG_MEMBER,
+            NotificationMode::Off,
+            None,
+        );
+
+        let outbox = send_alerts(&db_connection, &query_project, ACTIVE_REPORT).await;
+        assert!(outbox.messages().is_empty(), "{:?}", outbox.messages());
+    }
+
+    #[tokio::test]
+    async fn test_send_digests() {
+        let (db_connection, query_project) = project_database();
+        let hour_ago = seconds_ago(60 * 60);
+        let four_days_ago = seconds_ago(4 * 24 * 60 * 60);
+        {
+            let conn = &mut *db_connection.lock().await;
+            // Never digested, so everything from the last day is included
+            notify(
+                conn,
+                &query_project,
+                ORG_MEMBER,
+                NotificationMode::Digest,
+                None,
+            );
+            // Digested before the older alert, so it is included as well
+            notify(
+                conn,
+                &query_project,
+                PROJECT_MEMBER,
+                NotificationMode::Digest,
+                Some(four_days_ago),
+            );
+            // Digested within the last day, so it is not due yet
+            notify(
+                conn,
+                &query_project,
+                DIGEST_MEMBER,
+                NotificationMode::Digest,
+                Some(hour_ago),
+            );
+            notify(
+                conn,
+                &query_project,
+                LOCKED_MEMBER,
+                NotificationMode::Digest,
+                None,
+            );
+            notify(
+                conn,
+                &query_project,
+                NON_MEMBER,
+                NotificationMode::Digest,
+                None,
+            );
+        }
+
+        let outbox = Outbox::default();
+        QueryNotification::send_digests(
+            &outbox.logger(),
+            &db_connection,
+            &Messenger::StdOut,
+            &CONSOLE_URL.parse().unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(outbox.messages().len(), 2, "{:?}", outbox.messages());
+
+        let message = outbox.sent_one(ORG_MEMBER);
+        assert!(
+            message.contains("Subject: Daily alert digest for Project"),
+            "{message}"
+        );
+        assert!(message.contains("bench_recent"), "{message}");
+        assert!(!message.contains("bench_old"), "{message}");
+        assert!(!message.contains("bench_dismissed"), "{message}");
+
+        let message = outbox.sent_one(PROJECT_MEMBER);
+        assert!(message.contains("bench_recent"), "{message}");
+        assert!(message.contains("bench_old"), "{message}");
+        assert!(!message.contains("bench_dismissed"), "{message}");
+
+        for user_id in [DIGEST_MEMBER, LOCKED_MEMBER, NON_MEMBER] {
+            assert!(
+                outbox.sent_to(user_id).is_empty(),
+                "{:?}",
+                outbox.messages()
+            );
+        }
+
+        let conn = &mut *db_connection.lock().await;
+        // Every due digest is marked as sent, even if the user was skipped
+        for user_id in [ORG_MEMBER, PROJECT_MEMBER, LOCKED_MEMBER, NON_MEMBER] {
+            let digested = digested(conn, user_id).unwrap();
+            assert!(digested.timestamp() > hour_ago, "{user_id}: {digested}");
+        }
+        assert_eq!(
+            digested(conn, DIGEST_MEMBER).map(|digested| digested.timestamp()),
+            Some(hour_ago)
+        );
+    }
+}
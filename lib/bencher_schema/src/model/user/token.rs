@@ -1,24 +1,46 @@
+use std::net::IpAddr;
+
 use bencher_json::{
-    DateTime, JsonNewToken, JsonToken, Jwt, ResourceId, ResourceName, TokenUuid,
+    DateTime, Email, JsonNewToken, JsonToken, JsonTokenPermissions, Jwt, NameId, NonEmpty,
+    ProjectUuid, ResourceId, ResourceName, TokenUuid, project::ProjectPermission,
     user::token::JsonUpdateToken,
 };
+use bencher_rbac::{Scope, project::Permission};
 use bencher_token::TokenKey;
-use diesel::{ExpressionMethods as _, QueryDsl as _, RunQueryDsl as _};
+use diesel::{
+    ExpressionMethods as _, NullableExpressionMethods as _, OptionalExtension as _, QueryDsl as _,
+    RunQueryDsl as _,
+};
 use dropshot::HttpError;
 
 use crate::{
     context::{DbConnection, Rbac},
     error::{
-        BencherResource, assert_parentage, bad_request_error, issue_error, resource_not_found_err,
+        BencherResource, assert_parentage, bad_request_error, forbidden_error, issue_error,
+        resource_conflict_err, resource_not_found_err, unauthorized_error,
     },
     macros::fn_get::{fn_get, fn_get_id, fn_get_uuid},
-    model::user::same_user,
+    model::{
+        project::{
+            ProjectId, QueryProject, branch::QueryBranch, project_role,
+            trust_policy::QueryTrustPolicy,
+        },
+        user::same_user,
+    },
     schema,
     schema::token as token_table,
 };
 
 use super::{QueryUser, UserId, auth::AuthUser};
 
+const DEFAULT_PROJECT_PERMISSIONS: [ProjectPermission; 1] = [ProjectPermission::View];
+// Only record the use of a token at most once per minute,
+// so every authenticated request does not have to write to the database.
+const LAST_USED_GRANULARITY_SECONDS: i64 = 60;
+
+const CI_PROJECT_PERMISSIONS: [ProjectPermission; 2] =
+    [ProjectPermission::View, ProjectPermission::Create];
+
 crate::macros::typed_id::typed_id!(TokenId);
 
 #[derive(Debug, Clone, diesel::Queryable)]
@@ -26,10 +48,17 @@ pub struct QueryToken {
     pub id: TokenId,
     pub uuid: TokenUuid,
     pub user_id: UserId,
+    pub project_id: Option<ProjectId>,
     pub name: ResourceName,
     pub jwt: Jwt,
+    pub permissions: Option<JsonTokenPermissions>,
+    pub ci: bool,
+    pub branch: Option<NonEmpty>,
     pub creation: DateTime,
     pub expiration: DateTime,
+    pub last_used: Option<DateTime>,
+    pub last_used_ip: Option<String>,
+    pub revoked: Option<DateTime>,
 }
 
 impl QueryToken {
@@ -49,19 +78,139 @@ impl QueryToken {
             .map_err(resource_not_found_err!(Token, (user_id, uuid)))
     }
 
+    /// Check an API key against the deny-list of revoked tokens and record its use.
+    /// The use is only recorded if the token has not been used in the last minute.
+    /// API keys that are not in the token table are not scoped.
+    pub fn authenticate(
+        conn: &mut DbConnection,
+        query_user: &QueryUser,
+        jwt: &Jwt,
+        remote_ip: Option<IpAddr>,
+    ) -> Result<Option<Self>, HttpError> {
+        let Some(query_token) = schema::token::table
+            .filter(schema::token::user_id.eq(query_user.id))
+            .filter(schema::token::jwt.eq(jwt))
+            .first::<QueryToken>(conn)
+            .optional()
+            .map_err(resource_not_found_err!(Token, query_user))?
+        else {
+            return Ok(None);
+        };
+
+        if let Some(revoked) = query_token.revoked {
+            return Err(unauthorized_error(format!(
+                "API token ({uuid}) was revoked at {revoked}",
+                uuid = query_token.uuid
+            )));
+        }
+
+        let now = DateTime::now();
+        if query_token.last_used.is_none_or(|last_used| {
+            now.timestamp() - last_used.timestamp() >= LAST_USED_GRANULARITY_SECONDS
+        }) {
+            let update_token = UpdateToken::used(now, remote_ip);
+            diesel::update(schema::token::table.filter(schema::token::id.eq(query_token.id)))
+                .set(&update_token)
+                .execute(conn)
+                .map_err(resource_conflict_err!(Token, &query_token))?;
+        }
+
+        Ok(Some(query_token))
+    }
+
+    /// The RBAC scope for the token, if it is scoped to a project.
+    pub fn scope(&self) -> Result<Option<Scope>, HttpError> {
+        let Some(permissions) = self.permissions.clone() else {
+            return Ok(None);
+        };
+        let Some(project_id) = self.project_id else {
+            return Err(forbidden_error(format!(
+                "The project for scoped API token ({uuid}) no longer exists",
+                uuid = self.uuid
+            )));
+        };
+        Ok(Some(Scope {
+            project: project_id.to_string(),
+            permissions: permissions
+                .0
+                .into_iter()
+                .map(|permission| project_role::Permission::from(permission).into())
+                .collect(),
+        }))
+    }
+
+    /// Check that a CI token is allowed to create a report for the branch.
+    /// If the branch does not exist yet, then the branch name is checked as given.
+    pub fn check_branch(
+        &self,
+        conn: &mut DbConnection,
+        project_id: ProjectId,
+        branch: &NameId,
+    ) -> Result<(), HttpError> {
+        let Some(pattern) = self.branch.as_ref() else {
+            return Ok(());
+        };
+        let branch_name = QueryBranch::from_name_id(conn, project_id, branch).map_or_else(
+            |_| branch.to_string(),
+            |query_branch| query_branch.name.to_string(),
+        );
+        if is_pattern_match(pattern.as_ref(), &branch_name) {
+            Ok(())
+        } else {
+            Err(forbidden_error(format!(
+                "CI token ({uuid}) is limited to branches matching `{pattern}` but the branch is `{branch_name}`",
+                uuid = self.uuid
+            )))
+        }
+    }
+
+    /// Delete the expired CI tokens for a project.
+    /// A new CI token is created for every CI ID token exchange,
+    /// so the expired ones are pruned whenever another one is created.
+    /// CI tokens that were used to create a report are kept as a record of who created it.
+    pub fn prune_expired_ci(
+        conn: &mut DbConnection,
+        query_project: &QueryProject,
+    ) -> Result<usize, HttpError> {
+        let report_tokens = schema::report::table
+            .filter(schema::report::project_id.eq(query_project.id))
+            .filter(schema::report::token_id.is_not_null())
+            .select(schema::report::token_id);
+        diesel::delete(
+            schema::token::table
+                .filter(schema::token::project_id.eq(query_project.id))
+                .filter(schema::token::ci.eq(true))
+                .filter(schema::token::expiration.lt(DateTime::now()))
+                .filter(schema::token::id.nullable().ne_all(report_tokens)),
+        )
+        .execute(conn)
+        .map_err(resource_conflict_err!(Token, query_project))
+    }
+
     pub fn into_json(self, conn: &mut DbConnection) -> Result<JsonToken, HttpError> {
         let query_user = QueryUser::get(conn, self.user_id)?;
-        Ok(self.into_json_for_user(&query_user))
+        self.into_json_for_user(conn, &query_user)
     }
 
-    pub fn into_json_for_user(self, query_user: &QueryUser) -> JsonToken {
+    pub fn into_json_for_user(
+        self,
+        conn: &mut DbConnection,
+        query_user: &QueryUser,
+    ) -> Result<JsonToken, HttpError> {
         let Self {
             uuid,
             user_id,
+            project_id,
             name,
             jwt,
+            permissions,
             creation,
             expiration,
+            last_used,
+            ci,
+            branch,
+            last_used_ip,
+            revoked,
             ..
         } = self;
         assert_parentage(
@@ -70,24 +219,58 @@ impl QueryToken {
             BencherResource::Token,
             user_id,
         );
-        JsonToken {
+        let project = project_id
+            .map(|project_id| QueryProject::get_uuid(conn, project_id))
+            .transpose()?;
+        Ok(JsonToken {
             uuid,
             user: query_user.uuid,
             name,
             token: jwt,
             creation,
             expiration,
-        }
+            project,
+            permissions,
+            last_used,
+            last_used_ip,
+            revoked,
+            ci,
+            branch,
+        })
     }
 }
 
+/// Match a name against a pattern where a `*` matches any sequence of characters.
+pub(crate) fn is_pattern_match(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = parts.next().and_then(|prefix| name.strip_prefix(prefix)) else {
+        return false;
+    };
+    let parts = parts.collect::<Vec<_>>();
+    let Some((suffix, middle)) = parts.split_last() else {
+        // There are no wildcards, so the pattern must match exactly.
+        return rest.is_empty();
+    };
+    for part in middle {
+        let Some((_, after)) = rest.split_once(part) else {
+            return false;
+        };
+        rest = after;
+    }
+    rest.ends_with(suffix)
+}
+
 #[derive(Debug, diesel::Insertable)]
 #[diesel(table_name = token_table)]
 pub struct InsertToken {
     pub uuid: TokenUuid,
     pub user_id: UserId,
+    pub project_id: Option<ProjectId>,
     pub name: ResourceName,
     pub jwt: Jwt,
+    pub permissions: Option<JsonTokenPermissions>,
+    pub ci: bool,
+    pub branch: Option<NonEmpty>,
     pub creation: DateTime,
     pub expiration: DateTime,
 }
@@ -138,11 +321,69 @@ impl InsertToken {
         token: JsonNewToken,
         auth_user: &AuthUser,
     ) -> Result<Self, HttpError> {
-        let JsonNewToken { name, ttl } = token;
+        let JsonNewToken {
+            name,
+            ttl,
+            project,
+            permissions,
+            ci,
+            branch,
+        } = token;
+        let ci = ci.unwrap_or_default();
 
         let query_user = QueryUser::from_resource_id(conn, user)?;
         same_user!(auth_user, rbac, query_user.uuid);
 
+        if ci && permissions.is_some() {
+            return Err(bad_request_error(
+                "A CI token can not be granted custom permissions",
+            ));
+        }
+        let (query_project, permissions) = match (project, permissions) {
+            (Some(project), _) if ci => {
+                // A CI token must be able to create runs for the project
+                let query_project =
+                    QueryProject::is_allowed(conn, rbac, &project, auth_user, Permission::Create)?;
+                (Some(query_project), Some(CI_PROJECT_PERMISSIONS.to_vec()))
+            },
+            (None, _) if ci => {
+                return Err(bad_request_error("A CI token must be scoped to a project"));
+            },
+            (Some(project), permissions) => {
+                let query_project =
+                    QueryProject::is_allowed(conn, rbac, &project, auth_user, Permission::View)?;
+                let permissions = if let Some(permissions) = permissions {
+                    if permissions.is_empty() {
+                        return Err(bad_request_error(
+                            "A scoped token must be granted at least one permission",
+                        ));
+                    }
+                    let mut deduped = Vec::with_capacity(permissions.len());
+                    for permission in permissions {
+                        if !deduped.contains(&permission) {
+                            deduped.push(permission);
+                        }
+                    }
+                    deduped
+                } else {
+                    // A scoped token is only granted more than view access when explicitly asked
+                    DEFAULT_PROJECT_PERMISSIONS.to_vec()
+                };
+                (Some(query_project), Some(permissions))
+            },
+            (None, Some(_)) => {
+                return Err(bad_request_error(
+                    "Token permissions can only be set when the token is scoped to a project",
+                ));
+            },
+            (None, None) => (None, None),
+        };
+        if branch.is_some() && !ci {
+            return Err(bad_request_error(
+                "A branch pattern can only be set for a CI token",
+            ));
+        }
+
         // TODO Custom max TTL
         let max_ttl = u32::MAX;
         let ttl = if let Some(ttl) = ttl {
@@ -156,14 +397,94 @@ impl InsertToken {
             max_ttl
         };
 
-        let jwt = token_key.new_api_key(query_user.email, ttl).map_err(|e| {
+        let uuid = TokenUuid::new();
+        let (jwt, creation, expiration) = match query_project.as_ref() {
+            Some(query_project) if ci => Self::new_ci(
+                token_key,
+                query_user.email,
+                ttl,
+                uuid,
+                query_project.uuid,
+                branch.clone(),
+            )?,
+            _ => Self::new_api_key(token_key, query_user.email, ttl, uuid)?,
+        };
+
+        Ok(Self {
+            uuid,
+            user_id: query_user.id,
+            project_id: query_project.map(|query_project| query_project.id),
+            name,
+            jwt,
+            permissions: permissions.map(Into::into),
+            ci,
+            branch,
+            creation,
+            expiration,
+        })
+    }
+
+    /// Create a short-lived CI token for the user that created a trust policy
+    pub fn from_trust_policy(
+        conn: &mut DbConnection,
+        token_key: &TokenKey,
+        query_project: &QueryProject,
+        query_trust_policy: &QueryTrustPolicy,
+        ttl: u32,
+    ) -> Result<Self, HttpError> {
+        let query_user = QueryUser::get(conn, query_trust_policy.user_id)?;
+        let name = format!(
+            "{provider} ({repository})",
+            provider = query_trust_policy.provider,
+            repository = query_trust_policy.repository
+        );
+        // The repository may make the name too long, so fall back to just the provider
+        let name = name
+            .parse()
+            .or_else(|_| query_trust_policy.provider.to_string().parse())
+            .map_err(|e| {
+                issue_error(
+                    "Failed to create CI token name",
+                    &format!("Failed to create CI token name: {name}"),
+                    e,
+                )
+            })?;
+        let uuid = TokenUuid::new();
+        let (jwt, creation, expiration) = Self::new_ci(
+            token_key,
+            query_user.email,
+            ttl,
+            uuid,
+            query_project.uuid,
+            None,
+        )?;
+        Ok(Self {
+            uuid,
+            user_id: query_user.id,
+            project_id: Some(query_project.id),
+            name,
+            jwt,
+            permissions: Some(CI_PROJECT_PERMISSIONS.to_vec().into()),
+            ci: true,
+            branch: None,
+            creation,
+            expiration,
+        })
+    }
+
+    fn new_api_key(
+        token_key: &TokenKey,
+        email: Email,
+        ttl: u32,
+        uuid: TokenUuid,
+    ) -> Result<(Jwt, DateTime, DateTime), HttpError> {
+        let jwt = token_key.new_api_key(email, ttl, uuid).map_err(|e| {
             issue_error(
                 "Failed to create new API key",
                 "Failed to create new API key.",
                 e,
             )
         })?;
-
         let claims = token_key.validate_api_key(&jwt).map_err(|e| {
             issue_error(
                 "Failed to validate new API key",
@@ -171,15 +492,34 @@ impl InsertToken {
                 e,
             )
         })?;
+        Ok((jwt, claims.issued_at(), claims.expiration()))
+    }
 
-        Ok(Self {
-            uuid: TokenUuid::new(),
-            user_id: query_user.id,
-            name,
-            jwt,
-            creation: claims.issued_at(),
-            expiration: claims.expiration(),
-        })
+    fn new_ci(
+        token_key: &TokenKey,
+        email: Email,
+        ttl: u32,
+        uuid: TokenUuid,
+        project_uuid: ProjectUuid,
+        branch: Option<NonEmpty>,
+    ) -> Result<(Jwt, DateTime, DateTime), HttpError> {
+        let jwt = token_key
+            .new_ci(email, ttl, uuid, project_uuid, branch)
+            .map_err(|e| {
+                issue_error(
+                    "Failed to create new CI token",
+                    "Failed to create new CI token.",
+                    e,
+                )
+            })?;
+        let claims = token_key.validate_ci(&jwt).map_err(|e| {
+            issue_error(
+                "Failed to validate new CI token",
+                &format!("Failed to validate new CI token: {jwt}"),
+                e,
+            )
+        })?;
+        Ok((jwt, claims.issued_at(), claims.expiration()))
     }
 }
 
@@ -187,11 +527,106 @@ impl InsertToken {
 #[diesel(table_name = token_table)]
 pub struct UpdateToken {
     pub name: Option<ResourceName>,
+    pub last_used: Option<DateTime>,
+    pub last_used_ip: Option<String>,
+    pub revoked: Option<DateTime>,
 }
 
 impl From<JsonUpdateToken> for UpdateToken {
     fn from(update: JsonUpdateToken) -> Self {
         let JsonUpdateToken { name } = update;
-        Self { name }
+        Self {
+            name,
+            last_used: None,
+            last_used_ip: None,
+            revoked: None,
+        }
+    }
+}
+
+impl UpdateToken {
+    fn used(now: DateTime, remote_ip: Option<IpAddr>) -> Self {
+        Self {
+            name: None,
+            last_used: Some(now),
+            last_used_ip: remote_ip.map(|ip| ip.to_string()),
+            revoked: None,
+        }
+    }
+
+    pub fn revoke() -> Self {
+        Self {
+            name: None,
+            last_used: None,
+            last_used_ip: None,
+            revoked: Some(DateTime::now()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::is_pattern_match;
+
+    #[test]
+    fn test_pattern_match_exact() {
+        assert!(is_pattern_match("main", "main"));
+        assert!(is_pattern_match("", ""));
+        assert!(!is_pattern_match("main", "mainline"));
+        assert!(!is_pattern_match("main", "domain"));
+        assert!(!is_pattern_match("main", ""));
+        assert!(!is_pattern_match("", "main"));
+    }
+
+    #[test]
+    fn test_pattern_match_leading_wildcard() {
+        assert!(is_pattern_match("*-release", "v1-release"));
+        assert!(is_pattern_match("*-release", "-release"));
+        assert!(!is_pattern_match("*-release", "v1-release-candidate"));
+        assert!(!is_pattern_match("*-release", "release"));
+    }
+
+    #[test]
+    fn test_pattern_match_trailing_wildcard() {
+        assert!(is_pattern_match("feature/*", "feature/login"));
+        assert!(is_pattern_match("feature/*", "feature/"));
+        assert!(is_pattern_match("feature/*", "feature/a/b"));
+        assert!(!is_pattern_match("feature/*", "bugfix/login"));
+        assert!(!is_pattern_match("feature/*", "feature"));
+    }
+
+    #[test]
+    fn test_pattern_match_only_wildcard() {
+        assert!(is_pattern_match("*", ""));
+        assert!(is_pattern_match("*", "main"));
+        assert!(is_pattern_match("**", "main"));
+    }
+
+    #[test]
+    fn test_pattern_match_multiple_wildcards() {
+        assert!(is_pattern_match("repo:*:ref:*", "repo:bencher:ref:main"));
+        assert!(is_pattern_match("*/*/*", "a/b/c"));
+        assert!(is_pattern_match("*/*/*", "//"));
+        assert!(is_pattern_match("a*b*c", "abc"));
+        assert!(is_pattern_match("a*b*c", "a-b-b-c"));
+        assert!(!is_pattern_match("*/*/*", "a/b"));
+        assert!(!is_pattern_match("a*b*c", "acb"));
+        assert!(!is_pattern_match("repo:*:ref:*", "repo:bencher:main"));
+    }
+
+    #[test]
+    fn test_pattern_match_overlapping_suffix() {
+        // The prefix, middle parts, and suffix may not share characters.
+        assert!(!is_pattern_match("a*a", "a"));
+        assert!(is_pattern_match("a*a", "aa"));
+        assert!(!is_pattern_match("ab*bc", "abc"));
+        assert!(is_pattern_match("ab*bc", "abbc"));
+        assert!(!is_pattern_match("a*b*bc", "abc"));
+        assert!(is_pattern_match("a*b*bc", "abbc"));
+        assert!(is_pattern_match("*ab*ab", "abab"));
+        assert!(!is_pattern_match("*ab*ab", "aba"));
+        // The suffix is matched at the end, not at its first occurrence.
+        assert!(is_pattern_match("*-rc", "1-rc-2-rc"));
+        assert!(is_pattern_match("x*x*x", "xxxxx"));
     }
 }
@@ -34,6 +34,8 @@ diesel::table! {
         baseline -> Nullable<Double>,
         lower_limit -> Nullable<Double>,
         upper_limit -> Nullable<Double>,
+        change_point -> Nullable<Text>,
+        significance -> Nullable<Double>,
     }
 }
 
@@ -107,11 +109,27 @@ diesel::table! {
         window -> Nullable<BigInt>,
         lower_boundary -> Nullable<Double>,
         upper_boundary -> Nullable<Double>,
+        smoothing -> Nullable<Double>,
+        trend -> Nullable<Double>,
+        season -> Nullable<BigInt>,
+        environment_change -> Nullable<Integer>,
         created -> BigInt,
         replaced -> Nullable<BigInt>,
     }
 }
 
+diesel::table! {
+    notification (id) {
+        id -> Integer,
+        user_id -> Integer,
+        project_id -> Integer,
+        alerts -> Integer,
+        digested -> Nullable<BigInt>,
+        created -> BigInt,
+        modified -> BigInt,
+    }
+}
+
 diesel::table! {
     organization (id) {
         id -> Integer,
@@ -227,11 +245,15 @@ diesel::table! {
         id -> Integer,
         uuid -> Text,
         user_id -> Nullable<Integer>,
+        token_id -> Nullable<Integer>,
         project_id -> Integer,
         head_id -> Integer,
         version_id -> Integer,
         testbed_id -> Integer,
         adapter -> Integer,
+        hardware -> Nullable<Text>,
+        environment_change -> Bool,
+        hardware_change -> Nullable<Integer>,
         start_time -> BigInt,
         end_time -> BigInt,
         created -> BigInt,
@@ -263,6 +285,7 @@ diesel::table! {
         project_id -> Integer,
         name -> Text,
         slug -> Text,
+        hardware -> Nullable<Text>,
         created -> BigInt,
         modified -> BigInt,
         archived -> Nullable<BigInt>,
@@ -288,10 +311,32 @@ diesel::table! {
         id -> Integer,
         uuid -> Text,
         user_id -> Integer,
+        project_id -> Nullable<Integer>,
         name -> Text,
         jwt -> Text,
+        permissions -> Nullable<Text>,
+        ci -> Bool,
+        branch -> Nullable<Text>,
         creation -> BigInt,
         expiration -> BigInt,
+        last_used -> Nullable<BigInt>,
+        last_used_ip -> Nullable<Text>,
+        revoked -> Nullable<BigInt>,
+    }
+}
+
+diesel::table! {
+    trust_policy (id) {
+        id -> Integer,
+        uuid -> Text,
+        project_id -> Integer,
+        user_id -> Integer,
+        provider -> Integer,
+        issuer -> Nullable<Text>,
+        repository -> Text,
+        git_ref -> Nullable<Text>,
+        workflow -> Nullable<Text>,
+        created -> BigInt,
     }
 }
 
@@ -319,6 +364,34 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    webhook (id) {
+        id -> Integer,
+        uuid -> Text,
+        project_id -> Integer,
+        url -> Text,
+        format -> Integer,
+        secret -> Nullable<Text>,
+        events -> Text,
+        created -> BigInt,
+    }
+}
+
+diesel::table! {
+    webhook_delivery (id) {
+        id -> Integer,
+        uuid -> Text,
+        webhook_id -> Integer,
+        event -> Integer,
+        status -> Integer,
+        attempts -> Integer,
+        response_status -> Nullable<Integer>,
+        error -> Nullable<Text>,
+        created -> BigInt,
+        modified -> BigInt,
+    }
+}
+
 diesel::joinable!(alert -> boundary (boundary_id));
 diesel::joinable!(benchmark -> project (project_id));
 diesel::joinable!(boundary -> metric (metric_id));
@@ -329,6 +402,8 @@ diesel::joinable!(head_version -> version (version_id));
 diesel::joinable!(measure -> project (project_id));
 diesel::joinable!(metric -> measure (measure_id));
 diesel::joinable!(metric -> report_benchmark (report_benchmark_id));
+diesel::joinable!(notification -> project (project_id));
+diesel::joinable!(notification -> user (user_id));
 diesel::joinable!(organization_role -> organization (organization_id));
 diesel::joinable!(organization_role -> user (user_id));
 diesel::joinable!(plot -> project (project_id));
@@ -346,6 +421,7 @@ diesel::joinable!(project_role -> user (user_id));
 diesel::joinable!(report -> head (head_id));
 diesel::joinable!(report -> project (project_id));
 diesel::joinable!(report -> testbed (testbed_id));
+diesel::joinable!(report -> token (token_id));
 diesel::joinable!(report -> user (user_id));
 diesel::joinable!(report -> version (version_id));
 diesel::joinable!(report_benchmark -> benchmark (benchmark_id));
@@ -355,8 +431,13 @@ diesel::joinable!(threshold -> branch (branch_id));
 diesel::joinable!(threshold -> measure (measure_id));
 diesel::joinable!(threshold -> project (project_id));
 diesel::joinable!(threshold -> testbed (testbed_id));
+diesel::joinable!(token -> project (project_id));
 diesel::joinable!(token -> user (user_id));
+diesel::joinable!(trust_policy -> project (project_id));
+diesel::joinable!(trust_policy -> user (user_id));
 diesel::joinable!(version -> project (project_id));
+diesel::joinable!(webhook -> project (project_id));
+diesel::joinable!(webhook_delivery -> webhook (webhook_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     alert,
@@ -368,6 +449,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     measure,
     metric,
     model,
+    notification,
     organization,
     organization_role,
     plan,
@@ -384,6 +466,9 @@ diesel::allow_tables_to_appear_in_same_query!(
     testbed,
     threshold,
     token,
+    trust_policy,
     user,
     version,
+    webhook,
+    webhook_delivery,
 );
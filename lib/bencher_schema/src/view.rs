@@ -19,6 +19,8 @@ diesel::table! {
         baseline -> Nullable<Double>,
         lower_limit -> Nullable<Double>,
         upper_limit -> Nullable<Double>,
+        change_point -> Nullable<Text>,
+        significance -> Nullable<Double>,
     }
 }
 
@@ -1,4 +1,7 @@
-use bencher_json::{DateTime, Email, OrganizationUuid, organization::member::OrganizationRole};
+use bencher_json::{
+    DateTime, Email, NonEmpty, OrganizationUuid, ProjectUuid, TokenUuid,
+    organization::member::OrganizationRole,
+};
 use chrono::Utc;
 use jsonwebtoken::errors::ErrorKind as JsonWebTokenErrorKind;
 use serde::{Deserialize, Serialize};
@@ -7,12 +10,14 @@ use super::{TokenError, audience::Audience};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
-    pub aud: String,            // Audience
-    pub exp: i64,               // Expiration time (as UTC timestamp)
-    pub iat: i64,               // Issued at (as UTC timestamp)
-    pub iss: String,            // Issuer
-    pub sub: Email,             // Subject (whom token refers to)
-    pub org: Option<OrgClaims>, // Organization (for invitation)
+    pub aud: String,                 // Audience
+    pub exp: i64,                    // Expiration time (as UTC timestamp)
+    pub iat: i64,                    // Issued at (as UTC timestamp)
+    pub iss: String,                 // Issuer
+    pub sub: Email,                  // Subject (whom token refers to)
+    pub org: Option<OrgClaims>,      // Organization (for invitation)
+    pub jti: Option<TokenUuid>,      // JWT ID (for API key and CI token)
+    pub proj: Option<ProjectClaims>, // Project (for CI token)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +26,12 @@ pub struct OrgClaims {
     pub role: OrganizationRole,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectClaims {
+    pub uuid: ProjectUuid,
+    pub branch: Option<NonEmpty>,
+}
+
 impl Claims {
     pub fn new(
         audience: Audience,
@@ -28,6 +39,8 @@ impl Claims {
         email: Email,
         ttl: u32,
         org: Option<OrgClaims>,
+        jti: Option<TokenUuid>,
+        proj: Option<ProjectClaims>,
     ) -> Self {
         let now = Utc::now().timestamp();
         Self {
@@ -37,6 +50,8 @@ impl Claims {
             iss: issuer,
             sub: email,
             org,
+            jti,
+            proj,
         }
     }
 
@@ -44,6 +59,10 @@ impl Claims {
         &self.sub
     }
 
+    pub fn is_api_key(&self) -> bool {
+        self.aud == Audience::ApiKey.to_string()
+    }
+
     pub fn issued_at(&self) -> DateTime {
         let date_time = DateTime::try_from(self.iat);
         debug_assert!(date_time.is_ok(), "Issued at time is invalid");
@@ -92,3 +111,58 @@ impl InviteClaims {
         &self.sub
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct CiClaims {
+    pub aud: String,
+    pub exp: i64,
+    pub iat: i64,
+    pub iss: String,
+    pub sub: Email,
+    pub jti: TokenUuid,
+    pub proj: ProjectClaims,
+}
+
+impl TryFrom<Claims> for CiClaims {
+    type Error = TokenError;
+
+    fn try_from(claims: Claims) -> Result<Self, Self::Error> {
+        let Some(jti) = claims.jti else {
+            return Err(TokenError::Ci {
+                error: JsonWebTokenErrorKind::MissingRequiredClaim("jti".into()).into(),
+            });
+        };
+        let Some(proj) = claims.proj else {
+            return Err(TokenError::Ci {
+                error: JsonWebTokenErrorKind::MissingRequiredClaim("proj".into()).into(),
+            });
+        };
+        Ok(Self {
+            aud: claims.aud,
+            exp: claims.exp,
+            iat: claims.iat,
+            iss: claims.iss,
+            sub: claims.sub,
+            jti,
+            proj,
+        })
+    }
+}
+
+impl CiClaims {
+    pub fn email(&self) -> &Email {
+        &self.sub
+    }
+
+    pub fn issued_at(&self) -> DateTime {
+        let date_time = DateTime::try_from(self.iat);
+        debug_assert!(date_time.is_ok(), "Issued at time is invalid");
+        date_time.unwrap_or_default()
+    }
+
+    pub fn expiration(&self) -> DateTime {
+        let date_time = DateTime::try_from(self.exp);
+        debug_assert!(date_time.is_ok(), "Expiration time is invalid");
+        date_time.unwrap_or_default()
+    }
+}
@@ -1,14 +1,17 @@
 use std::str::FromStr as _;
 use std::sync::LazyLock;
 
-use bencher_json::{Email, Jwt, OrganizationUuid, Secret, organization::member::OrganizationRole};
+use bencher_json::{
+    Email, Jwt, NonEmpty, OrganizationUuid, ProjectUuid, Secret, TokenUuid,
+    organization::member::OrganizationRole,
+};
 use chrono::Utc;
 use jsonwebtoken::{
     Algorithm, DecodingKey, EncodingKey, Header, TokenData, Validation, decode, encode,
     errors::ErrorKind as JsonWebTokenErrorKind,
 };
 
-use crate::{Audience, Claims, InviteClaims, OrgClaims, TokenError};
+use crate::{Audience, CiClaims, Claims, InviteClaims, OrgClaims, ProjectClaims, TokenError};
 
 static HEADER: LazyLock<Header> = LazyLock::new(Header::default);
 static ALGORITHM: LazyLock<Algorithm> = LazyLock::new(Algorithm::default);
@@ -34,25 +37,34 @@ impl TokenKey {
         email: Email,
         ttl: u32,
         org: Option<OrgClaims>,
+        jti: Option<TokenUuid>,
+        proj: Option<ProjectClaims>,
     ) -> Result<Jwt, TokenError> {
-        let claims = Claims::new(audience, self.issuer.clone(), email, ttl, org);
-        Jwt::from_str(
-            &encode(&HEADER, &claims, &self.encoding)
-                .map_err(|e| TokenError::Encode { claims, error: e })?,
-        )
+        let claims = Claims::new(audience, self.issuer.clone(), email, ttl, org, jti, proj);
+        Jwt::from_str(&encode(&HEADER, &claims, &self.encoding).map_err(|e| {
+            TokenError::Encode {
+                claims: Box::new(claims),
+                error: e,
+            }
+        })?)
         .map_err(TokenError::Parse)
     }
 
     pub fn new_auth(&self, email: Email, ttl: u32) -> Result<Jwt, TokenError> {
-        self.new_jwt(Audience::Auth, email, ttl, None)
+        self.new_jwt(Audience::Auth, email, ttl, None, None, None)
     }
 
     pub fn new_client(&self, email: Email, ttl: u32) -> Result<Jwt, TokenError> {
-        self.new_jwt(Audience::Client, email, ttl, None)
+        self.new_jwt(Audience::Client, email, ttl, None, None, None)
     }
 
-    pub fn new_api_key(&self, email: Email, ttl: u32) -> Result<Jwt, TokenError> {
-        self.new_jwt(Audience::ApiKey, email, ttl, None)
+    pub fn new_api_key(
+        &self,
+        email: Email,
+        ttl: u32,
+        token_uuid: TokenUuid,
+    ) -> Result<Jwt, TokenError> {
+        self.new_jwt(Audience::ApiKey, email, ttl, None, Some(token_uuid), None)
     }
 
     pub fn new_invite(
@@ -66,7 +78,29 @@ impl TokenKey {
             uuid: org_uuid,
             role,
         };
-        self.new_jwt(Audience::Invite, email, ttl, Some(org_claims))
+        self.new_jwt(Audience::Invite, email, ttl, Some(org_claims), None, None)
+    }
+
+    pub fn new_ci(
+        &self,
+        email: Email,
+        ttl: u32,
+        token_uuid: TokenUuid,
+        project_uuid: ProjectUuid,
+        branch: Option<NonEmpty>,
+    ) -> Result<Jwt, TokenError> {
+        let proj_claims = ProjectClaims {
+            uuid: project_uuid,
+            branch,
+        };
+        self.new_jwt(
+            Audience::Ci,
+            email,
+            ttl,
+            None,
+            Some(token_uuid),
+            Some(proj_claims),
+        )
     }
 
     fn validate(
@@ -114,13 +148,20 @@ impl TokenKey {
     pub fn validate_invite(&self, token: &Jwt) -> Result<InviteClaims, TokenError> {
         self.validate(token, &[Audience::Invite])?.claims.try_into()
     }
+
+    pub fn validate_ci(&self, token: &Jwt) -> Result<CiClaims, TokenError> {
+        self.validate(token, &[Audience::Ci])?.claims.try_into()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use std::{sync::LazyLock, thread, time};
 
-    use bencher_json::{Email, OrganizationUuid, organization::member::OrganizationRole};
+    use bencher_json::{
+        Email, NonEmpty, OrganizationUuid, ProjectUuid, TokenUuid,
+        organization::member::OrganizationRole,
+    };
 
     use crate::{Audience, DEFAULT_SECRET_KEY};
 
@@ -190,7 +231,10 @@ mod test {
     fn test_jwt_api_key() {
         let secret_key = TokenKey::new(BENCHER_DOT_DEV_ISSUER.to_owned(), &DEFAULT_SECRET_KEY);
 
-        let token = secret_key.new_api_key(EMAIL.clone(), TTL).unwrap();
+        let token_uuid = TokenUuid::new();
+        let token = secret_key
+            .new_api_key(EMAIL.clone(), TTL, token_uuid)
+            .unwrap();
 
         let claims = secret_key.validate_api_key(&token).unwrap();
 
@@ -198,13 +242,16 @@ mod test {
         assert_eq!(claims.iss, BENCHER_DOT_DEV_ISSUER.to_owned());
         assert_eq!(claims.iat, claims.exp - i64::from(TTL));
         assert_eq!(claims.sub, *EMAIL);
+        assert_eq!(claims.jti, Some(token_uuid));
     }
 
     #[test]
     fn test_jwt_api_key_expired() {
         let secret_key = TokenKey::new(BENCHER_DOT_DEV_ISSUER.to_owned(), &DEFAULT_SECRET_KEY);
 
-        let token = secret_key.new_api_key(EMAIL.clone(), 0).unwrap();
+        let token = secret_key
+            .new_api_key(EMAIL.clone(), 0, TokenUuid::new())
+            .unwrap();
 
         sleep_for_a_second();
 
@@ -248,4 +295,51 @@ mod test {
 
         assert!(secret_key.validate_invite(&token).is_err());
     }
+
+    #[test]
+    fn test_jwt_ci() {
+        let secret_key = TokenKey::new(BENCHER_DOT_DEV_ISSUER.to_owned(), &DEFAULT_SECRET_KEY);
+
+        let token_uuid = TokenUuid::new();
+        let project_uuid = ProjectUuid::new();
+        let branch: NonEmpty = "release/*".parse().unwrap();
+
+        let token = secret_key
+            .new_ci(
+                EMAIL.clone(),
+                TTL,
+                token_uuid,
+                project_uuid,
+                Some(branch.clone()),
+            )
+            .unwrap();
+
+        let claims = secret_key.validate_ci(&token).unwrap();
+
+        assert_eq!(claims.aud, Audience::Ci.to_string());
+        assert_eq!(claims.iss, BENCHER_DOT_DEV_ISSUER.to_owned());
+        assert_eq!(claims.iat, claims.exp - i64::from(TTL));
+        assert_eq!(claims.sub, *EMAIL);
+        assert_eq!(claims.jti, token_uuid);
+
+        assert_eq!(claims.proj.uuid, project_uuid);
+        assert_eq!(claims.proj.branch, Some(branch));
+
+        // A CI token can not be used as a client token
+        assert!(secret_key.validate_client(&token).is_err());
+        assert!(secret_key.validate_api_key(&token).is_err());
+    }
+
+    #[test]
+    fn test_jwt_ci_expired() {
+        let secret_key = TokenKey::new(BENCHER_DOT_DEV_ISSUER.to_owned(), &DEFAULT_SECRET_KEY);
+
+        let token = secret_key
+            .new_ci(EMAIL.clone(), 0, TokenUuid::new(), ProjectUuid::new(), None)
+            .unwrap();
+
+        sleep_for_a_second();
+
+        assert!(secret_key.validate_ci(&token).is_err());
+    }
 }
@@ -11,7 +11,7 @@ mod error;
 mod key;
 
 pub use audience::Audience;
-pub use claims::{Claims, InviteClaims, OrgClaims};
+pub use claims::{CiClaims, Claims, InviteClaims, OrgClaims, ProjectClaims};
 pub use error::TokenError;
 pub use key::TokenKey;
 
@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-use crate::{Boundary, SampleSize, Window};
+use crate::{Boundary, SampleSize, Smoothing, Window};
 
 pub(crate) const REGEX_ERROR: &str = "Failed to compile regex.";
 
@@ -52,6 +52,10 @@ pub enum ValidError {
     Window(u32),
     #[error("Failed to parse model window: {0}")]
     WindowStr(std::num::ParseIntError),
+    #[error("Invalid model smoothing factor: {0}")]
+    Smoothing(f64),
+    #[error("Failed to parse model smoothing factor: {0}")]
+    SmoothingStr(std::num::ParseFloatError),
     #[error("Invalid plot index: {0}")]
     Index(u8),
     #[error("Failed to parse plot index: {0}")]
@@ -104,6 +108,14 @@ pub enum ValidError {
         "Invalid model, minimum sample size ({min}) is greater than maximum sample size ({max})"
     )]
     SampleSizes { min: SampleSize, max: SampleSize },
+    #[error(
+        "Invalid model, maximum sample size ({max}) is too small to ever reach the boundary ({boundary}), it must be at least {min}"
+    )]
+    RankSampleSize {
+        max: SampleSize,
+        boundary: Boundary,
+        min: u32,
+    },
     #[error("Invalid model, lower boundary ({lower}) is greater than upper boundary ({upper})")]
     Boundaries { lower: Boundary, upper: Boundary },
     #[error("Invalid model, no boundary provided")]
@@ -120,4 +132,10 @@ pub enum ValidError {
     CdfBoundary(f64),
     #[error("Invalid inter-quartile range boundary: {0}")]
     IqrBoundary(f64),
+    #[error("Invalid model, only forecast models have a smoothing factor: {0}")]
+    NonForecastSmoothing(Smoothing),
+    #[error("Invalid model, only forecast models have a trend smoothing factor: {0}")]
+    NonForecastTrend(Smoothing),
+    #[error("Invalid model, only forecast models have a season: {0}")]
+    NonForecastSeason(SampleSize),
 }
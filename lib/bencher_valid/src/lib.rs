@@ -42,8 +42,10 @@ pub use jwt::Jwt;
 pub use model::{
     Model,
     boundary::{Boundary, CdfBoundary, IqrBoundary, PercentageBoundary},
+    environment_change::EnvironmentChange,
     model_test::ModelTest,
     sample_size::SampleSize,
+    smoothing::Smoothing,
     window::Window,
 };
 pub use name_id::{NameId, NameIdKind};
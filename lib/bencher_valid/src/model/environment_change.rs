@@ -0,0 +1,68 @@
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+
+use serde::{Deserialize, Serialize};
+
+const ANNOTATE_INT: i32 = 0;
+const SUPPRESS_INT: i32 = 1;
+
+/// How to handle an alert when the testbed hardware has changed since its previous report.
+/// This only applies if the report `hardware_change` is `warn`.
+/// If it is `refuse`, then the report is not compared to the historical metrics and there are no alerts.
+#[typeshare::typeshare]
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, derive_more::Display, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
+#[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Integer))]
+#[serde(rename_all = "snake_case")]
+#[repr(i32)]
+pub enum EnvironmentChange {
+    /// Create an active alert that is annotated as an environment change.
+    #[default]
+    Annotate = ANNOTATE_INT,
+    /// Create a silenced alert that is annotated as an environment change.
+    Suppress = SUPPRESS_INT,
+}
+
+#[cfg(feature = "db")]
+mod db {
+    use super::{ANNOTATE_INT, EnvironmentChange, SUPPRESS_INT};
+
+    #[derive(Debug, thiserror::Error)]
+    pub enum EnvironmentChangeError {
+        #[error("Invalid environment change value: {0}")]
+        Invalid(i32),
+    }
+
+    impl<DB> diesel::serialize::ToSql<diesel::sql_types::Integer, DB> for EnvironmentChange
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::serialize::ToSql<diesel::sql_types::Integer, DB>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            match self {
+                Self::Annotate => ANNOTATE_INT.to_sql(out),
+                Self::Suppress => SUPPRESS_INT.to_sql(out),
+            }
+        }
+    }
+
+    impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Integer, DB> for EnvironmentChange
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            match i32::from_sql(bytes)? {
+                ANNOTATE_INT => Ok(Self::Annotate),
+                SUPPRESS_INT => Ok(Self::Suppress),
+                value => Err(Box::new(EnvironmentChangeError::Invalid(value))),
+            }
+        }
+    }
+}
@@ -8,13 +8,17 @@ use serde::{Deserialize, Serialize};
 use crate::ValidError;
 
 pub mod boundary;
+pub mod environment_change;
 pub mod model_test;
 pub mod sample_size;
+pub mod smoothing;
 pub mod window;
 
 use boundary::{Boundary, CdfBoundary, IqrBoundary, PercentageBoundary};
+use environment_change::EnvironmentChange;
 use model_test::ModelTest;
 use sample_size::SampleSize;
+use smoothing::Smoothing;
 use window::Window;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
@@ -37,6 +41,18 @@ pub struct Model {
     /// The upper boundary used to calculate the upper boundary limit.
     /// The requirements for this field depend on which `test` is selected.
     pub upper_boundary: Option<Boundary>,
+    /// The smoothing factor for the level and seasonality of a forecast.
+    /// Only used by the `forecast` test, which defaults to `0.3` if not set.
+    pub smoothing: Option<Smoothing>,
+    /// The smoothing factor for the trend of a forecast.
+    /// Only used by the `forecast` test. If not set, the forecast does not have a trend.
+    pub trend: Option<Smoothing>,
+    /// The number of samples in each season of a forecast.
+    /// Only used by the `forecast` test. If not set, the forecast does not have seasonality.
+    pub season: Option<SampleSize>,
+    /// How to handle alerts when the testbed hardware has changed since its previous report.
+    /// Defaults to `annotate` if not set.
+    pub environment_change: Option<EnvironmentChange>,
 }
 
 impl Model {
@@ -48,6 +64,10 @@ impl Model {
             window: None,
             lower_boundary: Some(Boundary::NINETY_NINE),
             upper_boundary: None,
+            smoothing: None,
+            trend: None,
+            season: None,
+            environment_change: None,
         }
     }
 
@@ -59,6 +79,10 @@ impl Model {
             window: None,
             lower_boundary: None,
             upper_boundary: Some(Boundary::NINETY_NINE),
+            smoothing: None,
+            trend: None,
+            season: None,
+            environment_change: None,
         }
     }
 
@@ -75,7 +99,14 @@ pub fn validate_model(model: Model) -> Result<(), ValidError> {
         window,
         lower_boundary,
         upper_boundary,
+        smoothing,
+        trend,
+        season,
+        environment_change: _,
     } = model;
+    if test != ModelTest::Forecast {
+        validate_no_forecast(smoothing, trend, season)?;
+    }
     match test {
         ModelTest::Static => {
             if let Some(&min_sample_size) = min_sample_size.as_ref() {
@@ -102,10 +133,20 @@ pub fn validate_model(model: Model) -> Result<(), ValidError> {
             validate_sample_size(min_sample_size, max_sample_size)?;
             validate_boundary::<PercentageBoundary>(lower_boundary, upper_boundary)
         },
-        ModelTest::ZScore | ModelTest::TTest | ModelTest::LogNormal => {
+        ModelTest::ZScore
+        | ModelTest::TTest
+        | ModelTest::LogNormal
+        | ModelTest::ChangePoint
+        | ModelTest::Forecast => {
             validate_sample_size(min_sample_size, max_sample_size)?;
             validate_boundary::<CdfBoundary>(lower_boundary, upper_boundary)
         },
+        ModelTest::MannWhitneyU => {
+            validate_sample_size(min_sample_size, max_sample_size)?;
+            validate_boundary::<CdfBoundary>(lower_boundary, upper_boundary)?;
+            validate_rank_sample_size(max_sample_size, lower_boundary)?;
+            validate_rank_sample_size(max_sample_size, upper_boundary)
+        },
         ModelTest::Iqr | ModelTest::DeltaIqr => {
             validate_sample_size(min_sample_size, max_sample_size)?;
             validate_boundary::<IqrBoundary>(lower_boundary, upper_boundary)
@@ -113,6 +154,22 @@ pub fn validate_model(model: Model) -> Result<(), ValidError> {
     }
 }
 
+fn validate_no_forecast(
+    smoothing: Option<Smoothing>,
+    trend: Option<Smoothing>,
+    season: Option<SampleSize>,
+) -> Result<(), ValidError> {
+    if let Some(smoothing) = smoothing {
+        Err(ValidError::NonForecastSmoothing(smoothing))
+    } else if let Some(trend) = trend {
+        Err(ValidError::NonForecastTrend(trend))
+    } else if let Some(season) = season {
+        Err(ValidError::NonForecastSeason(season))
+    } else {
+        Ok(())
+    }
+}
+
 fn validate_sample_size(
     min_sample_size: Option<SampleSize>,
     max_sample_size: Option<SampleSize>,
@@ -126,6 +183,27 @@ fn validate_sample_size(
     Ok(())
 }
 
+// A single new value can at most be ranked beyond all of the other samples.
+// So a rank based test needs enough samples to ever reach its boundary.
+// The new value itself counts towards the maximum sample size.
+fn validate_rank_sample_size(
+    max_sample_size: Option<SampleSize>,
+    boundary: Option<Boundary>,
+) -> Result<(), ValidError> {
+    let (Some(max), Some(boundary)) = (max_sample_size, boundary) else {
+        return Ok(());
+    };
+    let significance = 1.0 - f64::from(boundary);
+    let min = (1.0 / significance).ceil();
+    if f64::from(u32::from(max)) < min {
+        #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let min = min.min(f64::from(u32::MAX)) as u32;
+        return Err(ValidError::RankSampleSize { max, boundary, min });
+    }
+
+    Ok(())
+}
+
 fn validate_boundary<B>(lower: Option<Boundary>, upper: Option<Boundary>) -> Result<(), ValidError>
 where
     B: TryFrom<Boundary, Error = ValidError>,
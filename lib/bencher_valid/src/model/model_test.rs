@@ -10,6 +10,9 @@ const T_TEST_INT: i32 = 1;
 const LOG_NORMAL_INT: i32 = 10;
 const IQR_INT: i32 = 40;
 const DELTA_IQR_INT: i32 = 41;
+const CHANGE_POINT_INT: i32 = 50;
+const MANN_WHITNEY_U_INT: i32 = 60;
+const FORECAST_INT: i32 = 70;
 
 #[typeshare::typeshare]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display, Serialize, Deserialize)]
@@ -28,13 +31,18 @@ pub enum ModelTest {
     LogNormal = LOG_NORMAL_INT,
     Iqr = IQR_INT,
     DeltaIqr = DELTA_IQR_INT,
+    ChangePoint = CHANGE_POINT_INT,
+    #[serde(alias = "mwu")]
+    MannWhitneyU = MANN_WHITNEY_U_INT,
+    #[serde(alias = "holt_winters")]
+    Forecast = FORECAST_INT,
 }
 
 #[cfg(feature = "db")]
 mod db {
     use super::{
-        DELTA_IQR_INT, IQR_INT, LOG_NORMAL_INT, ModelTest, PERCENTAGE_INT, STATIC_INT, T_TEST_INT,
-        Z_SCORE_INT,
+        CHANGE_POINT_INT, DELTA_IQR_INT, FORECAST_INT, IQR_INT, LOG_NORMAL_INT, MANN_WHITNEY_U_INT,
+        ModelTest, PERCENTAGE_INT, STATIC_INT, T_TEST_INT, Z_SCORE_INT,
     };
 
     #[derive(Debug, thiserror::Error)]
@@ -60,6 +68,9 @@ mod db {
                 Self::LogNormal => LOG_NORMAL_INT.to_sql(out),
                 Self::Iqr => IQR_INT.to_sql(out),
                 Self::DeltaIqr => DELTA_IQR_INT.to_sql(out),
+                Self::ChangePoint => CHANGE_POINT_INT.to_sql(out),
+                Self::MannWhitneyU => MANN_WHITNEY_U_INT.to_sql(out),
+                Self::Forecast => FORECAST_INT.to_sql(out),
             }
         }
     }
@@ -78,6 +89,9 @@ mod db {
                 LOG_NORMAL_INT => Ok(Self::LogNormal),
                 IQR_INT => Ok(Self::Iqr),
                 DELTA_IQR_INT => Ok(Self::DeltaIqr),
+                CHANGE_POINT_INT => Ok(Self::ChangePoint),
+                MANN_WHITNEY_U_INT => Ok(Self::MannWhitneyU),
+                FORECAST_INT => Ok(Self::Forecast),
                 value => Err(Box::new(ModelTestError::Invalid(value))),
             }
         }
@@ -15,6 +15,8 @@ pub const SANITIZED_SECRET: &str = "************";
 #[typeshare::typeshare]
 #[derive(Clone, Eq, PartialEq, Hash, Serialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
+#[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Text))]
 pub struct Secret(String);
 
 impl fmt::Debug for Secret {
@@ -96,3 +98,38 @@ impl Visitor<'_> for SecretVisitor {
         v.parse().map_err(E::custom)
     }
 }
+
+#[cfg(feature = "db")]
+mod db {
+    use super::Secret;
+
+    // Unlike `typed_string!`, this does not go through `Display`,
+    // which would store the sanitized secret in release builds.
+    impl<DB> diesel::serialize::ToSql<diesel::sql_types::Text, DB> for Secret
+    where
+        DB: diesel::backend::Backend,
+        for<'a> String: diesel::serialize::ToSql<diesel::sql_types::Text, DB>
+            + Into<<DB::BindCollector<'a> as diesel::query_builder::BindCollector<'a, DB>>::Buffer>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            out.set_value(self.0.clone());
+            Ok(diesel::serialize::IsNull::No)
+        }
+    }
+
+    impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Text, DB> for Secret
+    where
+        DB: diesel::backend::Backend,
+        String: diesel::deserialize::FromSql<diesel::sql_types::Text, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            String::from_sql(bytes)?
+                .as_str()
+                .parse()
+                .map_err(Into::into)
+        }
+    }
+}
@@ -115,6 +115,11 @@ impl PubBackend {
         self.inner.client.log = log;
         self
     }
+
+    pub fn token(mut self, token: Jwt) -> Self {
+        self.inner.client.token = Some(token);
+        self
+    }
 }
 
 impl Deref for AuthBackend {
@@ -27,13 +27,15 @@ use project::{
     report::Report,
     testbed::Testbed,
     threshold::Threshold,
+    trust_policy::TrustPolicy,
+    webhook::Webhook,
 };
 pub use project::{archive::ArchiveError, report::ThresholdsError, threshold::ThresholdError};
 use run::Run;
 pub use run::{RunError, runner::output::Output};
 pub use sub_cmd::SubCmd;
 use system::{auth::Auth, server::Server};
-use user::{token::Token, user::User};
+use user::{notification::Notification, token::Token, user::User};
 
 #[derive(Debug)]
 pub enum Sub {
@@ -58,8 +60,11 @@ pub enum Sub {
     Metric(Metric),
     Threshold(Threshold),
     Alert(Alert),
+    TrustPolicy(TrustPolicy),
+    Webhook(Webhook),
     User(User),
     Token(Token),
+    Notification(Notification),
     Server(Server),
     Auth(Auth),
 }
@@ -95,8 +100,11 @@ impl TryFrom<CliSub> for Sub {
             CliSub::Metric(metric) => Self::Metric(metric.try_into()?),
             CliSub::Threshold(threshold) => Self::Threshold(threshold.try_into()?),
             CliSub::Alert(alert) => Self::Alert(alert.try_into()?),
+            CliSub::TrustPolicy(trust_policy) => Self::TrustPolicy(trust_policy.try_into()?),
+            CliSub::Webhook(webhook) => Self::Webhook(webhook.try_into()?),
             CliSub::User(user) => Self::User(user.try_into()?),
             CliSub::Token(token) => Self::Token(token.try_into()?),
+            CliSub::Notification(notification) => Self::Notification(notification.try_into()?),
             CliSub::Server(server) => Self::Server(server.try_into()?),
             CliSub::Auth(auth) => Self::Auth(auth.try_into()?),
         })
@@ -127,8 +135,11 @@ impl SubCmd for Sub {
             Self::Metric(metric) => metric.exec().await,
             Self::Threshold(threshold) => threshold.exec().await,
             Self::Alert(alert) => alert.exec().await,
+            Self::TrustPolicy(trust_policy) => trust_policy.exec().await,
+            Self::Webhook(webhook) => webhook.exec().await,
             Self::User(user) => user.exec().await,
             Self::Token(token) => token.exec().await,
+            Self::Notification(notification) => notification.exec().await,
             Self::Server(server) => server.exec().await,
             Self::Auth(auth) => auth.exec().await,
         }
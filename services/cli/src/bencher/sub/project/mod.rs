@@ -11,3 +11,5 @@ pub mod project;
 pub mod report;
 pub mod testbed;
 pub mod threshold;
+pub mod trust_policy;
+pub mod webhook;
@@ -31,6 +31,7 @@ impl From<CliReportAdapter> for Adapter {
             CliReportAdapter::RustIaiCallgrind => Self::RustIaiCallgrind,
             CliReportAdapter::Shell => Self::Shell,
             CliReportAdapter::ShellHyperfine => Self::ShellHyperfine,
+            CliReportAdapter::ShellPerfStat => Self::ShellPerfStat,
         }
     }
 }
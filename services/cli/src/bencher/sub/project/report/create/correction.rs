@@ -0,0 +1,12 @@
+use bencher_client::types::JsonCorrection;
+
+use crate::parser::project::report::CliReportCorrection;
+
+impl From<CliReportCorrection> for JsonCorrection {
+    fn from(correction: CliReportCorrection) -> Self {
+        match correction {
+            CliReportCorrection::Bonferroni => Self::Bonferroni,
+            CliReportCorrection::BenjaminiHochberg => Self::BenjaminiHochberg,
+        }
+    }
+}
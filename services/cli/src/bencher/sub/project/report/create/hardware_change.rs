@@ -0,0 +1,12 @@
+use bencher_client::types::JsonHardwareChange;
+
+use crate::parser::project::report::CliReportHardwareChange;
+
+impl From<CliReportHardwareChange> for JsonHardwareChange {
+    fn from(hardware_change: CliReportHardwareChange) -> Self {
+        match hardware_change {
+            CliReportHardwareChange::Warn => Self::Warn,
+            CliReportHardwareChange::Refuse => Self::Refuse,
+        }
+    }
+}
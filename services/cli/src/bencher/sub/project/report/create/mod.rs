@@ -1,6 +1,6 @@
 use bencher_client::types::{
-    Adapter, DateTime, GitHash, JsonAverage, JsonFold, JsonNewReport, JsonReportSettings,
-    JsonUpdateStartPoint, NameId,
+    Adapter, DateTime, GitHash, JsonAverage, JsonCorrection, JsonFold, JsonHardwareChange,
+    JsonNewReport, JsonReportSettings, JsonUpdateStartPoint, NameId,
 };
 use bencher_json::ResourceId;
 
@@ -15,7 +15,9 @@ use crate::{
 
 mod adapter;
 mod average;
+mod correction;
 mod fold;
+mod hardware_change;
 mod thresholds;
 
 pub use thresholds::{Thresholds, ThresholdsError};
@@ -34,6 +36,9 @@ pub struct Create {
     pub adapter: Option<Adapter>,
     pub average: Option<JsonAverage>,
     pub fold: Option<JsonFold>,
+    pub sample: bool,
+    pub correction: Option<JsonCorrection>,
+    pub hardware_change: Option<JsonHardwareChange>,
     pub backend: AuthBackend,
 }
 
@@ -54,6 +59,9 @@ impl TryFrom<CliReportCreate> for Create {
             adapter,
             average,
             fold,
+            sample,
+            correction,
+            hardware_change,
             backend,
         } = create;
         Ok(Self {
@@ -69,6 +77,9 @@ impl TryFrom<CliReportCreate> for Create {
             adapter: adapter.map(Into::into),
             average: average.map(Into::into),
             fold: fold.map(Into::into),
+            sample,
+            correction: correction.map(Into::into),
+            hardware_change: hardware_change.map(Into::into),
             backend: backend.try_into()?,
         })
     }
@@ -87,6 +98,9 @@ impl From<Create> for JsonNewReport {
             adapter,
             average,
             fold,
+            sample,
+            correction,
+            hardware_change,
             ..
         } = create;
         Self {
@@ -102,7 +116,11 @@ impl From<Create> for JsonNewReport {
                 adapter,
                 average,
                 fold,
+                sample: sample.then_some(true),
+                correction,
+                hardware_change,
             }),
+            hardware: None,
         }
     }
 }
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use bencher_client::types::JsonReportThresholds;
-use bencher_json::{Boundary, NameId, SampleSize, Window};
+use bencher_json::{Boundary, NameId, SampleSize, Smoothing, Window};
 
 use crate::{
     ThresholdError,
@@ -10,7 +10,7 @@ use crate::{
         ElidedOption,
         project::{
             report::CliReportThresholds,
-            threshold::{CliModel, CliModelTest},
+            threshold::{CliEnvironmentChange, CliModel, CliModelTest},
         },
     },
 };
@@ -44,11 +44,20 @@ pub enum ThresholdsError {
     ExtraLowerBoundaries(Vec<ElidedOption<Boundary>>),
     #[error("There are more upper boundaries than model tests")]
     ExtraUpperBoundaries(Vec<ElidedOption<Boundary>>),
+    #[error("There are more smoothing factors than model tests")]
+    ExtraSmoothings(Vec<ElidedOption<Smoothing>>),
+    #[error("There are more trend smoothing factors than model tests")]
+    ExtraTrends(Vec<ElidedOption<Smoothing>>),
+    #[error("There are more seasons than model tests")]
+    ExtraSeasons(Vec<ElidedOption<SampleSize>>),
+    #[error("There are more environment changes than model tests")]
+    ExtraEnvironmentChanges(Vec<ElidedOption<CliEnvironmentChange>>),
 }
 
 impl TryFrom<CliReportThresholds> for Thresholds {
     type Error = ThresholdsError;
 
+    #[expect(clippy::too_many_lines)]
     fn try_from(thresholds: CliReportThresholds) -> Result<Self, Self::Error> {
         let CliReportThresholds {
             threshold_measure,
@@ -58,6 +67,10 @@ impl TryFrom<CliReportThresholds> for Thresholds {
             threshold_window,
             threshold_lower_boundary,
             threshold_upper_boundary,
+            threshold_smoothing,
+            threshold_trend,
+            threshold_season,
+            threshold_environment_change,
             thresholds_reset,
         } = thresholds;
 
@@ -69,6 +82,10 @@ impl TryFrom<CliReportThresholds> for Thresholds {
         let mut windows = threshold_window.into_iter();
         let mut lower_boundaries = threshold_lower_boundary.into_iter();
         let mut upper_boundaries = threshold_upper_boundary.into_iter();
+        let mut smoothings = threshold_smoothing.into_iter();
+        let mut trends = threshold_trend.into_iter();
+        let mut seasons = threshold_season.into_iter();
+        let mut environment_changes = threshold_environment_change.into_iter();
         for measure in threshold_measure {
             let test = tests
                 .next()
@@ -78,6 +95,10 @@ impl TryFrom<CliReportThresholds> for Thresholds {
             let window = windows.next();
             let lower_boundary = lower_boundaries.next();
             let upper_boundary = upper_boundaries.next();
+            let smoothing = smoothings.next();
+            let trend = trends.next();
+            let season = seasons.next();
+            let environment_change = environment_changes.next();
 
             let cli_model = CliModel {
                 test,
@@ -86,6 +107,10 @@ impl TryFrom<CliReportThresholds> for Thresholds {
                 window: window.and_then(Into::into),
                 lower_boundary: lower_boundary.and_then(Into::into),
                 upper_boundary: upper_boundary.and_then(Into::into),
+                smoothing: smoothing.and_then(Into::into),
+                trend: trend.and_then(Into::into),
+                season: season.and_then(Into::into),
+                environment_change: environment_change.and_then(Into::into),
             };
             let model = Model::try_from(cli_model).map_err(|err| ThresholdsError::BadModel {
                 measure: measure.clone(),
@@ -127,6 +152,24 @@ impl TryFrom<CliReportThresholds> for Thresholds {
                 remaining_upper_boundaries,
             ));
         }
+        let remaining_smoothings = smoothings.collect::<Vec<_>>();
+        if !remaining_smoothings.is_empty() {
+            return Err(ThresholdsError::ExtraSmoothings(remaining_smoothings));
+        }
+        let remaining_trends = trends.collect::<Vec<_>>();
+        if !remaining_trends.is_empty() {
+            return Err(ThresholdsError::ExtraTrends(remaining_trends));
+        }
+        let remaining_seasons = seasons.collect::<Vec<_>>();
+        if !remaining_seasons.is_empty() {
+            return Err(ThresholdsError::ExtraSeasons(remaining_seasons));
+        }
+        let remaining_environment_changes = environment_changes.collect::<Vec<_>>();
+        if !remaining_environment_changes.is_empty() {
+            return Err(ThresholdsError::ExtraEnvironmentChanges(
+                remaining_environment_changes,
+            ));
+        }
 
         Ok(Self {
             // Do not short circuit early if there are no measures
@@ -67,6 +67,10 @@ impl From<Create> for JsonNewThreshold {
             window,
             lower_boundary,
             upper_boundary,
+            smoothing,
+            trend,
+            season,
+            environment_change,
         } = model;
         Self {
             branch: branch.into(),
@@ -78,6 +82,10 @@ impl From<Create> for JsonNewThreshold {
             window,
             lower_boundary,
             upper_boundary,
+            smoothing,
+            trend,
+            season,
+            environment_change,
         }
     }
 }
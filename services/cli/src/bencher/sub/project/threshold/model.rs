@@ -1,6 +1,8 @@
-use bencher_client::types::{Boundary, ModelTest, SampleSize, Window};
+use bencher_client::types::{
+    Boundary, EnvironmentChange, ModelTest, SampleSize, Smoothing, Window,
+};
 
-use crate::parser::project::threshold::{CliModel, CliModelTest};
+use crate::parser::project::threshold::{CliEnvironmentChange, CliModel, CliModelTest};
 
 use super::ThresholdError;
 
@@ -12,6 +14,10 @@ pub struct Model {
     pub window: Option<Window>,
     pub lower_boundary: Option<Boundary>,
     pub upper_boundary: Option<Boundary>,
+    pub smoothing: Option<Smoothing>,
+    pub trend: Option<Smoothing>,
+    pub season: Option<SampleSize>,
+    pub environment_change: Option<EnvironmentChange>,
 }
 
 impl TryFrom<CliModel> for Model {
@@ -25,6 +31,10 @@ impl TryFrom<CliModel> for Model {
             window,
             lower_boundary,
             upper_boundary,
+            smoothing,
+            trend,
+            season,
+            environment_change,
         } = model;
         bencher_json::Model {
             test: test.into(),
@@ -33,6 +43,10 @@ impl TryFrom<CliModel> for Model {
             window,
             lower_boundary,
             upper_boundary,
+            smoothing,
+            trend,
+            season,
+            environment_change: environment_change.map(Into::into),
         }
         .validate()
         .map_err(ThresholdError::BadModel)?;
@@ -43,6 +57,10 @@ impl TryFrom<CliModel> for Model {
             window: window.map(Into::into),
             lower_boundary: lower_boundary.map(Into::into),
             upper_boundary: upper_boundary.map(Into::into),
+            smoothing: smoothing.map(Into::into),
+            trend: trend.map(Into::into),
+            season: season.map(Into::into),
+            environment_change: environment_change.map(Into::into),
         })
     }
 }
@@ -57,6 +75,9 @@ impl From<CliModelTest> for bencher_json::ModelTest {
             CliModelTest::LogNormal => Self::LogNormal,
             CliModelTest::Iqr => Self::Iqr,
             CliModelTest::DeltaIqr => Self::DeltaIqr,
+            CliModelTest::ChangePoint => Self::ChangePoint,
+            CliModelTest::MannWhitneyU => Self::MannWhitneyU,
+            CliModelTest::Forecast => Self::Forecast,
         }
     }
 }
@@ -71,6 +92,27 @@ impl From<CliModelTest> for ModelTest {
             CliModelTest::LogNormal => Self::LogNormal,
             CliModelTest::Iqr => Self::Iqr,
             CliModelTest::DeltaIqr => Self::DeltaIqr,
+            CliModelTest::ChangePoint => Self::ChangePoint,
+            CliModelTest::MannWhitneyU => Self::MannWhitneyU,
+            CliModelTest::Forecast => Self::Forecast,
+        }
+    }
+}
+
+impl From<CliEnvironmentChange> for bencher_json::EnvironmentChange {
+    fn from(environment_change: CliEnvironmentChange) -> Self {
+        match environment_change {
+            CliEnvironmentChange::Annotate => Self::Annotate,
+            CliEnvironmentChange::Suppress => Self::Suppress,
+        }
+    }
+}
+
+impl From<CliEnvironmentChange> for EnvironmentChange {
+    fn from(environment_change: CliEnvironmentChange) -> Self {
+        match environment_change {
+            CliEnvironmentChange::Annotate => Self::Annotate,
+            CliEnvironmentChange::Suppress => Self::Suppress,
         }
     }
 }
@@ -84,6 +126,10 @@ impl From<Model> for bencher_client::types::Model {
             window,
             lower_boundary,
             upper_boundary,
+            smoothing,
+            trend,
+            season,
+            environment_change,
         } = model;
         bencher_client::types::Model {
             test,
@@ -92,6 +138,10 @@ impl From<Model> for bencher_client::types::Model {
             window,
             lower_boundary,
             upper_boundary,
+            smoothing,
+            trend,
+            season,
+            environment_change,
         }
     }
 }
@@ -31,6 +31,10 @@ impl TryFrom<CliThresholdUpdate> for Update {
                     window,
                     lower_boundary,
                     upper_boundary,
+                    smoothing,
+                    trend,
+                    season,
+                    environment_change,
                     remove_model,
                 },
             backend,
@@ -43,6 +47,10 @@ impl TryFrom<CliThresholdUpdate> for Update {
                 window,
                 lower_boundary,
                 upper_boundary,
+                smoothing,
+                trend,
+                season,
+                environment_change,
             };
             Some(cli_model.try_into()?)
         } else if remove_model {
@@ -71,6 +79,10 @@ impl From<Update> for JsonUpdateThreshold {
                 window,
                 lower_boundary,
                 upper_boundary,
+                smoothing,
+                trend,
+                season,
+                environment_change,
             } = model;
             Self {
                 subtype_0: Some(JsonUpdateModel {
@@ -80,6 +92,10 @@ impl From<Update> for JsonUpdateThreshold {
                     window,
                     lower_boundary,
                     upper_boundary,
+                    smoothing,
+                    trend,
+                    season,
+                    environment_change,
                 }),
                 subtype_1: None,
             }
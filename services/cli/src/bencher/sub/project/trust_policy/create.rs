@@ -0,0 +1,84 @@
+use bencher_client::types::{JsonNewTrustPolicy, TrustProvider};
+use bencher_json::{NonEmpty, ResourceId, Url};
+
+use crate::{
+    CliError,
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::trust_policy::{CliTrustPolicyCreate, CliTrustProvider},
+};
+
+#[derive(Debug, Clone)]
+pub struct Create {
+    pub project: ResourceId,
+    pub provider: TrustProvider,
+    pub issuer: Option<Url>,
+    pub repository: NonEmpty,
+    pub git_ref: Option<NonEmpty>,
+    pub workflow: Option<NonEmpty>,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliTrustPolicyCreate> for Create {
+    type Error = CliError;
+
+    fn try_from(create: CliTrustPolicyCreate) -> Result<Self, Self::Error> {
+        let CliTrustPolicyCreate {
+            project,
+            provider,
+            issuer,
+            repository,
+            git_ref,
+            workflow,
+            backend,
+        } = create;
+        Ok(Self {
+            project,
+            provider: match provider {
+                CliTrustProvider::GithubActions => TrustProvider::GithubActions,
+                CliTrustProvider::GitlabCi => TrustProvider::GitlabCi,
+            },
+            issuer,
+            repository,
+            git_ref,
+            workflow,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Create> for JsonNewTrustPolicy {
+    fn from(create: Create) -> Self {
+        let Create {
+            provider,
+            issuer,
+            repository,
+            git_ref,
+            workflow,
+            ..
+        } = create;
+        Self {
+            provider,
+            issuer: issuer.map(Into::into),
+            repository: repository.into(),
+            git_ref: git_ref.map(Into::into),
+            workflow: workflow.map(Into::into),
+        }
+    }
+}
+
+impl SubCmd for Create {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_trust_policy_post()
+                    .project(self.project.clone())
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
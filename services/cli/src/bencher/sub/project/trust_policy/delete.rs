@@ -0,0 +1,48 @@
+use bencher_json::{ResourceId, TrustPolicyUuid};
+
+use crate::{
+    CliError,
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::trust_policy::CliTrustPolicyDelete,
+};
+
+#[derive(Debug)]
+pub struct Delete {
+    pub project: ResourceId,
+    pub trust_policy: TrustPolicyUuid,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliTrustPolicyDelete> for Delete {
+    type Error = CliError;
+
+    fn try_from(delete: CliTrustPolicyDelete) -> Result<Self, Self::Error> {
+        let CliTrustPolicyDelete {
+            project,
+            trust_policy,
+            backend,
+        } = delete;
+        Ok(Self {
+            project,
+            trust_policy,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for Delete {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_trust_policy_delete()
+                    .project(self.project.clone())
+                    .trust_policy(self.trust_policy)
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
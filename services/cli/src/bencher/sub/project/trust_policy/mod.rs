@@ -0,0 +1,38 @@
+use crate::{CliError, bencher::sub::SubCmd, parser::project::trust_policy::CliTrustPolicy};
+
+mod create;
+mod delete;
+mod list;
+mod view;
+
+#[derive(Debug)]
+pub enum TrustPolicy {
+    List(list::List),
+    Create(create::Create),
+    View(view::View),
+    Delete(delete::Delete),
+}
+
+impl TryFrom<CliTrustPolicy> for TrustPolicy {
+    type Error = CliError;
+
+    fn try_from(trust_policy: CliTrustPolicy) -> Result<Self, Self::Error> {
+        Ok(match trust_policy {
+            CliTrustPolicy::List(list) => Self::List(list.try_into()?),
+            CliTrustPolicy::Create(create) => Self::Create(create.try_into()?),
+            CliTrustPolicy::View(view) => Self::View(view.try_into()?),
+            CliTrustPolicy::Delete(delete) => Self::Delete(delete.try_into()?),
+        })
+    }
+}
+
+impl SubCmd for TrustPolicy {
+    async fn exec(&self) -> Result<(), CliError> {
+        match self {
+            Self::List(list) => list.exec().await,
+            Self::Create(create) => create.exec().await,
+            Self::View(view) => view.exec().await,
+            Self::Delete(delete) => delete.exec().await,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use bencher_json::{ResourceId, TrustPolicyUuid};
+
+use crate::{
+    CliError,
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::trust_policy::CliTrustPolicyView,
+};
+
+#[derive(Debug)]
+pub struct View {
+    pub project: ResourceId,
+    pub trust_policy: TrustPolicyUuid,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliTrustPolicyView> for View {
+    type Error = CliError;
+
+    fn try_from(view: CliTrustPolicyView) -> Result<Self, Self::Error> {
+        let CliTrustPolicyView {
+            project,
+            trust_policy,
+            backend,
+        } = view;
+        Ok(Self {
+            project,
+            trust_policy,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for View {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_trust_policy_get()
+                    .project(self.project.clone())
+                    .trust_policy(self.trust_policy)
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use bencher_client::types::{JsonNewWebhook, WebhookEvent, WebhookFormat};
+use bencher_json::{ResourceId, Secret, Url};
+
+use crate::{
+    CliError,
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::webhook::{CliWebhookCreate, CliWebhookEvent, CliWebhookFormat},
+};
+
+#[derive(Debug, Clone)]
+pub struct Create {
+    pub project: ResourceId,
+    pub url: Url,
+    pub format: Option<WebhookFormat>,
+    pub secret: Option<Secret>,
+    pub events: Vec<WebhookEvent>,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliWebhookCreate> for Create {
+    type Error = CliError;
+
+    fn try_from(create: CliWebhookCreate) -> Result<Self, Self::Error> {
+        let CliWebhookCreate {
+            project,
+            url,
+            format,
+            secret,
+            event,
+            backend,
+        } = create;
+        Ok(Self {
+            project,
+            url,
+            format: format.map(|format| match format {
+                CliWebhookFormat::Json => WebhookFormat::Json,
+                CliWebhookFormat::Slack => WebhookFormat::Slack,
+                CliWebhookFormat::Teams => WebhookFormat::Teams,
+                CliWebhookFormat::Matrix => WebhookFormat::Matrix,
+            }),
+            secret,
+            events: event
+                .into_iter()
+                .map(|event| match event {
+                    CliWebhookEvent::AlertCreated => WebhookEvent::AlertCreated,
+                    CliWebhookEvent::AlertDismissed => WebhookEvent::AlertDismissed,
+                    CliWebhookEvent::ReportCreated => WebhookEvent::ReportCreated,
+                    CliWebhookEvent::ThresholdUpdated => WebhookEvent::ThresholdUpdated,
+                })
+                .collect(),
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Create> for JsonNewWebhook {
+    fn from(create: Create) -> Self {
+        let Create {
+            url,
+            format,
+            secret,
+            events,
+            ..
+        } = create;
+        Self {
+            url: url.into(),
+            format,
+            secret: secret.map(Into::into),
+            events: events.into(),
+        }
+    }
+}
+
+impl SubCmd for Create {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_webhook_post()
+                    .project(self.project.clone())
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,435 @@
+use bencher_comment::ReportComment;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::cli_println_quietable;
+
+const GITLAB_CI: &str = "GITLAB_CI";
+const CI_MERGE_REQUEST_IID: &str = "CI_MERGE_REQUEST_IID";
+const CI_PROJECT_ID: &str = "CI_PROJECT_ID";
+const CI_API_V4_URL: &str = "CI_API_V4_URL";
+
+const PRIVATE_TOKEN: &str = "PRIVATE-TOKEN";
+
+// https://docs.gitlab.com/administration/instance_limits/#size-of-comments-and-descriptions-of-issues-merge-requests-and-epics
+const NOTE_MAX_LENGTH: usize = 1_000_000;
+
+#[derive(Debug)]
+pub struct GitLabCi {
+    pub token: String,
+    pub ci_only_thresholds: bool,
+    pub ci_only_on_alert: bool,
+    pub ci_id: Option<String>,
+    pub ci_number: Option<u64>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum GitLabError {
+    #[error("GitLab CI merge request IID is invalid ({0}): {1}")]
+    BadMergeRequestIid(String, std::num::ParseIntError),
+    #[error("Failed to get GitLab CI project ID\n{}", docker_env(CI_PROJECT_ID))]
+    NoProjectId,
+    #[error("Failed to get GitLab CI API URL\n{}", docker_env(CI_API_V4_URL))]
+    NoApiUrl,
+    #[error("Failed to create GitLab API client: {0}")]
+    Client(reqwest::Error),
+    #[error("Failed to list GitLab MR notes: {0}")]
+    Notes(reqwest::Error),
+    #[error("Failed to create GitLab MR note: {0}")]
+    CreateNote(reqwest::Error),
+    #[error("Failed to update GitLab MR note: {0}")]
+    UpdateNote(reqwest::Error),
+    #[error("{}", permissions_help(_0))]
+    BadNotePermissions(reqwest::Error),
+}
+
+// https://docs.gitlab.com/user/project/settings/project_access_tokens/#scopes-for-a-project-access-token
+fn permissions_help(err: &reqwest::Error) -> String {
+    format!(
+        "GitLab access token (`--gitlab-ci`) does not have permission to comment on merge requests.\nTo fix, use a project access token with the `api` scope and at least the `Reporter` role.\nSee: https://bencher.dev/docs/how-to/gitlab-ci-cd/\nError: {err}",
+    )
+}
+
+fn docker_env(env_var: &str) -> String {
+    format!(
+        "If you are running in a Docker container, then you need to pass in the `{env_var}` environment variable. See https://bencher.dev/docs/explanation/bencher-run/#--gitlab-ci",
+    )
+}
+
+impl GitLabCi {
+    pub async fn run(&self, report_comment: &ReportComment, log: bool) -> Result<(), GitLabError> {
+        if !is_gitlab_ci() {
+            cli_println_quietable!(
+                log,
+                "Not running in GitLab CI. Skipping CI integration.\n{}",
+                docker_env(GITLAB_CI)
+            );
+            return Ok(());
+        }
+
+        // Only post to CI if there are thresholds set
+        if self.ci_only_thresholds && !report_comment.has_threshold() {
+            cli_println_quietable!(log, "No thresholds set. Skipping CI integration.");
+            return Ok(());
+        }
+
+        let merge_request_iid = if let Some(merge_request_iid) = self.ci_number {
+            merge_request_iid
+        } else if let Ok(merge_request_iid) = std::env::var(CI_MERGE_REQUEST_IID) {
+            // The project-level internal ID of the merge request.
+            // Only available in merge request pipelines.
+            merge_request_iid
+                .parse()
+                .map_err(|e| GitLabError::BadMergeRequestIid(merge_request_iid, e))?
+        } else {
+            cli_println_quietable!(
+                log,
+                "Not running in a GitLab CI merge request pipeline and the `--ci-number` option was not set. Skipping CI integration.\n{}",
+                docker_env(CI_MERGE_REQUEST_IID)
+            );
+            return Ok(());
+        };
+
+        // The ID of the current project. This ID is unique across all projects on the GitLab instance.
+        let Ok(project_id) = std::env::var(CI_PROJECT_ID) else {
+            return Err(GitLabError::NoProjectId);
+        };
+        // The GitLab API v4 root URL.
+        let Ok(api_url) = std::env::var(CI_API_V4_URL) else {
+            return Err(GitLabError::NoApiUrl);
+        };
+        let gitlab_client = GitLabClient::new(&api_url, &project_id, self.token.clone())?;
+
+        self.create_merge_request_note(report_comment, log, &gitlab_client, merge_request_iid)
+            .await
+    }
+
+    async fn create_merge_request_note(
+        &self,
+        report_comment: &ReportComment,
+        log: bool,
+        gitlab_client: &GitLabClient,
+        merge_request_iid: u64,
+    ) -> Result<(), GitLabError> {
+        // Get the note ID if it exists
+        let note_id = gitlab_client
+            .get_note(
+                merge_request_iid,
+                &report_comment.bencher_tag(self.ci_id.as_deref()),
+            )
+            .await?;
+
+        // Update or create the note
+        let body = report_comment.markdown_with_max_length(
+            self.ci_only_thresholds,
+            self.ci_id.as_deref(),
+            NOTE_MAX_LENGTH,
+        );
+        // Always update the note if it exists
+        if let Some(note_id) = note_id {
+            gitlab_client
+                .update_note(merge_request_iid, note_id, body)
+                .await
+        } else {
+            if self.ci_only_on_alert && !report_comment.has_alert() {
+                cli_println_quietable!(log, "No alerts found. Skipping CI integration.");
+                return Ok(());
+            }
+            gitlab_client.create_note(merge_request_iid, body).await
+        }
+    }
+}
+
+// https://docs.gitlab.com/ci/variables/predefined_variables/
+// Available for all jobs executed in CI/CD. `true` when available.
+fn is_gitlab_ci() -> bool {
+    std::env::var(GITLAB_CI).as_deref() == Ok("true")
+}
+
+// https://docs.gitlab.com/api/notes/#merge-requests
+struct GitLabClient {
+    client: reqwest::Client,
+    project_url: String,
+    token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabNote {
+    id: u64,
+    body: String,
+}
+
+#[derive(Debug, Serialize)]
+struct GitLabNoteBody {
+    body: String,
+}
+
+impl GitLabClient {
+    fn new(api_url: &str, project_id: &str, token: String) -> Result<Self, GitLabError> {
+        let client = reqwest::Client::builder()
+            .build()
+            .map_err(GitLabError::Client)?;
+        let api_url = api_url.trim_end_matches('/');
+        Ok(Self {
+            client,
+            project_url: format!("{api_url}/projects/{project_id}"),
+            token,
+        })
+    }
+
+    fn notes_url(&self, merge_request_iid: u64) -> String {
+        format!(
+            "{project_url}/merge_requests/{merge_request_iid}/notes",
+            project_url = self.project_url
+        )
+    }
+
+    async fn get_note(
+        &self,
+        merge_request_iid: u64,
+        bencher_tag: &str,
+    ) -> Result<Option<u64>, GitLabError> {
+        const PER_PAGE: u8 = 100;
+
+        let mut page: u32 = 1;
+        loop {
+            let notes = self
+                .client
+                .get(self.notes_url(merge_request_iid))
+                .header(PRIVATE_TOKEN, &self.token)
+                .query(&[("per_page", u32::from(PER_PAGE)), ("page", page)])
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .map_err(|e| note_error(e, GitLabError::Notes))?
+                .json::<Vec<GitLabNote>>()
+                .await
+                .map_err(GitLabError::Notes)?;
+
+            let notes_len = notes.len();
+            if notes_len == 0 {
+                return Ok(None);
+            }
+
+            for note in notes {
+                if note.body.ends_with(bencher_tag) {
+                    return Ok(Some(note.id));
+                }
+            }
+
+            if notes_len < usize::from(PER_PAGE) {
+                return Ok(None);
+            }
+
+            page += 1;
+        }
+    }
+
+    async fn create_note(&self, merge_request_iid: u64, body: String) -> Result<(), GitLabError> {
+        self.client
+            .post(self.notes_url(merge_request_iid))
+            .header(PRIVATE_TOKEN, &self.token)
+            .json(&GitLabNoteBody { body })
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map(drop)
+            .map_err(|e| note_error(e, GitLabError::CreateNote))
+    }
+
+    async fn update_note(
+        &self,
+        merge_request_iid: u64,
+        note_id: u64,
+        body: String,
+    ) -> Result<(), GitLabError> {
+        self.client
+            .put(format!(
+                "{notes_url}/{note_id}",
+                notes_url = self.notes_url(merge_request_iid)
+            ))
+            .header(PRIVATE_TOKEN, &self.token)
+            .json(&GitLabNoteBody { body })
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map(drop)
+            .map_err(|e| note_error(e, GitLabError::UpdateNote))
+    }
+}
+
+fn note_error(err: reqwest::Error, into_error: fn(reqwest::Error) -> GitLabError) -> GitLabError {
+    if matches!(
+        err.status(),
+        Some(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)
+    ) {
+        GitLabError::BadNotePermissions(err)
+    } else {
+        into_error(err)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::{BufRead as _, BufReader, Read as _, Write as _},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    use super::{GitLabClient, GitLabError};
+
+    const PROJECT_ID: &str = "42";
+    const TOKEN: &str = "glpat-bencher";
+    const BENCHER_TAG: &str =
+        r#"<div id="bencher.dev/projects/the-computer/main/localhost/json"></div>"#;
+
+    #[derive(Debug)]
+    struct MockRequest {
+        method: String,
+        path: String,
+        token: Option<String>,
+        body: String,
+    }
+
+    // A mock GitLab API server that responds to each request in order
+    fn mock_server(responses: Vec<(u16, String)>) -> (GitLabClient, JoinHandle<Vec<MockRequest>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let api_url = format!("http://{}/api/v4/", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, response_body)| {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+                    let mut request_line = String::new();
+                    reader.read_line(&mut request_line).unwrap();
+                    let mut token = None;
+                    let mut content_length = 0;
+                    loop {
+                        let mut header = String::new();
+                        reader.read_line(&mut header).unwrap();
+                        let Some((name, value)) = header.split_once(':') else {
+                            break;
+                        };
+                        let value = value.trim();
+                        if name.eq_ignore_ascii_case("private-token") {
+                            token = Some(value.to_owned());
+                        } else if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.parse().unwrap();
+                        }
+                    }
+                    let mut body = vec![0; content_length];
+                    reader.read_exact(&mut body).unwrap();
+
+                    let response = format!(
+                        "HTTP/1.1 {status} Mock\r\nContent-Type: application/json\r\nContent-Length: {len}\r\nConnection: close\r\n\r\n{response_body}",
+                        len = response_body.len()
+                    );
+                    reader.get_mut().write_all(response.as_bytes()).unwrap();
+
+                    let mut request_line = request_line.split_whitespace();
+                    MockRequest {
+                        method: request_line.next().unwrap().to_owned(),
+                        path: request_line.next().unwrap().to_owned(),
+                        token,
+                        body: String::from_utf8(body).unwrap(),
+                    }
+                })
+                .collect()
+        });
+        let gitlab_client = GitLabClient::new(&api_url, PROJECT_ID, TOKEN.to_owned()).unwrap();
+        (gitlab_client, handle)
+    }
+
+    fn notes(len: u64, bencher_note: Option<u64>) -> String {
+        let notes = (1..=len)
+            .map(|id| {
+                let body = if Some(id) == bencher_note {
+                    format!("Bencher Report{BENCHER_TAG}")
+                } else {
+                    "LGTM".to_owned()
+                };
+                serde_json::json!({ "id": id, "body": body, "system": false })
+            })
+            .collect::<Vec<_>>();
+        serde_json::to_string(&notes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_gitlab_get_note() {
+        let (gitlab_client, handle) = mock_server(vec![(200, notes(3, Some(2)))]);
+        let note_id = gitlab_client.get_note(7, BENCHER_TAG).await.unwrap();
+        assert_eq!(note_id, Some(2));
+
+        let requests = handle.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        let request = requests.first().unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(
+            request.path,
+            "/api/v4/projects/42/merge_requests/7/notes?per_page=100&page=1"
+        );
+        assert_eq!(request.token.as_deref(), Some(TOKEN));
+    }
+
+    #[tokio::test]
+    async fn test_gitlab_get_note_pages() {
+        let (gitlab_client, handle) =
+            mock_server(vec![(200, notes(100, None)), (200, notes(1, None))]);
+        let note_id = gitlab_client.get_note(7, BENCHER_TAG).await.unwrap();
+        assert_eq!(note_id, None);
+
+        let requests = handle.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests.last().unwrap().path,
+            "/api/v4/projects/42/merge_requests/7/notes?per_page=100&page=2"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gitlab_create_note() {
+        let (gitlab_client, handle) = mock_server(vec![(201, "{}".to_owned())]);
+        gitlab_client
+            .create_note(7, format!("Bencher Report{BENCHER_TAG}"))
+            .await
+            .unwrap();
+
+        let requests = handle.join().unwrap();
+        let request = requests.first().unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/api/v4/projects/42/merge_requests/7/notes");
+        assert_eq!(request.token.as_deref(), Some(TOKEN));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&request.body).unwrap(),
+            serde_json::json!({ "body": format!("Bencher Report{BENCHER_TAG}") })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gitlab_update_note() {
+        let (gitlab_client, handle) = mock_server(vec![(200, "{}".to_owned())]);
+        gitlab_client
+            .update_note(7, 2, "Bencher Report".to_owned())
+            .await
+            .unwrap();
+
+        let requests = handle.join().unwrap();
+        let request = requests.first().unwrap();
+        assert_eq!(request.method, "PUT");
+        assert_eq!(request.path, "/api/v4/projects/42/merge_requests/7/notes/2");
+    }
+
+    #[tokio::test]
+    async fn test_gitlab_note_permissions() {
+        let (gitlab_client, handle) =
+            mock_server(vec![(403, r#"{"message":"403 Forbidden"}"#.to_owned())]);
+        let err = gitlab_client
+            .create_note(7, "Bencher Report".to_owned())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, GitLabError::BadNotePermissions(_)), "{err}");
+        handle.join().unwrap();
+    }
+}
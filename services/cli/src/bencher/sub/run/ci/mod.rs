@@ -3,18 +3,23 @@ use bencher_comment::ReportComment;
 use crate::parser::run::CliRunCi;
 
 mod github_actions;
+mod gitlab_ci;
 
 use github_actions::{GitHubActions, GitHubError};
+use gitlab_ci::{GitLabCi, GitLabError};
 
 #[derive(Debug)]
 pub enum Ci {
     GitHubActions(GitHubActions),
+    GitLabCi(GitLabCi),
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum CiError {
     #[error("{0}")]
     GitHub(#[from] GitHubError),
+    #[error("{0}")]
+    GitLab(#[from] GitLabError),
 }
 
 impl TryFrom<CliRunCi> for Option<Ci> {
@@ -23,6 +28,7 @@ impl TryFrom<CliRunCi> for Option<Ci> {
     fn try_from(ci: CliRunCi) -> Result<Self, Self::Error> {
         let CliRunCi {
             github_actions,
+            gitlab_ci,
             ci_only_thresholds,
             ci_only_on_alert,
             ci_public_links,
@@ -31,8 +37,8 @@ impl TryFrom<CliRunCi> for Option<Ci> {
             ci_i_am_vulnerable_to_pwn_requests,
             ci_deprecated: _,
         } = ci;
-        Ok(github_actions.map(|token| {
-            Ci::GitHubActions(GitHubActions {
+        Ok(if let Some(token) = github_actions {
+            Some(Ci::GitHubActions(GitHubActions {
                 token,
                 ci_only_thresholds,
                 ci_only_on_alert,
@@ -40,8 +46,18 @@ impl TryFrom<CliRunCi> for Option<Ci> {
                 ci_id,
                 ci_number,
                 ci_i_am_vulnerable_to_pwn_requests,
+            }))
+        } else {
+            gitlab_ci.map(|token| {
+                Ci::GitLabCi(GitLabCi {
+                    token,
+                    ci_only_thresholds,
+                    ci_only_on_alert,
+                    ci_id,
+                    ci_number,
+                })
             })
-        }))
+        })
     }
 }
 
@@ -51,6 +67,7 @@ impl Ci {
             Self::GitHubActions(github_actions) => {
                 github_actions.safety_check(log).map_err(Into::into)
             },
+            Self::GitLabCi(_) => Ok(()),
         }
     }
 
@@ -60,12 +77,16 @@ impl Ci {
                 .run(report_comment, log)
                 .await
                 .map_err(Into::into),
+            Self::GitLabCi(gitlab_ci) => {
+                gitlab_ci.run(report_comment, log).await.map_err(Into::into)
+            },
         }
     }
 
     pub fn source(&self) -> String {
         match self {
             Self::GitHubActions(_) => "github".to_owned(),
+            Self::GitLabCi(_) => "gitlab".to_owned(),
         }
     }
 }
@@ -11,6 +11,8 @@ pub enum RunError {
     Branch(#[from] super::branch::BranchError),
     #[error("{0}")]
     Thresholds(#[from] crate::bencher::sub::ThresholdsError),
+    #[error("{0}")]
+    Oidc(#[from] super::oidc::OidcError),
 
     #[error(
         "No default shell command path for target family. Try setting a custom shell with the `--shell` argument."
@@ -60,10 +62,39 @@ pub enum RunError {
     CommandName(bencher_json::ValidError),
     #[error("Failed to serialize build time results: {0}")]
     SerializeBuildTime(serde_json::Error),
+    #[error("Failed to join benchmark harness for command `{command}`: {err}")]
+    HarnessJoinError {
+        command: Command,
+        err: tokio::task::JoinError,
+    },
+    #[error("Failed to serialize benchmark harness results: {0}")]
+    SerializeHarness(serde_json::Error),
+    #[error("Failed to join resource usage for command `{command}`: {err}")]
+    RusageJoinError {
+        command: Command,
+        err: tokio::task::JoinError,
+    },
+    #[error("Failed to convert benchmark results to add resource usage: {0}")]
+    RusageAdapter(serde_json::Error),
+    #[error("Failed to parse benchmark results to add resource usage:\n{0}")]
+    RusageConvert(String),
+    #[error("Failed to serialize resource usage results: {0}")]
+    SerializeRusage(serde_json::Error),
     #[error("Failed to read from output file: {0}")]
     OutputFileRead(std::io::Error),
     #[error("Failed to parse the output file name: {0}")]
     OutputFileName(bencher_json::ValidError),
+    #[error(
+        "The output directory ({0}) is not a Criterion output directory (ie `target/criterion`): no `new/benchmark.json` and `new/estimates.json` files were found"
+    )]
+    NotCriterionDir(camino::Utf8PathBuf),
+    #[error("Failed to parse Criterion results file ({path}): {err}")]
+    ParseCriterion {
+        path: camino::Utf8PathBuf,
+        err: serde_json::Error,
+    },
+    #[error("Failed to serialize Criterion results: {0}")]
+    SerializeCriterion(serde_json::Error),
     #[error("Failed to read size of output file: {0}")]
     OutputFileSize(std::io::Error),
     #[error("Failed to serialize file size results: {0}")]
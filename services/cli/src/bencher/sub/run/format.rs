@@ -5,6 +5,9 @@ pub enum Format {
     Human,
     Json,
     Html,
+    Markdown,
+    Junit,
+    Sarif,
 }
 
 impl From<CliRunFormat> for Format {
@@ -13,6 +16,9 @@ impl From<CliRunFormat> for Format {
             CliRunFormat::Human => Self::Human,
             CliRunFormat::Json => Self::Json,
             CliRunFormat::Html => Self::Html,
+            CliRunFormat::Markdown => Self::Markdown,
+            CliRunFormat::Junit => Self::Junit,
+            CliRunFormat::Sarif => Self::Sarif,
         }
     }
 }
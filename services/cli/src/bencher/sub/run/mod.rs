@@ -1,8 +1,15 @@
 use std::{future::Future, pin::Pin};
 
-use bencher_client::types::{Adapter, JsonAverage, JsonFold, JsonNewRun, JsonReportSettings};
+use bencher_adapter::{Adaptable as _, AdapterResults, Settings};
+use bencher_client::types::{
+    Adapter, JsonAverage, JsonCorrection, JsonFold, JsonHardwareChange, JsonNewRun,
+    JsonReportSettings,
+};
 use bencher_comment::ReportComment;
-use bencher_json::{DateTime, JsonReport, NameId, ResourceId, RunContext};
+use bencher_json::{
+    DateTime, JsonReport, NameId, ResourceId, RunContext,
+    project::{alert::AlertStatus, metric::MetricResults, report::Adapter as JsonAdapter},
+};
 
 use crate::{
     CliError,
@@ -15,6 +22,7 @@ mod branch;
 mod ci;
 mod error;
 mod format;
+mod oidc;
 pub mod runner;
 mod sub_adapter;
 
@@ -40,6 +48,9 @@ pub struct Run {
     average: Option<JsonAverage>,
     iter: usize,
     fold: Option<JsonFold>,
+    sample: bool,
+    correction: Option<JsonCorrection>,
+    hardware_change: Option<JsonHardwareChange>,
     backdate: Option<DateTime>,
     allow_failure: bool,
     thresholds: Thresholds,
@@ -50,6 +61,7 @@ pub struct Run {
     runner: Runner,
     #[expect(clippy::struct_field_names)]
     dry_run: bool,
+    oidc: bool,
     backend: PubBackend,
 }
 
@@ -65,6 +77,9 @@ impl TryFrom<CliRun> for Run {
             average,
             iter,
             fold,
+            sample,
+            correction,
+            hardware_change,
             backdate,
             allow_failure,
             thresholds,
@@ -73,6 +88,7 @@ impl TryFrom<CliRun> for Run {
             ci,
             cmd,
             dry_run,
+            oidc,
             backend,
         } = run;
         Ok(Self {
@@ -84,6 +100,9 @@ impl TryFrom<CliRun> for Run {
             average: average.map(Into::into),
             iter,
             fold: fold.map(Into::into),
+            sample,
+            correction: correction.map(Into::into),
+            hardware_change: hardware_change.map(Into::into),
             backdate,
             allow_failure,
             thresholds: thresholds.try_into().map_err(RunError::Thresholds)?,
@@ -93,6 +112,7 @@ impl TryFrom<CliRun> for Run {
             ci: ci.try_into().map_err(RunError::Ci)?,
             runner: cmd.try_into()?,
             dry_run,
+            oidc,
             backend: PubBackend::try_from(backend)?.log(false),
         })
     }
@@ -135,14 +155,27 @@ impl Run {
             return Ok(());
         }
 
+        // Exchange the CI ID token for a short-lived CI token to send the report
+        let ci_backend = match (self.oidc, &self.project) {
+            (true, Some(project)) => {
+                Some(oidc::ci_backend(&self.backend, project, self.log).await?)
+            },
+            _ => None,
+        };
+        let backend = ci_backend.as_ref().unwrap_or(&self.backend);
+
         let sender = run_sender(json_new_run);
-        let json_report: JsonReport = self
-            .backend
+        let json_report: JsonReport = backend
             .send_with(sender)
             .await
             .map_err(RunError::SendReport)?;
 
-        let alerts_count = json_report.alerts.len();
+        // Suppressed alerts are silenced, so only count the active alerts
+        let alerts_count = json_report
+            .alerts
+            .iter()
+            .filter(|alert| matches!(alert.status, AlertStatus::Active))
+            .count();
         self.display_results(json_report).await?;
 
         if self.err && alerts_count > 0 {
@@ -156,9 +189,19 @@ impl Run {
         let start_time = DateTime::now();
         let mut results = Vec::with_capacity(self.iter);
         for _ in 0..self.iter {
-            let output = self.runner.run(self.log).await?;
+            let mut output = self.runner.run(self.log).await?;
             if output.is_success() {
-                results.push(output.result());
+                let resource_usage = output.resource_usage.take();
+                let result = if matches!(self.adapter, Adapter::ShellPerfStat) {
+                    output.perf_stat_result()
+                } else {
+                    output.result()
+                };
+                results.push(if let Some(resource_usage) = resource_usage {
+                    self.with_resource_usage(&result, resource_usage)?
+                } else {
+                    result
+                });
             } else if self.allow_failure {
                 cli_eprintln_quietable!(self.log, "Skipping failure:\n{output}");
             } else {
@@ -195,14 +238,56 @@ impl Run {
             end_time: end_time.into(),
             results,
             settings: Some(JsonReportSettings {
-                adapter: Some(self.adapter),
+                // Results with resource usage have already been converted to BMF
+                adapter: Some(if self.runner.resource_usage() {
+                    Adapter::Json
+                } else {
+                    self.adapter
+                }),
                 average: self.average,
                 fold: self.fold,
+                sample: self.sample.then_some(true),
+                correction: self.correction,
+                hardware_change: self.hardware_change,
             }),
             context: Some(RunContext::current().into()),
         }))
     }
 
+    // Parse the benchmark results locally, so the resource usage of the benchmark command
+    // can be added to them under its own benchmark name.
+    fn with_resource_usage(
+        &self,
+        result: &str,
+        resource_usage: MetricResults,
+    ) -> Result<String, RunError> {
+        let adapter: JsonAdapter = serde_json::to_value(self.adapter)
+            .and_then(serde_json::from_value)
+            .map_err(RunError::RusageAdapter)?;
+        let average = self
+            .average
+            .map(|average| serde_json::to_value(average).and_then(serde_json::from_value))
+            .transpose()
+            .map_err(RunError::RusageAdapter)?;
+        // A command may not output any benchmark results of its own
+        let mut adapter_results = if result.trim().is_empty() {
+            AdapterResults::default()
+        } else {
+            adapter
+                .convert(result, Settings::new(average))
+                .ok_or_else(|| RunError::RusageConvert(result.to_owned()))?
+        };
+        for (benchmark_name, metrics) in resource_usage {
+            adapter_results
+                .inner
+                .entry(benchmark_name)
+                .or_default()
+                .inner
+                .extend(metrics);
+        }
+        serde_json::to_string(&adapter_results).map_err(RunError::SerializeRusage)
+    }
+
     async fn display_results(&self, json_report: JsonReport) -> Result<(), RunError> {
         let console_url = self
             .backend
@@ -220,6 +305,9 @@ impl Run {
             Format::Human => report_comment.human(),
             Format::Json => report_comment.json().map_err(RunError::SerializeReport)?,
             Format::Html => report_comment.html(false, None),
+            Format::Markdown => report_comment.markdown(false),
+            Format::Junit => report_comment.junit(),
+            Format::Sarif => report_comment.sarif().map_err(RunError::SerializeReport)?,
         };
         let newline_prefix = if self.log { "\n" } else { "" };
         cli_println!("{newline_prefix}{report_str}");
@@ -0,0 +1,95 @@
+use bencher_client::types::JsonCiAuth;
+use bencher_json::{JsonToken, ResourceId};
+use serde::Deserialize;
+
+use crate::{
+    bencher::backend::{BackendError, PubBackend},
+    cli_println_quietable,
+};
+
+// https://docs.github.com/en/actions/security-for-github-actions/security-hardening-your-deployments/about-security-hardening-with-openid-connect#updating-your-actions-for-oidc
+const ACTIONS_ID_TOKEN_REQUEST_URL: &str = "ACTIONS_ID_TOKEN_REQUEST_URL";
+const ACTIONS_ID_TOKEN_REQUEST_TOKEN: &str = "ACTIONS_ID_TOKEN_REQUEST_TOKEN";
+// https://docs.gitlab.com/ci/secrets/id_token_authentication/
+const BENCHER_ID_TOKEN: &str = "BENCHER_ID_TOKEN";
+
+#[derive(thiserror::Error, Debug)]
+pub enum OidcError {
+    #[error(
+        "Failed to find a CI OpenID Connect ID token. For GitHub Actions, set `permissions: id-token: write` for the job. For GitLab CI/CD, set `id_tokens: {BENCHER_ID_TOKEN}` for the job. See https://bencher.dev/docs/explanation/bencher-run/#--oidc"
+    )]
+    NoIdToken,
+    #[error("Failed to get Bencher Console URL for the ID token audience: {0}")]
+    ConsoleUrl(BackendError),
+    #[error("Failed to create OpenID Connect HTTP client: {0}")]
+    Client(reqwest::Error),
+    #[error("Failed to request GitHub Actions ID token: {0}")]
+    GitHubActions(reqwest::Error),
+    #[error("Failed to exchange ID token for a CI token: {0}")]
+    Exchange(BackendError),
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubIdToken {
+    value: String,
+}
+
+/// Exchange the CI OpenID Connect ID token for a short-lived CI token for the project
+pub async fn ci_backend(
+    backend: &PubBackend,
+    project: &ResourceId,
+    log: bool,
+) -> Result<PubBackend, OidcError> {
+    let audience = backend
+        .get_console_url()
+        .await
+        .map_err(OidcError::ConsoleUrl)?;
+    let id_token = id_token(audience.as_str().trim_end_matches('/')).await?;
+
+    let json_ci_auth = JsonCiAuth {
+        project: project.clone().into(),
+        id_token: id_token.into(),
+    };
+    let json_token: JsonToken = backend
+        .send_with(|client| {
+            let json_ci_auth = json_ci_auth.clone();
+            async move { client.auth_ci_post().body(json_ci_auth).send().await }
+        })
+        .await
+        .map_err(OidcError::Exchange)?;
+    cli_println_quietable!(
+        log,
+        "Exchanged CI ID token for CI token ({name}) valid until {expiration}",
+        name = json_token.name,
+        expiration = json_token.expiration
+    );
+
+    Ok(backend.clone().token(json_token.token))
+}
+
+async fn id_token(audience: &str) -> Result<String, OidcError> {
+    if let (Ok(request_url), Ok(request_token)) = (
+        std::env::var(ACTIONS_ID_TOKEN_REQUEST_URL),
+        std::env::var(ACTIONS_ID_TOKEN_REQUEST_TOKEN),
+    ) {
+        let client = reqwest::Client::builder()
+            .build()
+            .map_err(OidcError::Client)?;
+        client
+            .get(request_url)
+            .query(&[("audience", audience)])
+            .bearer_auth(request_token)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(OidcError::GitHubActions)?
+            .json::<GitHubIdToken>()
+            .await
+            .map(|id_token| id_token.value)
+            .map_err(OidcError::GitHubActions)
+    } else if let Ok(id_token) = std::env::var(BENCHER_ID_TOKEN) {
+        Ok(id_token)
+    } else {
+        Err(OidcError::NoIdToken)
+    }
+}
@@ -1,11 +1,15 @@
 use std::{fmt, process::Stdio};
 
 use chrono::Utc;
-use tokio::io::{AsyncBufReadExt as _, BufReader};
+use tokio::{
+    io::{AsyncBufReadExt as _, BufReader},
+    process::{ChildStderr, ChildStdout},
+};
 
 use super::build_time::{BuildCommand, BuildTime};
 use super::file_path::FilePath;
 use super::file_size::FileSize;
+use super::rusage::{ResourceUsage, Rusage};
 use super::{flag::Flag, output::Output, shell::Shell};
 use crate::{bencher::sub::RunError, parser::run::CliRunShell};
 use crate::{cli_eprintln_quietable, cli_println_quietable};
@@ -53,34 +57,58 @@ impl Command {
         Self::Exec { program, arguments }
     }
 
+    pub fn std_command(&self) -> std::process::Command {
+        match self {
+            Self::Shell {
+                shell,
+                flag,
+                command,
+            } => {
+                let mut std_command = std::process::Command::new(shell.as_ref());
+                std_command.arg(flag.as_ref()).arg(command);
+                std_command
+            },
+            Self::Exec { program, arguments } => {
+                let mut std_command = std::process::Command::new(program);
+                std_command.args(arguments);
+                std_command
+            },
+        }
+    }
+
     pub async fn run(
         &self,
         log: bool,
         build_time: Option<BuildTime>,
+        resource_usage: Option<ResourceUsage>,
     ) -> Result<CommandOutput, RunError> {
-        let (output, duration) = self.run_inner(log).await?;
+        let (mut output, duration, rusage) = self.run_inner(log, resource_usage.is_some()).await?;
         let build_command = build_time.map(|bt| bt.command(self.to_string(), duration));
+        if let Some(rusage_command) = resource_usage
+            .zip(rusage)
+            .map(|(ru, rusage)| ru.command(self.to_string(), rusage))
+        {
+            output.resource_usage = Some(rusage_command.to_metric_results()?);
+        }
         Ok(CommandOutput::new(build_command, output))
     }
 
-    async fn run_inner(&self, log: bool) -> Result<(Output, f64), RunError> {
+    async fn run_inner(
+        &self,
+        log: bool,
+        rusage: bool,
+    ) -> Result<(Output, f64, Option<Rusage>), RunError> {
         let start_time = Utc::now();
-        let mut child = match self {
-            Self::Shell {
-                shell,
-                flag,
-                command,
-            } => tokio::process::Command::new(shell.as_ref())
-                .arg(flag.as_ref())
-                .arg(command)
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn(),
-            Self::Exec { program, arguments } => tokio::process::Command::new(program)
-                .args(arguments)
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn(),
+        let mut std_command = self.std_command();
+        std_command.stdout(Stdio::piped()).stderr(Stdio::piped());
+        let mut child = if rusage {
+            // Resource usage can only be collected when reaping the child process,
+            // so it has to be waited on outside of the Tokio process driver.
+            std_command.spawn().map(Child::Rusage)
+        } else {
+            tokio::process::Command::from(std_command)
+                .spawn()
+                .map(Child::Tokio)
         }
         .map_err(|err| RunError::SpawnCommand {
             command: self.clone(),
@@ -88,8 +116,7 @@ impl Command {
         })?;
 
         let child_stdout = child
-            .stdout
-            .take()
+            .stdout()
             .ok_or_else(|| RunError::PipeStdout(self.clone()))?;
         let stdout = tokio::spawn(async move {
             let stdout_reader = BufReader::new(child_stdout);
@@ -109,8 +136,7 @@ impl Command {
         });
 
         let child_stderr = child
-            .stderr
-            .take()
+            .stderr()
             .ok_or_else(|| RunError::PipeStderr(self.clone()))?;
         let stderr = tokio::spawn(async move {
             let stderr_reader = BufReader::new(child_stderr);
@@ -129,13 +155,10 @@ impl Command {
             stderr
         });
 
-        let (status, stdout, stderr) = tokio::join!(child.wait(), stdout, stderr);
+        let (status, stdout, stderr) = tokio::join!(child.wait(self), stdout, stderr);
         let end_time = Utc::now();
 
-        let status = status.map_err(|err| RunError::RunCommand {
-            command: self.clone(),
-            err,
-        })?;
+        let (status, rusage) = status?;
         let stdout = stdout.map_err(|err| RunError::StdoutJoinError {
             command: self.clone(),
             err,
@@ -166,12 +189,62 @@ impl Command {
                 stdout,
                 stderr,
                 result: None,
+                resource_usage: None,
             },
             duration,
+            rusage,
         ))
     }
 }
 
+enum Child {
+    Tokio(tokio::process::Child),
+    Rusage(std::process::Child),
+}
+
+impl Child {
+    fn stdout(&mut self) -> Option<ChildStdout> {
+        match self {
+            Self::Tokio(child) => child.stdout.take(),
+            Self::Rusage(child) => child
+                .stdout
+                .take()
+                .and_then(|stdout| ChildStdout::from_std(stdout).ok()),
+        }
+    }
+
+    fn stderr(&mut self) -> Option<ChildStderr> {
+        match self {
+            Self::Tokio(child) => child.stderr.take(),
+            Self::Rusage(child) => child
+                .stderr
+                .take()
+                .and_then(|stderr| ChildStderr::from_std(stderr).ok()),
+        }
+    }
+
+    async fn wait(
+        self,
+        command: &Command,
+    ) -> Result<(std::process::ExitStatus, Option<Rusage>), RunError> {
+        match self {
+            Self::Tokio(mut child) => child.wait().await.map(|status| (status, None)),
+            Self::Rusage(mut child) => {
+                tokio::task::spawn_blocking(move || Rusage::wait(&mut child))
+                    .await
+                    .map_err(|err| RunError::RusageJoinError {
+                        command: command.clone(),
+                        err,
+                    })?
+            },
+        }
+        .map_err(|err| RunError::RunCommand {
+            command: command.clone(),
+            err,
+        })
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct CommandOutput {
     build_command: Option<BuildCommand>,
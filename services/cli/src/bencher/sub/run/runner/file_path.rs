@@ -1,9 +1,16 @@
 use std::fmt;
 
-use camino::Utf8PathBuf;
+use bencher_adapter::adapters::rust::criterion_estimates::CriterionBenchmark;
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::de::DeserializeOwned;
 
 use crate::RunError;
 
+// Criterion saves the latest results for each benchmark to a `new` directory
+const CRITERION_NEW_DIR: &str = "new";
+const CRITERION_BENCHMARK_FILE: &str = "benchmark.json";
+const CRITERION_ESTIMATES_FILE: &str = "estimates.json";
+
 #[derive(Debug, Clone)]
 pub struct FilePath(Utf8PathBuf);
 
@@ -19,6 +26,95 @@ impl FilePath {
     }
 
     pub fn get_results(&self) -> Result<String, RunError> {
-        std::fs::read_to_string(&self.0).map_err(RunError::OutputFileRead)
+        if self.0.is_dir() {
+            get_criterion_results(&self.0)
+        } else {
+            std::fs::read_to_string(&self.0).map_err(RunError::OutputFileRead)
+        }
+    }
+}
+
+// A Criterion output directory (ie `target/criterion`)
+fn get_criterion_results(dir: &Utf8Path) -> Result<String, RunError> {
+    let mut criterion_benchmarks = Vec::new();
+    find_criterion_benchmarks(dir, &mut criterion_benchmarks)?;
+    // Only a directory with at least one Criterion benchmark is considered Criterion output
+    if criterion_benchmarks.is_empty() {
+        return Err(RunError::NotCriterionDir(dir.to_owned()));
+    }
+    serde_json::to_string(&criterion_benchmarks).map_err(RunError::SerializeCriterion)
+}
+
+fn find_criterion_benchmarks(
+    dir: &Utf8Path,
+    criterion_benchmarks: &mut Vec<CriterionBenchmark>,
+) -> Result<(), RunError> {
+    let mut dir_paths = Vec::new();
+    for entry in dir.read_dir_utf8().map_err(RunError::OutputFileRead)? {
+        let entry = entry.map_err(RunError::OutputFileRead)?;
+        // Do not follow symlinks, as a symlink loop would recurse forever
+        if entry
+            .file_type()
+            .map_err(RunError::OutputFileRead)?
+            .is_dir()
+        {
+            dir_paths.push(entry.into_path());
+        }
+    }
+    // Keep the benchmarks in a consistent order
+    dir_paths.sort();
+
+    for path in dir_paths {
+        if path.file_name() == Some(CRITERION_NEW_DIR) {
+            let benchmark_path = path.join(CRITERION_BENCHMARK_FILE);
+            let estimates_path = path.join(CRITERION_ESTIMATES_FILE);
+            if benchmark_path.is_file() && estimates_path.is_file() {
+                criterion_benchmarks.push(CriterionBenchmark {
+                    benchmark: read_criterion_file(&benchmark_path)?,
+                    estimates: read_criterion_file(&estimates_path)?,
+                });
+            }
+        } else {
+            find_criterion_benchmarks(&path, criterion_benchmarks)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn read_criterion_file<T>(path: &Utf8Path) -> Result<T, RunError>
+where
+    T: DeserializeOwned,
+{
+    let json = std::fs::read_to_string(path).map_err(RunError::OutputFileRead)?;
+    serde_json::from_str(&json).map_err(|err| RunError::ParseCriterion {
+        path: path.to_owned(),
+        err,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use camino::Utf8PathBuf;
+
+    use super::get_criterion_results;
+    use crate::RunError;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_criterion_symlink_loop() {
+        let dir = Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(format!("bencher_criterion_{}", std::process::id()));
+        let bench_dir = dir.join("bench");
+        std::fs::create_dir_all(&bench_dir).unwrap();
+        std::os::unix::fs::symlink(&dir, bench_dir.join("loop")).unwrap();
+
+        let result = get_criterion_results(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(
+            matches!(result, Err(RunError::NotCriterionDir(_))),
+            "{result:?}"
+        );
     }
 }
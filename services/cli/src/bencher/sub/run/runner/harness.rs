@@ -0,0 +1,276 @@
+use std::{
+    fmt,
+    process::Stdio,
+    time::{Duration, Instant},
+};
+
+use bencher_json::{
+    JsonNewMetric,
+    project::{
+        measure::built_in::{self, BuiltInMeasure as _},
+        metric::MetricResults,
+    },
+};
+
+use super::{command::Command, output::Output, rusage::Rusage};
+use crate::{
+    RunError, cli_println_quietable,
+    parser::run::{CliRunHarness, CliRunShell},
+};
+
+#[derive(Debug, Clone)]
+pub struct Harness {
+    warmup: u32,
+    min_runs: u32,
+    min_time: Option<Duration>,
+    prepare: Option<Command>,
+    cleanup: Option<Command>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Measurement {
+    wall_time: Duration,
+    rusage: Option<Rusage>,
+}
+
+impl fmt::Display for Harness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            " (harness: {warmup} warmup, {min_runs} runs",
+            warmup = self.warmup,
+            min_runs = self.min_runs
+        )?;
+        if let Some(min_time) = self.min_time {
+            write!(f, ", {min_time}s", min_time = min_time.as_secs())?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl Harness {
+    pub fn new(harness: CliRunHarness, sh_c: &CliRunShell) -> Result<Option<Self>, RunError> {
+        let CliRunHarness {
+            harness,
+            warmup,
+            min_runs,
+            min_time,
+            prepare,
+            cleanup,
+        } = harness;
+        if !harness {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            warmup,
+            min_runs,
+            min_time: min_time.map(Duration::from_secs),
+            prepare: prepare
+                .map(|prepare| Command::new_shell(sh_c.clone(), prepare))
+                .transpose()?,
+            cleanup: cleanup
+                .map(|cleanup| Command::new_shell(sh_c.clone(), cleanup))
+                .transpose()?,
+        }))
+    }
+
+    pub async fn run(&self, command: &Command, log: bool) -> Result<Output, RunError> {
+        let harness = self.clone();
+        let harness_command = command.clone();
+        // Child processes are waited on synchronously in order to collect their resource usage
+        tokio::task::spawn_blocking(move || harness.run_blocking(&harness_command, log))
+            .await
+            .map_err(|err| RunError::HarnessJoinError {
+                command: command.clone(),
+                err,
+            })?
+    }
+
+    fn run_blocking(&self, command: &Command, log: bool) -> Result<Output, RunError> {
+        for _ in 0..self.warmup {
+            let (output, _) = self.run_once(command)?;
+            if !output.is_success() {
+                return Ok(output);
+            }
+        }
+
+        let mut measurements = Vec::new();
+        let start_time = Instant::now();
+        while !self.is_done(measurements.len(), start_time.elapsed()) {
+            let (output, measurement) = self.run_once(command)?;
+            if !output.is_success() {
+                return Ok(output);
+            }
+            measurements.push(measurement);
+        }
+        cli_println_quietable!(
+            log,
+            "Ran `{command}` {runs} times after {warmup} warmup runs",
+            runs = measurements.len(),
+            warmup = self.warmup
+        );
+
+        let results = JsonNewMetric::results(to_metric_results(command, &measurements)?);
+        Ok(Output {
+            result: Some(serde_json::to_string(&results).map_err(RunError::SerializeHarness)?),
+            ..Default::default()
+        })
+    }
+
+    fn is_done(&self, runs: usize, elapsed: Duration) -> bool {
+        // There must always be at least one run
+        runs >= usize::try_from(self.min_runs.max(1)).unwrap_or(usize::MAX)
+            && self.min_time.is_none_or(|min_time| elapsed >= min_time)
+    }
+
+    fn run_once(&self, command: &Command) -> Result<(Output, Measurement), RunError> {
+        if let Some(prepare) = &self.prepare {
+            let output = run_quietly(prepare)?.0;
+            if !output.is_success() {
+                return Ok((output, Measurement::default()));
+            }
+        }
+
+        let (output, measurement) = run_quietly(command)?;
+
+        if let Some(cleanup) = &self.cleanup {
+            let cleanup_output = run_quietly(cleanup)?.0;
+            if !cleanup_output.is_success() {
+                return Ok((cleanup_output, measurement));
+            }
+        }
+
+        Ok((output, measurement))
+    }
+}
+
+// The output of the benchmark command is discarded, as it is not parsed by an adapter
+fn run_quietly(command: &Command) -> Result<(Output, Measurement), RunError> {
+    let start_time = Instant::now();
+    let mut child = command
+        .std_command()
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| RunError::SpawnCommand {
+            command: command.clone(),
+            err,
+        })?;
+    let (status, rusage) = Rusage::wait(&mut child).map_err(|err| RunError::RunCommand {
+        command: command.clone(),
+        err,
+    })?;
+    let wall_time = start_time.elapsed();
+
+    Ok((
+        Output {
+            status: status.into(),
+            ..Default::default()
+        },
+        Measurement { wall_time, rusage },
+    ))
+}
+
+fn to_metric_results(
+    command: &Command,
+    measurements: &[Measurement],
+) -> Result<MetricResults, RunError> {
+    let mut metrics = Vec::new();
+    let wall_times = measurements
+        .iter()
+        .map(|measurement| as_nanos(measurement.wall_time))
+        .collect::<Vec<_>>();
+    if let Some(metric) = mean_std_dev(&wall_times) {
+        metrics.push((built_in::default::Latency::name_id(), metric));
+    }
+
+    let rusages = measurements
+        .iter()
+        .filter_map(|measurement| measurement.rusage)
+        .collect::<Vec<_>>();
+    let user_times = rusages
+        .iter()
+        .map(|rusage| as_nanos(rusage.user_time))
+        .collect::<Vec<_>>();
+    if let Some(metric) = mean_std_dev(&user_times) {
+        metrics.push((built_in::rusage::UserTime::name_id(), metric));
+    }
+    let system_times = rusages
+        .iter()
+        .map(|rusage| as_nanos(rusage.system_time))
+        .collect::<Vec<_>>();
+    if let Some(metric) = mean_std_dev(&system_times) {
+        metrics.push((built_in::rusage::SystemTime::name_id(), metric));
+    }
+    #[expect(clippy::cast_precision_loss)]
+    let max_rsses = rusages
+        .iter()
+        .map(|rusage| rusage.max_rss as f64)
+        .collect::<Vec<_>>();
+    if let Some(metric) = mean_std_dev(&max_rsses) {
+        metrics.push((built_in::rusage::MaxRss::name_id(), metric));
+    }
+
+    Ok(vec![(
+        command.to_string().parse().map_err(RunError::CommandName)?,
+        metrics,
+    )])
+}
+
+fn as_nanos(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000_000_000.0
+}
+
+// The lower and upper values are one sample standard deviation below and above the mean
+#[expect(clippy::cast_precision_loss)]
+fn mean_std_dev(values: &[f64]) -> Option<JsonNewMetric> {
+    if values.is_empty() {
+        return None;
+    }
+    let len = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / len;
+    let std_dev = if values.len() > 1 {
+        let variance = values
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>()
+            / (len - 1.0);
+        variance.sqrt()
+    } else {
+        0.0
+    };
+    Some(JsonNewMetric {
+        value: mean.into(),
+        lower_value: Some((mean - std_dev).into()),
+        upper_value: Some((mean + std_dev).into()),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::JsonNewMetric;
+
+    use super::mean_std_dev;
+
+    #[test]
+    fn test_mean_std_dev() {
+        assert_eq!(mean_std_dev(&[]), None);
+        assert_eq!(
+            mean_std_dev(&[3.0]),
+            Some(JsonNewMetric {
+                value: 3.0.into(),
+                lower_value: Some(3.0.into()),
+                upper_value: Some(3.0.into()),
+            })
+        );
+        assert_eq!(
+            mean_std_dev(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]),
+            Some(JsonNewMetric {
+                value: 5.0.into(),
+                lower_value: Some((5.0 - (32.0f64 / 7.0).sqrt()).into()),
+                upper_value: Some((5.0 + (32.0f64 / 7.0).sqrt()).into()),
+            })
+        );
+    }
+}
@@ -7,25 +7,30 @@ pub mod command;
 mod file_path;
 mod file_size;
 mod flag;
+mod harness;
 pub mod output;
 mod pipe;
+mod rusage;
 mod shell;
 
 use build_time::BuildTime;
 use command::{Command, CommandOutput};
 use file_path::FilePath;
 use file_size::FileSize;
+use harness::Harness;
 use output::Output;
 use pipe::Pipe;
+use rusage::ResourceUsage;
 
 use super::RunError;
 
 #[derive(Debug, Clone)]
 pub enum Runner {
     Pipe(Pipe),
-    Command(Command, Option<BuildTime>),
-    CommandToFile(Command, FilePath),
-    CommandToFileSize(Command, Option<BuildTime>, FileSize),
+    Command(Command, Option<BuildTime>, Option<ResourceUsage>),
+    Harness(Command, Harness),
+    CommandToFile(Command, Option<ResourceUsage>, FilePath),
+    CommandToFileSize(Command, Option<BuildTime>, Option<ResourceUsage>, FileSize),
     File(FilePath),
     FileSize(FileSize),
 }
@@ -39,6 +44,7 @@ impl TryFrom<CliRunCommand> for Runner {
             c.next().map(|program| (program, c.collect::<Vec<_>>()))
         });
         if let Some((program, arguments)) = program_arguments {
+            let harness = Harness::new(cmd.harness, &cmd.sh_c)?;
             let command = if !cmd.exec && arguments.is_empty() {
                 Command::new_shell(cmd.sh_c, program)?
             } else {
@@ -50,12 +56,20 @@ impl TryFrom<CliRunCommand> for Runner {
                 Command::new_exec(program, arguments)
             };
             let build_time = cmd.build_time.then_some(BuildTime);
-            Ok(if let Some(file_path) = cmd.file {
-                Self::CommandToFile(command, FilePath::new(file_path))
+            let resource_usage = cmd.rusage.then_some(ResourceUsage);
+            Ok(if let Some(harness) = harness {
+                Self::Harness(command, harness)
+            } else if let Some(file_path) = cmd.file {
+                Self::CommandToFile(command, resource_usage, FilePath::new(file_path))
             } else if let Some(file_paths) = cmd.file_size {
-                Self::CommandToFileSize(command, build_time, FileSize::new(file_paths))
+                Self::CommandToFileSize(
+                    command,
+                    build_time,
+                    resource_usage,
+                    FileSize::new(file_paths),
+                )
             } else {
-                Self::Command(command, build_time)
+                Self::Command(command, build_time, resource_usage)
             })
         } else if let Some(file_path) = cmd.file {
             Ok(Self::File(FilePath::new(file_path)))
@@ -73,26 +87,45 @@ impl fmt::Display for Runner {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Pipe(pipe) => write!(f, "{pipe}"),
-            Self::Command(command, build_time) => write!(
+            Self::Command(command, build_time, resource_usage) => write!(
                 f,
-                "{command}{build_time}",
+                "{command}{build_time}{resource_usage}",
                 build_time = if build_time.is_some() {
                     " (build time)"
                 } else {
                     ""
+                },
+                resource_usage = if resource_usage.is_some() {
+                    " (resource usage)"
+                } else {
+                    ""
                 }
             ),
-            Self::CommandToFile(command, file_path) => {
-                write!(f, "{command} > {file_path}")
+            Self::Harness(command, harness) => write!(f, "{command}{harness}"),
+            Self::CommandToFile(command, resource_usage, file_path) => {
+                write!(
+                    f,
+                    "{command}{resource_usage} > {file_path}",
+                    resource_usage = if resource_usage.is_some() {
+                        " (resource usage)"
+                    } else {
+                        ""
+                    }
+                )
             },
-            Self::CommandToFileSize(command, build_time, file_path) => {
+            Self::CommandToFileSize(command, build_time, resource_usage, file_path) => {
                 write!(
                     f,
-                    "{command}{build_time} > {file_path} (size)",
+                    "{command}{build_time}{resource_usage} > {file_path} (size)",
                     build_time = if build_time.is_some() {
                         " (build time)"
                     } else {
                         ""
+                    },
+                    resource_usage = if resource_usage.is_some() {
+                        " (resource usage)"
+                    } else {
+                        ""
                     }
                 )
             },
@@ -103,16 +136,29 @@ impl fmt::Display for Runner {
 }
 
 impl Runner {
+    pub fn resource_usage(&self) -> bool {
+        match self {
+            Self::Command(_, _, resource_usage)
+            | Self::CommandToFile(_, resource_usage, _)
+            | Self::CommandToFileSize(_, _, resource_usage, _) => resource_usage.is_some(),
+            Self::Pipe(_) | Self::Harness(_, _) | Self::File(_) | Self::FileSize(_) => false,
+        }
+    }
+
     pub async fn run(&self, log: bool) -> Result<Output, RunError> {
         match self {
             Self::Pipe(pipe) => Ok(pipe.output()),
-            Self::Command(command, build_time) => command.run(log, *build_time).await?.build(),
-            Self::CommandToFile(command, file_path) => command
-                .run(log, None)
+            Self::Command(command, build_time, resource_usage) => command
+                .run(log, *build_time, *resource_usage)
+                .await?
+                .build(),
+            Self::Harness(command, harness) => harness.run(command, log).await,
+            Self::CommandToFile(command, resource_usage, file_path) => command
+                .run(log, None, *resource_usage)
                 .await?
                 .build_with_file_path(file_path),
-            Self::CommandToFileSize(command, build_time, file_size) => command
-                .run(log, *build_time)
+            Self::CommandToFileSize(command, build_time, resource_usage, file_size) => command
+                .run(log, *build_time, *resource_usage)
                 .await?
                 .build_with_file_size(file_size),
             Self::File(file_path) => CommandOutput::default().build_with_file_path(file_path),
@@ -1,11 +1,15 @@
 use std::fmt;
 
+use bencher_adapter::{Adaptable as _, Settings};
+use bencher_json::project::{metric::MetricResults, report::Adapter as JsonAdapter};
+
 #[derive(Debug, Clone, Default)]
 pub struct Output {
     pub status: ExitStatus,
     pub stdout: String,
     pub stderr: String,
     pub result: Option<String>,
+    pub resource_usage: Option<MetricResults>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -46,6 +50,24 @@ impl Output {
     pub fn result(self) -> String {
         self.result.unwrap_or(self.stdout)
     }
+
+    // `perf stat` writes its report to stderr instead of stdout.
+    // So if stdout does not have a `perf stat` report but stderr does, then use stderr.
+    pub fn perf_stat_result(self) -> String {
+        if self.result.is_some() {
+            return self.result();
+        }
+        let has_report = |output: &str| {
+            JsonAdapter::ShellPerfStat
+                .convert(output, Settings::default())
+                .is_some()
+        };
+        if !has_report(&self.stdout) && has_report(&self.stderr) {
+            self.stderr
+        } else {
+            self.stdout
+        }
+    }
 }
 
 impl ExitStatus {
@@ -53,3 +75,49 @@ impl ExitStatus {
         self.0 == 0
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::parser::run::CliRunShell;
+
+    use super::super::{Runner, command::Command};
+
+    const PERF_STAT: &str =
+        "Performance counter stats for './fib':\n\n     1,962,305,712      cycles\n";
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_perf_stat_result_stderr() {
+        let command = Command::new_shell(
+            CliRunShell {
+                shell: None,
+                flag: None,
+            },
+            format!("echo fib; printf \"{PERF_STAT}\" >&2"),
+        )
+        .unwrap();
+        let runner = Runner::Command(command, None, None);
+
+        let output = runner.run(false).await.unwrap();
+        assert_eq!(output.clone().result(), "fib");
+        let result = output.perf_stat_result();
+        assert!(result.contains("1,962,305,712      cycles"), "{result}");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_perf_stat_result_stdout() {
+        let command = Command::new_shell(
+            CliRunShell {
+                shell: None,
+                flag: None,
+            },
+            format!("printf \"{PERF_STAT}\"; echo fib >&2"),
+        )
+        .unwrap();
+        let runner = Runner::Command(command, None, None);
+
+        let result = runner.run(false).await.unwrap().perf_stat_result();
+        assert!(result.contains("1,962,305,712      cycles"), "{result}");
+    }
+}
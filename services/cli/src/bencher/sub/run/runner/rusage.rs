@@ -0,0 +1,263 @@
+use std::{
+    io,
+    process::{Child, ExitStatus},
+    time::Duration,
+};
+
+use bencher_json::{
+    JsonNewMetric,
+    project::{
+        measure::built_in::{self, BuiltInMeasure as _},
+        metric::MetricResults,
+    },
+};
+
+use crate::RunError;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceUsage;
+
+impl ResourceUsage {
+    #[expect(clippy::unused_self)]
+    pub fn command(self, name: String, rusage: Rusage) -> RusageCommand {
+        RusageCommand { name, rusage }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RusageCommand {
+    name: String,
+    rusage: Rusage,
+}
+
+impl RusageCommand {
+    #[expect(clippy::cast_precision_loss)]
+    pub fn to_metric_results(&self) -> Result<MetricResults, RunError> {
+        let Rusage {
+            user_time,
+            system_time,
+            max_rss,
+            voluntary_context_switches,
+            involuntary_context_switches,
+            io,
+        } = self.rusage;
+        let mut metrics = vec![
+            (
+                built_in::rusage::UserTime::name_id(),
+                user_time.as_secs_f64() * 1_000_000_000.0,
+            ),
+            (
+                built_in::rusage::SystemTime::name_id(),
+                system_time.as_secs_f64() * 1_000_000_000.0,
+            ),
+            (built_in::rusage::MaxRss::name_id(), max_rss as f64),
+            (
+                built_in::rusage::VoluntaryContextSwitches::name_id(),
+                voluntary_context_switches as f64,
+            ),
+            (
+                built_in::rusage::InvoluntaryContextSwitches::name_id(),
+                involuntary_context_switches as f64,
+            ),
+        ];
+        if let Some(ProcIo {
+            read_bytes,
+            write_bytes,
+        }) = io
+        {
+            metrics.push((built_in::rusage::ReadBytes::name_id(), read_bytes as f64));
+            metrics.push((built_in::rusage::WriteBytes::name_id(), write_bytes as f64));
+        }
+
+        Ok(vec![(
+            self.name.parse().map_err(RunError::CommandName)?,
+            metrics
+                .into_iter()
+                .map(|(measure, value)| {
+                    (
+                        measure,
+                        JsonNewMetric {
+                            value: value.into(),
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect(),
+        )])
+    }
+}
+
+/// The resource usage of a child process, as reported by `wait4`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rusage {
+    pub user_time: Duration,
+    pub system_time: Duration,
+    /// Peak resident set size in bytes
+    pub max_rss: u64,
+    pub voluntary_context_switches: u64,
+    pub involuntary_context_switches: u64,
+    /// Storage I/O, as reported by `/proc/<pid>/io` on Linux
+    pub io: Option<ProcIo>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcIo {
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+}
+
+impl Rusage {
+    /// Wait for the child process to exit and collect its resource usage.
+    /// Resource usage is only available on Unix.
+    #[cfg(unix)]
+    pub fn wait(child: &mut Child) -> io::Result<(ExitStatus, Option<Self>)> {
+        use std::os::unix::process::ExitStatusExt as _;
+
+        let pid = libc::pid_t::try_from(child.id()).map_err(io::Error::other)?;
+        // The I/O of the child process has to be read after it exits but before it is reaped.
+        let io = ProcIo::wait(pid)?;
+        let mut status = 0;
+        // SAFETY: `rusage` is a plain C struct for which all zeroes is a valid value.
+        #[expect(unsafe_code)]
+        let mut rusage = unsafe { std::mem::zeroed::<libc::rusage>() };
+        loop {
+            // SAFETY: The child has not yet been reaped, so its PID has not been reused.
+            // `wait4` only writes to the status and resource usage that are passed in.
+            #[expect(unsafe_code)]
+            let result = unsafe { libc::wait4(pid, &mut status, 0, &mut rusage) };
+            if result != -1 {
+                break;
+            }
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+
+        let mut rusage = Self::from(rusage);
+        rusage.io = io;
+        Ok((ExitStatus::from_raw(status), Some(rusage)))
+    }
+
+    #[cfg(not(unix))]
+    pub fn wait(child: &mut Child) -> io::Result<(ExitStatus, Option<Self>)> {
+        child.wait().map(|status| (status, None))
+    }
+}
+
+#[cfg(unix)]
+impl From<libc::rusage> for Rusage {
+    fn from(rusage: libc::rusage) -> Self {
+        // On macOS `ru_maxrss` is in bytes, everywhere else it is in kilobytes.
+        const MAX_RSS_SCALE: u64 = if cfg!(target_os = "macos") { 1 } else { 1024 };
+
+        Self {
+            user_time: timeval_duration(rusage.ru_utime),
+            system_time: timeval_duration(rusage.ru_stime),
+            max_rss: u64::try_from(rusage.ru_maxrss)
+                .unwrap_or_default()
+                .saturating_mul(MAX_RSS_SCALE),
+            voluntary_context_switches: u64::try_from(rusage.ru_nvcsw).unwrap_or_default(),
+            involuntary_context_switches: u64::try_from(rusage.ru_nivcsw).unwrap_or_default(),
+            io: None,
+        }
+    }
+}
+
+#[cfg(unix)]
+fn timeval_duration(timeval: libc::timeval) -> Duration {
+    Duration::from_secs(u64::try_from(timeval.tv_sec).unwrap_or_default())
+        + Duration::from_micros(u64::try_from(timeval.tv_usec).unwrap_or_default())
+}
+
+impl ProcIo {
+    /// Wait for the child process to exit without reaping it, and then read its I/O.
+    #[cfg(target_os = "linux")]
+    fn wait(pid: libc::pid_t) -> io::Result<Option<Self>> {
+        loop {
+            // SAFETY: `siginfo_t` is a plain C struct for which all zeroes is a valid value.
+            #[expect(unsafe_code)]
+            let mut siginfo = unsafe { std::mem::zeroed::<libc::siginfo_t>() };
+            // SAFETY: The child has not yet been reaped, so its PID has not been reused.
+            // `WNOWAIT` leaves the child in a waitable state to be reaped by `wait4`.
+            #[expect(unsafe_code)]
+            let result = unsafe {
+                libc::waitid(
+                    libc::P_PID,
+                    libc::id_t::try_from(pid).map_err(io::Error::other)?,
+                    &mut siginfo,
+                    libc::WEXITED | libc::WNOWAIT,
+                )
+            };
+            if result != -1 {
+                break;
+            }
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+
+        // The I/O is optional, as it requires the kernel to have task I/O accounting enabled.
+        Ok(std::fs::read_to_string(format!("/proc/{pid}/io"))
+            .ok()
+            .and_then(|proc_io| Self::parse(&proc_io)))
+    }
+
+    #[cfg(all(unix, not(target_os = "linux")))]
+    #[expect(clippy::unnecessary_wraps)]
+    fn wait(_pid: libc::pid_t) -> io::Result<Option<Self>> {
+        Ok(None)
+    }
+
+    // rchar: 323934931
+    // wchar: 323929600
+    // syscr: 632687
+    // syscw: 632675
+    // read_bytes: 0
+    // write_bytes: 323932160
+    // cancelled_write_bytes: 0
+    #[cfg_attr(not(target_os = "linux"), expect(dead_code))]
+    fn parse(proc_io: &str) -> Option<Self> {
+        let field = |name: &str| {
+            proc_io.lines().find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                (key == name).then(|| value.trim().parse().ok()).flatten()
+            })
+        };
+        Some(Self {
+            read_bytes: field("read_bytes")?,
+            write_bytes: field("write_bytes")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ProcIo;
+
+    #[test]
+    fn test_proc_io_parse() {
+        let proc_io = ProcIo::parse(
+            "rchar: 323934931\nwchar: 323929600\nsyscr: 632687\nsyscw: 632675\nread_bytes: 4096\nwrite_bytes: 323932160\ncancelled_write_bytes: 0\n",
+        )
+        .unwrap();
+        assert_eq!(proc_io.read_bytes, 4096);
+        assert_eq!(proc_io.write_bytes, 323_932_160);
+
+        assert!(ProcIo::parse("rchar: 323934931\n").is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rusage_wait() {
+        let mut child = std::process::Command::new("sh")
+            .arg("-c")
+            .arg("exit 3")
+            .spawn()
+            .unwrap();
+        let (status, rusage) = super::Rusage::wait(&mut child).unwrap();
+        assert_eq!(status.code(), Some(3));
+        assert!(rusage.unwrap().max_rss > 0);
+    }
+}
@@ -4,6 +4,7 @@ use crate::parser::run::CliRunCommand;
 pub struct SubAdapter {
     build_time: bool,
     file_size: bool,
+    rusage: bool,
 }
 
 impl From<&CliRunCommand> for SubAdapter {
@@ -14,6 +15,7 @@ impl From<&CliRunCommand> for SubAdapter {
                 .file_size
                 .as_ref()
                 .is_some_and(|paths| !paths.is_empty()),
+            rusage: cmd.rusage,
         }
     }
 }
@@ -23,10 +25,12 @@ impl From<SubAdapter> for bencher_comment::SubAdapter {
         let SubAdapter {
             build_time,
             file_size,
+            rusage,
         } = sub_adapter;
         Self {
             build_time,
             file_size,
+            rusage,
         }
     }
 }
@@ -3,6 +3,7 @@ use crate::{CliError, bencher::sub::SubCmd, parser::system::server::CliServer};
 mod backup;
 mod config;
 mod restart;
+mod restore;
 mod spec;
 mod stats;
 mod version;
@@ -14,6 +15,7 @@ pub enum Server {
     Restart(restart::Restart),
     Config(config::Config),
     Backup(backup::Backup),
+    Restore(restore::Restore),
     #[cfg(feature = "plus")]
     Stats(stats::ServerStats),
 }
@@ -28,6 +30,7 @@ impl TryFrom<CliServer> for Server {
             CliServer::Restart(restart) => Self::Restart(restart.try_into()?),
             CliServer::Config(config) => Self::Config(config.try_into()?),
             CliServer::Backup(backup) => Self::Backup(backup.try_into()?),
+            CliServer::Restore(restore) => Self::Restore(restore.try_into()?),
             #[cfg(feature = "plus")]
             CliServer::Stats(stats) => Self::Stats(stats.try_into()?),
         })
@@ -42,6 +45,7 @@ impl SubCmd for Server {
             Self::Restart(restart) => restart.exec().await,
             Self::Config(config) => config.exec().await,
             Self::Backup(backup) => backup.exec().await,
+            Self::Restore(restore) => restore.exec().await,
             #[cfg(feature = "plus")]
             Self::Stats(stats) => stats.exec().await,
         }
@@ -0,0 +1,76 @@
+use bencher_client::types::JsonRestore;
+use camino::Utf8PathBuf;
+
+use crate::{
+    CliError,
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::system::server::CliRestore,
+};
+
+#[derive(Debug, Clone)]
+pub struct Restore {
+    pub backup: Option<String>,
+    pub file: Option<Utf8PathBuf>,
+    pub delay: u64,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliRestore> for Restore {
+    type Error = CliError;
+
+    fn try_from(restore: CliRestore) -> Result<Self, Self::Error> {
+        let CliRestore {
+            backup,
+            file,
+            delay,
+            backend,
+        } = restore;
+        Ok(Self {
+            backup,
+            file,
+            delay,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Restore> for JsonRestore {
+    fn from(restore: Restore) -> Self {
+        let Restore { backup, delay, .. } = restore;
+        Self {
+            backup,
+            delay: Some(delay),
+        }
+    }
+}
+
+impl SubCmd for Restore {
+    async fn exec(&self) -> Result<(), CliError> {
+        if let Some(file) = &self.file {
+            let backup =
+                std::fs::read(file).map_err(|e| CliError::ReadBackupFile(file.clone(), e))?;
+            let _json = self
+                .backend
+                .send(|client| {
+                    let backup = backup.clone();
+                    async move {
+                        client
+                            .server_restore_upload_post()
+                            .delay(self.delay)
+                            .body(backup)
+                            .send()
+                            .await
+                    }
+                })
+                .await?;
+        } else {
+            let _json = self
+                .backend
+                .send(|client| async move {
+                    client.server_restore_post().body(self.clone()).send().await
+                })
+                .await?;
+        }
+        Ok(())
+    }
+}
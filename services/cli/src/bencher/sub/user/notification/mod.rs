@@ -0,0 +1,30 @@
+use crate::{CliError, bencher::sub::SubCmd, parser::user::notification::CliNotification};
+
+mod update;
+mod view;
+
+#[derive(Debug)]
+pub enum Notification {
+    View(view::View),
+    Update(update::Update),
+}
+
+impl TryFrom<CliNotification> for Notification {
+    type Error = CliError;
+
+    fn try_from(notification: CliNotification) -> Result<Self, Self::Error> {
+        Ok(match notification {
+            CliNotification::View(view) => Self::View(view.try_into()?),
+            CliNotification::Update(update) => Self::Update(update.try_into()?),
+        })
+    }
+}
+
+impl SubCmd for Notification {
+    async fn exec(&self) -> Result<(), CliError> {
+        match self {
+            Self::View(view) => view.exec().await,
+            Self::Update(update) => update.exec().await,
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use bencher_client::types::{JsonUpdateNotification, NotificationMode};
+use bencher_json::ResourceId;
+
+use crate::{
+    CliError,
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::user::notification::{CliNotificationMode, CliNotificationUpdate},
+};
+
+#[derive(Debug, Clone)]
+pub struct Update {
+    pub user: ResourceId,
+    pub project: ResourceId,
+    pub alerts: Option<NotificationMode>,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliNotificationUpdate> for Update {
+    type Error = CliError;
+
+    fn try_from(update: CliNotificationUpdate) -> Result<Self, Self::Error> {
+        let CliNotificationUpdate {
+            user,
+            project,
+            alerts,
+            backend,
+        } = update;
+        Ok(Self {
+            user,
+            project,
+            alerts: alerts.map(Into::into),
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Update> for JsonUpdateNotification {
+    fn from(update: Update) -> Self {
+        let Update { alerts, .. } = update;
+        Self { alerts }
+    }
+}
+
+impl From<CliNotificationMode> for NotificationMode {
+    fn from(mode: CliNotificationMode) -> Self {
+        match mode {
+            CliNotificationMode::Immediate => Self::Immediate,
+            CliNotificationMode::Digest => Self::Digest,
+            CliNotificationMode::Off => Self::Off,
+        }
+    }
+}
+
+impl SubCmd for Update {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .user_notification_patch()
+                    .user(self.user.clone())
+                    .project(self.project.clone())
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
-use bencher_client::types::JsonNewToken;
-use bencher_json::{ResourceId, ResourceName};
+use bencher_client::types::{JsonNewToken, ProjectPermission};
+use bencher_json::{NonEmpty, ResourceId, ResourceName};
 
 use crate::{
     CliError,
@@ -12,6 +12,10 @@ pub struct Create {
     pub user: ResourceId,
     pub name: ResourceName,
     pub ttl: Option<u32>,
+    pub project: Option<ResourceId>,
+    pub permissions: Option<Vec<ProjectPermission>>,
+    pub ci: bool,
+    pub branch: Option<NonEmpty>,
     pub backend: AuthBackend,
 }
 
@@ -23,12 +27,21 @@ impl TryFrom<CliTokenCreate> for Create {
             user,
             name,
             ttl,
+            project,
+            permissions,
+            ci,
+            branch,
             backend,
         } = create;
         Ok(Self {
             user,
             name,
             ttl,
+            project,
+            permissions: (!permissions.is_empty())
+                .then(|| permissions.into_iter().map(Into::into).collect()),
+            ci,
+            branch,
             backend: backend.try_into()?,
         })
     }
@@ -36,10 +49,22 @@ impl TryFrom<CliTokenCreate> for Create {
 
 impl From<Create> for JsonNewToken {
     fn from(create: Create) -> Self {
-        let Create { name, ttl, .. } = create;
+        let Create {
+            name,
+            ttl,
+            project,
+            permissions,
+            ci,
+            branch,
+            ..
+        } = create;
         Self {
             name: name.into(),
             ttl,
+            project: project.map(Into::into),
+            permissions,
+            ci: ci.then_some(true),
+            branch: branch.map(Into::into),
         }
     }
 }
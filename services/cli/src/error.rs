@@ -19,4 +19,6 @@ pub enum CliError {
 
     #[error("Failed to serialize config: {0}")]
     SerializeConfig(serde_json::Error),
+    #[error("Failed to read database backup file ({0}): {1}")]
+    ReadBackupFile(camino::Utf8PathBuf, std::io::Error),
 }
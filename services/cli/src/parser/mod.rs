@@ -17,11 +17,12 @@ use organization::{CliOrganization, member::CliMember};
 use project::{
     CliProject, alert::CliAlert, archive::CliArchive, benchmark::CliBenchmark, branch::CliBranch,
     measure::CliMeasure, metric::CliMetric, perf::CliPerf, plot::CliPlot, report::CliReport,
-    testbed::CliTestbed, threshold::CliThreshold,
+    testbed::CliTestbed, threshold::CliThreshold, trust_policy::CliTrustPolicy,
+    webhook::CliWebhook,
 };
 use run::CliRun;
 use system::{auth::CliAuth, server::CliServer};
-use user::{CliUser, token::CliToken};
+use user::{CliUser, notification::CliNotification, token::CliToken};
 
 /// Bencher CLI
 #[derive(Parser, Debug)]
@@ -98,12 +99,22 @@ pub enum CliSub {
     #[clap(subcommand)]
     Alert(CliAlert),
 
+    /// Manage CI trust policies
+    #[clap(subcommand, alias = "trust")]
+    TrustPolicy(CliTrustPolicy),
+    /// Manage webhooks
+    #[clap(subcommand)]
+    Webhook(CliWebhook),
+
     /// Manage user
     #[clap(subcommand)]
     User(CliUser),
     /// Manage user API tokens
     #[clap(subcommand)]
     Token(CliToken),
+    /// Manage user notifications
+    #[clap(subcommand)]
+    Notification(CliNotification),
 
     /// Server commands
     #[clap(subcommand)]
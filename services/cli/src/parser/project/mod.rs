@@ -16,6 +16,8 @@ pub mod plot;
 pub mod report;
 pub mod testbed;
 pub mod threshold;
+pub mod trust_policy;
+pub mod webhook;
 
 #[derive(Subcommand, Debug)]
 pub enum CliProject {
@@ -1,9 +1,12 @@
 use bencher_json::{
-    Boundary, DateTime, GitHash, NameId, ReportUuid, ResourceId, SampleSize, Window,
+    Boundary, DateTime, GitHash, NameId, ReportUuid, ResourceId, SampleSize, Smoothing, Window,
 };
 use clap::{Args, Parser, Subcommand, ValueEnum};
 
-use super::{branch::CliStartPointUpdate, threshold::CliModelTest};
+use super::{
+    branch::CliStartPointUpdate,
+    threshold::{CliEnvironmentChange, CliModelTest},
+};
 use crate::parser::{CliBackend, CliPagination, ElidedOption};
 
 #[derive(Subcommand, Debug)]
@@ -108,6 +111,18 @@ pub struct CliReportCreate {
     #[clap(value_enum, long)]
     pub fold: Option<CliReportFold>,
 
+    /// Treat multiple results as a single sample for threshold detection
+    #[clap(long, conflicts_with = "fold")]
+    pub sample: bool,
+
+    /// Correct for multiple comparisons across all threshold boundary checks in the report
+    #[clap(value_enum, long)]
+    pub correction: Option<CliReportCorrection>,
+
+    /// How to handle a change in the testbed hardware since its previous report
+    #[clap(value_enum, long)]
+    pub hardware_change: Option<CliReportHardwareChange>,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
@@ -149,6 +164,26 @@ pub struct CliReportThresholds {
     #[clap(long, requires = "threshold_test")]
     pub threshold_upper_boundary: Vec<ElidedOption<Boundary>>,
 
+    /// Forecast smoothing factor for the level and seasonality
+    /// To ignore a this option when specifying multiple Thresholds, use an underscore (`_`).
+    #[clap(long, requires = "threshold_test")]
+    pub threshold_smoothing: Vec<ElidedOption<Smoothing>>,
+
+    /// Forecast smoothing factor for the trend
+    /// To ignore a this option when specifying multiple Thresholds, use an underscore (`_`).
+    #[clap(long, requires = "threshold_test")]
+    pub threshold_trend: Vec<ElidedOption<Smoothing>>,
+
+    /// Forecast season length (samples)
+    /// To ignore a this option when specifying multiple Thresholds, use an underscore (`_`).
+    #[clap(long, requires = "threshold_test")]
+    pub threshold_season: Vec<ElidedOption<SampleSize>>,
+
+    /// How to handle alerts when the testbed hardware has changed (`annotate` or `suppress`)
+    /// To ignore a this option when specifying multiple Thresholds, use an underscore (`_`).
+    #[clap(long, requires = "threshold_test")]
+    pub threshold_environment_change: Vec<ElidedOption<CliEnvironmentChange>>,
+
     /// Reset all unspecified Thresholds for the `branch` and `testbed`
     /// If a Threshold already exists and is not specified, its current Model will be removed.
     #[clap(long)]
@@ -220,6 +255,8 @@ pub enum CliReportAdapter {
     Shell,
     /// ❯_ Shell Hyperfine
     ShellHyperfine,
+    /// ❯_ Shell perf stat
+    ShellPerfStat,
 }
 
 /// Suggested Central Tendency (Average)
@@ -246,6 +283,27 @@ pub enum CliReportFold {
     Median,
 }
 
+/// Multiple Comparison Corrections
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "snake_case")]
+pub enum CliReportCorrection {
+    /// Bonferroni correction (family-wise error rate)
+    Bonferroni,
+    /// Benjamini-Hochberg procedure (false discovery rate)
+    #[clap(alias = "bh")]
+    BenjaminiHochberg,
+}
+
+/// Testbed Hardware Changes
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "snake_case")]
+pub enum CliReportHardwareChange {
+    /// Warn and still compare to the historical metrics
+    Warn,
+    /// Refuse to compare to the historical metrics
+    Refuse,
+}
+
 #[derive(Parser, Debug)]
 pub struct CliReportView {
     /// Project slug or UUID
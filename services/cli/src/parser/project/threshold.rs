@@ -1,4 +1,8 @@
-use bencher_json::{Boundary, ModelUuid, NameId, ResourceId, SampleSize, ThresholdUuid, Window};
+use std::str::FromStr;
+
+use bencher_json::{
+    Boundary, ModelUuid, NameId, ResourceId, SampleSize, Smoothing, ThresholdUuid, Window,
+};
 use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
 
 use crate::parser::{CliBackend, CliPagination};
@@ -125,6 +129,22 @@ pub struct CliModel {
     /// Upper boundary
     #[clap(long, value_name = "BOUNDARY")]
     pub upper_boundary: Option<Boundary>,
+
+    /// Forecast smoothing factor for the level and seasonality
+    #[clap(long, value_name = "SMOOTHING")]
+    pub smoothing: Option<Smoothing>,
+
+    /// Forecast smoothing factor for the trend
+    #[clap(long, value_name = "SMOOTHING")]
+    pub trend: Option<Smoothing>,
+
+    /// Forecast season length (samples)
+    #[clap(long, value_name = "SAMPLE_SIZE")]
+    pub season: Option<SampleSize>,
+
+    /// How to handle alerts when the testbed hardware has changed
+    #[clap(value_enum, long)]
+    pub environment_change: Option<CliEnvironmentChange>,
 }
 
 /// Supported threshold model tests
@@ -147,6 +167,32 @@ pub enum CliModelTest {
     Iqr,
     /// Delta interquartile range (ΔIQR)
     DeltaIqr,
+    /// Change point detection
+    ChangePoint,
+    /// Mann-Whitney U test (non-parametric)
+    #[clap(alias = "mwu")]
+    MannWhitneyU,
+    /// Forecast (Holt-Winters exponential smoothing)
+    #[clap(alias = "holt_winters")]
+    Forecast,
+}
+
+/// Supported ways to handle alerts when the testbed hardware has changed
+#[derive(ValueEnum, Debug, Clone, Copy)]
+#[clap(rename_all = "snake_case")]
+pub enum CliEnvironmentChange {
+    /// Annotate the alert as an environment change (default)
+    Annotate,
+    /// Annotate the alert as an environment change and silence it
+    Suppress,
+}
+
+impl FromStr for CliEnvironmentChange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <Self as ValueEnum>::from_str(s, false)
+    }
 }
 
 #[derive(Parser, Debug)]
@@ -212,6 +258,22 @@ pub struct CliUpdateModel {
     #[clap(long, requires = "test", value_name = "BOUNDARY")]
     pub upper_boundary: Option<Boundary>,
 
+    /// Forecast smoothing factor for the level and seasonality
+    #[clap(long, requires = "test", value_name = "SMOOTHING")]
+    pub smoothing: Option<Smoothing>,
+
+    /// Forecast smoothing factor for the trend
+    #[clap(long, requires = "test", value_name = "SMOOTHING")]
+    pub trend: Option<Smoothing>,
+
+    /// Forecast season length (samples)
+    #[clap(long, requires = "test", value_name = "SAMPLE_SIZE")]
+    pub season: Option<SampleSize>,
+
+    /// How to handle alerts when the testbed hardware has changed
+    #[clap(value_enum, long, requires = "test")]
+    pub environment_change: Option<CliEnvironmentChange>,
+
     /// Remove the threshold model
     #[clap(long)]
     pub remove_model: bool,
@@ -0,0 +1,108 @@
+use bencher_json::{NonEmpty, ResourceId, TrustPolicyUuid, Url};
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::parser::{CliBackend, CliPagination};
+
+#[derive(Subcommand, Debug)]
+pub enum CliTrustPolicy {
+    /// List trust policies
+    #[clap(alias = "ls")]
+    List(CliTrustPolicyList),
+    /// Create a trust policy
+    #[clap(alias = "add")]
+    Create(CliTrustPolicyCreate),
+    /// View a trust policy
+    #[clap(alias = "get")]
+    View(CliTrustPolicyView),
+    /// Delete a trust policy
+    #[clap(alias = "rm")]
+    Delete(CliTrustPolicyDelete),
+}
+
+#[derive(Parser, Debug)]
+pub struct CliTrustPolicyList {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    #[clap(flatten)]
+    pub pagination: CliPagination<CliTrustPoliciesSort>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "snake_case")]
+pub enum CliTrustPoliciesSort {
+    /// Creation date time of the trust policy
+    Created,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliTrustPolicyCreate {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// The CI provider that issues the OpenID Connect ID tokens.
+    #[clap(long)]
+    pub provider: CliTrustProvider,
+
+    /// The OpenID Connect issuer URL for a self-hosted CI provider.
+    /// If not provided, the issuer for the hosted CI provider is used.
+    /// The issuer URL must use `https`.
+    #[clap(long)]
+    pub issuer: Option<Url>,
+
+    /// The repository that is trusted (ex: `bencherdev/bencher`).
+    #[clap(long)]
+    pub repository: NonEmpty,
+
+    /// The git ref pattern that is trusted (ex: `refs/heads/main`).
+    /// A `*` matches any sequence of characters.
+    /// If not provided, all git refs are trusted.
+    #[clap(long)]
+    pub git_ref: Option<NonEmpty>,
+
+    /// The workflow file path pattern that is trusted (ex: `.github/workflows/bench.yml`).
+    /// A `*` matches any sequence of characters.
+    /// If not provided, all workflows are trusted.
+    #[clap(long)]
+    pub workflow: Option<NonEmpty>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+/// Supported CI providers
+#[derive(ValueEnum, Debug, Clone, Copy)]
+#[clap(rename_all = "snake_case")]
+pub enum CliTrustProvider {
+    /// GitHub Actions
+    GithubActions,
+    /// GitLab CI/CD
+    GitlabCi,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliTrustPolicyView {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Trust policy UUID
+    pub trust_policy: TrustPolicyUuid,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliTrustPolicyDelete {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Trust policy UUID
+    pub trust_policy: TrustPolicyUuid,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
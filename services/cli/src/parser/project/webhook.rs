@@ -0,0 +1,146 @@
+use bencher_json::{ResourceId, Secret, Url, WebhookUuid};
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::parser::{CliBackend, CliPagination};
+
+#[derive(Subcommand, Debug)]
+pub enum CliWebhook {
+    /// List webhooks
+    #[clap(alias = "ls")]
+    List(CliWebhookList),
+    /// Create a webhook
+    #[clap(alias = "add")]
+    Create(CliWebhookCreate),
+    /// View a webhook
+    #[clap(alias = "get")]
+    View(CliWebhookView),
+    /// Delete a webhook
+    #[clap(alias = "rm")]
+    Delete(CliWebhookDelete),
+    /// List the delivery log for a webhook
+    Deliveries(CliWebhookDeliveries),
+}
+
+#[derive(Parser, Debug)]
+pub struct CliWebhookList {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    #[clap(flatten)]
+    pub pagination: CliPagination<CliWebhooksSort>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "snake_case")]
+pub enum CliWebhooksSort {
+    /// Creation date time of the webhook
+    Created,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliWebhookCreate {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// The URL that the webhook payloads are sent to.
+    /// For the `matrix` format, this is the URL to send a message to a room, without the transaction ID.
+    #[clap(long)]
+    pub url: Url,
+
+    /// The format of the webhook payloads.
+    #[clap(value_enum, long)]
+    pub format: Option<CliWebhookFormat>,
+
+    /// The secret used to sign the webhook payloads with HMAC-SHA256.
+    /// If not provided, the webhook payloads are not signed.
+    /// For the `matrix` format, the access token for the Matrix user that sends the messages.
+    #[clap(long)]
+    pub secret: Option<Secret>,
+
+    /// An event to subscribe the webhook to (can be used multiple times).
+    #[clap(long, required = true)]
+    pub event: Vec<CliWebhookEvent>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+/// Webhook formats
+#[derive(ValueEnum, Debug, Clone, Copy)]
+#[clap(rename_all = "snake_case")]
+pub enum CliWebhookFormat {
+    /// A signed JSON payload with the full event data (default)
+    Json,
+    /// A Slack Block Kit message for a Slack incoming webhook
+    Slack,
+    /// A `MessageCard` for a Microsoft Teams incoming webhook
+    Teams,
+    /// An `m.room.message` event for a Matrix room
+    Matrix,
+}
+
+/// Webhook events
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum CliWebhookEvent {
+    /// An alert was raised for a new report
+    #[value(name = "alert.created")]
+    AlertCreated,
+    /// An alert was dismissed by a user
+    #[value(name = "alert.dismissed")]
+    AlertDismissed,
+    /// A new report was created
+    #[value(name = "report.created")]
+    ReportCreated,
+    /// The model for a threshold was updated by a user
+    #[value(name = "threshold.updated")]
+    ThresholdUpdated,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliWebhookView {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Webhook UUID
+    pub webhook: WebhookUuid,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliWebhookDelete {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Webhook UUID
+    pub webhook: WebhookUuid,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliWebhookDeliveries {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Webhook UUID
+    pub webhook: WebhookUuid,
+
+    #[clap(flatten)]
+    pub pagination: CliPagination<CliWebhookDeliveriesSort>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "snake_case")]
+pub enum CliWebhookDeliveriesSort {
+    /// Creation date time of the webhook delivery
+    Created,
+}
@@ -5,10 +5,12 @@ use clap::{ArgGroup, Args, Parser, ValueEnum};
 use crate::parser::CliBackend;
 
 use super::project::report::{
-    CliReportAdapter, CliReportAverage, CliReportFold, CliReportThresholds,
+    CliReportAdapter, CliReportAverage, CliReportCorrection, CliReportFold,
+    CliReportHardwareChange, CliReportThresholds,
 };
 
 #[derive(Parser, Debug)]
+#[expect(clippy::struct_excessive_bools)]
 pub struct CliRun {
     /// Project slug or UUID
     #[clap(long, env = "BENCHER_PROJECT")]
@@ -38,6 +40,18 @@ pub struct CliRun {
     #[clap(value_enum, long, requires = "iter", value_name = "AGGREGATE_FUNCTION")]
     pub fold: Option<CliReportFold>,
 
+    /// Treat the results of all run iterations as a single sample for threshold detection
+    #[clap(long, requires = "iter", conflicts_with = "fold")]
+    pub sample: bool,
+
+    /// Correct for multiple comparisons across all threshold boundary checks in the report
+    #[clap(value_enum, long)]
+    pub correction: Option<CliReportCorrection>,
+
+    /// How to handle a change in the testbed hardware since its previous report
+    #[clap(value_enum, long)]
+    pub hardware_change: Option<CliReportHardwareChange>,
+
     /// Backdate the report (seconds since epoch)
     /// NOTE: This will NOT effect the ordering of past reports
     #[clap(long, value_name = "SECONDS")]
@@ -68,6 +82,11 @@ pub struct CliRun {
     #[clap(long)]
     pub dry_run: bool,
 
+    /// Exchange the CI OpenID Connect ID token for a short-lived CI token (requires: `--project`).
+    /// The project must have a trust policy that matches the CI job.
+    #[clap(long, requires = "project", conflicts_with = "token")]
+    pub oidc: bool,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
@@ -128,6 +147,10 @@ pub struct CliRunCommand {
     #[clap(long, requires = "command", conflicts_with = "file")]
     pub build_time: bool,
 
+    /// Track the resource usage of the benchmark command
+    #[clap(long, requires = "command", conflicts_with = "harness")]
+    pub rusage: bool,
+
     /// Benchmark command output file path
     #[clap(long, conflicts_with = "file_size")]
     pub file: Option<Utf8PathBuf>,
@@ -136,6 +159,9 @@ pub struct CliRunCommand {
     #[clap(long, conflicts_with = "file")]
     pub file_size: Option<Vec<Utf8PathBuf>>,
 
+    #[clap(flatten)]
+    pub harness: CliRunHarness,
+
     #[clap(flatten)]
     pub sh_c: CliRunShell,
 
@@ -158,6 +184,37 @@ pub struct CliRunCommand {
 }
 
 #[derive(Args, Debug)]
+pub struct CliRunHarness {
+    /// Time the benchmark command with the built-in benchmark harness
+    #[clap(
+        long,
+        requires = "command",
+        conflicts_with_all = ["build_time", "file", "file_size"]
+    )]
+    pub harness: bool,
+
+    /// Number of warmup runs before timing the benchmark command (requires: `--harness`)
+    #[clap(long, value_name = "COUNT", requires = "harness", default_value = "0")]
+    pub warmup: u32,
+
+    /// Minimum number of timed runs of the benchmark command (requires: `--harness`)
+    #[clap(long, value_name = "COUNT", requires = "harness", default_value = "10")]
+    pub min_runs: u32,
+
+    /// Minimum time to spend on timed runs of the benchmark command (requires: `--harness`)
+    #[clap(long, value_name = "SECONDS", requires = "harness")]
+    pub min_time: Option<u64>,
+
+    /// Shell command to run before each run of the benchmark command (requires: `--harness`)
+    #[clap(long, value_name = "COMMAND", requires = "harness")]
+    pub prepare: Option<String>,
+
+    /// Shell command to run after each run of the benchmark command (requires: `--harness`)
+    #[clap(long, value_name = "COMMAND", requires = "harness")]
+    pub cleanup: Option<String>,
+}
+
+#[derive(Args, Debug, Clone)]
 pub struct CliRunShell {
     /// Shell command path
     #[clap(long)]
@@ -188,6 +245,12 @@ pub enum CliRunFormat {
     Json,
     /// HTML
     Html,
+    /// Markdown
+    Markdown,
+    /// `JUnit` XML
+    Junit,
+    /// SARIF
+    Sarif,
 }
 
 #[expect(clippy::struct_excessive_bools)]
@@ -195,25 +258,28 @@ pub enum CliRunFormat {
 #[clap(group(
     ArgGroup::new("ci_cd")
         .multiple(false)
-        .args(&["github_actions"]),
+        .args(&["github_actions", "gitlab_ci"]),
 ))]
 pub struct CliRunCi {
     /// GitHub API authentication token for GitHub Actions to comment on PRs (ie `--github-actions ${{ secrets.GITHUB_TOKEN }}`)
     #[clap(long)]
     pub github_actions: Option<String>,
-    /// Only post results to CI if a Threshold exists for the Branch, Testbed, and Measure (requires: `--github-actions`)
+    /// GitLab API access token for GitLab CI/CD to comment on MRs (ie `--gitlab-ci $BENCHER_GITLAB_TOKEN`)
+    #[clap(long)]
+    pub gitlab_ci: Option<String>,
+    /// Only post results to CI if a Threshold exists for the Branch, Testbed, and Measure (requires: `--github-actions` or `--gitlab-ci`)
     #[clap(long, requires = "ci_cd")]
     pub ci_only_thresholds: bool,
-    /// Only start posting results to CI if an Alert is generated (requires: `--github-actions`)
+    /// Only start posting results to CI if an Alert is generated (requires: `--github-actions` or `--gitlab-ci`)
     #[clap(long, requires = "ci_cd")]
     pub ci_only_on_alert: bool,
     /// All links should be to public URLs that do not require a login (requires: `--github-actions`)
-    #[clap(long, requires = "ci_cd")]
+    #[clap(long, requires = "github_actions")]
     pub ci_public_links: bool,
-    /// Custom ID for posting results to CI (requires: `--github-actions`)
+    /// Custom ID for posting results to CI (requires: `--github-actions` or `--gitlab-ci`)
     #[clap(long, requires = "ci_cd")]
     pub ci_id: Option<String>,
-    /// Issue number for posting results to CI (requires: `--github-actions`)
+    /// Issue or merge request number for posting results to CI (requires: `--github-actions` or `--gitlab-ci`)
     #[clap(long, requires = "ci_cd")]
     pub ci_number: Option<u64>,
     /// CAUTION: Override safety checks and accept that you are vulnerable to pwn requests (requires: `--github-actions`)
@@ -1,3 +1,4 @@
+use camino::Utf8PathBuf;
 use clap::{Parser, Subcommand, ValueEnum};
 
 use crate::parser::CliBackend;
@@ -15,6 +16,8 @@ pub enum CliServer {
     Config(CliConfig),
     /// Backup database
     Backup(CliBackup),
+    /// Restore database from backup and restart
+    Restore(CliRestore),
     #[cfg(feature = "plus")]
     /// Server usage statistics
     Stats(CliServerStats),
@@ -103,6 +106,26 @@ pub struct CliBackup {
 pub enum CliBackupDataStore {
     /// AWS S3
     AwsS3,
+    /// Local directory
+    Local,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliRestore {
+    /// Restore this database backup from the data store (default is the latest backup)
+    #[clap(long, conflicts_with = "file")]
+    pub backup: Option<String>,
+
+    /// Upload and restore this local database backup file
+    #[clap(long)]
+    pub file: Option<Utf8PathBuf>,
+
+    /// Server restart delay seconds
+    #[clap(long, default_value = "3")]
+    pub delay: u64,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
 }
 
 #[cfg(feature = "plus")]
@@ -3,6 +3,7 @@ use clap::{Parser, Subcommand, ValueEnum};
 
 use crate::parser::{CliBackend, CliPagination};
 
+pub mod notification;
 pub mod token;
 
 #[derive(Subcommand, Debug)]
@@ -0,0 +1,53 @@
+use bencher_json::ResourceId;
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::parser::CliBackend;
+
+#[derive(Subcommand, Debug)]
+pub enum CliNotification {
+    /// View notifications for a project
+    #[clap(alias = "get")]
+    View(CliNotificationView),
+    // Update notifications for a project
+    #[clap(alias = "edit")]
+    Update(CliNotificationUpdate),
+}
+
+#[derive(Parser, Debug)]
+pub struct CliNotificationView {
+    /// User slug or UUID
+    pub user: ResourceId,
+
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliNotificationUpdate {
+    /// User slug or UUID
+    pub user: ResourceId,
+
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// How to be notified by email of new alerts
+    #[clap(long)]
+    pub alerts: Option<CliNotificationMode>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "snake_case")]
+pub enum CliNotificationMode {
+    /// Send an email as soon as an alert is generated
+    Immediate,
+    /// Send a daily digest email of the alerts generated in the last day
+    Digest,
+    /// Do not send any emails
+    Off,
+}
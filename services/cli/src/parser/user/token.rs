@@ -1,7 +1,7 @@
-use bencher_json::{ResourceId, ResourceName, TokenUuid};
+use bencher_json::{NonEmpty, ResourceId, ResourceName, TokenUuid};
 use clap::{Parser, Subcommand, ValueEnum};
 
-use crate::parser::{CliBackend, CliPagination};
+use crate::parser::{CliBackend, CliPagination, project::CliProjectPermission};
 
 #[derive(Subcommand, Debug)]
 pub enum CliToken {
@@ -17,6 +17,9 @@ pub enum CliToken {
     // Update a token
     #[clap(alias = "edit")]
     Update(CliTokenUpdate),
+    /// Revoke a token
+    #[clap(alias = "rm")]
+    Revoke(CliTokenRevoke),
 }
 
 #[derive(Parser, Debug)]
@@ -59,6 +62,22 @@ pub struct CliTokenCreate {
     #[clap(long)]
     pub ttl: Option<u32>,
 
+    /// Project slug or UUID to scope the token to
+    #[clap(long)]
+    pub project: Option<ResourceId>,
+
+    /// Project permission to grant a scoped token (defaults to only `view`)
+    #[clap(long = "permission", value_enum, requires = "project")]
+    pub permissions: Vec<CliProjectPermission>,
+
+    /// Create a CI token that can only create runs for the project
+    #[clap(long, requires = "project", conflicts_with = "permissions")]
+    pub ci: bool,
+
+    /// Branch name pattern to limit a CI token to (`*` matches any characters)
+    #[clap(long, requires = "ci")]
+    pub branch: Option<NonEmpty>,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
@@ -90,3 +109,15 @@ pub struct CliTokenUpdate {
     #[clap(flatten)]
     pub backend: CliBackend,
 }
+
+#[derive(Parser, Debug)]
+pub struct CliTokenRevoke {
+    /// User slug or UUID
+    pub user: ResourceId,
+
+    /// Token UUID
+    pub uuid: TokenUuid,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
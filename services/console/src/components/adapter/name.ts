@@ -54,6 +54,8 @@ export const adapterName = (adapter: Adapter): string => {
 			return "Shell";
 		case Adapter.ShellHyperfine:
 			return "Hyperfine";
+		case Adapter.ShellPerfStat:
+			return "perf stat";
 	}
 };
 
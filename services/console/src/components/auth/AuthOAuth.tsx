@@ -27,9 +27,10 @@ const STATE_PARAM = "state";
 
 interface Props {
 	apiUrl: string;
+	pathname: string;
 }
 
-const AuthGitHub = (props: Props) => {
+const AuthOAuth = (props: Props) => {
 	const [bencher_valid] = createResource(init_valid);
 
 	const [searchParams, _setSearchParams] = useSearchParams();
@@ -69,7 +70,7 @@ const AuthGitHub = (props: Props) => {
 			oauth.plan = state as PlanLevel;
 			setParams.push([PLAN_PARAM, state as PlanLevel]);
 		}
-		return await httpPost(props.apiUrl, "/v0/auth/github", null, oauth)
+		return await httpPost(props.apiUrl, props.pathname, null, oauth)
 			.then((resp) => {
 				const user = resp.data;
 				if (setUser(user)) {
@@ -107,4 +108,4 @@ const AuthGitHub = (props: Props) => {
 	return <></>;
 };
 
-export default AuthGitHub;
+export default AuthOAuth;
@@ -1,8 +1,11 @@
-import { Show, createMemo } from "solid-js";
+import { Show, createMemo, createResource } from "solid-js";
+import type { JsonOAuthUrl } from "../../types/bencher";
+import { httpGet } from "../../util/http";
 import { useSearchParams } from "../../util/url";
 import { CLAIM_PARAM, INVITE_PARAM, PLAN_PARAM } from "./auth";
 
 interface Props {
+	apiUrl: string;
 	newUser: boolean;
 	githubClientId: undefined | string;
 }
@@ -10,34 +13,74 @@ interface Props {
 const OAuthForm = (props: Props) => {
 	const [searchParams, _setSearchParams] = useSearchParams();
 
-	const githubPath = createMemo(() => {
-		let path = `https://github.com/login/oauth/authorize?client_id=${props.githubClientId}`;
+	const state = createMemo(() => {
 		const invite = searchParams[INVITE_PARAM];
 		const claim = searchParams[CLAIM_PARAM];
 		const plan = searchParams[PLAN_PARAM];
 		if (invite) {
-			path += `&state=${invite}`;
-		} else if (claim) {
-			path += `&state=${claim}`;
-		} else if (plan) {
-			path += `&state=${plan}`;
+			return invite;
+		}
+		if (claim) {
+			return claim;
+		}
+		if (plan) {
+			return plan;
+		}
+		return null;
+	});
+
+	const githubPath = createMemo(() => {
+		let path = `https://github.com/login/oauth/authorize?client_id=${props.githubClientId}`;
+		const githubState = state();
+		if (githubState) {
+			path += `&state=${githubState}`;
 		}
 		return path;
 	});
 
+	// The OpenID Connect authorization URL is only available if the server has it configured
+	const oidcFetcher = createMemo(() => {
+		return {
+			state: state(),
+		};
+	});
+	const getOidcPath = async (fetcher: { state: null | string }) => {
+		const search = fetcher.state
+			? `?state=${encodeURIComponent(fetcher.state)}`
+			: "";
+		return await httpGet(props.apiUrl, `/v0/auth/oidc${search}`, null)
+			.then((resp) => (resp?.data as JsonOAuthUrl)?.url ?? null)
+			.catch(() => null);
+	};
+	const [oidcPath] = createResource(oidcFetcher, getOidcPath);
+
 	return (
-		<Show when={props.githubClientId}>
-			<a
-				class="button is-fullwidth"
-				href={githubPath()}
-				style="margin-top: 3rem;"
-			>
-				<span class="icon">
-					<i class="fab fa-github" />
-				</span>
-				<span>{props.newUser ? "Sign up" : "Log in"} with GitHub</span>
-			</a>
-		</Show>
+		<>
+			<Show when={props.githubClientId}>
+				<a
+					class="button is-fullwidth"
+					href={githubPath()}
+					style="margin-top: 3rem;"
+				>
+					<span class="icon">
+						<i class="fab fa-github" />
+					</span>
+					<span>{props.newUser ? "Sign up" : "Log in"} with GitHub</span>
+				</a>
+			</Show>
+			<Show when={oidcPath()}>
+				<a
+					class="button is-fullwidth"
+					href={oidcPath() as string}
+					style={props.githubClientId ? "margin-top: 1rem;" : "margin-top: 3rem;"}
+				>
+					<span class="icon">
+						<i class="fas fa-key" />
+					</span>
+					<span>{props.newUser ? "Sign up" : "Log in"} with SSO</span>
+				</a>
+			</Show>
+		</>
 	);
 };
 
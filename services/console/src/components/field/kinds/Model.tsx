@@ -6,6 +6,7 @@ import {
 	validIqrBoundary,
 	validPercentageBoundary,
 	validSampleSize,
+	validSmoothing,
 	validU32,
 } from "../../../util/valid";
 import type { FieldConfig, FieldHandler, FieldValue } from "../Field";
@@ -115,6 +116,27 @@ const STATISTIC_FIELDS = {
 		help: "Must be an integer greater than zero",
 		validate: validU32,
 	},
+	smoothing: {
+		type: "input",
+		placeholder: "0.3",
+		icon: "fas fa-water",
+		help: "Must be greater than 0.0 and less than or equal to 1.0 (lower is smoother; higher is more responsive)",
+		validate: validSmoothing,
+	},
+	trend: {
+		type: "input",
+		placeholder: "0.1",
+		icon: "fas fa-chart-line",
+		help: "Must be greater than 0.0 and less than or equal to 1.0 (leave empty for no trend)",
+		validate: validSmoothing,
+	},
+	season: {
+		type: "number",
+		placeholder: "7",
+		icon: "fas fa-calendar-days",
+		help: "Must be an integer greater than or equal to 2 (leave empty for no seasonality)",
+		validate: validSampleSize,
+	},
 };
 
 export const fmtModelTest = (test: ModelTest) => {
@@ -133,6 +155,12 @@ export const fmtModelTest = (test: ModelTest) => {
 			return "Interquartile Range (IQR)";
 		case ModelTest.DeltaIqr:
 			return "Delta Interquartile Range (ΔIQR)";
+		case ModelTest.ChangePoint:
+			return "Change Point";
+		case ModelTest.MannWhitneyU:
+			return "Mann-Whitney U";
+		case ModelTest.Forecast:
+			return "Forecast";
 		default:
 			return "No Model";
 	}
@@ -166,6 +194,18 @@ const testValue = (selected: ModelTest) => {
 				value: ModelTest.DeltaIqr,
 				option: fmtModelTest(ModelTest.DeltaIqr),
 			},
+			{
+				value: ModelTest.ChangePoint,
+				option: fmtModelTest(ModelTest.ChangePoint),
+			},
+			{
+				value: ModelTest.MannWhitneyU,
+				option: fmtModelTest(ModelTest.MannWhitneyU),
+			},
+			{
+				value: ModelTest.Forecast,
+				option: fmtModelTest(ModelTest.Forecast),
+			},
 			{
 				value: ModelTest.Static,
 				option: fmtModelTest(ModelTest.Static),
@@ -220,6 +260,12 @@ export const testFragment = (modelTest: ModelTest) => {
 			return "interquartile-range";
 		case ModelTest.DeltaIqr:
 			return "delta-interquartile-range";
+		case ModelTest.ChangePoint:
+			return "change-point";
+		case ModelTest.MannWhitneyU:
+			return "mann-whitney-u";
+		case ModelTest.Forecast:
+			return "forecast";
 	}
 };
 
@@ -250,6 +296,42 @@ const cdfConfig = (modelTest: ModelTest) => {
 	];
 };
 
+const forecastConfig = (modelTest: ModelTest) => {
+	return [
+		...cdfConfig(modelTest),
+		{
+			kind: FieldKind.NUMBER,
+			label: "Smoothing",
+			key: "smoothing",
+			value: "",
+			valid: true,
+			validate: true,
+			nullable: true,
+			config: STATISTIC_FIELDS.smoothing,
+		},
+		{
+			kind: FieldKind.NUMBER,
+			label: "Trend Smoothing",
+			key: "trend",
+			value: "",
+			valid: true,
+			validate: true,
+			nullable: true,
+			config: STATISTIC_FIELDS.trend,
+		},
+		{
+			kind: FieldKind.NUMBER,
+			label: "Season Length (samples)",
+			key: "season",
+			value: "",
+			valid: true,
+			validate: true,
+			nullable: true,
+			config: STATISTIC_FIELDS.season,
+		},
+	];
+};
+
 const iqrConfig = (modelTest: ModelTest) => {
 	return [
 		testSelectConfig(modelTest),
@@ -363,6 +445,9 @@ const FIELDS = {
 	[ModelTest.LogNormal]: cdfConfig(ModelTest.LogNormal),
 	[ModelTest.Iqr]: iqrConfig(ModelTest.Iqr),
 	[ModelTest.DeltaIqr]: iqrConfig(ModelTest.DeltaIqr),
+	[ModelTest.ChangePoint]: cdfConfig(ModelTest.ChangePoint),
+	[ModelTest.MannWhitneyU]: cdfConfig(ModelTest.MannWhitneyU),
+	[ModelTest.Forecast]: forecastConfig(ModelTest.Forecast),
 };
 
 const initForm = (fields: object[]) => {
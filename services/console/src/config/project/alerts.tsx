@@ -84,6 +84,12 @@ const alertsConfig = {
 					keys: ["status"],
 					display: Display.RAW,
 				},
+				{
+					kind: Card.FIELD,
+					label: "Environment Change",
+					key: "environment_change",
+					display: Display.RAW,
+				},
 				{
 					kind: Card.FIELD,
 					label: "Report",
@@ -186,6 +192,30 @@ const alertsConfig = {
 					keys: ["threshold", "model", "window"],
 					display: Display.RAW,
 				},
+				{
+					kind: Card.NESTED_FIELD,
+					label: "Smoothing",
+					keys: ["threshold", "model", "smoothing"],
+					display: Display.RAW,
+				},
+				{
+					kind: Card.NESTED_FIELD,
+					label: "Trend Smoothing",
+					keys: ["threshold", "model", "trend"],
+					display: Display.RAW,
+				},
+				{
+					kind: Card.NESTED_FIELD,
+					label: "Season Length (samples)",
+					keys: ["threshold", "model", "season"],
+					display: Display.RAW,
+				},
+				{
+					kind: Card.NESTED_FIELD,
+					label: "On Environment Change",
+					keys: ["threshold", "model", "environment_change"],
+					display: Display.RAW,
+				},
 			],
 			buttons: [
 				{
@@ -273,6 +273,30 @@ const thresholdsConfig = {
 					keys: ["model", "window"],
 					display: Display.RAW,
 				},
+				{
+					kind: Card.NESTED_FIELD,
+					label: "Smoothing",
+					keys: ["model", "smoothing"],
+					display: Display.RAW,
+				},
+				{
+					kind: Card.NESTED_FIELD,
+					label: "Trend Smoothing",
+					keys: ["model", "trend"],
+					display: Display.RAW,
+				},
+				{
+					kind: Card.NESTED_FIELD,
+					label: "Season Length (samples)",
+					keys: ["model", "season"],
+					display: Display.RAW,
+				},
+				{
+					kind: Card.NESTED_FIELD,
+					label: "On Environment Change",
+					keys: ["model", "environment_change"],
+					display: Display.RAW,
+				},
 			],
 			buttons: [
 				{
@@ -2,7 +2,7 @@ import FieldKind from "../../components/field/kind";
 import { getUserRaw } from "../../util/auth";
 import type { Params } from "../../util/url";
 import { validResourceName, validU32 } from "../../util/valid";
-import { Button, Card, Display, Operation } from "../types";
+import { ActionButton, Button, Card, Display, Operation } from "../types";
 import { addPath, createdUuidPath, parentPath, viewUuidPath } from "../util";
 
 const TOKEN_FIELDS = {
@@ -161,6 +161,56 @@ const tokensConfig = {
 					key: "expiration",
 					display: Display.RAW,
 				},
+				{
+					kind: Card.FIELD,
+					label: "API Token Project Scope",
+					key: "project",
+					display: Display.RAW,
+				},
+				{
+					kind: Card.FIELD,
+					label: "API Token Permissions",
+					key: "permissions",
+					display: Display.RAW,
+				},
+				{
+					kind: Card.FIELD,
+					label: "CI Token",
+					key: "ci",
+					display: Display.RAW,
+				},
+				{
+					kind: Card.FIELD,
+					label: "CI Token Branch Pattern",
+					key: "branch",
+					display: Display.RAW,
+				},
+				{
+					kind: Card.FIELD,
+					label: "API Token Last Used",
+					key: "last_used",
+					display: Display.RAW,
+				},
+				{
+					kind: Card.FIELD,
+					label: "API Token Last Used IP",
+					key: "last_used_ip",
+					display: Display.RAW,
+				},
+				{
+					kind: Card.FIELD,
+					label: "API Token Revoked",
+					key: "revoked",
+					display: Display.RAW,
+				},
+			],
+			buttons: [
+				{
+					kind: ActionButton.DELETE,
+					subtitle:
+						"⚠️ A revoked API Token can no longer be used and can not be restored! ⚠️",
+					path: parentPath,
+				},
 			],
 		},
 	},
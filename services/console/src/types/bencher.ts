@@ -100,11 +100,36 @@ export interface JsonTestbed {
 	project: Uuid;
 	name: ResourceName;
 	slug: Slug;
+	/** The current hardware profile of the testbed, as detected by its most recent report. */
+	hardware?: JsonTestbedHardware;
 	created: string;
 	modified: string;
 	archived?: string;
 }
 
+/**
+ * The hardware profile of a testbed, as detected when the report was run.
+ * Any value that could not be detected is left empty.
+ */
+export interface JsonTestbedHardware {
+	/** The operating system. */
+	os?: string;
+	/** The CPU model name. */
+	cpu?: string;
+	/** The number of physical CPU cores. */
+	cores?: number;
+	/** The number of logical CPU threads. */
+	threads?: number;
+	/** The CPU frequency scaling governor. */
+	governor?: string;
+	/** The total memory in bytes. */
+	memory?: number;
+	/** The kernel version. */
+	kernel?: string;
+	/** The hypervisor, if virtualized, or `none` for bare metal. */
+	virtualization?: string;
+}
+
 export interface JsonMeasure {
 	uuid: Uuid;
 	project: Uuid;
@@ -124,10 +149,27 @@ export enum ModelTest {
 	LogNormal = "log_normal",
 	Iqr = "iqr",
 	DeltaIqr = "delta_iqr",
+	ChangePoint = "change_point",
+	MannWhitneyU = "mann_whitney_u",
+	Forecast = "forecast",
+}
+
+/**
+ * How to handle an alert when the testbed hardware has changed since its previous report.
+ * This only applies if the report `hardware_change` is `warn`.
+ * If it is `refuse`, then the report is not compared to the historical metrics and there are no alerts.
+ */
+export enum EnvironmentChange {
+	/** Create an active alert that is annotated as an environment change. */
+	Annotate = "annotate",
+	/** Create a silenced alert that is annotated as an environment change. */
+	Suppress = "suppress",
 }
 
 export type SampleSize = number;
 
+export type Smoothing = number;
+
 export type Window = number;
 
 export interface JsonModel {
@@ -138,6 +180,10 @@ export interface JsonModel {
 	window?: Window;
 	lower_boundary?: Boundary;
 	upper_boundary?: Boundary;
+	smoothing?: Smoothing;
+	trend?: Smoothing;
+	season?: SampleSize;
+	environment_change?: EnvironmentChange;
 	created: string;
 	replaced?: string;
 }
@@ -157,6 +203,16 @@ export interface JsonBoundary {
 	baseline?: number;
 	lower_limit?: number;
 	upper_limit?: number;
+	/**
+	 * The first metric after a detected change point, if any.
+	 * This is only set by the change point threshold model.
+	 */
+	change_point?: Uuid;
+	/**
+	 * The significance level of the boundary test after correcting for multiple comparisons, if any.
+	 * This is only set for statistical threshold model tests when the report used a correction.
+	 */
+	significance?: number;
 }
 
 export enum BoundaryLimit {
@@ -183,6 +239,8 @@ export interface JsonAlert {
 	boundary: JsonBoundary;
 	limit: BoundaryLimit;
 	status: AlertStatus;
+	/** Whether the alert was raised for a report where the testbed hardware changed. */
+	environment_change: boolean;
 	created: string;
 	modified: string;
 }
@@ -215,6 +273,10 @@ export type JsonReportResults = JsonReportIteration[];
 
 export type JsonResultsMap = Record<BenchmarkName, JsonMetricsMap>;
 
+export type JsonTokenPermissions = ProjectPermission[];
+
+export type JsonWebhookEvents = WebhookEvent[];
+
 export type Jwt = string;
 
 export type LastFour = string;
@@ -291,6 +353,16 @@ export interface JsonCheckout {
 	url: string;
 }
 
+export interface JsonCiAuth {
+	/** The slug or UUID for the project to create a CI token for. */
+	project: ResourceId;
+	/**
+	 * The OpenID Connect ID token issued by the CI provider.
+	 * The audience (`aud`) of the ID token must be the Bencher Console URL.
+	 */
+	id_token: Secret;
+}
+
 export interface JsonConfirm {
 	token: Jwt;
 }
@@ -459,6 +531,116 @@ export interface JsonNewToken {
 	 * If not provided, the token will not expire for over 128 years.
 	 */
 	ttl?: number;
+	/**
+	 * The slug or UUID of a project to scope the token to.
+	 * A scoped token can only access this one project.
+	 */
+	project?: ResourceId;
+	/**
+	 * The project permissions granted to a scoped token.
+	 * Requires `project` to be set.
+	 * If not provided, a scoped token is only granted the `view` permission.
+	 */
+	permissions?: ProjectPermission[];
+	/**
+	 * Create a CI token.
+	 * A CI token can only be used to create runs for the `project` and to view the reports that it created.
+	 * Requires `project` to be set and can not be used with `permissions`.
+	 */
+	ci?: boolean;
+	/**
+	 * The branch name pattern that a CI token is limited to.
+	 * A `*` matches any sequence of characters.
+	 * Requires `ci` to be set.
+	 */
+	branch?: NonEmpty;
+}
+
+export enum TrustProvider {
+	GithubActions = "github_actions",
+	GitlabCi = "gitlab_ci",
+}
+
+export interface JsonNewTrustPolicy {
+	/** The CI provider that issues the OpenID Connect ID tokens. */
+	provider: TrustProvider;
+	/**
+	 * The OpenID Connect issuer URL for a self-hosted CI provider.
+	 * If not provided, the issuer for the hosted CI provider is used.
+	 */
+	issuer?: Url;
+	/**
+	 * The repository that is trusted, for example `bencherdev/bencher`.
+	 * For GitHub Actions this is matched against the `repository` claim,
+	 * and for GitLab CI/CD this is matched against the `project_path` claim.
+	 */
+	repository: NonEmpty;
+	/**
+	 * The git ref pattern that is trusted, matched against the `ref` claim.
+	 * A `*` matches any sequence of characters.
+	 * If not provided, all git refs are trusted.
+	 */
+	git_ref?: NonEmpty;
+	/**
+	 * The workflow file path pattern that is trusted, for example `.github/workflows/bench.yml`.
+	 * A `*` matches any sequence of characters.
+	 * If not provided, all workflows are trusted.
+	 */
+	workflow?: NonEmpty;
+}
+
+export enum WebhookFormat {
+	/** A signed JSON payload with the full event data. */
+	Json = "json",
+	/** A Slack Block Kit message for a Slack incoming webhook. */
+	Slack = "slack",
+	/** A `MessageCard` for a Microsoft Teams incoming webhook. */
+	Teams = "teams",
+	/** An `m.room.message` event for a Matrix room. */
+	Matrix = "matrix",
+}
+
+export enum WebhookEvent {
+	/** An alert was raised for a new report. */
+	AlertCreated = "alert.created",
+	/** An alert was dismissed by a user. */
+	AlertDismissed = "alert.dismissed",
+	/** A new report was created. */
+	ReportCreated = "report.created",
+	/** The model for a threshold was updated by a user. */
+	ThresholdUpdated = "threshold.updated",
+}
+
+export interface JsonNewWebhook {
+	/**
+	 * The URL that the webhook payloads are sent to.
+	 * Unless the server allows private webhooks,
+	 * the URL host must not be a loopback, link-local, or private network address.
+	 * For the `matrix` format, this is the URL to send a message to a room, without the transaction ID:
+	 * `https://{homeserver}/_matrix/client/v3/rooms/{room_id}/send/m.room.message`
+	 */
+	url: Url;
+	/**
+	 * The format of the webhook payloads.
+	 * If not provided, the payloads are sent as `json`.
+	 */
+	format?: WebhookFormat;
+	/**
+	 * For the `json` format, the secret used to sign the webhook payloads.
+	 * Each delivery attempt is sent with its Unix timestamp in the `X-Bencher-Timestamp` header.
+	 * The hex encoded HMAC-SHA256 signature of the timestamp, a `.`, and the payload
+	 * is sent in the `X-Bencher-Signature-256` header, prefixed with `sha256=`.
+	 * If not provided, the webhook payloads are not signed.
+	 * For the `matrix` format, the access token for the Matrix user that sends the messages.
+	 * This is required for the `matrix` format and not allowed for the `slack` and `teams` formats.
+	 */
+	secret?: Secret;
+	/**
+	 * The events that the webhook is subscribed to.
+	 * There must be at least one event.
+	 * Chat formats can not be subscribed to the `threshold.updated` event.
+	 */
+	events: JsonWebhookEvents;
 }
 
 export interface JsonOAuth {
@@ -468,6 +650,10 @@ export interface JsonOAuth {
 	claim?: Uuid;
 }
 
+export interface JsonOAuthUrl {
+	url: Url;
+}
+
 export interface JsonPerfAlert {
 	uuid: Uuid;
 	limit: BoundaryLimit;
@@ -633,6 +819,19 @@ export enum Adapter {
 	RubyBenchmark = "ruby_benchmark",
 	Shell = "shell",
 	ShellHyperfine = "shell_hyperfine",
+	ShellPerfStat = "shell_perf_stat",
+}
+
+/**
+ * How to handle a report when the testbed hardware has changed since its previous report.
+ * If the report is still compared to the historical metrics,
+ * then the threshold model `environment_change` decides how to handle any alerts.
+ */
+export enum JsonHardwareChange {
+	/** Warn about the change and compare the report to the historical metrics. */
+	Warn = "warn",
+	/** Refuse to compare the report to the historical metrics. */
+	Refuse = "refuse",
 }
 
 export interface JsonReport {
@@ -644,6 +843,11 @@ export interface JsonReport {
 	start_time: string;
 	end_time: string;
 	adapter: Adapter;
+	hardware?: JsonTestbedHardware;
+	/** Whether the testbed hardware changed since its previous report. */
+	environment_change: boolean;
+	/** How the report was handled if the testbed hardware changed since its previous report. */
+	hardware_change?: JsonHardwareChange;
 	results: JsonReportResults;
 	alerts: JsonReportAlerts;
 	created: string;
@@ -660,6 +864,22 @@ export interface JsonSignup {
 	i_agree: boolean;
 }
 
+export enum NotificationMode {
+	/** Send an email as soon as an alert is generated. */
+	Immediate = "immediate",
+	/** Send a daily digest email with all of the alerts generated in the last day. */
+	Digest = "digest",
+	/** Do not send any emails. */
+	Off = "off",
+}
+
+export interface JsonNotification {
+	user: Uuid;
+	project: Uuid;
+	/** How the user is notified by email when an alert is generated for the project. */
+	alerts: NotificationMode;
+}
+
 export interface JsonToken {
 	uuid: Uuid;
 	user: Uuid;
@@ -667,6 +887,36 @@ export interface JsonToken {
 	token: Jwt;
 	creation: string;
 	expiration: string;
+	/** The project that the token is scoped to, if any. */
+	project?: Uuid;
+	/** The project permissions granted to the token, if it is scoped. */
+	permissions?: JsonTokenPermissions;
+	/** The last time that the token was used, to within a minute. */
+	last_used?: string;
+	/** The IP address that the token was last used from. */
+	last_used_ip?: string;
+	/**
+	 * The time that the token was revoked.
+	 * A revoked token can no longer be used.
+	 */
+	revoked?: string;
+	/** Whether the token is a CI token. */
+	ci: boolean;
+	/** The branch name pattern that a CI token is limited to, if any. */
+	branch?: NonEmpty;
+}
+
+export interface JsonTrustPolicy {
+	uuid: Uuid;
+	project: Uuid;
+	/** The user that the short-lived CI tokens are issued for. */
+	user: Uuid;
+	provider: TrustProvider;
+	issuer: Url;
+	repository: NonEmpty;
+	git_ref?: NonEmpty;
+	workflow?: NonEmpty;
+	created: string;
 }
 
 export enum UpdateAlertStatus {
@@ -681,6 +931,11 @@ export interface JsonUpdateAlert {
 	status?: UpdateAlertStatus;
 }
 
+export interface JsonUpdateNotification {
+	/** How the user should be notified by email when an alert is generated for the project. */
+	alerts?: NotificationMode;
+}
+
 export interface JsonUpdateUser {
 	/**
 	 * The new name of the user.
@@ -707,6 +962,41 @@ export interface JsonUpdateUser {
 	locked?: boolean;
 }
 
+export interface JsonWebhook {
+	uuid: Uuid;
+	project: Uuid;
+	url: Url;
+	format: WebhookFormat;
+	/** Whether the webhook payloads are signed with a secret. */
+	signed: boolean;
+	events: JsonWebhookEvents;
+	created: string;
+}
+
+export enum WebhookDeliveryStatus {
+	/** The delivery has not succeeded yet and will be retried. */
+	Pending = "pending",
+	/** The webhook responded with a successful status code. */
+	Delivered = "delivered",
+	/** All of the delivery attempts failed. */
+	Failed = "failed",
+}
+
+export interface JsonWebhookDelivery {
+	uuid: Uuid;
+	webhook: Uuid;
+	event: WebhookEvent;
+	status: WebhookDeliveryStatus;
+	/** The number of delivery attempts that have been made. */
+	attempts: number;
+	/** The HTTP status code of the response to the last delivery attempt, if any. */
+	response_status?: number;
+	/** The error for the last delivery attempt, if it failed. */
+	error?: string;
+	created: string;
+	modified: string;
+}
+
 export enum UsageKind {
 	/** Bencher Cloud (Free) */
 	CloudFree = "cloud_free",
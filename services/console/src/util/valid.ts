@@ -24,6 +24,7 @@ import bencher_valid_init, {
 	is_valid_iqr_boundary,
 	is_valid_model,
 	is_valid_window,
+	is_valid_smoothing,
 } from "bencher_valid";
 import type { JsonAuthUser } from "../types/bencher";
 
@@ -152,6 +153,9 @@ export const validCdfBoundary = (boundary: string): boolean =>
 export const validIqrBoundary = (boundary: string): boolean =>
 	validateNumber(boundary, is_valid_iqr_boundary);
 
+export const validSmoothing = (smoothing: string): boolean =>
+	validateNumber(smoothing, is_valid_smoothing);
+
 export const validSampleSize = (sample_size: string) =>
 	validU32(sample_size) && validateNumber(sample_size, is_valid_sample_size);
 